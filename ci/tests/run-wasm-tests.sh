@@ -0,0 +1,15 @@
+#!/bin/bash
+set -euo pipefail
+
+# Runs the wasm-bindgen tests of rust/wasm in node.js. The test runner has to match
+# the resolved wasm-bindgen version exactly, so it is installed after resolving it.
+
+cd "$(dirname "$BASH_SOURCE")/../../rust"
+
+rustup target add wasm32-unknown-unknown
+WASM_BINDGEN_VERSION=$(cargo metadata --format-version 1 \
+    | jq -r '.packages[] | select(.name == "wasm-bindgen") | .version')
+cargo install wasm-bindgen-cli --locked --version "${WASM_BINDGEN_VERSION}"
+
+# The runner is set for this target in .cargo/config.toml
+cargo test -p rsnano_wasm --target wasm32-unknown-unknown
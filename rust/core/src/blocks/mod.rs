@@ -26,6 +26,9 @@ use serde::{Deserialize, Serialize};
 use state_block::JsonStateBlock;
 pub use state_block::{StateBlock, StateHashables};
 
+mod signing_payload;
+pub use signing_payload::SigningPayload;
+
 mod builders;
 pub use builders::*;
 
@@ -0,0 +1,169 @@
+use super::{BlockEnum, StateBlock, StateHashables};
+use crate::{
+    sign_message, validate_message, Account, Amount, BlockHash, KeyPair, Link, PublicKey, RawKey,
+    Signature,
+};
+use anyhow::bail;
+
+/// An unsigned state block in the canonical form that gets passed to an offline signer.
+///
+/// Layout: format version (1 byte), account, previous, representative, balance,
+/// previous balance, link and work (big endian). The signer recomputes the hash from
+/// these fields, so it never has to trust a hash it was given.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SigningPayload {
+    pub hashables: StateHashables,
+    /// Balance of the previous block, so that the signer can show the amount that gets
+    /// sent or received. It isn't part of the hash
+    pub previous_balance: Amount,
+    pub work: u64,
+}
+
+impl SigningPayload {
+    const VERSION: u8 = 2;
+    pub const SERIALIZED_SIZE: usize = 1 + 32 + 32 + 32 + 16 + 16 + 32 + 8;
+
+    pub fn new(hashables: StateHashables, previous_balance: Amount, work: u64) -> Self {
+        Self {
+            hashables,
+            previous_balance,
+            work,
+        }
+    }
+
+    pub fn hash(&self) -> BlockHash {
+        BlockHash::from(&self.hashables)
+    }
+
+    /// The block as it would look like with a zero signature
+    pub fn unsigned_block(&self) -> BlockEnum {
+        self.block_with_signature(Signature::new())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SERIALIZED_SIZE);
+        bytes.push(Self::VERSION);
+        bytes.extend_from_slice(self.hashables.account.as_bytes());
+        bytes.extend_from_slice(self.hashables.previous.as_bytes());
+        bytes.extend_from_slice(self.hashables.representative.as_bytes());
+        bytes.extend_from_slice(&self.hashables.balance.to_be_bytes());
+        bytes.extend_from_slice(&self.previous_balance.to_be_bytes());
+        bytes.extend_from_slice(self.hashables.link.as_bytes());
+        bytes.extend_from_slice(&self.work.to_be_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        if bytes.len() != Self::SERIALIZED_SIZE {
+            bail!("invalid signing payload length: {}", bytes.len());
+        }
+        if bytes[0] != Self::VERSION {
+            bail!("unsupported signing payload version: {}", bytes[0]);
+        }
+        let field = |start: usize| -> [u8; 32] { bytes[start..start + 32].try_into().unwrap() };
+        Ok(Self {
+            hashables: StateHashables {
+                account: Account::from_bytes(field(1)),
+                previous: BlockHash::from_bytes(field(33)),
+                representative: PublicKey::from_bytes(field(65)),
+                balance: Amount::from_be_bytes(bytes[97..113].try_into().unwrap()),
+                link: Link::from_bytes(field(129)),
+            },
+            previous_balance: Amount::from_be_bytes(bytes[113..129].try_into().unwrap()),
+            work: u64::from_be_bytes(bytes[161..169].try_into().unwrap()),
+        })
+    }
+
+    pub fn encode_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    pub fn decode_hex(s: impl AsRef<str>) -> anyhow::Result<Self> {
+        Self::from_bytes(&hex::decode(s.as_ref().trim())?)
+    }
+
+    /// Signs the block hash. Fails if the key doesn't belong to the account of the block
+    pub fn sign(&self, key: &RawKey) -> anyhow::Result<Signature> {
+        let keys = KeyPair::from(*key);
+        if keys.account() != self.hashables.account {
+            bail!(
+                "the key does not belong to account {}",
+                self.hashables.account.encode_account()
+            );
+        }
+        Ok(sign_message(key, self.hash().as_bytes()))
+    }
+
+    /// Combines the payload with a detached signature and checks that the signature is valid
+    pub fn assemble(&self, signature: Signature) -> anyhow::Result<BlockEnum> {
+        validate_message(
+            &self.hashables.account.into(),
+            self.hash().as_bytes(),
+            &signature,
+        )?;
+        Ok(self.block_with_signature(signature))
+    }
+
+    fn block_with_signature(&self, signature: Signature) -> BlockEnum {
+        BlockEnum::State(StateBlock::with_signature(
+            self.hashables.account,
+            self.hashables.previous,
+            self.hashables.representative,
+            self.hashables.balance,
+            self.hashables.link,
+            signature,
+            self.work,
+        ))
+    }
+}
+
+impl From<&StateBlock> for SigningPayload {
+    /// The previous balance is unknown and left at zero
+    fn from(block: &StateBlock) -> Self {
+        Self::new(block.hashables.clone(), Amount::zero(), block.work)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Block;
+
+    #[test]
+    fn hex_roundtrip() {
+        let mut payload = SigningPayload::from(&StateBlock::new_test_instance());
+        payload.previous_balance = Amount::raw(42);
+        let encoded = payload.encode_hex();
+        assert_eq!(encoded.len(), SigningPayload::SERIALIZED_SIZE * 2);
+        assert_eq!(SigningPayload::decode_hex(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn sign_and_assemble() {
+        let key = KeyPair::from(42);
+        let block = StateBlock::new_test_instance_with_key(key.clone());
+        let payload = SigningPayload::from(&block);
+
+        let signature = payload.sign(&key.private_key()).unwrap();
+        let assembled = payload.assemble(signature).unwrap();
+
+        assert_eq!(assembled.hash(), block.hash());
+        assert_eq!(*assembled.block_signature(), *block.block_signature());
+    }
+
+    #[test]
+    fn reject_wrong_key() {
+        let payload = SigningPayload::from(&StateBlock::new_test_instance());
+        assert!(payload.sign(&KeyPair::from(1).private_key()).is_err());
+        let signature = sign_message(&KeyPair::from(1).private_key(), payload.hash().as_bytes());
+        assert!(payload.assemble(signature).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_payload() {
+        let mut bytes = SigningPayload::from(&StateBlock::new_test_instance()).to_bytes();
+        assert!(SigningPayload::from_bytes(&bytes[1..]).is_err());
+        bytes[0] = 3;
+        assert!(SigningPayload::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use once_cell::sync::Lazy;
+use pbkdf2::pbkdf2_hmac;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256, Sha512};
+use std::{fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+
+static WORDLIST: Lazy<Vec<&'static str>> =
+    Lazy::new(|| include_str!("english.txt").split_whitespace().collect());
+
+const SEED_ITERATIONS: u32 = 2048;
+
+/// BIP39 mnemonic with the English wordlist
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    pub const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+    pub const DEFAULT_WORD_COUNT: usize = 24;
+
+    pub fn random(word_count: usize) -> anyhow::Result<Self> {
+        let mut entropy = vec![0; Self::entropy_len(word_count)?];
+        thread_rng().fill(entropy.as_mut_slice());
+        Self::from_entropy(&entropy)
+    }
+
+    pub fn from_entropy(entropy: &[u8]) -> anyhow::Result<Self> {
+        if ![16, 20, 24, 28, 32].contains(&entropy.len()) {
+            bail!("invalid entropy length: {}", entropy.len());
+        }
+        Ok(Self {
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Parses the words of a mnemonic and verifies its checksum
+    pub fn from_phrase(phrase: &str) -> anyhow::Result<Self> {
+        let words: Vec<String> = phrase
+            .split_whitespace()
+            .map(|w| w.to_lowercase())
+            .collect();
+        let entropy_len = Self::entropy_len(words.len())?;
+
+        let mut bits = Vec::with_capacity(words.len() * 11);
+        for word in &words {
+            let index = WORDLIST
+                .binary_search(&word.as_str())
+                .map_err(|_| anyhow!("unknown mnemonic word: {}", word))?;
+            bits.extend((0..11).rev().map(|i| (index >> i) & 1 == 1));
+        }
+
+        let mut entropy = vec![0u8; entropy_len];
+        for (i, bit) in bits.iter().take(entropy_len * 8).enumerate() {
+            if *bit {
+                entropy[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+
+        let mnemonic = Self::from_entropy(&entropy)?;
+        if mnemonic.checksum_bits() != bits[entropy_len * 8..] {
+            bail!("invalid mnemonic checksum");
+        }
+        Ok(mnemonic)
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.entropy.len() * 3 / 4
+    }
+
+    pub fn words(&self) -> Vec<&'static str> {
+        let mut bits: Vec<bool> = self
+            .entropy
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+            .collect();
+        bits.extend(self.checksum_bits());
+
+        bits.chunks(11)
+            .map(|chunk| {
+                let index = chunk
+                    .iter()
+                    .fold(0usize, |acc, bit| (acc << 1) | *bit as usize);
+                WORDLIST[index]
+            })
+            .collect()
+    }
+
+    pub fn phrase(&self) -> String {
+        self.words().join(" ")
+    }
+
+    /// The 64 byte BIP39 seed. The passphrase is empty if the user didn't choose one
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let phrase: String = self.phrase().nfkd().collect();
+        let salt: String = format!("mnemonic{}", passphrase).nfkd().collect();
+        let mut seed = [0; 64];
+        pbkdf2_hmac::<Sha512>(
+            phrase.as_bytes(),
+            salt.as_bytes(),
+            SEED_ITERATIONS,
+            &mut seed,
+        );
+        seed
+    }
+
+    fn entropy_len(word_count: usize) -> anyhow::Result<usize> {
+        if !Self::WORD_COUNTS.contains(&word_count) {
+            bail!("invalid mnemonic word count: {}", word_count);
+        }
+        Ok(word_count * 4 / 3)
+    }
+
+    fn checksum_bits(&self) -> Vec<bool> {
+        let hash = Sha256::digest(&self.entropy);
+        (0..self.entropy.len() / 4)
+            .map(|i| (hash[i / 8] >> (7 - i % 8)) & 1 == 1)
+            .collect()
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(s)
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.phrase())
+    }
+}
+
+/// Doesn't print the words, so that the mnemonic doesn't end up in logs
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mnemonic({} words)", self.word_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wordlist() {
+        assert_eq!(WORDLIST.len(), 2048);
+        assert_eq!(WORDLIST[0], "abandon");
+        assert_eq!(WORDLIST[2047], "zoo");
+        assert!(WORDLIST.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn bip39_test_vectors() {
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+                "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+            ),
+        ];
+
+        for (entropy, phrase, seed) in vectors {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.phrase(), phrase);
+            assert_eq!(Mnemonic::from_phrase(phrase).unwrap(), mnemonic);
+            assert_eq!(hex::encode(mnemonic.to_seed("TREZOR")), seed);
+        }
+    }
+
+    #[test]
+    fn random_mnemonic_roundtrip() {
+        for word_count in Mnemonic::WORD_COUNTS {
+            let mnemonic = Mnemonic::random(word_count).unwrap();
+            assert_eq!(mnemonic.word_count(), word_count);
+            assert_eq!(mnemonic.words().len(), word_count);
+            assert_eq!(Mnemonic::from_phrase(&mnemonic.phrase()).unwrap(), mnemonic);
+        }
+    }
+
+    #[test]
+    fn reject_invalid_checksum() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert!(Mnemonic::from_phrase(phrase).is_err());
+    }
+
+    #[test]
+    fn reject_unknown_word() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon nano";
+        assert!(Mnemonic::from_phrase(phrase).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_word_count() {
+        assert!(Mnemonic::random(13).is_err());
+        assert!(Mnemonic::from_phrase("abandon about").is_err());
+    }
+
+    #[test]
+    fn debug_output_hides_words() {
+        let mnemonic = Mnemonic::random(12).unwrap();
+        assert_eq!(format!("{:?}", mnemonic), "Mnemonic(12 words)");
+    }
+}
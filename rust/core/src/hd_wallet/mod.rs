@@ -0,0 +1,9 @@
+//! Hierarchical deterministic wallets as used by hardware wallets: BIP39
+//! mnemonics and SLIP-0010 ed25519 key derivation along the BIP44 path
+//! `m/44'/165'/i'`
+
+mod mnemonic;
+mod slip10;
+
+pub use mnemonic::Mnemonic;
+pub use slip10::{Slip10Node, NANO_COIN_TYPE};
@@ -0,0 +1,118 @@
+use crate::RawKey;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Registered BIP44 coin type of Nano
+pub const NANO_COIN_TYPE: u32 = 165;
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// A node of the SLIP-0010 ed25519 key tree. ed25519 only supports hardened
+/// derivation, so every index is hardened
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Slip10Node {
+    pub key: RawKey,
+    pub chain_code: RawKey,
+}
+
+impl Slip10Node {
+    /// Highest index that can be derived. Indexes at or above 2^31 would
+    /// collide with the hardened indexes below it
+    pub const MAX_INDEX: u32 = HARDENED - 1;
+
+    pub fn master(seed: &[u8]) -> Self {
+        let mut hmac = new_hmac(b"ed25519 seed");
+        hmac.update(seed);
+        Self::from_hmac(hmac.finalize().into_bytes().into())
+    }
+
+    /// Derives the hardened child `index'`
+    pub fn derive(&self, index: u32) -> Self {
+        assert!(index <= Self::MAX_INDEX, "index {} is out of range", index);
+        let mut hmac = new_hmac(self.chain_code.as_bytes());
+        hmac.update(&[0]);
+        hmac.update(self.key.as_bytes());
+        hmac.update(&(index | HARDENED).to_be_bytes());
+        Self::from_hmac(hmac.finalize().into_bytes().into())
+    }
+
+    /// The node `m/44'/165'` whose children are the Nano accounts
+    pub fn nano_accounts(seed: &[u8]) -> Self {
+        Self::master(seed).derive(44).derive(NANO_COIN_TYPE)
+    }
+
+    /// Private key of the account `m/44'/165'/index'` if called on the node returned by `nano_accounts`
+    pub fn account_key(&self, index: u32) -> RawKey {
+        self.derive(index).key
+    }
+
+    fn from_hmac(i: [u8; 64]) -> Self {
+        Self {
+            key: RawKey::from_bytes(i[..32].try_into().unwrap()),
+            chain_code: RawKey::from_bytes(i[32..].try_into().unwrap()),
+        }
+    }
+}
+
+fn new_hmac(key: &[u8]) -> HmacSha512 {
+    HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hd_wallet::Mnemonic, Account, PublicKey};
+
+    #[test]
+    fn slip10_test_vector_1() {
+        let master = Slip10Node::master(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap());
+        assert_eq!(
+            master.key,
+            RawKey::decode_hex("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7")
+                .unwrap()
+        );
+        assert_eq!(
+            master.chain_code,
+            RawKey::decode_hex("90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb")
+                .unwrap()
+        );
+
+        let child = master.derive(0).derive(1);
+        assert_eq!(
+            child.key,
+            RawKey::decode_hex("b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2")
+                .unwrap()
+        );
+        assert_eq!(
+            child.chain_code,
+            RawKey::decode_hex("a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn nano_account_from_mnemonic() {
+        let mnemonic = Mnemonic::from_phrase("edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur").unwrap();
+        let accounts = Slip10Node::nano_accounts(&mnemonic.to_seed("some password"));
+
+        let key = accounts.account_key(0);
+
+        assert_eq!(
+            key,
+            RawKey::decode_hex("3be4fc2ef3f3b7374e6fc4fb6e7bb153f8a2998b3b3dab50853eabe128024143")
+                .unwrap()
+        );
+        assert_eq!(
+            Account::from(PublicKey::try_from(&key).unwrap()).encode_account(),
+            "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn dont_derive_index_out_of_range() {
+        Slip10Node::master(&[1; 64]).derive(Slip10Node::MAX_INDEX + 1);
+    }
+}
@@ -1,6 +1,111 @@
 use crate::RawKey;
+use anyhow::bail;
 use argon2::{Variant, Version};
 
+/// Argon2 variant used to derive a wallet key from its password
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KdfAlgorithm {
+    Argon2d,
+    Argon2id,
+}
+
+impl KdfAlgorithm {
+    fn variant(&self) -> Variant {
+        match self {
+            KdfAlgorithm::Argon2d => Variant::Argon2d,
+            KdfAlgorithm::Argon2id => Variant::Argon2id,
+        }
+    }
+
+    /// Legacy Argon2d wallets were hashed with version 1.0, which must be kept to unlock them
+    fn version(&self) -> Version {
+        match self {
+            KdfAlgorithm::Argon2d => Version::Version10,
+            KdfAlgorithm::Argon2id => Version::Version13,
+        }
+    }
+}
+
+/// Argon2 parameters that are stored with each wallet
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KdfParams {
+    pub algorithm: KdfAlgorithm,
+    /// Memory cost in KiB
+    pub mem_cost: u32,
+    pub time_cost: u32,
+    pub lanes: u32,
+}
+
+impl KdfParams {
+    const ARGON2D: u8 = 0;
+    const ARGON2ID: u8 = 2;
+    /// Upper bounds that keep imported wallets from demanding unreasonable resources
+    const MAX_MEM_COST: u32 = 4 * 1024 * 1024;
+    const MAX_TIME_COST: u32 = 64;
+    const MAX_LANES: u32 = 64;
+
+    /// Parameters for new and upgraded wallets. This is the second recommended option
+    /// of RFC 9106 (t=3, p=4, m=64 MiB)
+    pub const RECOMMENDED: KdfParams = KdfParams {
+        algorithm: KdfAlgorithm::Argon2id,
+        mem_cost: 64 * 1024,
+        time_cost: 3,
+        lanes: 4,
+    };
+
+    pub fn hash_password(&self, password: &str, salt: &[u8; 32]) -> RawKey {
+        let config = argon2::Config {
+            hash_length: 32,
+            lanes: self.lanes,
+            mem_cost: self.mem_cost,
+            thread_mode: argon2::ThreadMode::Sequential,
+            time_cost: self.time_cost,
+            variant: self.algorithm.variant(),
+            version: self.algorithm.version(),
+            ..Default::default()
+        };
+
+        let hash = argon2::hash_raw(password.as_bytes(), salt, &config).unwrap();
+        RawKey::from_bytes(hash.as_slice().try_into().unwrap())
+    }
+
+    /// Layout: algorithm (1 byte), mem_cost, time_cost and lanes (4 bytes big endian each),
+    /// zero padded to 32 bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes[0] = match self.algorithm {
+            KdfAlgorithm::Argon2d => Self::ARGON2D,
+            KdfAlgorithm::Argon2id => Self::ARGON2ID,
+        };
+        bytes[1..5].copy_from_slice(&self.mem_cost.to_be_bytes());
+        bytes[5..9].copy_from_slice(&self.time_cost.to_be_bytes());
+        bytes[9..13].copy_from_slice(&self.lanes.to_be_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> anyhow::Result<Self> {
+        let algorithm = match bytes[0] {
+            Self::ARGON2D => KdfAlgorithm::Argon2d,
+            Self::ARGON2ID => KdfAlgorithm::Argon2id,
+            other => bail!("unknown kdf algorithm: {}", other),
+        };
+        let read_u32 = |i: usize| u32::from_be_bytes(bytes[i..i + 4].try_into().unwrap());
+        let params = Self {
+            algorithm,
+            mem_cost: read_u32(1),
+            time_cost: read_u32(5),
+            lanes: read_u32(9),
+        };
+        if !(1..=Self::MAX_TIME_COST).contains(&params.time_cost)
+            || !(1..=Self::MAX_LANES).contains(&params.lanes)
+            || !(8 * params.lanes..=Self::MAX_MEM_COST).contains(&params.mem_cost)
+        {
+            bail!("invalid kdf parameters");
+        }
+        Ok(params)
+    }
+}
+
 /// Key derivation function
 #[derive(Clone)]
 pub struct KeyDerivationFunction {
@@ -12,19 +117,87 @@ impl KeyDerivationFunction {
         Self { kdf_work }
     }
 
+    /// Parameters of wallets that were created before the KDF was stored per wallet
+    pub fn legacy_params(&self) -> KdfParams {
+        KdfParams {
+            algorithm: KdfAlgorithm::Argon2d,
+            mem_cost: self.kdf_work,
+            time_cost: 1,
+            lanes: 1,
+        }
+    }
+
+    /// Parameters for new and upgraded wallets. They don't depend on kdf_work, because
+    /// kdf_work is tiny outside of the live network
+    pub fn default_params(&self) -> KdfParams {
+        KdfParams::RECOMMENDED
+    }
+
     pub fn hash_password(&self, password: &str, salt: &[u8; 32]) -> RawKey {
+        self.legacy_params().hash_password(password, salt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_roundtrip() {
+        let params = KdfParams::RECOMMENDED;
+        assert_eq!(KdfParams::from_bytes(&params.to_bytes()).unwrap(), params);
+    }
+
+    #[test]
+    fn reject_invalid_params() {
+        let mut bytes = KeyDerivationFunction::new(8).legacy_params().to_bytes();
+        bytes[0] = 1;
+        assert!(KdfParams::from_bytes(&bytes).is_err());
+        assert!(KdfParams::from_bytes(&[0; 32]).is_err());
+    }
+
+    #[test]
+    fn argon2id_uses_version_13() {
+        let params = KdfParams {
+            algorithm: KdfAlgorithm::Argon2id,
+            mem_cost: 32,
+            time_cost: 1,
+            lanes: 4,
+        };
+        let salt = [1; 32];
         let config = argon2::Config {
             hash_length: 32,
-            lanes: 1,
-            mem_cost: self.kdf_work,
-            thread_mode: argon2::ThreadMode::Sequential,
-            time_cost: 1,
-            variant: Variant::Argon2d,
-            version: Version::Version10,
+            lanes: params.lanes,
+            mem_cost: params.mem_cost,
+            time_cost: params.time_cost,
+            variant: Variant::Argon2id,
+            version: Version::Version13,
             ..Default::default()
         };
+        let expected = argon2::hash_raw(b"password", &salt, &config).unwrap();
+        assert_eq!(
+            params.hash_password("password", &salt).as_bytes(),
+            expected.as_slice()
+        );
+    }
 
-        let hash = argon2::hash_raw(password.as_bytes(), salt, &config).unwrap();
-        RawKey::from_bytes(hash.as_slice().try_into().unwrap())
+    #[test]
+    fn default_params_differ_from_legacy() {
+        let kdf = KeyDerivationFunction::new(64 * 1024);
+        let salt = [1; 32];
+        assert_ne!(
+            kdf.legacy_params().hash_password("password", &salt),
+            kdf.default_params().hash_password("password", &salt)
+        );
+    }
+
+    #[test]
+    fn default_params_dont_depend_on_kdf_work() {
+        let params = KeyDerivationFunction::new(8).default_params();
+        assert_eq!(params, KdfParams::RECOMMENDED);
+        assert_eq!(params.algorithm, KdfAlgorithm::Argon2id);
+        assert_eq!(params.mem_cost, 64 * 1024);
+        assert_eq!(params.time_cost, 3);
+        assert_eq!(params.lanes, 4);
     }
 }
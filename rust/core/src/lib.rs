@@ -38,6 +38,10 @@ pub use signature::Signature;
 
 mod u256_struct;
 
+pub mod hd_wallet;
+
+pub mod threshold;
+
 pub mod utils;
 
 mod qualified_root;
@@ -70,7 +74,10 @@ mod unchecked_info;
 pub use unchecked_info::{UncheckedInfo, UncheckedKey};
 
 mod kdf;
-pub use kdf::KeyDerivationFunction;
+pub use kdf::{KdfAlgorithm, KdfParams, KeyDerivationFunction};
+
+mod passphrase_cipher;
+pub use passphrase_cipher::PassphraseCipher;
 use utils::{BufferWriter, Deserialize, Serialize, Stream};
 
 use std::{
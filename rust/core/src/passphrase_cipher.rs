@@ -0,0 +1,133 @@
+use crate::KdfParams;
+use aes::cipher::{KeyIvInit, StreamCipher};
+use anyhow::bail;
+use hmac::{Hmac, Mac};
+use rand::{thread_rng, Rng};
+use sha2::Sha512;
+
+type Aes256Ctr = ctr::Ctr64BE<aes::Aes256>;
+type HmacSha512 = Hmac<Sha512>;
+
+/// Authenticated, passphrase based encryption for data that leaves the node, like wallet backups.
+///
+/// Layout: magic, format version, KDF parameters, salt, IV, AES-256-CTR ciphertext and a
+/// HMAC-SHA512 tag over everything before it. Encryption and MAC keys are derived from the
+/// Argon2 hash of the passphrase.
+pub struct PassphraseCipher {
+    params: KdfParams,
+}
+
+impl PassphraseCipher {
+    const MAGIC: &'static [u8; 6] = b"RSNENC";
+    const VERSION: u8 = 1;
+    const HEADER_LEN: usize = 6 + 1 + 32 + 32 + 16;
+    const TAG_LEN: usize = 64;
+
+    pub fn new(params: KdfParams) -> Self {
+        Self { params }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8], passphrase: &str) -> Vec<u8> {
+        let salt: [u8; 32] = thread_rng().gen();
+        let iv: [u8; 16] = thread_rng().gen();
+
+        let mut data = Vec::with_capacity(Self::HEADER_LEN + plaintext.len() + Self::TAG_LEN);
+        data.extend_from_slice(Self::MAGIC);
+        data.push(Self::VERSION);
+        data.extend_from_slice(&self.params.to_bytes());
+        data.extend_from_slice(&salt);
+        data.extend_from_slice(&iv);
+
+        let (encryption_key, mac_key) = Self::derive_keys(&self.params, passphrase, &salt);
+        let mut ciphertext = plaintext.to_vec();
+        Aes256Ctr::new(&encryption_key.into(), &iv.into()).apply_keystream(&mut ciphertext);
+        data.extend_from_slice(&ciphertext);
+
+        let mut hmac = new_hmac(&mac_key);
+        hmac.update(&data);
+        data.extend_from_slice(&hmac.finalize().into_bytes());
+        data
+    }
+
+    pub fn decrypt(data: &[u8], passphrase: &str) -> anyhow::Result<Vec<u8>> {
+        if data.len() < Self::HEADER_LEN + Self::TAG_LEN || &data[..6] != Self::MAGIC {
+            bail!("not an encrypted file");
+        }
+        if data[6] != Self::VERSION {
+            bail!("unsupported encryption format version: {}", data[6]);
+        }
+        let params = KdfParams::from_bytes(data[7..39].try_into().unwrap())?;
+        let salt: [u8; 32] = data[39..71].try_into().unwrap();
+        let iv: [u8; 16] = data[71..87].try_into().unwrap();
+        let (authenticated, tag) = data.split_at(data.len() - Self::TAG_LEN);
+
+        let (encryption_key, mac_key) = Self::derive_keys(&params, passphrase, &salt);
+        let mut hmac = new_hmac(&mac_key);
+        hmac.update(authenticated);
+        if hmac.verify_slice(tag).is_err() {
+            bail!("invalid passphrase or corrupted file");
+        }
+
+        let mut plaintext = authenticated[Self::HEADER_LEN..].to_vec();
+        Aes256Ctr::new(&encryption_key.into(), &iv.into()).apply_keystream(&mut plaintext);
+        Ok(plaintext)
+    }
+
+    fn derive_keys(params: &KdfParams, passphrase: &str, salt: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+        let master = params.hash_password(passphrase, salt);
+        let mut hmac = new_hmac(master.as_bytes());
+        hmac.update(b"rsnano passphrase cipher");
+        let keys = hmac.finalize().into_bytes();
+        (
+            keys[..32].try_into().unwrap(),
+            keys[32..].try_into().unwrap(),
+        )
+    }
+}
+
+fn new_hmac(key: &[u8]) -> HmacSha512 {
+    HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KdfAlgorithm;
+
+    fn cipher() -> PassphraseCipher {
+        // Cheap parameters to keep the tests fast
+        PassphraseCipher::new(KdfParams {
+            algorithm: KdfAlgorithm::Argon2id,
+            mem_cost: 32,
+            time_cost: 1,
+            lanes: 4,
+        })
+    }
+
+    #[test]
+    fn roundtrip() {
+        let encrypted = cipher().encrypt(b"hello world", "secret");
+        assert_eq!(
+            PassphraseCipher::decrypt(&encrypted, "secret").unwrap(),
+            b"hello world"
+        );
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let encrypted = cipher().encrypt(b"hello world", "secret");
+        assert!(PassphraseCipher::decrypt(&encrypted, "wrong").is_err());
+    }
+
+    #[test]
+    fn tampered_ciphertext() {
+        let mut encrypted = cipher().encrypt(b"hello world", "secret");
+        encrypted[PassphraseCipher::HEADER_LEN] ^= 1;
+        assert!(PassphraseCipher::decrypt(&encrypted, "secret").is_err());
+    }
+
+    #[test]
+    fn not_encrypted() {
+        assert!(PassphraseCipher::decrypt(b"{}", "secret").is_err());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::utils::{BufferReader, Stream};
+use anyhow::anyhow;
+use blake2::{Blake2b512, Digest};
+use curve25519_dalek::{edwards::CompressedEdwardsY, EdwardsPoint, Scalar};
+use rand::thread_rng;
+
+pub(super) fn random_scalar() -> Scalar {
+    Scalar::random(&mut thread_rng())
+}
+
+/// Hashes the parts with blake2b-512 and reduces the result to a scalar
+pub(super) fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Blake2b512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    Scalar::from_hash(hasher)
+}
+
+pub(super) fn write_u16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.extend_from_slice(&value.to_be_bytes());
+}
+
+pub(super) fn read_u16(reader: &mut BufferReader) -> anyhow::Result<u16> {
+    let mut buffer = [0; 2];
+    reader.read_bytes(&mut buffer, 2)?;
+    Ok(u16::from_be_bytes(buffer))
+}
+
+pub(super) fn read_array(reader: &mut BufferReader) -> anyhow::Result<[u8; 32]> {
+    let mut buffer = [0; 32];
+    reader.read_bytes(&mut buffer, 32)?;
+    Ok(buffer)
+}
+
+pub(super) fn read_scalar(reader: &mut BufferReader) -> anyhow::Result<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(read_array(reader)?))
+        .ok_or_else(|| anyhow!("invalid scalar"))
+}
+
+pub(super) fn read_point(reader: &mut BufferReader) -> anyhow::Result<EdwardsPoint> {
+    CompressedEdwardsY(read_array(reader)?)
+        .decompress()
+        .ok_or_else(|| anyhow!("invalid curve point"))
+}
+
+pub(super) fn ensure_at_end(reader: &BufferReader) -> anyhow::Result<()> {
+    if !reader.remaining().is_empty() {
+        bail!("unexpected trailing bytes");
+    }
+    Ok(())
+}
+
+pub(super) fn read_version(reader: &mut BufferReader, expected: u8) -> anyhow::Result<()> {
+    let version = reader.read_u8()?;
+    if version != expected {
+        bail!("unsupported version: {}", version);
+    }
+    Ok(())
+}
+
+pub(super) fn scalar_from_id(id: u16) -> Scalar {
+    Scalar::from(id as u64)
+}
@@ -0,0 +1,389 @@
+use super::{
+    encoding::{
+        ensure_at_end, hash_to_scalar, random_scalar, read_point, read_scalar, read_u16,
+        read_version, scalar_from_id, write_u16,
+    },
+    ParticipantId, ThresholdGroup, ThresholdKeyShare,
+};
+use crate::{utils::BufferReader, PublicKey};
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, EdwardsPoint, Scalar};
+
+/// One participant of the distributed key generation. Participants are numbered
+/// from 1 to `participants`. The secret polynomial only lives in memory until
+/// `finish` is called
+pub struct KeyGenParticipant {
+    participant: ParticipantId,
+    threshold: u16,
+    participants: u16,
+    coefficients: Vec<Scalar>,
+    commitment: KeyGenCommitment,
+}
+
+impl KeyGenParticipant {
+    pub fn new(
+        participant: ParticipantId,
+        threshold: u16,
+        participants: u16,
+    ) -> anyhow::Result<Self> {
+        if threshold == 0 || threshold > participants {
+            bail!(
+                "invalid threshold {} for {} participants",
+                threshold,
+                participants
+            );
+        }
+        if participant == 0 || participant > participants {
+            bail!("participant must be between 1 and {}", participants);
+        }
+
+        let coefficients: Vec<Scalar> = (0..threshold).map(|_| random_scalar()).collect();
+        let commitments: Vec<EdwardsPoint> = coefficients
+            .iter()
+            .map(|c| c * ED25519_BASEPOINT_TABLE)
+            .collect();
+
+        // Proves knowledge of the constant term, so nobody can pick their
+        // contribution to the group key depending on the others
+        let nonce = random_scalar();
+        let proof_r = &nonce * ED25519_BASEPOINT_TABLE;
+        let challenge = proof_challenge(participant, &commitments[0], &proof_r);
+        let proof_z = nonce + coefficients[0] * challenge;
+
+        Ok(Self {
+            participant,
+            threshold,
+            participants,
+            coefficients,
+            commitment: KeyGenCommitment {
+                participant,
+                coefficients: commitments,
+                proof_r,
+                proof_z,
+            },
+        })
+    }
+
+    pub fn participant(&self) -> ParticipantId {
+        self.participant
+    }
+
+    /// Has to be broadcast to all other participants
+    pub fn commitment(&self) -> &KeyGenCommitment {
+        &self.commitment
+    }
+
+    /// Has to be sent privately to the recipient
+    pub fn secret_share_for(&self, recipient: ParticipantId) -> anyhow::Result<KeyGenSecretShare> {
+        if recipient == 0 || recipient > self.participants || recipient == self.participant {
+            bail!("invalid recipient: {}", recipient);
+        }
+        Ok(KeyGenSecretShare {
+            from: self.participant,
+            to: recipient,
+            value: evaluate_polynomial(&self.coefficients, recipient),
+        })
+    }
+
+    /// Checks the commitments of all participants and the shares sent to this participant
+    /// and combines them into this participant's key share
+    pub fn finish(
+        self,
+        commitments: &[KeyGenCommitment],
+        shares: &[KeyGenSecretShare],
+    ) -> anyhow::Result<ThresholdKeyShare> {
+        let mut all_commitments = Vec::with_capacity(self.participants as usize);
+        for id in 1..=self.participants {
+            let mut matching = commitments.iter().filter(|c| c.participant == id);
+            let commitment = match (matching.next(), matching.next()) {
+                (Some(c), None) => c,
+                _ => bail!("expected exactly one commitment of participant {}", id),
+            };
+            if commitment.coefficients.len() != self.threshold as usize {
+                bail!("commitment of participant {} has the wrong threshold", id);
+            }
+            commitment.verify()?;
+            all_commitments.push(commitment);
+        }
+        if *all_commitments[self.participant as usize - 1] != self.commitment {
+            bail!("the own commitment was modified");
+        }
+
+        let mut secret = evaluate_polynomial(&self.coefficients, self.participant);
+        for id in (1..=self.participants).filter(|id| *id != self.participant) {
+            let mut matching = shares
+                .iter()
+                .filter(|s| s.from == id && s.to == self.participant);
+            let share = match (matching.next(), matching.next()) {
+                (Some(s), None) => s,
+                _ => bail!("expected exactly one secret share from participant {}", id),
+            };
+            let expected = evaluate_commitment(
+                &all_commitments[id as usize - 1].coefficients,
+                self.participant,
+            );
+            if &share.value * ED25519_BASEPOINT_TABLE != expected {
+                bail!("invalid secret share from participant {}", id);
+            }
+            secret += share.value;
+        }
+
+        let group_key: EdwardsPoint = all_commitments.iter().map(|c| c.coefficients[0]).sum();
+        let verifying_shares = (1..=self.participants)
+            .map(|id| {
+                let share = all_commitments
+                    .iter()
+                    .map(|c| evaluate_commitment(&c.coefficients, id))
+                    .sum();
+                (id, share)
+            })
+            .collect();
+        let group = ThresholdGroup::new(
+            self.threshold,
+            PublicKey::from_bytes(group_key.compress().to_bytes()),
+            verifying_shares,
+        )?;
+        ThresholdKeyShare::new(self.participant, secret, group)
+    }
+}
+
+/// The public commitment to a participant's secret polynomial
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeyGenCommitment {
+    participant: ParticipantId,
+    coefficients: Vec<EdwardsPoint>,
+    proof_r: EdwardsPoint,
+    proof_z: Scalar,
+}
+
+impl KeyGenCommitment {
+    const VERSION: u8 = 1;
+
+    pub fn participant(&self) -> ParticipantId {
+        self.participant
+    }
+
+    fn verify(&self) -> anyhow::Result<()> {
+        let challenge = proof_challenge(self.participant, &self.coefficients[0], &self.proof_r);
+        if &self.proof_z * ED25519_BASEPOINT_TABLE
+            != self.proof_r + self.coefficients[0] * challenge
+        {
+            bail!(
+                "invalid proof of knowledge from participant {}",
+                self.participant
+            );
+        }
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![Self::VERSION];
+        write_u16(&mut bytes, self.participant);
+        write_u16(&mut bytes, self.coefficients.len() as u16);
+        for coefficient in &self.coefficients {
+            bytes.extend_from_slice(coefficient.compress().as_bytes());
+        }
+        bytes.extend_from_slice(self.proof_r.compress().as_bytes());
+        bytes.extend_from_slice(self.proof_z.as_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = BufferReader::new(bytes);
+        read_version(&mut reader, Self::VERSION)?;
+        let participant = read_u16(&mut reader)?;
+        let count = read_u16(&mut reader)?;
+        if count == 0 {
+            bail!("commitment without coefficients");
+        }
+        let mut coefficients = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            coefficients.push(read_point(&mut reader)?);
+        }
+        let commitment = Self {
+            participant,
+            coefficients,
+            proof_r: read_point(&mut reader)?,
+            proof_z: read_scalar(&mut reader)?,
+        };
+        ensure_at_end(&reader)?;
+        Ok(commitment)
+    }
+
+    pub fn encode_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    pub fn decode_hex(s: impl AsRef<str>) -> anyhow::Result<Self> {
+        Self::from_bytes(&hex::decode(s.as_ref().trim())?)
+    }
+}
+
+/// A secret value that one participant sends privately to another one
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyGenSecretShare {
+    from: ParticipantId,
+    to: ParticipantId,
+    value: Scalar,
+}
+
+impl KeyGenSecretShare {
+    const VERSION: u8 = 1;
+
+    pub fn from(&self) -> ParticipantId {
+        self.from
+    }
+
+    pub fn to(&self) -> ParticipantId {
+        self.to
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![Self::VERSION];
+        write_u16(&mut bytes, self.from);
+        write_u16(&mut bytes, self.to);
+        bytes.extend_from_slice(self.value.as_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = BufferReader::new(bytes);
+        read_version(&mut reader, Self::VERSION)?;
+        let share = Self {
+            from: read_u16(&mut reader)?,
+            to: read_u16(&mut reader)?,
+            value: read_scalar(&mut reader)?,
+        };
+        ensure_at_end(&reader)?;
+        Ok(share)
+    }
+
+    pub fn encode_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    pub fn decode_hex(s: impl AsRef<str>) -> anyhow::Result<Self> {
+        Self::from_bytes(&hex::decode(s.as_ref().trim())?)
+    }
+}
+
+impl std::fmt::Debug for KeyGenSecretShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyGenSecretShare")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .finish_non_exhaustive()
+    }
+}
+
+fn proof_challenge(
+    participant: ParticipantId,
+    constant: &EdwardsPoint,
+    proof_r: &EdwardsPoint,
+) -> Scalar {
+    hash_to_scalar(&[
+        b"rsnano threshold keygen",
+        &participant.to_be_bytes(),
+        constant.compress().as_bytes(),
+        proof_r.compress().as_bytes(),
+    ])
+}
+
+fn evaluate_polynomial(coefficients: &[Scalar], participant: ParticipantId) -> Scalar {
+    let x = scalar_from_id(participant);
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, coefficient| acc * x + coefficient)
+}
+
+fn evaluate_commitment(coefficients: &[EdwardsPoint], participant: ParticipantId) -> EdwardsPoint {
+    let x = scalar_from_id(participant);
+    coefficients
+        .iter()
+        .rev()
+        .fold(EdwardsPoint::default(), |acc, coefficient| {
+            acc * x + coefficient
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threshold::create_key_shares;
+
+    #[test]
+    fn all_participants_get_the_same_group() {
+        let shares = create_key_shares(2, 3);
+        assert_eq!(shares[0].group(), shares[1].group());
+        assert_eq!(shares[0].group(), shares[2].group());
+        assert_eq!(shares[0].group().threshold(), 2);
+        assert_eq!(
+            shares[0].group().participants().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert!(KeyGenParticipant::new(1, 0, 3).is_err());
+        assert!(KeyGenParticipant::new(1, 4, 3).is_err());
+        assert!(KeyGenParticipant::new(0, 2, 3).is_err());
+        assert!(KeyGenParticipant::new(4, 2, 3).is_err());
+        let participant = KeyGenParticipant::new(1, 2, 3).unwrap();
+        assert!(participant.secret_share_for(1).is_err());
+        assert!(participant.secret_share_for(4).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_secret_share() {
+        let p1 = KeyGenParticipant::new(1, 2, 2).unwrap();
+        let p2 = KeyGenParticipant::new(2, 2, 2).unwrap();
+        let commitments = vec![p1.commitment().clone(), p2.commitment().clone()];
+        let mut share = p2.secret_share_for(1).unwrap();
+        share.value += Scalar::ONE;
+
+        let error = p1.finish(&commitments, &[share]).unwrap_err();
+        assert_eq!(error.to_string(), "invalid secret share from participant 2");
+    }
+
+    #[test]
+    fn reject_invalid_proof_of_knowledge() {
+        let p1 = KeyGenParticipant::new(1, 2, 2).unwrap();
+        let p2 = KeyGenParticipant::new(2, 2, 2).unwrap();
+        let share = p2.secret_share_for(1).unwrap();
+        let mut commitment2 = p2.commitment().clone();
+        commitment2.proof_z += Scalar::ONE;
+
+        let commitments = vec![p1.commitment().clone(), commitment2];
+
+        let error = p1.finish(&commitments, &[share]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid proof of knowledge from participant 2"
+        );
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let participant = KeyGenParticipant::new(1, 2, 3).unwrap();
+        let commitment = participant.commitment();
+        assert_eq!(
+            &KeyGenCommitment::decode_hex(commitment.encode_hex()).unwrap(),
+            commitment
+        );
+        let share = participant.secret_share_for(2).unwrap();
+        assert_eq!(
+            KeyGenSecretShare::decode_hex(share.encode_hex()).unwrap(),
+            share
+        );
+
+        let key_share = &create_key_shares(2, 3)[0];
+        let decoded = ThresholdKeyShare::decode_hex(key_share.encode_hex()).unwrap();
+        assert_eq!(decoded.participant(), key_share.participant());
+        assert_eq!(decoded.group(), key_share.group());
+        assert_eq!(
+            &ThresholdGroup::decode_hex(key_share.group().encode_hex()).unwrap(),
+            key_share.group()
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use super::{
+    encoding::{
+        ensure_at_end, read_array, read_point, read_scalar, read_u16, read_version, write_u16,
+    },
+    ParticipantId,
+};
+use crate::{utils::BufferReader, Account, PublicKey};
+use anyhow::Context;
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE, edwards::CompressedEdwardsY, EdwardsPoint, Scalar,
+};
+
+/// The public part of a threshold key: everything needed to check partial
+/// signatures and to combine them into a signature of the group's account
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ThresholdGroup {
+    threshold: u16,
+    public_key: PublicKey,
+    /// The public key of each participant's secret share, sorted by participant
+    verifying_shares: Vec<(ParticipantId, EdwardsPoint)>,
+}
+
+impl ThresholdGroup {
+    const VERSION: u8 = 1;
+
+    pub(super) fn new(
+        threshold: u16,
+        public_key: PublicKey,
+        mut verifying_shares: Vec<(ParticipantId, EdwardsPoint)>,
+    ) -> anyhow::Result<Self> {
+        verifying_shares.sort_by_key(|(id, _)| *id);
+        if threshold == 0 || threshold as usize > verifying_shares.len() {
+            bail!(
+                "invalid threshold {} for {} participants",
+                threshold,
+                verifying_shares.len()
+            );
+        }
+        if verifying_shares.first().map(|(id, _)| *id) == Some(0)
+            || verifying_shares.windows(2).any(|w| w[0].0 == w[1].0)
+        {
+            bail!("participant ids must be unique and greater than zero");
+        }
+        CompressedEdwardsY(*public_key.as_bytes())
+            .decompress()
+            .context("invalid group public key")?;
+        Ok(Self {
+            threshold,
+            public_key,
+            verifying_shares,
+        })
+    }
+
+    /// Number of participants that have to sign
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    pub fn account(&self) -> Account {
+        self.public_key.into()
+    }
+
+    pub fn participants(&self) -> impl Iterator<Item = ParticipantId> + '_ {
+        self.verifying_shares.iter().map(|(id, _)| *id)
+    }
+
+    pub fn contains(&self, participant: ParticipantId) -> bool {
+        self.verifying_share(participant).is_some()
+    }
+
+    pub(super) fn verifying_share(&self, participant: ParticipantId) -> Option<&EdwardsPoint> {
+        self.verifying_shares
+            .iter()
+            .find(|(id, _)| *id == participant)
+            .map(|(_, share)| share)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![Self::VERSION];
+        self.write(&mut bytes);
+        bytes
+    }
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        write_u16(bytes, self.threshold);
+        bytes.extend_from_slice(self.public_key.as_bytes());
+        write_u16(bytes, self.verifying_shares.len() as u16);
+        for (id, share) in &self.verifying_shares {
+            write_u16(bytes, *id);
+            bytes.extend_from_slice(share.compress().as_bytes());
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = BufferReader::new(bytes);
+        read_version(&mut reader, Self::VERSION)?;
+        let group = Self::read(&mut reader)?;
+        ensure_at_end(&reader)?;
+        Ok(group)
+    }
+
+    fn read(reader: &mut BufferReader) -> anyhow::Result<Self> {
+        let threshold = read_u16(reader)?;
+        let public_key = PublicKey::from_bytes(read_array(reader)?);
+        let count = read_u16(reader)?;
+        let mut verifying_shares = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            verifying_shares.push((read_u16(reader)?, read_point(reader)?));
+        }
+        Self::new(threshold, public_key, verifying_shares)
+    }
+
+    pub fn encode_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    pub fn decode_hex(s: impl AsRef<str>) -> anyhow::Result<Self> {
+        Self::from_bytes(&hex::decode(s.as_ref().trim())?)
+    }
+}
+
+/// The secret share of one participant of a threshold group
+#[derive(Clone)]
+pub struct ThresholdKeyShare {
+    participant: ParticipantId,
+    secret: Scalar,
+    group: ThresholdGroup,
+}
+
+impl ThresholdKeyShare {
+    const VERSION: u8 = 1;
+
+    pub(super) fn new(
+        participant: ParticipantId,
+        secret: Scalar,
+        group: ThresholdGroup,
+    ) -> anyhow::Result<Self> {
+        let expected = group
+            .verifying_share(participant)
+            .ok_or_else(|| anyhow!("participant {} is not in the group", participant))?;
+        if &secret * ED25519_BASEPOINT_TABLE != *expected {
+            bail!("secret share does not match the group");
+        }
+        Ok(Self {
+            participant,
+            secret,
+            group,
+        })
+    }
+
+    pub fn participant(&self) -> ParticipantId {
+        self.participant
+    }
+
+    pub fn group(&self) -> &ThresholdGroup {
+        &self.group
+    }
+
+    pub(super) fn secret(&self) -> &Scalar {
+        &self.secret
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![Self::VERSION];
+        write_u16(&mut bytes, self.participant);
+        bytes.extend_from_slice(self.secret.as_bytes());
+        self.group.write(&mut bytes);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = BufferReader::new(bytes);
+        read_version(&mut reader, Self::VERSION)?;
+        let participant = read_u16(&mut reader)?;
+        let secret = read_scalar(&mut reader)?;
+        let group = ThresholdGroup::read(&mut reader)?;
+        ensure_at_end(&reader)?;
+        Self::new(participant, secret, group)
+    }
+
+    pub fn encode_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    pub fn decode_hex(s: impl AsRef<str>) -> anyhow::Result<Self> {
+        Self::from_bytes(&hex::decode(s.as_ref().trim())?)
+    }
+}
+
+impl std::fmt::Debug for ThresholdKeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThresholdKeyShare")
+            .field("participant", &self.participant)
+            .field("group", &self.group)
+            .finish_non_exhaustive()
+    }
+}
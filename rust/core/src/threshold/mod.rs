@@ -0,0 +1,48 @@
+//! Threshold signing for shared accounts: `t` of `n` participants have to cooperate
+//! to sign a block, and no participant ever holds the full private key.
+//!
+//! The scheme is FROST with the blake2b challenge of Nano's ed25519, so the result is
+//! a normal `Signature` for a normal `Account`.
+//!
+//! Key generation (no trusted dealer):
+//! 1. every participant creates a `KeyGenParticipant` and broadcasts its `KeyGenCommitment`
+//! 2. every participant privately sends each other participant a `KeyGenSecretShare`
+//!    and then calls `KeyGenParticipant::finish`
+//!
+//! Signing:
+//! 1. every signer creates `SigningNonces` and publishes their `SigningCommitment`
+//! 2. once `t` commitments are collected, every signer creates a `PartialSignature`
+//!    which `ThresholdGroup::aggregate` combines into the final signature
+
+mod encoding;
+mod key_generation;
+mod key_share;
+mod signing;
+
+pub use key_generation::{KeyGenCommitment, KeyGenParticipant, KeyGenSecretShare};
+pub use key_share::{ThresholdGroup, ThresholdKeyShare};
+pub use signing::{PartialSignature, SigningCommitment, SigningNonces};
+
+/// Identifies a participant of a threshold group. Participants are numbered from 1
+pub type ParticipantId = u16;
+
+/// Runs the key generation for all participants at once
+#[cfg(any(test, feature = "test-utils"))]
+pub fn create_key_shares(threshold: u16, participants: u16) -> Vec<ThresholdKeyShare> {
+    let keygens: Vec<_> = (1..=participants)
+        .map(|id| KeyGenParticipant::new(id, threshold, participants).unwrap())
+        .collect();
+    let commitments: Vec<_> = keygens.iter().map(|p| p.commitment().clone()).collect();
+    let shares: Vec<_> = keygens
+        .iter()
+        .flat_map(|p| {
+            (1..=participants)
+                .filter(|to| *to != p.participant())
+                .map(|to| p.secret_share_for(to).unwrap())
+        })
+        .collect();
+    keygens
+        .into_iter()
+        .map(|p| p.finish(&commitments, &shares).unwrap())
+        .collect()
+}
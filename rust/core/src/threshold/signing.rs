@@ -0,0 +1,495 @@
+use super::{
+    encoding::{
+        ensure_at_end, hash_to_scalar, read_point, read_scalar, read_u16, read_version,
+        scalar_from_id, write_u16,
+    },
+    ParticipantId, ThresholdGroup, ThresholdKeyShare,
+};
+use crate::{utils::BufferReader, validate_message, Signature};
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, EdwardsPoint, Scalar};
+use rand::{thread_rng, RngCore};
+
+/// The secret nonces of one signer for one signature. Nonces must never be reused,
+/// so `ThresholdKeyShare::sign` consumes them
+pub struct SigningNonces {
+    participant: ParticipantId,
+    hiding: Scalar,
+    binding: Scalar,
+    commitment: SigningCommitment,
+}
+
+impl SigningNonces {
+    pub fn new(key_share: &ThresholdKeyShare) -> Self {
+        let participant = key_share.participant();
+        let secret = key_share.secret();
+        let hiding = generate_nonce(secret);
+        let binding = generate_nonce(secret);
+        let hiding_point = &hiding * ED25519_BASEPOINT_TABLE;
+        let binding_point = &binding * ED25519_BASEPOINT_TABLE;
+
+        // Schnorr proof that the commitment was made by the holder of the secret share
+        let proof_nonce = generate_nonce(secret);
+        let proof_point = &proof_nonce * ED25519_BASEPOINT_TABLE;
+        let challenge = commitment_challenge(
+            key_share.group(),
+            participant,
+            &hiding_point,
+            &binding_point,
+            &proof_point,
+        );
+
+        Self {
+            participant,
+            hiding,
+            binding,
+            commitment: SigningCommitment {
+                participant,
+                hiding: hiding_point,
+                binding: binding_point,
+                proof_point,
+                proof_scalar: proof_nonce + challenge * secret,
+            },
+        }
+    }
+
+    /// Has to be sent to the coordinator in the first round
+    pub fn commitment(&self) -> SigningCommitment {
+        self.commitment.clone()
+    }
+}
+
+/// Mixes the secret share into the nonce, so a weak random number generator
+/// alone doesn't leak the secret share
+fn generate_nonce(secret: &Scalar) -> Scalar {
+    let mut random = [0u8; 32];
+    thread_rng().fill_bytes(&mut random);
+    hash_to_scalar(&[b"rsnano threshold nonce", &random, secret.as_bytes()])
+}
+
+fn commitment_challenge(
+    group: &ThresholdGroup,
+    participant: ParticipantId,
+    hiding: &EdwardsPoint,
+    binding: &EdwardsPoint,
+    proof_point: &EdwardsPoint,
+) -> Scalar {
+    hash_to_scalar(&[
+        b"rsnano threshold commitment",
+        group.public_key().as_bytes(),
+        &participant.to_be_bytes(),
+        hiding.compress().as_bytes(),
+        binding.compress().as_bytes(),
+        proof_point.compress().as_bytes(),
+    ])
+}
+
+/// The public commitment to the nonces of one signer. It carries a proof of
+/// knowledge of the signer's secret share, so nobody can commit in the name of
+/// another group member
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SigningCommitment {
+    participant: ParticipantId,
+    hiding: EdwardsPoint,
+    binding: EdwardsPoint,
+    proof_point: EdwardsPoint,
+    proof_scalar: Scalar,
+}
+
+impl SigningCommitment {
+    const VERSION: u8 = 2;
+
+    pub fn participant(&self) -> ParticipantId {
+        self.participant
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![Self::VERSION];
+        write_u16(&mut bytes, self.participant);
+        bytes.extend_from_slice(self.hiding.compress().as_bytes());
+        bytes.extend_from_slice(self.binding.compress().as_bytes());
+        bytes.extend_from_slice(self.proof_point.compress().as_bytes());
+        bytes.extend_from_slice(self.proof_scalar.as_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = BufferReader::new(bytes);
+        read_version(&mut reader, Self::VERSION)?;
+        let commitment = Self {
+            participant: read_u16(&mut reader)?,
+            hiding: read_point(&mut reader)?,
+            binding: read_point(&mut reader)?,
+            proof_point: read_point(&mut reader)?,
+            proof_scalar: read_scalar(&mut reader)?,
+        };
+        ensure_at_end(&reader)?;
+        Ok(commitment)
+    }
+
+    pub fn encode_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    pub fn decode_hex(s: impl AsRef<str>) -> anyhow::Result<Self> {
+        Self::from_bytes(&hex::decode(s.as_ref().trim())?)
+    }
+}
+
+/// The contribution of one signer to the final signature
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PartialSignature(Scalar);
+
+impl PartialSignature {
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = BufferReader::new(bytes);
+        let scalar = read_scalar(&mut reader)?;
+        ensure_at_end(&reader)?;
+        Ok(Self(scalar))
+    }
+
+    pub fn encode_hex(&self) -> String {
+        hex::encode_upper(self.to_bytes())
+    }
+
+    pub fn decode_hex(s: impl AsRef<str>) -> anyhow::Result<Self> {
+        Self::from_bytes(&hex::decode(s.as_ref().trim())?)
+    }
+}
+
+impl ThresholdKeyShare {
+    /// Second round: signs the message with the nonces of the first round. `commitments`
+    /// are the commitments of all signers, including this one
+    pub fn sign(
+        &self,
+        nonces: SigningNonces,
+        message: &[u8],
+        commitments: &[SigningCommitment],
+    ) -> anyhow::Result<PartialSignature> {
+        if nonces.participant != self.participant() {
+            bail!("the nonces belong to another participant");
+        }
+        let package = SigningPackage::new(self.group(), message, commitments)?;
+        let index = package
+            .index_of(self.participant())
+            .ok_or_else(|| anyhow!("participant {} is not a signer", self.participant()))?;
+        if *package.commitments[index] != nonces.commitment() {
+            bail!("the commitment doesn't match the nonces");
+        }
+
+        let z = nonces.hiding
+            + nonces.binding * package.binding_factors[index]
+            + package.lagrange_coefficient(index) * self.secret() * package.challenge;
+        Ok(PartialSignature(z))
+    }
+}
+
+impl ThresholdGroup {
+    /// Checks that the commitment was made by the group member it names
+    pub fn verify_commitment(&self, commitment: &SigningCommitment) -> anyhow::Result<()> {
+        let verifying_share = self
+            .verifying_share(commitment.participant)
+            .ok_or_else(|| anyhow!("participant {} is not in the group", commitment.participant))?;
+        let challenge = commitment_challenge(
+            self,
+            commitment.participant,
+            &commitment.hiding,
+            &commitment.binding,
+            &commitment.proof_point,
+        );
+        if &commitment.proof_scalar * ED25519_BASEPOINT_TABLE
+            != commitment.proof_point + verifying_share * challenge
+        {
+            bail!(
+                "commitment is not signed by participant {}",
+                commitment.participant
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks the partial signature of one signer
+    pub fn verify_partial(
+        &self,
+        message: &[u8],
+        commitments: &[SigningCommitment],
+        participant: ParticipantId,
+        partial: &PartialSignature,
+    ) -> anyhow::Result<()> {
+        let package = SigningPackage::new(self, message, commitments)?;
+        package.verify_partial(self, participant, partial)
+    }
+
+    /// Checks all partial signatures and combines them into the signature of the group account
+    pub fn aggregate(
+        &self,
+        message: &[u8],
+        commitments: &[SigningCommitment],
+        partials: &[(ParticipantId, PartialSignature)],
+    ) -> anyhow::Result<Signature> {
+        let package = SigningPackage::new(self, message, commitments)?;
+        let mut z = Scalar::ZERO;
+        for commitment in &package.commitments {
+            let mut matching = partials
+                .iter()
+                .filter(|(id, _)| *id == commitment.participant);
+            let partial = match (matching.next(), matching.next()) {
+                (Some((_, partial)), None) => partial,
+                _ => bail!(
+                    "expected exactly one partial signature of participant {}",
+                    commitment.participant
+                ),
+            };
+            package.verify_partial(self, commitment.participant, partial)?;
+            z += partial.0;
+        }
+        if partials.len() != package.commitments.len() {
+            bail!("got partial signatures of participants that are not signers");
+        }
+
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(package.group_commitment.compress().as_bytes());
+        bytes[32..].copy_from_slice(z.as_bytes());
+        let signature = Signature::from_bytes(bytes);
+        validate_message(&self.public_key(), message, &signature)?;
+        Ok(signature)
+    }
+}
+
+/// Everything the signers derive from the message and the commitments
+struct SigningPackage<'a> {
+    /// Sorted by participant
+    commitments: Vec<&'a SigningCommitment>,
+    binding_factors: Vec<Scalar>,
+    group_commitment: EdwardsPoint,
+    challenge: Scalar,
+}
+
+impl<'a> SigningPackage<'a> {
+    fn new(
+        group: &ThresholdGroup,
+        message: &[u8],
+        commitments: &'a [SigningCommitment],
+    ) -> anyhow::Result<Self> {
+        let mut commitments: Vec<_> = commitments.iter().collect();
+        commitments.sort_by_key(|c| c.participant);
+        if commitments.len() < group.threshold() as usize {
+            bail!(
+                "{} signers are needed, but only {} commitments were given",
+                group.threshold(),
+                commitments.len()
+            );
+        }
+        if commitments
+            .windows(2)
+            .any(|w| w[0].participant == w[1].participant)
+        {
+            bail!("duplicate commitments");
+        }
+        for commitment in &commitments {
+            group.verify_commitment(commitment)?;
+        }
+
+        let encoded: Vec<u8> = commitments.iter().flat_map(|c| c.to_bytes()).collect();
+        let public_key = group.public_key();
+        let binding_factors: Vec<Scalar> = commitments
+            .iter()
+            .map(|c| {
+                hash_to_scalar(&[
+                    b"rsnano threshold binding",
+                    public_key.as_bytes(),
+                    message,
+                    &encoded,
+                    &c.participant.to_be_bytes(),
+                ])
+            })
+            .collect();
+        let group_commitment: EdwardsPoint = commitments
+            .iter()
+            .zip(&binding_factors)
+            .map(|(c, factor)| c.hiding + c.binding * factor)
+            .sum();
+        // The same challenge that ed25519 signature validation computes
+        let challenge = hash_to_scalar(&[
+            group_commitment.compress().as_bytes(),
+            public_key.as_bytes(),
+            message,
+        ]);
+
+        Ok(Self {
+            commitments,
+            binding_factors,
+            group_commitment,
+            challenge,
+        })
+    }
+
+    fn index_of(&self, participant: ParticipantId) -> Option<usize> {
+        self.commitments
+            .iter()
+            .position(|c| c.participant == participant)
+    }
+
+    fn lagrange_coefficient(&self, index: usize) -> Scalar {
+        let x = scalar_from_id(self.commitments[index].participant);
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for (i, commitment) in self.commitments.iter().enumerate() {
+            if i != index {
+                let other = scalar_from_id(commitment.participant);
+                numerator *= other;
+                denominator *= other - x;
+            }
+        }
+        numerator * denominator.invert()
+    }
+
+    fn verify_partial(
+        &self,
+        group: &ThresholdGroup,
+        participant: ParticipantId,
+        partial: &PartialSignature,
+    ) -> anyhow::Result<()> {
+        let index = self
+            .index_of(participant)
+            .ok_or_else(|| anyhow!("participant {} is not a signer", participant))?;
+        let verifying_share = group
+            .verifying_share(participant)
+            .ok_or_else(|| anyhow!("participant {} is not in the group", participant))?;
+        let commitment = self.commitments[index];
+        let expected = commitment.hiding
+            + commitment.binding * self.binding_factors[index]
+            + verifying_share * (self.challenge * self.lagrange_coefficient(index));
+        if &partial.0 * ED25519_BASEPOINT_TABLE != expected {
+            bail!("invalid partial signature of participant {}", participant);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{threshold::create_key_shares, validate_block_signature, BlockBuilder, BlockEnum};
+
+    fn sign(signers: &[&ThresholdKeyShare], message: &[u8]) -> anyhow::Result<Signature> {
+        let nonces: Vec<_> = signers.iter().map(|s| SigningNonces::new(s)).collect();
+        let commitments: Vec<_> = nonces.iter().map(|n| n.commitment()).collect();
+        let mut partials = Vec::new();
+        for (signer, nonces) in signers.iter().zip(nonces) {
+            partials.push((
+                signer.participant(),
+                signer.sign(nonces, message, &commitments)?,
+            ));
+        }
+        signers[0]
+            .group()
+            .aggregate(message, &commitments, &partials)
+    }
+
+    #[test]
+    fn signature_validates_like_a_normal_block_signature() {
+        let shares = create_key_shares(2, 3);
+        let group = shares[0].group();
+
+        for signers in [
+            vec![&shares[0], &shares[1]],
+            vec![&shares[0], &shares[2]],
+            vec![&shares[2], &shares[1]],
+            vec![&shares[0], &shares[1], &shares[2]],
+        ] {
+            let mut block = BlockBuilder::state()
+                .account(group.account())
+                .sign_zero()
+                .build();
+            let signature = sign(&signers, block.hash().as_bytes()).unwrap();
+            block.set_block_signature(&signature);
+            let BlockEnum::State(state) = &block else {
+                unreachable!()
+            };
+            validate_block_signature(state).unwrap();
+        }
+    }
+
+    #[test]
+    fn too_few_signers() {
+        let shares = create_key_shares(2, 3);
+        let error = sign(&[&shares[1]], b"message").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "2 signers are needed, but only 1 commitments were given"
+        );
+    }
+
+    #[test]
+    fn reject_invalid_partial_signature() {
+        let shares = create_key_shares(2, 2);
+        let group = shares[0].group();
+        let nonces1 = SigningNonces::new(&shares[0]);
+        let nonces2 = SigningNonces::new(&shares[1]);
+        let commitments = vec![nonces1.commitment(), nonces2.commitment()];
+        let partial1 = shares[0].sign(nonces1, b"message", &commitments).unwrap();
+        // signed a different message
+        let partial2 = shares[1].sign(nonces2, b"other", &commitments).unwrap();
+
+        group
+            .verify_partial(b"message", &commitments, 1, &partial1)
+            .unwrap();
+        let error = group
+            .aggregate(b"message", &commitments, &[(1, partial1), (2, partial2)])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid partial signature of participant 2"
+        );
+    }
+
+    #[test]
+    fn reject_nonces_of_other_participant() {
+        let shares = create_key_shares(2, 2);
+        let nonces1 = SigningNonces::new(&shares[0]);
+        let nonces2 = SigningNonces::new(&shares[1]);
+        let commitments = vec![nonces1.commitment(), nonces2.commitment()];
+        assert!(shares[1].sign(nonces1, b"message", &commitments).is_err());
+    }
+
+    #[test]
+    fn reject_commitment_in_the_name_of_another_participant() {
+        let shares = create_key_shares(2, 2);
+        let group = shares[0].group();
+        let commitment = SigningNonces::new(&shares[0]).commitment();
+        group.verify_commitment(&commitment).unwrap();
+
+        let mut forged = commitment.clone();
+        forged.participant = 2;
+        assert_eq!(
+            group.verify_commitment(&forged).unwrap_err().to_string(),
+            "commitment is not signed by participant 2"
+        );
+
+        // a commitment of another group with the same participant ids
+        let other = create_key_shares(2, 2);
+        let foreign = SigningNonces::new(&other[0]).commitment();
+        assert!(group.verify_commitment(&foreign).is_err());
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let shares = create_key_shares(1, 1);
+        let nonces = SigningNonces::new(&shares[0]);
+        let commitment = nonces.commitment();
+        assert_eq!(
+            SigningCommitment::decode_hex(commitment.encode_hex()).unwrap(),
+            commitment
+        );
+        let partial = shares[0].sign(nonces, b"message", &[commitment]).unwrap();
+        assert_eq!(
+            PartialSignature::decode_hex(partial.encode_hex()).unwrap(),
+            partial
+        );
+        assert!(SigningCommitment::from_bytes(&[1, 0, 1]).is_err());
+    }
+}
@@ -69,15 +69,26 @@ impl Vote {
     }
 
     pub fn new(keys: &KeyPair, timestamp: u64, duration: u8, hashes: Vec<BlockHash>) -> Self {
+        let mut result = Self::new_unsigned(keys.public_key(), timestamp, duration, hashes);
+        result.signature = sign_message(&keys.private_key(), result.hash().as_bytes());
+        result
+    }
+
+    /// Creates a vote without a signature. The signature has to be created for `Vote::hash`,
+    /// for example by a remote signer
+    pub fn new_unsigned(
+        voting_account: PublicKey,
+        timestamp: u64,
+        duration: u8,
+        hashes: Vec<BlockHash>,
+    ) -> Self {
         assert!(hashes.len() <= Self::MAX_HASHES);
-        let mut result = Self {
-            voting_account: keys.public_key(),
+        Self {
+            voting_account,
             timestamp: packed_timestamp(timestamp, duration),
             signature: Signature::new(),
             hashes,
-        };
-        result.signature = sign_message(&keys.private_key(), result.hash().as_bytes());
-        result
+        }
     }
 
     pub fn new_test_instance() -> Self {
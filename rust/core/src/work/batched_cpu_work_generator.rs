@@ -0,0 +1,210 @@
+use super::{
+    cpu_work_generator::{Sleeper, ThreadSleeper},
+    work_hash_lanes::{select_kernel, WorkHashKernel, LANES},
+    WorkGenerator, WorkRng, WorkTicket, XorShift1024Star,
+};
+use crate::{Root, WorkVersion};
+use std::time::Duration;
+
+/// PoW generation on the CPU that hashes several consecutive nonces at once.
+/// The hash kernel is chosen at runtime depending on the CPU features
+pub(crate) struct BatchedCpuWorkGenerator<Rng = XorShift1024Star, Sleep = ThreadSleeper>
+where
+    Rng: WorkRng,
+    Sleep: Sleeper,
+{
+    rng: Rng,
+    sleeper: Sleep,
+    rate_limiter: Duration,
+    kernel: WorkHashKernel,
+    hashes: u64,
+    pub iteration_size: usize,
+}
+
+const DEFAULT_ITERATION_SIZE: usize = 1024;
+
+impl BatchedCpuWorkGenerator {
+    pub fn new(rate_limiter: Duration) -> Self {
+        Self::with(XorShift1024Star::new(), ThreadSleeper::new(), rate_limiter)
+    }
+}
+
+impl<Rng, Sleep> BatchedCpuWorkGenerator<Rng, Sleep>
+where
+    Rng: WorkRng,
+    Sleep: Sleeper,
+{
+    pub(crate) fn with(rng: Rng, sleeper: Sleep, rate_limiter: Duration) -> Self {
+        Self {
+            rng,
+            sleeper,
+            rate_limiter,
+            kernel: select_kernel().0,
+            hashes: 0,
+            iteration_size: DEFAULT_ITERATION_SIZE,
+        }
+    }
+
+    /// Tries `iteration_size` consecutive nonces starting at a random one
+    fn try_create_batch(&mut self, root: &[u64; 4], min_difficulty: u64) -> Option<u64> {
+        let mut nonces = [0; LANES];
+        let start = self.rng.next_work();
+        let mut offset = 0;
+        while offset < self.iteration_size {
+            for (lane, nonce) in nonces.iter_mut().enumerate() {
+                *nonce = start.wrapping_add((offset + lane) as u64);
+            }
+
+            let difficulties = (self.kernel)(root, &nonces);
+            self.hashes += LANES as u64;
+            for lane in 0..LANES {
+                if difficulties[lane] >= min_difficulty {
+                    return Some(nonces[lane]);
+                }
+            }
+            offset += LANES;
+        }
+        None
+    }
+}
+
+impl<Rng, Sleep> WorkGenerator for BatchedCpuWorkGenerator<Rng, Sleep>
+where
+    Rng: WorkRng,
+    Sleep: Sleeper,
+{
+    fn create(
+        &mut self,
+        _version: WorkVersion,
+        item: &Root,
+        min_difficulty: u64,
+        work_ticket: &WorkTicket,
+    ) -> Option<u64> {
+        let bytes = item.as_bytes();
+        let root: [u64; 4] = std::array::from_fn(|i| {
+            u64::from_le_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap())
+        });
+
+        while !work_ticket.expired() {
+            let result = self.try_create_batch(&root, min_difficulty);
+            if result.is_some() {
+                return result;
+            }
+
+            // Add a rate limiter (if specified) to the pow calculation to save some CPUs which don't want to operate at full throttle
+            if !self.rate_limiter.is_zero() {
+                self.sleeper.sleep(self.rate_limiter);
+            }
+        }
+        None
+    }
+
+    fn hash_count(&self) -> u64 {
+        self.hashes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        work::cpu_work_generator::{StubSleeper, StubWorkRng},
+        Difficulty, DifficultyV1,
+    };
+
+    #[test]
+    fn create_valid_work() {
+        let root = Root::from(42);
+        let min_difficulty = 0xff00000000000000;
+        let mut generator = BatchedCpuWorkGenerator::new(Duration::ZERO);
+
+        let work = generator
+            .create(
+                WorkVersion::Work1,
+                &root,
+                min_difficulty,
+                &WorkTicket::never_expires(),
+            )
+            .unwrap();
+
+        assert!(DifficultyV1::default().get_difficulty(&root, work) >= min_difficulty);
+        assert!(generator.hash_count() > 0);
+    }
+
+    #[test]
+    fn finds_the_first_sufficient_nonce() {
+        let root = Root::from(1);
+        let start = 1000;
+        let difficulty = DifficultyV1::default();
+        let expected = (start..)
+            .find(|&nonce| difficulty.get_difficulty(&root, nonce) >= 0xf000000000000000)
+            .unwrap();
+
+        let mut generator = BatchedCpuWorkGenerator::with(
+            StubWorkRng::new(vec![start]),
+            StubSleeper::new(),
+            RATE_LIMIT,
+        );
+        generator.iteration_size = usize::MAX;
+
+        let work = generator.create(
+            WorkVersion::Work1,
+            &root,
+            0xf000000000000000,
+            &WorkTicket::never_expires(),
+        );
+
+        assert_eq!(work, Some(expected));
+    }
+
+    #[test]
+    fn rate_limit() {
+        let root = Root::from(1);
+        let min_difficulty = 0xf000000000000000;
+        let batch_succeeds = |start: u64| {
+            (start..start + LANES as u64)
+                .any(|nonce| DifficultyV1::default().get_difficulty(&root, nonce) >= min_difficulty)
+        };
+        let failing = (0..).step_by(LANES).find(|&s| !batch_succeeds(s)).unwrap();
+        let succeeding = (0..).step_by(LANES).find(|&s| batch_succeeds(s)).unwrap();
+
+        let sleeper = StubSleeper::new();
+        let mut generator = BatchedCpuWorkGenerator::with(
+            StubWorkRng::new(vec![failing, succeeding]),
+            sleeper.clone(),
+            RATE_LIMIT,
+        );
+        generator.iteration_size = LANES;
+
+        let work = generator.create(
+            WorkVersion::Work1,
+            &root,
+            min_difficulty,
+            &WorkTicket::never_expires(),
+        );
+
+        assert!(work.is_some());
+        assert_eq!(sleeper.calls(), vec![RATE_LIMIT]);
+        assert_eq!(generator.hash_count(), 2 * LANES as u64);
+    }
+
+    #[test]
+    fn expired_work_ticket() {
+        let sleeper = StubSleeper::new();
+        let mut generator =
+            BatchedCpuWorkGenerator::with(StubWorkRng::new(vec![]), sleeper.clone(), RATE_LIMIT);
+
+        let result = generator.create(
+            WorkVersion::Work1,
+            &Root::from(1),
+            100,
+            &WorkTicket::already_expired(),
+        );
+
+        assert_eq!(result, None);
+        assert_eq!(generator.hash_count(), 0);
+        assert_eq!(sleeper.calls(), vec![]);
+    }
+
+    const RATE_LIMIT: Duration = Duration::from_millis(1000);
+}
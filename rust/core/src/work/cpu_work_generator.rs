@@ -75,6 +75,7 @@ pub(crate) struct CpuWorkGenerator<
     difficulty: Diff,
     sleeper: Sleep,
     rate_limiter: Duration,
+    hashes: u64,
     pub iteration_size: usize,
 }
 
@@ -96,6 +97,7 @@ where
         difficulty,
         sleeper,
         rate_limiter,
+        hashes: 0,
         iteration_size: DEFAULT_ITERATION_SIZE,
     }
 }
@@ -136,6 +138,7 @@ where
             (work, difficulty) = self.next(item);
             iteration -= 1;
         }
+        self.hashes += (self.iteration_size - iteration) as u64;
 
         if difficulty >= min_difficulty {
             Some(work)
@@ -171,6 +174,10 @@ where
         }
         None
     }
+
+    fn hash_count(&self) -> u64 {
+        self.hashes
+    }
 }
 
 #[cfg(test)]
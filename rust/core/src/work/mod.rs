@@ -1,22 +1,62 @@
+#[cfg(feature = "work_generation")]
+mod batched_cpu_work_generator;
+#[cfg(feature = "work_generation")]
 mod cpu_work_generator;
 mod opencl_work_generator;
 mod stub_work_pool;
+#[cfg(feature = "work_generation")]
+mod work_benchmark;
+#[cfg(feature = "work_generation")]
+mod work_hash_lanes;
+#[cfg(feature = "work_generation")]
 mod work_pool;
+#[cfg(feature = "work_generation")]
 mod work_queue;
+#[cfg(feature = "work_generation")]
 mod work_thread;
 mod work_thresholds;
+#[cfg(feature = "work_generation")]
 mod xorshift;
 
+use crate::{Root, WorkVersion};
+#[cfg(feature = "work_generation")]
+pub(crate) use batched_cpu_work_generator::BatchedCpuWorkGenerator;
+#[cfg(feature = "work_generation")]
 pub(crate) use cpu_work_generator::CpuWorkGenerator;
-pub use stub_work_pool::StubWorkPool;
+pub use stub_work_pool::{StubWorkPool, STUB_WORK_POOL};
+#[cfg(feature = "work_generation")]
+pub use work_benchmark::{run_work_benchmark, WorkBenchmarkResult};
+#[cfg(feature = "work_generation")]
 pub(crate) use work_pool::WorkGenerator;
-pub use work_pool::{WorkPool, WorkPoolImpl, STUB_WORK_POOL};
+#[cfg(feature = "work_generation")]
+pub use work_pool::{CpuWorkBackend, WorkPoolImpl};
+#[cfg(feature = "work_generation")]
 pub use work_queue::WorkTicket;
+#[cfg(feature = "work_generation")]
 pub(crate) use work_queue::{WorkItem, WorkQueueCoordinator};
+#[cfg(feature = "work_generation")]
 pub(crate) use work_thread::WorkThread;
 pub use work_thresholds::{WorkThresholds, WORK_THRESHOLDS_STUB};
+#[cfg(feature = "work_generation")]
 pub(crate) use xorshift::XorShift1024Star;
 
+pub trait WorkPool: Send + Sync {
+    fn generate_async(
+        &self,
+        version: WorkVersion,
+        root: Root,
+        difficulty: u64,
+        done: Option<Box<dyn FnOnce(Option<u64>) + Send>>,
+    );
+
+    fn generate_dev(&self, root: Root, difficulty: u64) -> Option<u64>;
+
+    fn generate_dev2(&self, root: Root) -> Option<u64>;
+
+    fn generate(&self, version: WorkVersion, root: Root, difficulty: u64) -> Option<u64>;
+}
+
+#[cfg(feature = "work_generation")]
 pub(crate) trait WorkRng {
     fn next_work(&mut self) -> u64;
 }
@@ -1,5 +1,9 @@
-use super::WorkPool;
+use super::{WorkPool, WorkThresholds};
 use crate::{Root, WorkVersion};
+use once_cell::sync::Lazy;
+
+pub static STUB_WORK_POOL: Lazy<StubWorkPool> =
+    Lazy::new(|| StubWorkPool::new(WorkThresholds::publish_dev().base));
 
 /// The StubWorkPool assumes work == difficulty
 pub struct StubWorkPool {
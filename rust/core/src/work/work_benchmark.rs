@@ -0,0 +1,131 @@
+use super::{
+    work_hash_lanes::select_kernel, BatchedCpuWorkGenerator, CpuWorkBackend, CpuWorkGenerator,
+    WorkGenerator, WorkTicket,
+};
+use crate::{Root, WorkVersion};
+use std::{
+    sync::atomic::{AtomicI32, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Debug)]
+pub struct WorkBenchmarkResult {
+    pub backend: CpuWorkBackend,
+    /// Name of the hash kernel that was used, e.g. "avx2" or "portable"
+    pub kernel: &'static str,
+    pub threads: usize,
+    pub hashes: u64,
+    pub elapsed: Duration,
+}
+
+impl WorkBenchmarkResult {
+    pub fn hashes_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            return 0.0;
+        }
+        self.hashes as f64 / seconds
+    }
+
+    /// Average number of hashes needed to reach the given difficulty
+    pub fn expected_hashes(difficulty: u64) -> f64 {
+        // A hash reaches the difficulty with a probability of (2^64 - difficulty) / 2^64
+        let reaching = (u64::MAX - difficulty) as f64 + 1.0;
+        2f64.powi(64) / reaching
+    }
+
+    /// Average time to generate work for the given difficulty with the measured hash rate
+    pub fn expected_time(&self, difficulty: u64) -> Option<Duration> {
+        let hashes_per_second = self.hashes_per_second();
+        if hashes_per_second == 0.0 {
+            return None;
+        }
+        Duration::try_from_secs_f64(Self::expected_hashes(difficulty) / hashes_per_second).ok()
+    }
+}
+
+/// Hashes with an unreachable difficulty on `threads` threads for the given duration
+pub fn run_work_benchmark(
+    backend: CpuWorkBackend,
+    threads: usize,
+    duration: Duration,
+) -> WorkBenchmarkResult {
+    let threads = threads.max(1);
+    let ticket = AtomicI32::new(0);
+    let start = Instant::now();
+
+    let hashes = thread::scope(|s| {
+        let workers: Vec<_> = (0..threads)
+            .map(|i| {
+                let ticket = &ticket;
+                s.spawn(move || {
+                    let root = Root::from(i as u64);
+                    let work_ticket = WorkTicket::new(ticket);
+                    match backend {
+                        CpuWorkBackend::Simple => benchmark_generator(
+                            CpuWorkGenerator::new(Duration::ZERO),
+                            &root,
+                            &work_ticket,
+                        ),
+                        CpuWorkBackend::Batched => benchmark_generator(
+                            BatchedCpuWorkGenerator::new(Duration::ZERO),
+                            &root,
+                            &work_ticket,
+                        ),
+                    }
+                })
+            })
+            .collect();
+
+        thread::sleep(duration);
+        ticket.fetch_add(1, Ordering::SeqCst);
+        workers.into_iter().map(|w| w.join().unwrap()).sum::<u64>()
+    });
+
+    let kernel = match backend {
+        CpuWorkBackend::Simple => "blake2b",
+        CpuWorkBackend::Batched => select_kernel().1,
+    };
+
+    WorkBenchmarkResult {
+        backend,
+        kernel,
+        threads,
+        hashes,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn benchmark_generator(
+    mut generator: impl WorkGenerator,
+    root: &Root,
+    work_ticket: &WorkTicket,
+) -> u64 {
+    // u64::MAX can never be reached, so the generator runs until the ticket expires
+    generator.create(WorkVersion::Work1, root, u64::MAX, work_ticket);
+    generator.hash_count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_hashes() {
+        assert_eq!(WorkBenchmarkResult::expected_hashes(0), 1.0);
+        assert_eq!(
+            WorkBenchmarkResult::expected_hashes(0xfffffff800000000),
+            (1u64 << 29) as f64
+        );
+    }
+
+    #[test]
+    fn benchmark_counts_hashes() {
+        let result = run_work_benchmark(CpuWorkBackend::Batched, 2, Duration::from_millis(50));
+        assert_eq!(result.threads, 2);
+        assert!(result.hashes > 0);
+        assert!(result.hashes_per_second() > 0.0);
+        assert!(result.expected_time(0xfffffff800000000).is_some());
+    }
+}
@@ -0,0 +1,225 @@
+//! Blake2b specialised for proof of work: an 8 byte digest of the 40 byte
+//! input `work || root`. That input fits into a single compression, so the
+//! whole hash is one call of the compression function. Several nonces are
+//! hashed side by side, which lets the compiler keep one nonce per SIMD lane.
+
+/// Number of nonces hashed at once
+pub(crate) const LANES: usize = 4;
+
+/// Hashes `LANES` nonces for the root given as four little endian words
+pub(crate) type WorkHashKernel = fn(&[u64; 4], &[u64; LANES]) -> [u64; LANES];
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+/// Parameter block: digest length 8, no key, fanout 1, depth 1
+const H0: u64 = IV[0] ^ 0x0101_0000 ^ 8;
+const INPUT_LEN: u64 = 40;
+
+type Lanes = [u64; LANES];
+
+/// Picks the fastest kernel for the CPU the node runs on
+pub(crate) fn select_kernel() -> (WorkHashKernel, &'static str) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            return (hash_lanes_avx2, "avx2");
+        }
+    }
+    (hash_lanes_portable, "portable")
+}
+
+fn hash_lanes_portable(root: &[u64; 4], nonces: &Lanes) -> Lanes {
+    hash_lanes(root, nonces)
+}
+
+#[cfg(target_arch = "x86_64")]
+fn hash_lanes_avx2(root: &[u64; 4], nonces: &Lanes) -> Lanes {
+    // SAFETY: only selected after AVX2 support was detected at runtime
+    unsafe { hash_lanes_avx2_impl(root, nonces) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_lanes_avx2_impl(root: &[u64; 4], nonces: &Lanes) -> Lanes {
+    hash_lanes(root, nonces)
+}
+
+#[inline(always)]
+fn hash_lanes(root: &[u64; 4], nonces: &Lanes) -> Lanes {
+    let message: [u64; 16] = [
+        0, root[0], root[1], root[2], root[3], 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    let mut v: [Lanes; 16] = [
+        [H0; LANES],
+        [IV[1]; LANES],
+        [IV[2]; LANES],
+        [IV[3]; LANES],
+        [IV[4]; LANES],
+        [IV[5]; LANES],
+        [IV[6]; LANES],
+        [IV[7]; LANES],
+        [IV[0]; LANES],
+        [IV[1]; LANES],
+        [IV[2]; LANES],
+        [IV[3]; LANES],
+        [IV[4] ^ INPUT_LEN; LANES],
+        [IV[5]; LANES],
+        [!IV[6]; LANES], // last block
+        [IV[7]; LANES],
+    ];
+
+    for sigma in SIGMA.iter() {
+        g(
+            &mut v,
+            0,
+            4,
+            8,
+            12,
+            word(sigma[0], &message, nonces),
+            word(sigma[1], &message, nonces),
+        );
+        g(
+            &mut v,
+            1,
+            5,
+            9,
+            13,
+            word(sigma[2], &message, nonces),
+            word(sigma[3], &message, nonces),
+        );
+        g(
+            &mut v,
+            2,
+            6,
+            10,
+            14,
+            word(sigma[4], &message, nonces),
+            word(sigma[5], &message, nonces),
+        );
+        g(
+            &mut v,
+            3,
+            7,
+            11,
+            15,
+            word(sigma[6], &message, nonces),
+            word(sigma[7], &message, nonces),
+        );
+        g(
+            &mut v,
+            0,
+            5,
+            10,
+            15,
+            word(sigma[8], &message, nonces),
+            word(sigma[9], &message, nonces),
+        );
+        g(
+            &mut v,
+            1,
+            6,
+            11,
+            12,
+            word(sigma[10], &message, nonces),
+            word(sigma[11], &message, nonces),
+        );
+        g(
+            &mut v,
+            2,
+            7,
+            8,
+            13,
+            word(sigma[12], &message, nonces),
+            word(sigma[13], &message, nonces),
+        );
+        g(
+            &mut v,
+            3,
+            4,
+            9,
+            14,
+            word(sigma[14], &message, nonces),
+            word(sigma[15], &message, nonces),
+        );
+    }
+
+    let mut result = [0; LANES];
+    for lane in 0..LANES {
+        result[lane] = H0 ^ v[0][lane] ^ v[8][lane];
+    }
+    result
+}
+
+/// Message word 0 is the nonce and differs per lane
+#[inline(always)]
+fn word(index: usize, message: &[u64; 16], nonces: &Lanes) -> Lanes {
+    if index == 0 {
+        *nonces
+    } else {
+        [message[index]; LANES]
+    }
+}
+
+#[inline(always)]
+fn g(v: &mut [Lanes; 16], a: usize, b: usize, c: usize, d: usize, x: Lanes, y: Lanes) {
+    for lane in 0..LANES {
+        v[a][lane] = v[a][lane].wrapping_add(v[b][lane]).wrapping_add(x[lane]);
+        v[d][lane] = (v[d][lane] ^ v[a][lane]).rotate_right(32);
+        v[c][lane] = v[c][lane].wrapping_add(v[d][lane]);
+        v[b][lane] = (v[b][lane] ^ v[c][lane]).rotate_right(24);
+        v[a][lane] = v[a][lane].wrapping_add(v[b][lane]).wrapping_add(y[lane]);
+        v[d][lane] = (v[d][lane] ^ v[a][lane]).rotate_right(16);
+        v[c][lane] = v[c][lane].wrapping_add(v[d][lane]);
+        v[b][lane] = (v[b][lane] ^ v[c][lane]).rotate_right(63);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Difficulty, DifficultyV1, Root};
+
+    #[test]
+    fn matches_reference_blake2b() {
+        let root =
+            Root::decode_hex("2387767168F9453DB0C5A5CA6F2A5FD4E1D1E3F0A2C63C3D0B69A3C6D9E1F0A1")
+                .unwrap();
+        let nonces = [0, 1, 0x123456789abcdef0, u64::MAX];
+
+        let expected = nonces.map(|nonce| DifficultyV1::default().get_difficulty(&root, nonce));
+
+        assert_eq!(hash_lanes_portable(&root_words(&root), &nonces), expected);
+        let (kernel, _) = select_kernel();
+        assert_eq!(kernel(&root_words(&root), &nonces), expected);
+    }
+
+    fn root_words(root: &Root) -> [u64; 4] {
+        let bytes = root.as_bytes();
+        std::array::from_fn(|i| u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap()))
+    }
+}
@@ -1,5 +1,7 @@
+use anyhow::anyhow;
 use std::{
     mem::size_of,
+    str::FromStr,
     sync::{Arc, Condvar, Mutex},
     thread::{self, JoinHandle},
     time::Duration,
@@ -9,27 +11,41 @@ use crate::{
     utils::{ContainerInfo, ContainerInfoComponent},
     Root, WorkVersion,
 };
-use once_cell::sync::Lazy;
 
 use super::{
-    CpuWorkGenerator, StubWorkPool, WorkItem, WorkQueueCoordinator, WorkThread, WorkThresholds,
-    WorkTicket, WORK_THRESHOLDS_STUB,
+    BatchedCpuWorkGenerator, CpuWorkGenerator, WorkItem, WorkPool, WorkQueueCoordinator,
+    WorkThread, WorkThresholds, WorkTicket, WORK_THRESHOLDS_STUB,
 };
 
-pub trait WorkPool: Send + Sync {
-    fn generate_async(
-        &self,
-        version: WorkVersion,
-        root: Root,
-        difficulty: u64,
-        done: Option<Box<dyn FnOnce(Option<u64>) + Send>>,
-    );
+/// Implementation used for generating work on the CPU
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CpuWorkBackend {
+    /// One nonce after the other with the generic blake2b implementation
+    Simple,
+    /// Several nonces at once with a specialised blake2b, using SIMD if the CPU supports it
+    #[default]
+    Batched,
+}
 
-    fn generate_dev(&self, root: Root, difficulty: u64) -> Option<u64>;
+impl CpuWorkBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CpuWorkBackend::Simple => "simple",
+            CpuWorkBackend::Batched => "batched",
+        }
+    }
+}
 
-    fn generate_dev2(&self, root: Root) -> Option<u64>;
+impl FromStr for CpuWorkBackend {
+    type Err = anyhow::Error;
 
-    fn generate(&self, version: WorkVersion, root: Root, difficulty: u64) -> Option<u64>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "simple" => Ok(CpuWorkBackend::Simple),
+            "batched" => Ok(CpuWorkBackend::Batched),
+            _ => Err(anyhow!("Invalid work backend: {}", s)),
+        }
+    }
 }
 
 pub struct WorkPoolImpl {
@@ -37,6 +53,7 @@ pub struct WorkPoolImpl {
     work_queue: Arc<WorkQueueCoordinator>,
     work_thresholds: WorkThresholds,
     pow_rate_limiter: Duration,
+    backend: CpuWorkBackend,
 }
 
 impl WorkPoolImpl {
@@ -44,12 +61,27 @@ impl WorkPoolImpl {
         work_thresholds: WorkThresholds,
         thread_count: usize,
         pow_rate_limiter: Duration,
+    ) -> Self {
+        Self::with_backend(
+            work_thresholds,
+            thread_count,
+            pow_rate_limiter,
+            CpuWorkBackend::default(),
+        )
+    }
+
+    pub fn with_backend(
+        work_thresholds: WorkThresholds,
+        thread_count: usize,
+        pow_rate_limiter: Duration,
+        backend: CpuWorkBackend,
     ) -> Self {
         let mut pool = Self {
             threads: Vec::new(),
             work_queue: Arc::new(WorkQueueCoordinator::new()),
             work_thresholds,
             pow_rate_limiter,
+            backend,
         };
 
         pool.spawn_threads(thread_count);
@@ -62,6 +94,7 @@ impl WorkPoolImpl {
             work_queue: Arc::new(WorkQueueCoordinator::new()),
             work_thresholds: WORK_THRESHOLDS_STUB.clone(),
             pow_rate_limiter: Duration::ZERO,
+            backend: CpuWorkBackend::Simple,
         };
 
         pool.threads
@@ -75,6 +108,7 @@ impl WorkPoolImpl {
             work_queue: Arc::new(WorkQueueCoordinator::new()),
             work_thresholds: WORK_THRESHOLDS_STUB.clone(),
             pow_rate_limiter: Duration::ZERO,
+            backend: CpuWorkBackend::Simple,
         }
     }
 
@@ -85,7 +119,14 @@ impl WorkPoolImpl {
     }
 
     fn spawn_cpu_worker_thread(&self) -> JoinHandle<()> {
-        self.spawn_worker_thread(CpuWorkGenerator::new(self.pow_rate_limiter))
+        match self.backend {
+            CpuWorkBackend::Simple => {
+                self.spawn_worker_thread(CpuWorkGenerator::new(self.pow_rate_limiter))
+            }
+            CpuWorkBackend::Batched => {
+                self.spawn_worker_thread(BatchedCpuWorkGenerator::new(self.pow_rate_limiter))
+            }
+        }
     }
 
     fn spawn_stub_worker_thread(&self, configured_work: u64) -> JoinHandle<()> {
@@ -129,6 +170,10 @@ impl WorkPoolImpl {
         size_of::<WorkItem>()
     }
 
+    pub fn backend(&self) -> CpuWorkBackend {
+        self.backend
+    }
+
     pub fn thread_count(&self) -> usize {
         self.threads.len()
     }
@@ -258,6 +303,9 @@ pub(crate) trait WorkGenerator {
         min_difficulty: u64,
         work_ticket: &WorkTicket,
     ) -> Option<u64>;
+
+    /// Number of hashes calculated so far
+    fn hash_count(&self) -> u64;
 }
 
 struct StubWorkGenerator(u64);
@@ -272,15 +320,17 @@ impl WorkGenerator for StubWorkGenerator {
     ) -> Option<u64> {
         Some(self.0)
     }
-}
 
-pub static STUB_WORK_POOL: Lazy<StubWorkPool> =
-    Lazy::new(|| StubWorkPool::new(WorkThresholds::publish_dev().base));
+    fn hash_count(&self) -> u64 {
+        0
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{BlockBuilder, BlockEnum};
+    use once_cell::sync::Lazy;
     use std::sync::mpsc;
 
     pub static WORK_POOL: Lazy<WorkPoolImpl> = Lazy::new(|| {
@@ -144,6 +144,7 @@ impl From<&TcpConfigDto> for TcpConfig {
             max_attempts: value.max_attempts,
             max_attempts_per_ip: value.max_attempts_per_ip,
             connect_timeout: Duration::from_secs(value.connect_timeout_s),
+            socks5_proxy: None,
         }
     }
 }
@@ -413,6 +414,14 @@ impl TryFrom<&NodeConfigDto> for NodeConfig {
             bandwidth_limit_burst_ratio: value.bandwidth_limit_burst_ratio,
             bootstrap_bandwidth_limit: value.bootstrap_bandwidth_limit,
             bootstrap_bandwidth_burst_ratio: value.bootstrap_bandwidth_burst_ratio,
+            vote_bandwidth_limit: 0,
+            vote_bandwidth_burst_ratio: 3_f64,
+            block_bandwidth_limit: 0,
+            block_bandwidth_burst_ratio: 3_f64,
+            telemetry_bandwidth_limit: 0,
+            telemetry_bandwidth_burst_ratio: 3_f64,
+            peer_bandwidth_limit: 0,
+            peer_bandwidth_burst_ratio: 3_f64,
             bootstrap_ascending: (&value.bootstrap_ascending).into(),
             bootstrap_server: (&value.bootstrap_server).into(),
             confirming_set_batch_time: Duration::from_millis(
@@ -430,8 +439,12 @@ impl TryFrom<&NodeConfigDto> for NodeConfig {
             secondary_work_peers,
             preconfigured_peers,
             preconfigured_representatives,
+            trusted_peers: Vec::new(),
             max_pruning_age_s: value.max_pruning_age_s,
             max_pruning_depth: value.max_pruning_depth,
+            pruning_keep_wallet_accounts: false,
+            pruning_keep_representatives: false,
+            pruning_keep_accounts: Vec::new(),
             callback_address: String::from_utf8_lossy(
                 &value.callback_address[..value.callback_address_len],
             )
@@ -462,6 +475,10 @@ impl TryFrom<&NodeConfigDto> for NodeConfig {
             local_block_broadcaster: (&value.local_block_broadcaster).into(),
             confirming_set: (&value.confirming_set).into(),
             monitor: (&value.monitor).into(),
+            ledger_backup: Default::default(),
+            remote_signer: Default::default(),
+            port_mapping: Default::default(),
+            wallet_backup: Default::default(),
         };
 
         Ok(cfg)
@@ -345,6 +345,7 @@ impl From<&ActiveElectionsConfigDto> for ActiveElectionsConfig {
             confirmation_history_size: value.confirmation_history_size,
             confirmation_cache: value.confirmation_cache,
             max_election_winners: value.max_election_winners,
+            fork_journal_size: ActiveElectionsConfig::default().fork_journal_size,
         }
     }
 }
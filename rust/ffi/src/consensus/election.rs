@@ -8,6 +8,7 @@ use rsnano_core::{utils::system_time_as_nanoseconds, BlockEnum, BlockHash, Publi
 use rsnano_node::consensus::{
     Election, ElectionBehavior, ElectionData, ElectionState, VoteInfo, NEXT_ELECTION_ID,
 };
+use rsnano_nullable_clock::SteadyClock;
 use std::{
     ffi::c_void,
     ops::Deref,
@@ -69,6 +70,7 @@ pub unsafe extern "C" fn rsn_election_create(
         Box::new(move |account| {
             live_vote_action(live_vote_context.get_context(), account.as_bytes().as_ptr())
         }),
+        Arc::new(SteadyClock::default()),
     )))
 }
 
@@ -180,7 +180,7 @@ pub unsafe extern "C" fn rsn_lmdb_wallet_store_derive_key(
     password: *const c_char,
 ) {
     let password = CStr::from_ptr(password).to_str().unwrap();
-    let key = (*handle).0.derive_key((*txn).as_txn(), password);
+    let key = (*handle).0.derive_key((*txn).as_txn(), password).unwrap();
     key.copy_bytes(prv);
 }
 
@@ -273,7 +273,9 @@ pub unsafe extern "C" fn rsn_lmdb_wallet_store_attempt_password(
     password: *const c_char,
 ) -> bool {
     let password = CStr::from_ptr(password).to_str().unwrap();
-    (*handle).0.attempt_password((*txn).as_txn(), password)
+    (*handle)
+        .0
+        .attempt_password((*txn).as_write_txn(), password)
 }
 
 #[no_mangle]
@@ -15,6 +15,7 @@ impl From<&OutboundBandwidthLimiterConfigDto> for OutboundBandwidthLimiterConfig
             standard_burst_ratio: dto.standard_burst_ratio,
             bootstrap_limit: dto.bootstrap_limit,
             bootstrap_burst_ratio: dto.bootstrap_burst_ratio,
+            ..Default::default()
         }
     }
 }
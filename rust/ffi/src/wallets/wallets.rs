@@ -717,13 +717,13 @@ pub unsafe extern "C" fn rsn_wallets_search_receivable_wallet(
 pub unsafe extern "C" fn rsn_wallets_enter_password(
     handle: &LmdbWalletsHandle,
     wallet: &WalletHandle,
-    tx: &TransactionHandle,
+    tx: &mut TransactionHandle,
     password: *const c_char,
 ) -> bool {
     handle
         .enter_password_wallet(
             wallet,
-            tx.as_txn(),
+            tx.as_write_txn(),
             CStr::from_ptr(password).to_str().unwrap(),
         )
         .is_err()
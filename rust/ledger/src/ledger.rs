@@ -14,11 +14,13 @@ use rsnano_core::{
 };
 use rsnano_store_lmdb::{
     ConfiguredAccountDatabaseBuilder, ConfiguredBlockDatabaseBuilder,
-    ConfiguredConfirmationHeightDatabaseBuilder, ConfiguredPeersDatabaseBuilder,
-    ConfiguredPendingDatabaseBuilder, ConfiguredPrunedDatabaseBuilder, LedgerCache,
+    ConfiguredConfirmationHeightDatabaseBuilder, ConfiguredPeerBansDatabaseBuilder,
+    ConfiguredPeersDatabaseBuilder, ConfiguredPendingDatabaseBuilder,
+    ConfiguredPrunedDatabaseBuilder, ConfiguredRepresentativesDatabaseBuilder, LedgerCache,
     LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbEnv, LmdbFinalVoteStore,
-    LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction,
-    LmdbRepWeightStore, LmdbStore, LmdbVersionStore, LmdbWriteTransaction, Transaction,
+    LmdbOnlineWeightStore, LmdbPeerBanStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore,
+    LmdbReadTransaction, LmdbRepWeightStore, LmdbRepresentativeStore, LmdbStore, LmdbVersionStore,
+    LmdbWriteTransaction, StoredPeerBan, StoredRepresentative, Transaction,
 };
 use std::{
     collections::{HashMap, VecDeque},
@@ -86,6 +88,8 @@ pub struct NullLedgerBuilder {
     pending: ConfiguredPendingDatabaseBuilder,
     pruned: ConfiguredPrunedDatabaseBuilder,
     peers: ConfiguredPeersDatabaseBuilder,
+    peer_bans: ConfiguredPeerBansDatabaseBuilder,
+    representatives: ConfiguredRepresentativesDatabaseBuilder,
     confirmation_height: ConfiguredConfirmationHeightDatabaseBuilder,
     min_rep_weight: Amount,
 }
@@ -98,6 +102,8 @@ impl NullLedgerBuilder {
             pending: ConfiguredPendingDatabaseBuilder::new(),
             pruned: ConfiguredPrunedDatabaseBuilder::new(),
             peers: ConfiguredPeersDatabaseBuilder::new(),
+            peer_bans: ConfiguredPeerBansDatabaseBuilder::new(),
+            representatives: ConfiguredRepresentativesDatabaseBuilder::new(),
             confirmation_height: ConfiguredConfirmationHeightDatabaseBuilder::new(),
             min_rep_weight: Amount::zero(),
         }
@@ -122,6 +128,25 @@ impl NullLedgerBuilder {
         self
     }
 
+    pub fn peer_bans(mut self, bans: impl IntoIterator<Item = StoredPeerBan>) -> Self {
+        for ban in bans.into_iter() {
+            self.peer_bans = self.peer_bans.ban(&ban)
+        }
+        self
+    }
+
+    pub fn representatives(
+        mut self,
+        representatives: impl IntoIterator<Item = (PublicKey, StoredRepresentative)>,
+    ) -> Self {
+        for (account, representative) in representatives.into_iter() {
+            self.representatives = self
+                .representatives
+                .representative(&account, &representative)
+        }
+        self
+    }
+
     pub fn confirmation_height(mut self, account: &Account, info: &ConfirmationHeightInfo) -> Self {
         self.confirmation_height = self.confirmation_height.height(account, info);
         self
@@ -151,6 +176,8 @@ impl NullLedgerBuilder {
                 .configured_database(self.pruned.build())
                 .configured_database(self.confirmation_height.build())
                 .configured_database(self.peers.build())
+                .configured_database(self.peer_bans.build())
+                .configured_database(self.representatives.build())
                 .build(),
         );
 
@@ -163,9 +190,11 @@ impl NullLedgerBuilder {
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone()).unwrap()),
             online_weight: Arc::new(LmdbOnlineWeightStore::new(env.clone()).unwrap()),
             peer: Arc::new(LmdbPeerStore::new(env.clone()).unwrap()),
+            peer_ban: Arc::new(LmdbPeerBanStore::new(env.clone()).unwrap()),
             pending: Arc::new(LmdbPendingStore::new(env.clone()).unwrap()),
             pruned: Arc::new(LmdbPrunedStore::new(env.clone()).unwrap()),
             rep_weight: Arc::new(LmdbRepWeightStore::new(env.clone()).unwrap()),
+            representative: Arc::new(LmdbRepresentativeStore::new(env.clone()).unwrap()),
             version: Arc::new(LmdbVersionStore::new(env.clone()).unwrap()),
         };
         Ledger::new(
@@ -0,0 +1,68 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::Parser;
+use rsnano_core::Amount;
+use rsnano_ledger::{Ledger, RepWeightCache};
+use rsnano_node::{
+    config::NetworkConstants,
+    ledger_backup::{LedgerBackup, LedgerBackupConfig},
+    NetworkParams,
+};
+use rsnano_store_lmdb::LmdbStore;
+use std::{path::PathBuf, sync::Arc};
+
+#[derive(Parser)]
+pub(crate) struct BackupArgs {
+    /// Directory for the backup. Defaults to a new directory in <data_path>/backup/ledger
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Number of backups to keep in the default backup directory
+    #[arg(long)]
+    max_backups: Option<usize>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl BackupArgs {
+    pub(crate) fn backup(&self) -> Result<()> {
+        let data_path = get_path(&self.data_path, &self.network);
+        let network_params = NetworkParams::new(NetworkConstants::active_network());
+        // The ledger fills the cached counts that get written into the manifest
+        let ledger = Ledger::new(
+            Arc::new(LmdbStore::open(&data_path.join("data.ldb")).build()?),
+            network_params.ledger,
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )?;
+
+        let mut config = LedgerBackupConfig::default();
+        if let Some(max_backups) = self.max_backups {
+            config.max_backups = max_backups;
+        }
+        let ledger_backup = LedgerBackup::new(
+            config,
+            ledger.store.clone(),
+            data_path.join("backup").join("ledger"),
+        );
+
+        println!("Ledger backup in progress. This may take a while...");
+
+        let (backup_path, manifest) = match &self.output {
+            Some(output) => (output.clone(), ledger_backup.backup(output)?),
+            None => ledger_backup.backup_with_retention()?,
+        };
+
+        println!("Backup completed. This can be found at {:?}", backup_path);
+        println!("Block count: {}", manifest.block_count);
+        println!("Cemented count: {}", manifest.cemented_count);
+        println!("Account count: {}", manifest.account_count);
+        println!("Store version: {}", manifest.store_version);
+        println!("SHA-256: {}", manifest.sha256);
+
+        Ok(())
+    }
+}
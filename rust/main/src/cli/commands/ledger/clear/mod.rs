@@ -4,11 +4,13 @@ use confirmation_height::ConfirmationHeightArgs;
 use final_vote::FinalVoteArgs;
 use online_weight::OnlineWeightArgs;
 use peers::PeersArgs;
+use representatives::RepresentativesArgs;
 
 pub(crate) mod confirmation_height;
 pub(crate) mod final_vote;
 pub(crate) mod online_weight;
 pub(crate) mod peers;
+pub(crate) mod representatives;
 
 #[derive(Subcommand)]
 pub(crate) enum ClearSubcommands {
@@ -20,6 +22,8 @@ pub(crate) enum ClearSubcommands {
     Peers(PeersArgs),
     /// Clears the confirmation height of accounts
     ConfirmationHeight(ConfirmationHeightArgs),
+    /// Clears the persisted representatives found by the rep crawler
+    Representatives(RepresentativesArgs),
 }
 
 #[derive(Parser)]
@@ -35,6 +39,7 @@ impl ClearCommand {
             Some(ClearSubcommands::ConfirmationHeight(args)) => args.confirmation_height()?,
             Some(ClearSubcommands::OnlineWeight(args)) => args.online_weight()?,
             Some(ClearSubcommands::Peers(args)) => args.peers()?,
+            Some(ClearSubcommands::Representatives(args)) => args.representatives()?,
             None => ClearCommand::command().print_long_help()?,
         }
 
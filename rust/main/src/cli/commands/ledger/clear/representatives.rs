@@ -0,0 +1,35 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsnano_store_lmdb::{LmdbEnv, LmdbRepresentativeStore};
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct RepresentativesArgs {
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RepresentativesArgs {
+    pub(crate) fn representatives(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        let env = Arc::new(LmdbEnv::new(&path)?);
+
+        let representative_store = LmdbRepresentativeStore::new(env.clone())?;
+
+        let mut txn = env.tx_begin_write();
+
+        representative_store.clear(&mut txn);
+
+        println!("Representatives were cleared from the database");
+
+        Ok(())
+    }
+}
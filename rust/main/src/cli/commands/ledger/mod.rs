@@ -1,10 +1,12 @@
 use anyhow::Result;
+use backup::BackupArgs;
 use clap::{CommandFactory, Parser, Subcommand};
 use clear::ClearCommand;
 use info::InfoCommand;
 use snapshot::SnapshotArgs;
 use vacuum::VacuumArgs;
 
+pub(crate) mod backup;
 pub(crate) mod clear;
 pub(crate) mod info;
 pub(crate) mod snapshot;
@@ -20,6 +22,8 @@ pub(crate) enum LedgerSubcommands {
     Vacuum(VacuumArgs),
     /// Similar to vacuum but does not replace the existing database
     Snapshot(SnapshotArgs),
+    /// Creates a consistent backup of the ledger with a manifest
+    Backup(BackupArgs),
 }
 
 #[derive(Parser)]
@@ -35,6 +39,7 @@ impl LedgerCommand {
             Some(LedgerSubcommands::Clear(command)) => command.run()?,
             Some(LedgerSubcommands::Vacuum(args)) => args.vacuum()?,
             Some(LedgerSubcommands::Snapshot(args)) => args.snapshot()?,
+            Some(LedgerSubcommands::Backup(args)) => args.backup()?,
             None => LedgerCommand::command().print_long_help()?,
         }
 
@@ -1,5 +1,6 @@
 pub(crate) mod config;
 pub(crate) mod ledger;
 pub(crate) mod node;
+pub(crate) mod signer;
 pub(crate) mod utils;
 pub(crate) mod wallets;
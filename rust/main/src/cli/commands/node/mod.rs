@@ -3,6 +3,7 @@ use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
 use generate_config::GenerateConfigArgs;
 use initialize::InitializeArgs;
+use replay_capture::ReplayCaptureArgs;
 use rsnano_core::{Account, Amount, BlockHash, RawKey, SendBlock};
 use rsnano_node::{wallets::Wallets, BUILD_INFO, VERSION_STRING};
 use rsnano_store_lmdb::LmdbEnv;
@@ -11,6 +12,7 @@ use std::{sync::Arc, time::Instant};
 
 pub(crate) mod generate_config;
 pub(crate) mod initialize;
+pub(crate) mod replay_capture;
 pub(crate) mod run_daemon;
 
 #[derive(Subcommand)]
@@ -23,6 +25,10 @@ pub(crate) enum NodeSubcommands {
     Initialize(InitializeArgs),
     /// Run internal diagnostics.
     Diagnostics,
+    /// Starts a fresh node that doesn't connect to any peer and replays the inbound messages of a capture file into it.
+    ///
+    /// Capture files are written by the message recorder of the insight tool.
+    ReplayCapture(ReplayCaptureArgs),
     /// Prints out version.
     Version,
     /// Writes node or rpc configuration to stdout, populated with defaults suitable for this system.
@@ -46,6 +52,7 @@ impl NodeCommand {
             Some(NodeSubcommands::GenerateConfig(args)) => args.generate_config()?,
             Some(NodeSubcommands::Version) => Self::version(),
             Some(NodeSubcommands::Diagnostics) => Self::diagnostics().await?,
+            Some(NodeSubcommands::ReplayCapture(args)) => args.replay_capture().await?,
             None => NodeCommand::command().print_long_help()?,
         }
 
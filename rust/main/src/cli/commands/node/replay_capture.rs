@@ -0,0 +1,101 @@
+use crate::cli::{get_path, init_tracing};
+use anyhow::{anyhow, bail, Result};
+use clap::{ArgGroup, Parser};
+use rsnano_core::utils::get_cpu_count;
+use rsnano_node::{
+    config::{NetworkConstants, NodeConfig, NodeFlags},
+    transport::{MessageCaptureReader, MessageReplay},
+    unique_path_for, NetworkParams, NodeBuilder, NodeExt,
+};
+use rsnano_nullable_clock::SteadyClock;
+use std::{fs::File, io::BufReader, path::PathBuf, sync::Arc, thread::sleep, time::Duration};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct ReplayCaptureArgs {
+    /// The message capture file to replay
+    #[arg(long)]
+    capture_file: PathBuf,
+    /// Uses the supplied path as the data directory of the fresh node.
+    /// Defaults to a new directory in the data folder of the network
+    #[arg(long, group = "input", verbatim_doc_comment)]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl ReplayCaptureArgs {
+    pub(crate) async fn replay_capture(&self) -> Result<()> {
+        init_tracing("rsnano_node=info");
+
+        let capture = File::open(&self.capture_file)
+            .map_err(anyhow::Error::from)
+            .and_then(|file| MessageCaptureReader::new(BufReader::new(file))?.read_all())
+            .map_err(|e| anyhow!("Could not read capture file {:?}: {}", self.capture_file, e))?;
+
+        let path = match &self.data_path {
+            Some(_) => get_path(&self.data_path, &None),
+            None => {
+                // get_path sets the active network
+                get_path(&None, &self.network);
+                unique_path_for(NetworkConstants::active_network())
+                    .ok_or_else(|| anyhow!("Could not create a data directory"))?
+            }
+        };
+        if path.join("data.ldb").exists() {
+            bail!(
+                "A capture can only be replayed into a fresh node, but {:?} already contains a ledger",
+                path
+            );
+        }
+        std::fs::create_dir_all(&path).map_err(|e| anyhow!("Create dir failed: {:?}", e))?;
+
+        let network_params = NetworkParams::new(NetworkConstants::active_network());
+
+        // The node must only see the replayed messages, so it doesn't connect to any peer
+        let mut config = NodeConfig::new(None, &network_params, get_cpu_count());
+        config.preconfigured_peers.clear();
+        config.tcp_incoming_connections_max = 0;
+
+        let mut flags = NodeFlags::new();
+        flags.set_disable_rep_crawler(true);
+        flags.set_disable_legacy_bootstrap(true);
+        flags.set_disable_ascending_bootstrap(true);
+        flags.set_disable_ongoing_bootstrap(true);
+        flags.set_disable_backup(true);
+
+        let node = NodeBuilder::new(network_params.network.current_network)
+            .data_path(&path)
+            .config(config)
+            .network_params(network_params)
+            .flags(flags)
+            .finish()?;
+        let node = Arc::new(node);
+        node.start();
+
+        let clock = Arc::new(SteadyClock::new_null_with(node.steady_clock.now()));
+        let mut replay = MessageReplay::new(
+            node.inbound_message_queue.clone(),
+            clock,
+            &node.network_params,
+        );
+        let result = replay.replay(&capture).await;
+
+        while node.inbound_message_queue.size() > 0 || node.block_processor.total_queue_len() > 0 {
+            sleep(Duration::from_millis(100));
+        }
+        let block_count = node.ledger.block_count();
+        node.stop();
+
+        println!("Replayed messages: {}", result.replayed);
+        println!("Dropped messages: {}", result.dropped);
+        println!("Invalid messages: {}", result.invalid);
+        println!("Skipped outbound messages: {}", result.outbound);
+        println!("Ledger block count: {}", block_count);
+        println!("Data path: {:?}", path);
+
+        Ok(())
+    }
+}
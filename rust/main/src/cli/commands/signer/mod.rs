@@ -0,0 +1,36 @@
+use anyhow::Result;
+use clap::{CommandFactory, Parser, Subcommand};
+use run::RunSignerArgs;
+use sign_payload::SignPayloadArgs;
+
+pub(crate) mod run;
+pub(crate) mod sign_payload;
+
+#[derive(Subcommand)]
+pub(crate) enum SignerSubcommands {
+    /// Starts a signer process that signs votes for the node over a Unix socket
+    Run(RunSignerArgs),
+    /// Signs a payload of the block_create_unsigned RPC with a key derived from a seed.
+    /// Meant for offline machines. The signature is submitted with the block_assemble RPC.
+    /// The previous balance and the balance change are supplied by the node and aren't
+    /// covered by the hash, so check them against your own records before signing
+    SignPayload(SignPayloadArgs),
+}
+
+#[derive(Parser)]
+pub(crate) struct SignerCommand {
+    #[command(subcommand)]
+    pub subcommand: Option<SignerSubcommands>,
+}
+
+impl SignerCommand {
+    pub(crate) fn run(&self) -> Result<()> {
+        match &self.subcommand {
+            Some(SignerSubcommands::Run(args)) => args.run()?,
+            Some(SignerSubcommands::SignPayload(args)) => args.sign_payload()?,
+            None => SignerCommand::command().print_long_help()?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::cli::{get_path, init_tracing};
+use anyhow::{bail, Result};
+use clap::Parser;
+use rsnano_node::signing::{SignerService, SlashingProtection};
+use std::path::PathBuf;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+pub(crate) struct RunSignerArgs {
+    /// Unix socket the node connects to. Must match node.remote_signer.socket_path.
+    /// Only the user that runs the signer can connect to it
+    #[arg(long, default_value = "/tmp/rsnano_signer")]
+    socket: PathBuf,
+    /// File with one representative private key in hex per line
+    #[arg(long)]
+    keys_file: PathBuf,
+    /// File that records the signed final votes. Defaults to <data_path>/signer_slashing_protection
+    #[arg(long)]
+    slashing_protection: Option<PathBuf>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RunSignerArgs {
+    #[cfg(unix)]
+    pub(crate) fn run(&self) -> Result<()> {
+        use rsnano_node::signing::bind_private_socket;
+        use std::sync::Arc;
+
+        let dirs =
+            std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or(String::from("rsnano_node=info"));
+        init_tracing(dirs);
+
+        let keys = SignerService::load_keys(&self.keys_file)?;
+        if keys.is_empty() {
+            bail!("No keys found in {:?}", self.keys_file);
+        }
+
+        let protection_path = match &self.slashing_protection {
+            Some(path) => path.clone(),
+            None => {
+                let data_path = get_path(&self.data_path, &self.network);
+                std::fs::create_dir_all(&data_path)?;
+                data_path.join("signer_slashing_protection")
+            }
+        };
+        let protection = SlashingProtection::open(&protection_path)?;
+        let service = Arc::new(SignerService::new(keys, protection));
+
+        // Anyone who can connect to the socket can get votes signed
+        let listener = bind_private_socket(&self.socket)?;
+
+        println!(
+            "Signer listening on {:?} for {} representative(s)",
+            self.socket,
+            service.representatives().len()
+        );
+        for representative in service.representatives() {
+            println!("{}", representative.as_account().encode_account());
+        }
+
+        service.serve(listener)
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn run(&self) -> Result<()> {
+        bail!("The remote signer is only supported on Unix")
+    }
+}
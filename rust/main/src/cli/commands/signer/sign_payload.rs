@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use rsnano_core::{deterministic_key, Account, Amount, KeyPair, RawKey, SigningPayload};
+use std::{
+    fs::read_to_string,
+    io::{stdin, Read},
+    path::PathBuf,
+};
+
+#[derive(Parser)]
+pub(crate) struct SignPayloadArgs {
+    /// Signing payload created by the block_create_unsigned RPC
+    #[arg(long)]
+    payload: String,
+    /// File that contains the seed of the account that signs the block.
+    /// If omitted, the seed is read from stdin, so that it doesn't show up in the process list
+    #[arg(long)]
+    seed_file: Option<PathBuf>,
+    /// Index of the account. If omitted, the indices up to --max-index are searched
+    #[arg(long)]
+    index: Option<u32>,
+    /// Highest index to search for the account of the payload
+    #[arg(long, default_value_t = 1000)]
+    max_index: u32,
+}
+
+impl SignPayloadArgs {
+    pub(crate) fn sign_payload(&self) -> Result<()> {
+        let payload = SigningPayload::decode_hex(&self.payload)?;
+        let seed = RawKey::decode_hex(self.read_seed()?.trim())?;
+        let account = payload.hashables.account;
+
+        let key = match self.index {
+            Some(index) => deterministic_key(&seed, index),
+            None => (0..=self.max_index)
+                .map(|index| deterministic_key(&seed, index))
+                .find(|key| KeyPair::from(*key).account() == account)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "account {} not found in the indices 0 to {} of the seed",
+                        account.encode_account(),
+                        self.max_index
+                    )
+                })?,
+        };
+        let signature = payload.sign(&key)?;
+
+        println!("Account: {}", account.encode_account());
+        println!("Previous: {}", payload.hashables.previous);
+        println!(
+            "Representative: {}",
+            Account::from(payload.hashables.representative).encode_account()
+        );
+        // The previous balance comes from the node and isn't covered by the hash
+        println!(
+            "Previous balance (unverified, supplied by the node): {}",
+            payload.previous_balance.to_string_dec()
+        );
+        println!("Balance: {}", payload.hashables.balance.to_string_dec());
+        println!(
+            "Balance change (unverified, supplied by the node): {}",
+            balance_change(payload.previous_balance, payload.hashables.balance)
+        );
+        println!("Link: {}", payload.hashables.link);
+        if payload.hashables.balance < payload.previous_balance {
+            println!(
+                "Destination: {}",
+                Account::from(payload.hashables.link).encode_account()
+            );
+        }
+        println!("Hash: {}", payload.hash());
+        println!("Signature: {}", signature.encode_hex());
+
+        Ok(())
+    }
+
+    fn read_seed(&self) -> Result<String> {
+        match &self.seed_file {
+            Some(path) => Ok(read_to_string(path)?),
+            None => {
+                let mut seed = String::new();
+                stdin().read_to_string(&mut seed)?;
+                Ok(seed)
+            }
+        }
+    }
+}
+
+/// Signed difference between the previous and the new balance in raw
+fn balance_change(previous: Amount, balance: Amount) -> String {
+    if balance >= previous {
+        format!("+{}", (balance - previous).to_string_dec())
+    } else {
+        format!("-{}", (previous - balance).to_string_dec())
+    }
+}
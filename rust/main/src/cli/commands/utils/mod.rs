@@ -4,10 +4,12 @@ use clap::{CommandFactory, Parser, Subcommand};
 use expand_private_key::ExpandPrivateKeyArgs;
 use public_key_to_account::PublicKeyToAccountArgs;
 use rsnano_core::{Account, KeyPair};
+use work_benchmark::WorkBenchmarkArgs;
 
 pub(crate) mod account_to_public_key;
 pub(crate) mod expand_private_key;
 pub(crate) mod public_key_to_account;
+pub(crate) mod work_benchmark;
 
 #[derive(Subcommand)]
 pub(crate) enum UtilsSubcommands {
@@ -19,6 +21,8 @@ pub(crate) enum UtilsSubcommands {
     ExpandPrivateKey(ExpandPrivateKeyArgs),
     /// Generates a adhoc random keypair and prints it to stdout
     CreateKeyPair,
+    /// Measures the CPU proof-of-work hash rate and the expected time per work difficulty
+    WorkBenchmark(WorkBenchmarkArgs),
 }
 
 #[derive(Parser)]
@@ -34,6 +38,7 @@ impl UtilsCommand {
             Some(UtilsSubcommands::AccountToPublicKey(args)) => args.account_to_public_key()?,
             Some(UtilsSubcommands::ExpandPrivateKey(args)) => args.expand_private_key()?,
             Some(UtilsSubcommands::CreateKeyPair) => UtilsCommand::create_key_pair(),
+            Some(UtilsSubcommands::WorkBenchmark(args)) => args.work_benchmark()?,
             None => UtilsCommand::command().print_long_help()?,
         }
 
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use clap::Parser;
+use rsnano_core::{
+    work::{run_work_benchmark, CpuWorkBackend, WorkBenchmarkResult, WorkThresholds},
+    Networks,
+};
+use std::{str::FromStr, time::Duration};
+
+#[derive(Parser)]
+pub(crate) struct WorkBenchmarkArgs {
+    /// CPU work backend to benchmark (simple or batched)
+    #[arg(long, default_value = "batched")]
+    backend: String,
+    /// Number of work threads, defaults to the number of CPU cores
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Duration of the benchmark in seconds
+    #[arg(long, default_value_t = 10)]
+    duration: u64,
+    /// Reports the expected time for the thresholds of the supplied network (live, test, beta or dev)
+    #[arg(long, default_value = "live")]
+    network: String,
+}
+
+impl WorkBenchmarkArgs {
+    pub(crate) fn work_benchmark(&self) -> Result<()> {
+        let backend = CpuWorkBackend::from_str(&self.backend)?;
+        let network = Networks::from_str(&self.network).map_err(anyhow::Error::msg)?;
+        let thresholds = WorkThresholds::default_for(network);
+        let threads = self.threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|i| i.get())
+                .unwrap_or(1)
+        });
+
+        println!(
+            "Benchmarking {} backend with {} threads for {} seconds...",
+            backend.as_str(),
+            threads,
+            self.duration
+        );
+        let result = run_work_benchmark(backend, threads, Duration::from_secs(self.duration));
+
+        println!("Kernel: {}", result.kernel);
+        println!("Hashes: {}", result.hashes);
+        println!("Hashes/s: {:.0}", result.hashes_per_second());
+        println!("Expected time per difficulty on {}:", self.network);
+        for (name, difficulty) in [
+            ("entry", thresholds.entry),
+            ("epoch_1", thresholds.epoch_1),
+            ("epoch_2", thresholds.epoch_2),
+            ("epoch_2_receive", thresholds.epoch_2_receive),
+        ] {
+            let expected = match result.expected_time(difficulty) {
+                Some(time) => format!("{:.3}s", time.as_secs_f64()),
+                None => "n/a".to_owned(),
+            };
+            println!(
+                "  {:<16} {:016x} {:>14.0} hashes {:>12}",
+                name,
+                difficulty,
+                WorkBenchmarkResult::expected_hashes(difficulty),
+                expected
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsnano_core::{hd_wallet::Mnemonic, WalletId};
+use rsnano_node::wallets::{Wallets, WalletsExt};
+use rsnano_store_lmdb::LmdbEnv;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct CreateMnemonicArgs {
+    /// The wallet whose keys are replaced by the new mnemonic
+    #[arg(long)]
+    wallet: String,
+    /// Number of mnemonic words (12, 15, 18, 21 or 24)
+    #[arg(long, default_value_t = Mnemonic::DEFAULT_WORD_COUNT)]
+    words: usize,
+    /// Optional BIP39 <passphrase> that is combined with the mnemonic
+    #[arg(long)]
+    passphrase: Option<String>,
+    /// Optional <password> to unlock the wallet
+    #[arg(long)]
+    password: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl CreateMnemonicArgs {
+    pub(crate) async fn create_mnemonic(&self) -> Result<()> {
+        let wallet_id = WalletId::decode_hex(&self.wallet)?;
+        let mnemonic = Mnemonic::random(self.words)?;
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        let password = self.password.clone().unwrap_or_default();
+
+        wallets.ensure_wallet_is_unlocked(wallet_id, &password);
+
+        let passphrase = self.passphrase.clone().unwrap_or_default();
+        let (_, account) = wallets
+            .restore_mnemonic(wallet_id, &mnemonic, &passphrase, 0)
+            .map_err(|e| anyhow!("Failed to set wallet mnemonic: {:?}", e))?;
+
+        println!("Mnemonic: {}", mnemonic.phrase());
+        println!("Account: {}", account.encode_account());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsnano_core::WalletId;
+use rsnano_node::wallets::{Wallets, WalletsExt};
+use rsnano_store_lmdb::LmdbEnv;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct ExportMnemonicArgs {
+    /// The wallet whose mnemonic is printed
+    #[arg(long)]
+    wallet: String,
+    /// Optional <password> to unlock the wallet
+    #[arg(long)]
+    password: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl ExportMnemonicArgs {
+    pub(crate) async fn export_mnemonic(&self) -> Result<()> {
+        let wallet_id = WalletId::decode_hex(&self.wallet)?;
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        let password = self.password.clone().unwrap_or_default();
+
+        wallets.ensure_wallet_is_unlocked(wallet_id, &password);
+
+        let mnemonic = wallets
+            .get_mnemonic(wallet_id)
+            .map_err(|e| anyhow!("Failed to get wallet mnemonic: {:?}", e))?;
+
+        println!("Mnemonic: {}", mnemonic.phrase());
+
+        Ok(())
+    }
+}
@@ -1,29 +1,44 @@
 use add_private_key::AddPrivateKeyArgs;
-use anyhow::Result;
+use anyhow::{bail, Result};
+use backup_wallets::BackupWalletsArgs;
 use change_wallet_seed::ChangeWalletSeedArgs;
 use clap::{CommandFactory, Parser, Subcommand};
 use clear_send_ids::ClearSendIdsArgs;
 use create_account::CreateAccountArgs;
+use create_mnemonic::CreateMnemonicArgs;
 use create_wallet::CreateWalletArgs;
 use decrypt_wallet::DecryptWalletArgs;
 use destroy_wallet::DestroyWalletArgs;
+use export_mnemonic::ExportMnemonicArgs;
 use get_wallet_representative::GetWalletRepresentativeArgs;
 use import_keys::ImportKeysArgs;
 use list_wallets::ListWalletsArgs;
 use remove_account::RemoveAccountArgs;
+use restore_mnemonic::RestoreMnemonicArgs;
+use restore_wallets::RestoreWalletsArgs;
 use set_wallet_representative::SetWalletRepresentativeArgs;
+use std::{
+    fs::read_to_string,
+    io::{stdin, Read},
+    path::PathBuf,
+};
 
 pub(crate) mod add_private_key;
+pub(crate) mod backup_wallets;
 pub(crate) mod change_wallet_seed;
 pub(crate) mod clear_send_ids;
 pub(crate) mod create_account;
+pub(crate) mod create_mnemonic;
 pub(crate) mod create_wallet;
 pub(crate) mod decrypt_wallet;
 pub(crate) mod destroy_wallet;
+pub(crate) mod export_mnemonic;
 pub(crate) mod get_wallet_representative;
 pub(crate) mod import_keys;
 pub(crate) mod list_wallets;
 pub(crate) mod remove_account;
+pub(crate) mod restore_mnemonic;
+pub(crate) mod restore_wallets;
 pub(crate) mod set_wallet_representative;
 
 #[derive(Subcommand)]
@@ -40,6 +55,12 @@ pub(crate) enum WalletSubcommands {
     AddPrivateKey(AddPrivateKeyArgs),
     /// Changes the seed of a wallet
     ChangeWalletSeed(ChangeWalletSeedArgs),
+    /// Replaces the keys of a wallet with a newly generated BIP39 mnemonic and prints it
+    CreateMnemonic(CreateMnemonicArgs),
+    /// Replaces the keys of a wallet with ones derived from an existing BIP39 mnemonic
+    RestoreMnemonic(RestoreMnemonicArgs),
+    /// Prints the BIP39 mnemonic of a wallet (WARNING: THIS WILL PRINT YOUR MNEMONIC TO STDOUT!)
+    ExportMnemonic(ExportMnemonicArgs),
     /// Prints the representative of a wallet
     GetWalletRepresentative(GetWalletRepresentativeArgs),
     /// Sets the representative of a wallet
@@ -48,6 +69,10 @@ pub(crate) enum WalletSubcommands {
     RemoveAccount(RemoveAccountArgs),
     /// Decrypts a wallet (WARNING: THIS WILL PRINT YOUR PRIVATE KEY TO STDOUT!)
     DecryptWallet(DecryptWalletArgs),
+    /// Writes all wallets into a backup file encrypted with a passphrase
+    Backup(BackupWalletsArgs),
+    /// Restores the wallets of an encrypted backup file. Existing wallets are skipped
+    Restore(RestoreWalletsArgs),
     /// List all wallets and their public keys
     ListWallets(ListWalletsArgs),
     /// Removes all send IDs from the wallets (dangerous: not intended for production use)
@@ -69,6 +94,9 @@ impl WalletsCommand {
             Some(WalletSubcommands::DestroyWallet(args)) => args.destroy_wallet().await?,
             Some(WalletSubcommands::AddPrivateKey(args)) => args.add_key().await?,
             Some(WalletSubcommands::ChangeWalletSeed(args)) => args.change_wallet_seed().await?,
+            Some(WalletSubcommands::CreateMnemonic(args)) => args.create_mnemonic().await?,
+            Some(WalletSubcommands::RestoreMnemonic(args)) => args.restore_mnemonic().await?,
+            Some(WalletSubcommands::ExportMnemonic(args)) => args.export_mnemonic().await?,
             Some(WalletSubcommands::ImportKeys(args)) => args.import_keys().await?,
             Some(WalletSubcommands::RemoveAccount(args)) => args.remove_account().await?,
             Some(WalletSubcommands::DecryptWallet(args)) => args.decrypt_wallet().await?,
@@ -79,9 +107,31 @@ impl WalletsCommand {
                 args.set_representative_wallet().await?
             }
             Some(WalletSubcommands::ClearSendIds(args)) => args.clear_send_ids().await?,
+            Some(WalletSubcommands::Backup(args)) => args.backup_wallets().await?,
+            Some(WalletSubcommands::Restore(args)) => args.restore_wallets().await?,
             None => WalletsCommand::command().print_long_help()?,
         }
 
         Ok(())
     }
 }
+
+/// Reads a backup passphrase from the file or, if there is none, from stdin,
+/// so that it doesn't show up in the process list
+fn read_passphrase(file: &Option<PathBuf>) -> Result<String> {
+    let mut passphrase = match file {
+        Some(path) => read_to_string(path)?,
+        None => {
+            let mut passphrase = String::new();
+            stdin().read_to_string(&mut passphrase)?;
+            passphrase
+        }
+    };
+    // Only strip the line break, whitespace can be part of the passphrase
+    let len = passphrase.trim_end_matches(['\r', '\n']).len();
+    passphrase.truncate(len);
+    if passphrase.is_empty() {
+        bail!("the passphrase must not be empty");
+    }
+    Ok(passphrase)
+}
@@ -0,0 +1,55 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsnano_core::{hd_wallet::Mnemonic, WalletId};
+use rsnano_node::wallets::{Wallets, WalletsExt};
+use rsnano_store_lmdb::LmdbEnv;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct RestoreMnemonicArgs {
+    /// The wallet whose keys are replaced by the restored mnemonic
+    #[arg(long)]
+    wallet: String,
+    /// The BIP39 <mnemonic> phrase, words separated by spaces
+    #[arg(long)]
+    mnemonic: String,
+    /// Optional BIP39 <passphrase> that is combined with the mnemonic
+    #[arg(long)]
+    passphrase: Option<String>,
+    /// Optional <password> to unlock the wallet
+    #[arg(long)]
+    password: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RestoreMnemonicArgs {
+    pub(crate) async fn restore_mnemonic(&self) -> Result<()> {
+        let wallet_id = WalletId::decode_hex(&self.wallet)?;
+        let mnemonic = Mnemonic::from_phrase(&self.mnemonic)?;
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        let password = self.password.clone().unwrap_or_default();
+
+        wallets.ensure_wallet_is_unlocked(wallet_id, &password);
+
+        let passphrase = self.passphrase.clone().unwrap_or_default();
+        wallets
+            .restore_mnemonic(wallet_id, &mnemonic, &passphrase, 0)
+            .map_err(|e| anyhow!("Failed to restore wallet mnemonic: {:?}", e))?;
+
+        Ok(())
+    }
+}
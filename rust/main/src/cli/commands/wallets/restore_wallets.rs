@@ -0,0 +1,50 @@
+use super::read_passphrase;
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsnano_node::wallets::{WalletBackup, Wallets};
+use rsnano_store_lmdb::LmdbEnv;
+use std::{path::PathBuf, sync::Arc};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct RestoreWalletsArgs {
+    /// The backup <file> to restore
+    #[arg(long)]
+    file: PathBuf,
+    /// File that contains the passphrase of the backup.
+    /// If omitted, the passphrase is read from stdin, so that it doesn't show up in the process list
+    #[arg(long)]
+    passphrase_file: Option<PathBuf>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RestoreWalletsArgs {
+    pub(crate) async fn restore_wallets(&self) -> Result<()> {
+        let passphrase = read_passphrase(&self.passphrase_file)?;
+        let backup = WalletBackup::read(&self.file, &passphrase)?;
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let mut wallets = Wallets::new_null_with_env(env, tokio::runtime::Handle::current());
+        wallets.initialize()?;
+
+        let restored = wallets.restore_backup(&backup)?;
+
+        for wallet_id in &restored {
+            println!("Restored wallet {}", wallet_id);
+        }
+        println!(
+            "Restored {} of {} wallets",
+            restored.len(),
+            backup.wallets.len()
+        );
+
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
 use commands::{
-    config::ConfigCommand, ledger::LedgerCommand, node::NodeCommand, utils::UtilsCommand,
-    wallets::WalletsCommand,
+    config::ConfigCommand, ledger::LedgerCommand, node::NodeCommand, signer::SignerCommand,
+    utils::UtilsCommand, wallets::WalletsCommand,
 };
 use rsnano_core::Networks;
 use rsnano_node::{config::NetworkConstants, working_path};
@@ -25,6 +25,7 @@ impl Cli {
             Some(Commands::Node(command)) => command.run().await?,
             Some(Commands::Ledger(command)) => command.run()?,
             Some(Commands::Config(command)) => command.run()?,
+            Some(Commands::Signer(command)) => command.run()?,
             None => Cli::command().print_long_help()?,
         }
         Ok(())
@@ -39,6 +40,8 @@ pub(crate) enum Commands {
     Ledger(LedgerCommand),
     /// Commands related to running the node
     Node(NodeCommand),
+    /// Commands to run a remote signer for representative keys
+    Signer(SignerCommand),
     /// Utils related to keys and accounts
     Utils(UtilsCommand),
     /// Commands to manage wallets
@@ -1,6 +1,9 @@
-use crate::{token_bucket::TokenBucket, TrafficType};
+use crate::{
+    token_bucket::{TokenBucket, UNLIMITED},
+    ChannelId, TrafficType,
+};
 use num_derive::FromPrimitive;
-use std::sync::Mutex;
+use std::{collections::HashMap, str::FromStr, sync::Mutex};
 
 impl From<TrafficType> for BandwidthLimitType {
     fn from(value: TrafficType) -> Self {
@@ -13,6 +16,7 @@ impl From<TrafficType> for BandwidthLimitType {
 
 pub struct BandwidthLimiter {
     bucket: Mutex<TokenBucket>,
+    settings: Mutex<(f64, usize)>,
 }
 
 impl BandwidthLimiter {
@@ -22,6 +26,7 @@ impl BandwidthLimiter {
                 (limit as f64 * limit_burst_ratio) as usize,
                 limit,
             )),
+            settings: Mutex::new((limit_burst_ratio, limit)),
         }
     }
 
@@ -29,21 +34,80 @@ impl BandwidthLimiter {
         self.bucket.lock().unwrap().try_consume(message_size)
     }
 
+    pub fn can_pass(&self, message_size: usize) -> bool {
+        self.bucket.lock().unwrap().can_consume(message_size)
+    }
+
+    pub fn consume(&self, message_size: usize) {
+        self.bucket.lock().unwrap().consume(message_size)
+    }
+
     pub fn reset(&self, limit_burst_ratio: f64, limit: usize) {
+        *self.settings.lock().unwrap() = (limit_burst_ratio, limit);
         self.bucket
             .lock()
             .unwrap()
             .reset((limit as f64 * limit_burst_ratio) as usize, limit)
     }
+
+    /// Limit in bytes per second. 0 means unlimited
+    pub fn limit(&self) -> usize {
+        self.settings.lock().unwrap().1
+    }
+
+    pub fn burst_ratio(&self) -> f64 {
+        self.settings.lock().unwrap().0
+    }
 }
 
 /// Enumeration for different bandwidth limits for different traffic types
-#[derive(FromPrimitive)]
+#[derive(FromPrimitive, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum BandwidthLimitType {
     /** For all message */
     Standard,
     /** For bootstrap (asc_pull_ack, asc_pull_req) traffic */
     Bootstrap,
+    /** For votes (confirm_ack) */
+    Vote,
+    /** For published blocks */
+    Block,
+    /** For telemetry requests and responses */
+    Telemetry,
+    /** For all messages to a single peer. Every peer gets its own budget */
+    Peer,
+}
+
+impl BandwidthLimitType {
+    pub const ALL: [BandwidthLimitType; 6] = [
+        Self::Standard,
+        Self::Bootstrap,
+        Self::Vote,
+        Self::Block,
+        Self::Telemetry,
+        Self::Peer,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Bootstrap => "bootstrap",
+            Self::Vote => "vote",
+            Self::Block => "block",
+            Self::Telemetry => "telemetry",
+            Self::Peer => "peer",
+        }
+    }
+}
+
+impl FromStr for BandwidthLimitType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str() == s)
+            .ok_or_else(|| anyhow!("invalid bandwidth limit type: {}", s))
+    }
 }
 
 pub struct OutboundBandwidthLimiterConfig {
@@ -53,6 +117,16 @@ pub struct OutboundBandwidthLimiterConfig {
     // bootstrap
     pub bootstrap_limit: usize,
     pub bootstrap_burst_ratio: f64,
+    // per message category. 0 means unlimited
+    pub vote_limit: usize,
+    pub vote_burst_ratio: f64,
+    pub block_limit: usize,
+    pub block_burst_ratio: f64,
+    pub telemetry_limit: usize,
+    pub telemetry_burst_ratio: f64,
+    // per peer. 0 means unlimited
+    pub peer_limit: usize,
+    pub peer_burst_ratio: f64,
 }
 
 impl Default for OutboundBandwidthLimiterConfig {
@@ -62,6 +136,14 @@ impl Default for OutboundBandwidthLimiterConfig {
             standard_burst_ratio: 3_f64,
             bootstrap_limit: 5 * 1024 * 1024,
             bootstrap_burst_ratio: 1_f64,
+            vote_limit: 0,
+            vote_burst_ratio: 3_f64,
+            block_limit: 0,
+            block_burst_ratio: 3_f64,
+            telemetry_limit: 0,
+            telemetry_burst_ratio: 3_f64,
+            peer_limit: 0,
+            peer_burst_ratio: 3_f64,
         }
     }
 }
@@ -69,9 +151,36 @@ impl Default for OutboundBandwidthLimiterConfig {
 pub struct OutboundBandwidthLimiter {
     limiter_standard: BandwidthLimiter,
     limiter_bootstrap: BandwidthLimiter,
+    limiter_vote: BandwidthLimiter,
+    limiter_block: BandwidthLimiter,
+    limiter_telemetry: BandwidthLimiter,
+    peer_limiters: Mutex<PeerLimiters>,
+}
+
+struct PeerLimiters {
+    limit: usize,
+    burst_ratio: f64,
+    buckets: HashMap<ChannelId, TokenBucket>,
+}
+
+impl PeerLimiters {
+    fn max_token_count(&self) -> usize {
+        (self.limit as f64 * self.burst_ratio) as usize
+    }
+
+    fn bucket(&mut self, channel_id: ChannelId) -> &mut TokenBucket {
+        let max_token_count = self.max_token_count();
+        let limit = self.limit;
+        self.buckets
+            .entry(channel_id)
+            .or_insert_with(|| TokenBucket::new(max_token_count, limit))
+    }
 }
 
 impl OutboundBandwidthLimiter {
+    /// The highest limit in bytes per second that can be configured
+    pub const MAX_LIMIT: usize = UNLIMITED;
+
     pub fn new(config: OutboundBandwidthLimiterConfig) -> Self {
         Self {
             limiter_standard: BandwidthLimiter::new(
@@ -82,6 +191,17 @@ impl OutboundBandwidthLimiter {
                 config.bootstrap_burst_ratio,
                 config.bootstrap_limit,
             ),
+            limiter_vote: BandwidthLimiter::new(config.vote_burst_ratio, config.vote_limit),
+            limiter_block: BandwidthLimiter::new(config.block_burst_ratio, config.block_limit),
+            limiter_telemetry: BandwidthLimiter::new(
+                config.telemetry_burst_ratio,
+                config.telemetry_limit,
+            ),
+            peer_limiters: Mutex::new(PeerLimiters {
+                limit: config.peer_limit,
+                burst_ratio: config.peer_burst_ratio,
+                buckets: HashMap::new(),
+            }),
         }
     }
 
@@ -90,17 +210,86 @@ impl OutboundBandwidthLimiter {
      * @return true if OK, false if needs to be dropped
      */
     pub fn should_pass(&self, buffer_size: usize, limit_type: BandwidthLimitType) -> bool {
-        self.select_limiter(limit_type).should_pass(buffer_size)
+        match self.select_limiter(limit_type) {
+            Some(limiter) => limiter.should_pass(buffer_size),
+            None => true,
+        }
+    }
+
+    /// Checks the budget of the category without using it. Call `consume` once the message was sent
+    pub fn can_pass(&self, buffer_size: usize, limit_type: BandwidthLimitType) -> bool {
+        match self.select_limiter(limit_type) {
+            Some(limiter) => limiter.can_pass(buffer_size),
+            None => true,
+        }
+    }
+
+    pub fn consume(&self, buffer_size: usize, limit_type: BandwidthLimitType) {
+        if let Some(limiter) = self.select_limiter(limit_type) {
+            limiter.consume(buffer_size);
+        }
+    }
+
+    /// Checks the budget of the peer without using it. Call `consume_peer` once the message was sent
+    pub fn can_pass_peer(&self, buffer_size: usize, channel_id: ChannelId) -> bool {
+        let mut peers = self.peer_limiters.lock().unwrap();
+        if peers.limit == 0 {
+            return true;
+        }
+        // Only consume_peer creates buckets, so that checking unknown channels doesn't grow the map
+        match peers.buckets.get_mut(&channel_id) {
+            Some(bucket) => bucket.can_consume(buffer_size),
+            None => buffer_size <= peers.max_token_count(),
+        }
+    }
+
+    pub fn consume_peer(&self, buffer_size: usize, channel_id: ChannelId) {
+        let mut peers = self.peer_limiters.lock().unwrap();
+        if peers.limit == 0 {
+            return;
+        }
+        peers.bucket(channel_id).consume(buffer_size);
+    }
+
+    pub fn remove_peer(&self, channel_id: ChannelId) {
+        self.peer_limiters
+            .lock()
+            .unwrap()
+            .buckets
+            .remove(&channel_id);
     }
 
     pub fn reset(&self, limit: usize, burst_ratio: f64, limit_type: BandwidthLimitType) {
-        self.select_limiter(limit_type).reset(burst_ratio, limit);
+        match self.select_limiter(limit_type) {
+            Some(limiter) => limiter.reset(burst_ratio, limit),
+            None => {
+                let mut peers = self.peer_limiters.lock().unwrap();
+                peers.limit = limit;
+                peers.burst_ratio = burst_ratio;
+                peers.buckets.clear();
+            }
+        }
     }
 
-    fn select_limiter(&self, limit_type: BandwidthLimitType) -> &BandwidthLimiter {
+    /// Returns the limit in bytes per second and the burst ratio
+    pub fn limit(&self, limit_type: BandwidthLimitType) -> (usize, f64) {
+        match self.select_limiter(limit_type) {
+            Some(limiter) => (limiter.limit(), limiter.burst_ratio()),
+            None => {
+                let peers = self.peer_limiters.lock().unwrap();
+                (peers.limit, peers.burst_ratio)
+            }
+        }
+    }
+
+    fn select_limiter(&self, limit_type: BandwidthLimitType) -> Option<&BandwidthLimiter> {
         match limit_type {
-            BandwidthLimitType::Standard => &self.limiter_standard,
-            BandwidthLimitType::Bootstrap => &self.limiter_bootstrap,
+            BandwidthLimitType::Standard => Some(&self.limiter_standard),
+            BandwidthLimitType::Bootstrap => Some(&self.limiter_bootstrap),
+            BandwidthLimitType::Vote => Some(&self.limiter_vote),
+            BandwidthLimitType::Block => Some(&self.limiter_block),
+            BandwidthLimitType::Telemetry => Some(&self.limiter_telemetry),
+            BandwidthLimitType::Peer => None,
         }
     }
 }
@@ -126,4 +315,93 @@ mod tests {
         assert_eq!(limiter.should_pass(1), true);
         assert_eq!(limiter.should_pass(1), false);
     }
+
+    #[test]
+    fn category_limits_are_unlimited_by_default() {
+        let limiter = OutboundBandwidthLimiter::default();
+        assert!(limiter.should_pass(100_000_000, BandwidthLimitType::Vote));
+        assert!(limiter.can_pass_peer(100_000_000, ChannelId::from(1)));
+    }
+
+    #[test]
+    fn reset_category_limit() {
+        let limiter = OutboundBandwidthLimiter::default();
+        limiter.reset(10, 1.0, BandwidthLimitType::Vote);
+
+        assert_eq!(limiter.limit(BandwidthLimitType::Vote), (10, 1.0));
+        assert!(limiter.should_pass(10, BandwidthLimitType::Vote));
+        assert!(!limiter.should_pass(1, BandwidthLimitType::Vote));
+        assert!(limiter.should_pass(1, BandwidthLimitType::Block));
+    }
+
+    #[test]
+    fn checking_doesnt_use_the_budget() {
+        let limiter = OutboundBandwidthLimiter::default();
+        limiter.reset(10, 1.0, BandwidthLimitType::Vote);
+        limiter.reset(10, 1.0, BandwidthLimitType::Peer);
+
+        assert!(limiter.can_pass(10, BandwidthLimitType::Vote));
+        assert!(limiter.can_pass_peer(10, ChannelId::from(1)));
+        assert!(limiter.can_pass(10, BandwidthLimitType::Vote));
+        assert!(limiter.can_pass_peer(10, ChannelId::from(1)));
+
+        limiter.consume(10, BandwidthLimitType::Vote);
+        limiter.consume_peer(10, ChannelId::from(1));
+        assert!(!limiter.can_pass(1, BandwidthLimitType::Vote));
+        assert!(!limiter.can_pass_peer(1, ChannelId::from(1)));
+        assert!(limiter.can_pass_peer(10, ChannelId::from(2)));
+    }
+
+    #[test]
+    fn each_peer_has_its_own_budget() {
+        let limiter = OutboundBandwidthLimiter::new(OutboundBandwidthLimiterConfig {
+            peer_limit: 10,
+            peer_burst_ratio: 1.0,
+            ..Default::default()
+        });
+
+        limiter.consume_peer(10, ChannelId::from(1));
+        assert!(!limiter.can_pass_peer(1, ChannelId::from(1)));
+        assert!(limiter.can_pass_peer(10, ChannelId::from(2)));
+
+        limiter.remove_peer(ChannelId::from(1));
+        assert!(limiter.can_pass_peer(10, ChannelId::from(1)));
+    }
+
+    #[test]
+    fn reset_peer_limit() {
+        let limiter = OutboundBandwidthLimiter::default();
+        limiter.reset(10, 1.0, BandwidthLimitType::Peer);
+
+        assert_eq!(limiter.limit(BandwidthLimitType::Peer), (10, 1.0));
+        assert!(limiter.can_pass_peer(10, ChannelId::from(1)));
+        assert!(!limiter.can_pass_peer(11, ChannelId::from(1)));
+        limiter.consume_peer(10, ChannelId::from(1));
+        assert!(!limiter.can_pass_peer(1, ChannelId::from(1)));
+    }
+
+    #[test]
+    fn checking_doesnt_create_peer_buckets() {
+        let limiter = OutboundBandwidthLimiter::default();
+        limiter.reset(10, 1.0, BandwidthLimitType::Peer);
+
+        for i in 0..100 {
+            assert!(limiter.can_pass_peer(10, ChannelId::from(i)));
+        }
+        assert_eq!(limiter.peer_limiters.lock().unwrap().buckets.len(), 0);
+
+        limiter.consume_peer(10, ChannelId::from(1));
+        assert_eq!(limiter.peer_limiters.lock().unwrap().buckets.len(), 1);
+    }
+
+    #[test]
+    fn parse_limit_type() {
+        for limit_type in BandwidthLimitType::ALL {
+            assert_eq!(
+                limit_type.as_str().parse::<BandwidthLimitType>().unwrap(),
+                limit_type
+            );
+        }
+        assert!("foo".parse::<BandwidthLimitType>().is_err());
+    }
 }
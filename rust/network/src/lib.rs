@@ -11,6 +11,7 @@ pub mod peer_exclusion;
 mod response_server_spawner;
 mod tcp_listener;
 pub mod token_bucket;
+mod trusted_peer;
 pub mod utils;
 pub mod write_queue;
 
@@ -26,6 +27,7 @@ pub use peer_connector::*;
 pub use response_server_spawner::*;
 use std::fmt::{Debug, Display};
 pub use tcp_listener::*;
+pub use trusted_peer::*;
 
 #[macro_use]
 extern crate anyhow;
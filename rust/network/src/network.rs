@@ -9,6 +9,7 @@ use rsnano_nullable_clock::SteadyClock;
 use rsnano_nullable_tcp::TcpStream;
 use std::{
     collections::HashMap,
+    net::SocketAddrV6,
     sync::{Arc, Mutex, RwLock},
     time::{Duration, Instant},
 };
@@ -61,17 +62,15 @@ impl Network {
         !info.is_inbound_slot_available() && !info.is_stopped()
     }
 
+    /// Adds a channel for the given stream. `peer_addr` is the endpoint that was
+    /// dialled or accepted, which isn't the stream's peer address if a proxy is used
     pub fn add(
         &self,
         stream: TcpStream,
+        peer_addr: SocketAddrV6,
         direction: ChannelDirection,
         planned_mode: ChannelMode,
     ) -> anyhow::Result<Arc<Channel>> {
-        let peer_addr = stream
-            .peer_addr()
-            .map(into_ipv6_socket_address)
-            .unwrap_or(NULL_ENDPOINT);
-
         let local_addr = stream
             .local_addr()
             .map(into_ipv6_socket_address)
@@ -156,6 +155,10 @@ impl Network {
     pub fn port(&self) -> u16 {
         self.info.read().unwrap().listening_port()
     }
+
+    pub fn limiter(&self) -> &Arc<OutboundBandwidthLimiter> {
+        &self.limiter
+    }
 }
 
 pub struct NetworkCleanup(Arc<Network>);
@@ -171,6 +174,7 @@ impl DeadChannelCleanupStep for NetworkCleanup {
         let mut channels = self.0.channels.lock().unwrap();
         for channel_id in dead_channel_ids {
             channels.remove(channel_id);
+            self.0.limiter.remove_peer(*channel_id);
         }
     }
 }
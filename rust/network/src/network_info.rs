@@ -1,20 +1,21 @@
 use super::ChannelDirection;
 use crate::{
     attempt_container::AttemptContainer,
-    peer_exclusion::PeerExclusion,
+    peer_exclusion::{ExcludedPeer, IpSubnet, PeerExclusion},
     utils::{is_ipv4_mapped, map_address_to_subnetwork, reserved_address},
-    ChannelId, ChannelInfo, ChannelMode, TrafficType,
+    ChannelId, ChannelInfo, ChannelMode, TrafficType, TrustedPeer,
 };
-use rand::{seq::SliceRandom, thread_rng};
+use rand::seq::SliceRandom;
 use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
     Networks, PublicKey,
 };
 use rsnano_nullable_clock::Timestamp;
+use rsnano_nullable_random::NullableRng;
 use std::{
     collections::HashMap,
     net::{Ipv6Addr, SocketAddrV6},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use tracing::{debug, warn};
@@ -36,6 +37,7 @@ pub struct NetworkConfig {
     pub disable_max_peers_per_subnetwork: bool, // For testing only
     pub disable_network: bool,
     pub listening_port: u16,
+    pub trusted_peers: Vec<TrustedPeer>,
 }
 
 impl NetworkConfig {
@@ -64,6 +66,7 @@ impl NetworkConfig {
                 Networks::NanoTestNetwork => 17076,
                 _ => 7075,
             },
+            trusted_peers: Vec::new(),
         }
     }
 }
@@ -88,6 +91,11 @@ pub struct NetworkInfo {
     attempts: AttemptContainer,
     network_config: NetworkConfig,
     excluded_peers: PeerExclusion,
+    /// Picks the random peers for flooding and keepalives
+    rng: Mutex<NullableRng>,
+    /// Where peers on the internet can reach this node. It is either configured
+    /// or discovered by mapping the peering port on the gateway
+    external_address: Option<SocketAddrV6>,
 }
 
 impl NetworkInfo {
@@ -100,9 +108,16 @@ impl NetworkInfo {
             attempts: Default::default(),
             network_config,
             excluded_peers: PeerExclusion::new(),
+            rng: Mutex::new(NullableRng::thread_rng()),
+            external_address: None,
         }
     }
 
+    /// Makes the random peer selection reproducible
+    pub fn set_rng_seed(&mut self, seed: u64) {
+        self.rng = Mutex::new(NullableRng::new_seeded(seed));
+    }
+
     #[allow(dead_code)]
     pub fn new_test_instance() -> Self {
         Self::new(NetworkConfig::default_for(Networks::NanoDevNetwork))
@@ -135,6 +150,71 @@ impl NetworkInfo {
         self.excluded_peers.is_excluded(peer_addr, now)
     }
 
+    pub fn trusted_peers(&self) -> &[TrustedPeer] {
+        &self.network_config.trusted_peers
+    }
+
+    /// Checks if the handshake of the channel proved the node ID of a trusted peer
+    pub fn is_trusted_channel(&self, channel: &ChannelInfo) -> bool {
+        let Some(node_id) = channel.node_id() else {
+            return false;
+        };
+        self.network_config
+            .trusted_peers
+            .iter()
+            .any(|trusted| trusted.node_id == node_id)
+    }
+
+    /// Checks if the IP belongs to a trusted peer. The node ID isn't known before
+    /// the handshake, so this only exempts the IP from the connection limits,
+    /// but not from the exclusion of misbehaving peers
+    fn is_trusted_ip(&self, peer_addr: &SocketAddrV6) -> bool {
+        self.network_config
+            .trusted_peers
+            .iter()
+            .any(|trusted| trusted.endpoint.ip() == peer_addr.ip())
+    }
+
+    /// Bans a subnet until the given time and closes all channels to peers in it.
+    /// Returns the number of closed channels
+    pub fn ban(&mut self, subnet: IpSubnet, until: Timestamp) -> usize {
+        self.excluded_peers.ban(subnet, until);
+        let mut closed = 0;
+        for channel in self.channels.values() {
+            if subnet.contains(channel.peer_addr().ip()) && !channel.is_closed() {
+                channel.close();
+                closed += 1;
+            }
+        }
+        if closed > 0 {
+            warn!(%subnet, closed, "Banned subnet");
+        }
+        closed
+    }
+
+    pub fn unban(&mut self, subnet: &IpSubnet) -> bool {
+        self.excluded_peers.unban(subnet)
+    }
+
+    pub fn banned_subnets(&self) -> Vec<(IpSubnet, Timestamp)> {
+        self.excluded_peers.banned_subnets()
+    }
+
+    pub fn misbehaving_peers(&self) -> Vec<ExcludedPeer> {
+        self.excluded_peers.misbehaving_peers()
+    }
+
+    /// Closes the channel. Returns false if there is no open channel with that id
+    pub fn close_channel(&self, channel_id: ChannelId) -> bool {
+        match self.channels.get(&channel_id) {
+            Some(channel) if !channel.is_closed() => {
+                channel.close();
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn add_outbound_attempt(
         &mut self,
         peer: SocketAddrV6,
@@ -186,6 +266,14 @@ impl NetworkInfo {
         self.network_config.listening_port = port
     }
 
+    pub fn external_address(&self) -> Option<SocketAddrV6> {
+        self.external_address
+    }
+
+    pub fn set_external_address(&mut self, address: Option<SocketAddrV6>) {
+        self.external_address = address;
+    }
+
     pub fn get(&self, channel_id: ChannelId) -> Option<&Arc<ChannelInfo>> {
         self.channels.get(&channel_id)
     }
@@ -231,8 +319,7 @@ impl NetworkInfo {
 
     pub fn random_realtime_channels(&self, count: usize, min_version: u8) -> Vec<Arc<ChannelInfo>> {
         let mut channels = self.list_realtime(min_version);
-        let mut rng = thread_rng();
-        channels.shuffle(&mut rng);
+        channels.shuffle(&mut *self.rng.lock().unwrap());
         if count > 0 {
             channels.truncate(count)
         }
@@ -244,7 +331,8 @@ impl NetworkInfo {
     }
 
     pub fn list_realtime(&self, min_version: u8) -> Vec<Arc<ChannelInfo>> {
-        self.channels
+        let mut channels: Vec<_> = self
+            .channels
             .values()
             .filter(|c| {
                 c.protocol_version() >= min_version
@@ -252,7 +340,10 @@ impl NetworkInfo {
                     && c.mode() == ChannelMode::Realtime
             })
             .map(|c| c.clone())
-            .collect()
+            .collect();
+        // Sorted so that a seeded shuffle always picks the same peers
+        channels.sort_by_key(|c| c.channel_id());
+        channels
     }
 
     pub fn list_realtime_channels(&self, min_version: u8) -> Vec<Arc<ChannelInfo>> {
@@ -269,8 +360,7 @@ impl NetworkInfo {
 
     pub fn random_list_realtime(&self, count: usize, min_version: u8) -> Vec<Arc<ChannelInfo>> {
         let mut channels = self.list_realtime(min_version);
-        let mut rng = thread_rng();
-        channels.shuffle(&mut rng);
+        channels.shuffle(&mut *self.rng.lock().unwrap());
         if count > 0 {
             channels.truncate(count)
         }
@@ -475,8 +565,11 @@ impl NetworkInfo {
             return Err(NetworkError::MaxConnections);
         }
 
+        // Trusted peers are not subject to the total, per IP and per subnetwork
+        // connection limits, so that they always find a free slot
+        let trusted = self.is_trusted_ip(peer);
         let count = self.count_by_direction(direction);
-        if count >= self.max_connections(direction) {
+        if count >= self.max_connections(direction) && !trusted {
             return Err(NetworkError::MaxConnections);
         }
 
@@ -484,20 +577,22 @@ impl NetworkInfo {
             return Err(NetworkError::PeerExcluded);
         }
 
-        if !self.network_config.disable_max_peers_per_ip {
-            let count = self.count_by_ip(peer.ip());
-            if count >= self.network_config.max_peers_per_ip {
-                return Err(NetworkError::MaxConnectionsPerIp);
+        if !trusted {
+            if !self.network_config.disable_max_peers_per_ip {
+                let count = self.count_by_ip(peer.ip());
+                if count >= self.network_config.max_peers_per_ip {
+                    return Err(NetworkError::MaxConnectionsPerIp);
+                }
             }
-        }
 
-        // Don't overload single IP
-        if self.max_ip_connections(peer) {
-            return Err(NetworkError::MaxConnectionsPerIp);
-        }
+            // Don't overload single IP
+            if self.max_ip_connections(peer) {
+                return Err(NetworkError::MaxConnectionsPerIp);
+            }
 
-        if self.max_subnetwork_connections(peer) {
-            return Err(NetworkError::MaxConnectionsPerSubnetwork);
+            if self.max_subnetwork_connections(peer) {
+                return Err(NetworkError::MaxConnectionsPerSubnetwork);
+            }
         }
 
         // Don't contact invalid IPs
@@ -545,10 +640,11 @@ impl NetworkInfo {
         };
         let channel = channel.clone();
 
-        // Add to peer exclusion list
-
-        self.excluded_peers
-            .peer_misbehaved(&channel.peer_addr(), now);
+        // Add to peer exclusion list. Trusted peers are never excluded
+        if !self.is_trusted_channel(&channel) {
+            self.excluded_peers
+                .peer_misbehaved(&channel.peer_addr(), now);
+        }
 
         let peer_addr = channel.peer_addr();
         let mode = channel.mode();
@@ -577,8 +673,7 @@ impl NetworkInfo {
         let mut peers = self.list_realtime(0);
         // Don't include channels with ephemeral remote ports
         peers.retain(|c| c.peering_addr().is_some());
-        let mut rng = thread_rng();
-        peers.shuffle(&mut rng);
+        peers.shuffle(&mut *self.rng.lock().unwrap());
         peers.truncate(endpoints.len());
 
         let null_endpoint = SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0);
@@ -708,6 +803,7 @@ pub struct ChannelsInfo {
 mod tests {
     use super::*;
     use rsnano_core::utils::{NULL_ENDPOINT, TEST_ENDPOINT_1, TEST_ENDPOINT_2, TEST_ENDPOINT_3};
+    use std::net::Ipv4Addr;
 
     #[test]
     fn newly_added_channel_is_not_a_realtime_channel() {
@@ -724,6 +820,179 @@ mod tests {
         assert_eq!(network.list_realtime_channels(0).len(), 0);
     }
 
+    #[test]
+    fn ban_closes_channels_in_subnet() {
+        let mut network = NetworkInfo::new_test_instance();
+        let now = Timestamp::new_test_instance();
+        let channel = network
+            .add(
+                NULL_ENDPOINT,
+                TEST_ENDPOINT_1,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now,
+            )
+            .unwrap();
+
+        let closed = network.ban(IpSubnet::single(*TEST_ENDPOINT_1.ip()), Timestamp::MAX);
+
+        assert_eq!(closed, 1);
+        assert!(channel.is_closed());
+        assert!(network.is_excluded(&TEST_ENDPOINT_1, now));
+        assert!(!network.is_excluded(&TEST_ENDPOINT_2, now));
+    }
+
+    #[test]
+    fn close_channel() {
+        let mut network = NetworkInfo::new_test_instance();
+        let channel = network
+            .add(
+                NULL_ENDPOINT,
+                TEST_ENDPOINT_1,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                Timestamp::new_test_instance(),
+            )
+            .unwrap();
+
+        assert!(network.close_channel(channel.channel_id()));
+        assert!(channel.is_closed());
+        assert!(!network.close_channel(channel.channel_id()));
+    }
+
+    #[test]
+    fn trusted_ip_is_exempt_from_total_connection_limit() {
+        let mut network = network_with_trusted_peer(|config| {
+            config.max_inbound_connections = 0;
+        });
+        let now = Timestamp::new_test_instance();
+
+        assert!(network
+            .validate_new_connection(
+                &TRUSTED_ENDPOINT,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now
+            )
+            .is_ok());
+        assert!(matches!(
+            network.validate_new_connection(
+                &TEST_ENDPOINT_2,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now
+            ),
+            Err(NetworkError::MaxConnections)
+        ));
+    }
+
+    #[test]
+    fn trusted_ip_is_exempt_from_per_ip_limit() {
+        let mut network = network_with_trusted_peer(|config| {
+            config.max_peers_per_ip = 1;
+        });
+        let now = Timestamp::new_test_instance();
+        network
+            .add(
+                NULL_ENDPOINT,
+                TRUSTED_ENDPOINT,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now,
+            )
+            .unwrap();
+
+        let same_ip = SocketAddrV6::new(*TRUSTED_ENDPOINT.ip(), 1234, 0, 0);
+        assert!(network
+            .validate_new_connection(
+                &same_ip,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn untrusted_ip_is_subject_to_per_ip_limit() {
+        let mut network = network_with_trusted_peer(|config| {
+            config.max_peers_per_ip = 1;
+        });
+        let now = Timestamp::new_test_instance();
+        network
+            .add(
+                NULL_ENDPOINT,
+                UNTRUSTED_ENDPOINT,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now,
+            )
+            .unwrap();
+
+        let same_ip = SocketAddrV6::new(*UNTRUSTED_ENDPOINT.ip(), 1234, 0, 0);
+        assert!(matches!(
+            network.validate_new_connection(
+                &same_ip,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now
+            ),
+            Err(NetworkError::MaxConnectionsPerIp)
+        ));
+    }
+
+    #[test]
+    fn only_channels_with_trusted_node_id_are_exempt_from_exclusion() {
+        let mut network = network_with_trusted_peer(|_| {});
+        let now = Timestamp::new_test_instance();
+        let trusted = network
+            .add(
+                NULL_ENDPOINT,
+                TRUSTED_ENDPOINT,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now,
+            )
+            .unwrap();
+        trusted.set_node_id(PublicKey::from(1));
+        let impostor = network
+            .add(
+                NULL_ENDPOINT,
+                SocketAddrV6::new(*TRUSTED_ENDPOINT.ip(), 1234, 0, 0),
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                now,
+            )
+            .unwrap();
+        impostor.set_node_id(PublicKey::from(2));
+
+        assert!(network.is_trusted_channel(&trusted));
+        assert!(!network.is_trusted_channel(&impostor));
+
+        network.peer_misbehaved(trusted.channel_id(), now);
+        assert!(trusted.is_closed());
+        assert_eq!(network.misbehaving_peers().len(), 0);
+
+        network.peer_misbehaved(impostor.channel_id(), now);
+        assert_eq!(network.misbehaving_peers().len(), 1);
+    }
+
+    fn network_with_trusted_peer(configure: impl FnOnce(&mut NetworkConfig)) -> NetworkInfo {
+        let mut config = NetworkConfig::default_for(Networks::NanoDevNetwork);
+        config.trusted_peers = vec![TrustedPeer {
+            node_id: PublicKey::from(1),
+            endpoint: TRUSTED_ENDPOINT,
+        }];
+        configure(&mut config);
+        NetworkInfo::new(config)
+    }
+
+    const TRUSTED_ENDPOINT: SocketAddrV6 =
+        SocketAddrV6::new(Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped(), 7075, 0, 0);
+
+    const UNTRUSTED_ENDPOINT: SocketAddrV6 =
+        SocketAddrV6::new(Ipv4Addr::new(10, 0, 0, 2).to_ipv6_mapped(), 7075, 0, 0);
+
     #[test]
     fn reserved_ip_is_not_a_peer() {
         let network = NetworkInfo::new_test_instance();
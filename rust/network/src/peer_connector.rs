@@ -3,7 +3,7 @@ use crate::{
     NullResponseServerSpawner, ResponseServerSpawner,
 };
 use rsnano_nullable_clock::SteadyClock;
-use rsnano_nullable_tcp::TcpStream;
+use rsnano_nullable_tcp::TcpStreamFactory;
 use rsnano_output_tracker::{OutputListenerMt, OutputTrackerMt};
 use std::{net::SocketAddrV6, sync::Arc, time::Duration};
 use tokio_util::sync::CancellationToken;
@@ -18,6 +18,7 @@ pub struct PeerConnector {
     response_server_spawner: Arc<dyn ResponseServerSpawner>,
     connect_listener: OutputListenerMt<SocketAddrV6>,
     clock: Arc<SteadyClock>,
+    tcp_stream_factory: Arc<TcpStreamFactory>,
 }
 
 impl PeerConnector {
@@ -30,6 +31,7 @@ impl PeerConnector {
         tokio: tokio::runtime::Handle,
         response_server_spawner: Arc<dyn ResponseServerSpawner>,
         clock: Arc<SteadyClock>,
+        tcp_stream_factory: Arc<TcpStreamFactory>,
     ) -> Self {
         Self {
             connect_timeout,
@@ -40,6 +42,7 @@ impl PeerConnector {
             response_server_spawner,
             connect_listener: OutputListenerMt::new(),
             clock,
+            tcp_stream_factory,
         }
     }
 
@@ -53,6 +56,7 @@ impl PeerConnector {
             response_server_spawner: Arc::new(NullResponseServerSpawner::new()),
             connect_listener: OutputListenerMt::new(),
             clock: Arc::new(SteadyClock::new_null()),
+            tcp_stream_factory: Arc::new(TcpStreamFactory::new_null()),
         }
     }
 
@@ -103,10 +107,11 @@ impl PeerConnector {
         let connect_timeout = self.connect_timeout;
         let cancel_token = self.cancel_token.clone();
         let observer = self.network_observer.clone();
+        let tcp_stream_factory = self.tcp_stream_factory.clone();
 
         self.tokio.spawn(async move {
             tokio::select! {
-                result =  connect_impl(peer, &network_l, &*response_server_spawner_l) =>{
+                result =  connect_impl(peer, &network_l, &*response_server_spawner_l, &tcp_stream_factory) =>{
                     if let Err(e) = result {
                         observer.connect_error(peer, e);
                     }
@@ -137,11 +142,13 @@ async fn connect_impl(
     peer: SocketAddrV6,
     network: &Network,
     response_server_spawner: &dyn ResponseServerSpawner,
+    tcp_stream_factory: &TcpStreamFactory,
 ) -> anyhow::Result<()> {
-    let tcp_stream = connect_stream(peer).await?;
+    let tcp_stream = tcp_stream_factory.connect(peer).await?;
 
     let channel = network.add(
         tcp_stream,
+        peer,
         ChannelDirection::Outbound,
         ChannelMode::Realtime,
     )?;
@@ -150,16 +157,14 @@ async fn connect_impl(
     Ok(())
 }
 
-async fn connect_stream(peer: SocketAddrV6) -> tokio::io::Result<TcpStream> {
-    let socket = tokio::net::TcpSocket::new_v6()?;
-    let tcp_stream = socket.connect(peer.into()).await?;
-    Ok(TcpStream::new(tcp_stream))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::into_ipv6_socket_address;
     use rsnano_core::utils::TEST_ENDPOINT_1;
+    use rsnano_nullable_tcp::Socks5StubProxy;
+    use std::time::Instant;
+    use tokio::net::TcpListener;
 
     #[tokio::test]
     async fn track_connections() {
@@ -170,4 +175,35 @@ mod tests {
 
         assert_eq!(connect_tracker.output(), vec![TEST_ENDPOINT_1]);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn create_one_channel_per_proxied_peer() {
+        let proxy = Socks5StubProxy::start(None).await.unwrap();
+        let listener_a = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let listener_b = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let peer_a = into_ipv6_socket_address(listener_a.local_addr().unwrap());
+        let peer_b = into_ipv6_socket_address(listener_b.local_addr().unwrap());
+        let network = Arc::new(Network::new_null(tokio::runtime::Handle::current()));
+        let peer_connector = PeerConnector::new(
+            PeerConnector::DEFAULT_TIMEOUT,
+            network.clone(),
+            Arc::new(NullNetworkObserver::new()),
+            tokio::runtime::Handle::current(),
+            Arc::new(NullResponseServerSpawner::new()),
+            Arc::new(SteadyClock::new_null()),
+            Arc::new(TcpStreamFactory::new_with_socks5_proxy(proxy.proxy())),
+        );
+
+        peer_connector.connect_to(peer_a);
+        peer_connector.connect_to(peer_b);
+
+        let start = Instant::now();
+        while network.info.read().unwrap().len() < 2 {
+            assert!(start.elapsed() < Duration::from_secs(5), "timeout");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let info = network.info.read().unwrap();
+        assert_eq!(info.find_channels_by_remote_addr(&peer_a).len(), 1);
+        assert_eq!(info.find_channels_by_remote_addr(&peer_b).len(), 1);
+    }
 }
@@ -2,8 +2,10 @@ use rsnano_core::utils::{ContainerInfo, ContainerInfoComponent};
 use rsnano_nullable_clock::Timestamp;
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
     mem::size_of,
-    net::{Ipv6Addr, SocketAddrV6},
+    net::{IpAddr, Ipv6Addr, SocketAddrV6},
+    str::FromStr,
     time::Duration,
 };
 
@@ -14,6 +16,8 @@ pub struct PeerExclusion {
     by_ip: HashMap<Ipv6Addr, Peer>,
     max_size: usize,
     perma_bans: HashSet<SocketAddrV6>,
+    /// Subnets that were banned by the node operator
+    banned_subnets: HashMap<IpSubnet, Timestamp>,
 }
 
 impl PeerExclusion {
@@ -28,6 +32,7 @@ impl PeerExclusion {
             by_ip: HashMap::new(),
             max_size,
             perma_bans: HashSet::new(),
+            banned_subnets: HashMap::new(),
         }
     }
 
@@ -56,9 +61,64 @@ impl PeerExclusion {
         self.perma_bans.insert(peer_addr);
     }
 
+    /// Bans all addresses of the subnet until the given time.
+    /// Use `Timestamp::MAX` for a ban that never expires
+    pub fn ban(&mut self, subnet: IpSubnet, until: Timestamp) {
+        self.banned_subnets.insert(subnet, until);
+    }
+
+    /// Removes the ban of the subnet and forgets the misbehavior of all peers in it.
+    /// Returns true if anything was removed
+    pub fn unban(&mut self, subnet: &IpSubnet) -> bool {
+        let mut removed = self.banned_subnets.remove(subnet).is_some();
+        let misbehaved: Vec<_> = self
+            .by_ip
+            .values()
+            .filter(|peer| subnet.contains(peer.address.ip()))
+            .map(|peer| peer.address)
+            .collect();
+        for address in misbehaved {
+            self.remove(&address);
+            removed = true;
+        }
+        removed
+    }
+
+    pub fn banned_subnets(&self) -> Vec<(IpSubnet, Timestamp)> {
+        self.banned_subnets
+            .iter()
+            .map(|(subnet, until)| (*subnet, *until))
+            .collect()
+    }
+
+    /// Peers that misbehaved, ordered by the end of their exclusion
+    pub fn misbehaving_peers(&self) -> Vec<ExcludedPeer> {
+        let mut peers: Vec<_> = self
+            .by_ip
+            .values()
+            .map(|peer| ExcludedPeer {
+                address: peer.address,
+                score: peer.score,
+                exclude_until: peer.exclude_until,
+            })
+            .collect();
+        peers.sort_by_key(|peer| peer.exclude_until);
+        peers
+    }
+
     #[allow(dead_code)]
     pub fn contains(&self, endpoint: &SocketAddrV6) -> bool {
-        self.by_ip.contains_key(&endpoint.ip()) || self.perma_bans.contains(endpoint)
+        self.by_ip.contains_key(&endpoint.ip())
+            || self.perma_bans.contains(endpoint)
+            || self.ban_end(endpoint.ip()).is_some()
+    }
+
+    fn ban_end(&self, ip: &Ipv6Addr) -> Option<Timestamp> {
+        self.banned_subnets
+            .iter()
+            .filter(|(subnet, _)| subnet.contains(ip))
+            .map(|(_, until)| *until)
+            .max()
     }
 
     #[allow(dead_code)]
@@ -66,9 +126,11 @@ impl PeerExclusion {
         if self.perma_bans.contains(endpoint) {
             Some(Timestamp::MAX)
         } else {
-            self.by_ip
+            let misbehaved = self
+                .by_ip
                 .get(&endpoint.ip())
-                .map(|item| item.exclude_until)
+                .map(|item| item.exclude_until);
+            misbehaved.max(self.ban_end(endpoint.ip()))
         }
     }
 
@@ -78,6 +140,13 @@ impl PeerExclusion {
             return true;
         }
 
+        if !self.banned_subnets.is_empty() {
+            self.banned_subnets.retain(|_, until| *until > now);
+            if self.ban_end(peer_addr.ip()).is_some() {
+                return true;
+            }
+        }
+
         if let Some(peer) = self.by_ip.get(&peer_addr.ip()).cloned() {
             if peer.has_expired(now) {
                 self.remove(&peer.address);
@@ -97,7 +166,7 @@ impl PeerExclusion {
 
     #[allow(dead_code)]
     pub fn len(&self) -> usize {
-        self.by_ip.len() + self.perma_bans.len()
+        self.by_ip.len() + self.perma_bans.len() + self.banned_subnets.len()
     }
 
     fn clean_old_peers(&mut self) {
@@ -116,11 +185,18 @@ impl PeerExclusion {
     pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
         ContainerInfoComponent::Composite(
             name.into(),
-            vec![ContainerInfoComponent::Leaf(ContainerInfo {
-                name: "peers".to_string(),
-                count: self.by_ip.len(),
-                sizeof_element: size_of::<Peer>(),
-            })],
+            vec![
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "peers".to_string(),
+                    count: self.by_ip.len(),
+                    sizeof_element: size_of::<Peer>(),
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "banned_subnets".to_string(),
+                    count: self.banned_subnets.len(),
+                    sizeof_element: size_of::<(IpSubnet, Timestamp)>(),
+                }),
+            ],
         )
     }
 }
@@ -131,6 +207,96 @@ impl Default for PeerExclusion {
     }
 }
 
+/// A peer that is excluded because it misbehaved
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExcludedPeer {
+    pub address: SocketAddrV6,
+    pub score: u64,
+    pub exclude_until: Timestamp,
+}
+
+/// A range of IP addresses. IPv4 subnets are stored as IPv4-mapped IPv6 subnets
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct IpSubnet {
+    address: Ipv6Addr,
+    prefix_len: u8,
+}
+
+impl IpSubnet {
+    pub fn new(address: Ipv6Addr, prefix_len: u8) -> Self {
+        let prefix_len = prefix_len.min(128);
+        Self {
+            address: Ipv6Addr::from(u128::from(address) & Self::mask(prefix_len)),
+            prefix_len,
+        }
+    }
+
+    /// A subnet that contains just the given address
+    pub fn single(address: Ipv6Addr) -> Self {
+        Self::new(address, 128)
+    }
+
+    pub fn address(&self) -> Ipv6Addr {
+        self.address
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    pub fn contains(&self, ip: &Ipv6Addr) -> bool {
+        u128::from(*ip) & Self::mask(self.prefix_len) == u128::from(self.address)
+    }
+
+    fn mask(prefix_len: u8) -> u128 {
+        if prefix_len == 0 {
+            0
+        } else {
+            u128::MAX << (128 - prefix_len as u32)
+        }
+    }
+}
+
+impl FromStr for IpSubnet {
+    type Err = anyhow::Error;
+
+    /// Parses an IP address ("1.2.3.4", "2001:db8::1") or a subnet in CIDR notation
+    /// ("1.2.3.0/24", "2001:db8::/32")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_len) = match s.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len.parse::<u8>()?)),
+            None => (s, None),
+        };
+        match address.parse::<IpAddr>()? {
+            IpAddr::V4(ip) => {
+                let prefix_len = prefix_len.unwrap_or(32);
+                if prefix_len > 32 {
+                    bail!("invalid IPv4 prefix length: {}", prefix_len);
+                }
+                Ok(Self::new(ip.to_ipv6_mapped(), 96 + prefix_len))
+            }
+            IpAddr::V6(ip) => {
+                let prefix_len = prefix_len.unwrap_or(128);
+                if prefix_len > 128 {
+                    bail!("invalid IPv6 prefix length: {}", prefix_len);
+                }
+                Ok(Self::new(ip, prefix_len))
+            }
+        }
+    }
+}
+
+impl Display for IpSubnet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.address.to_ipv4_mapped() {
+            Some(ip) if self.prefix_len >= 96 => {
+                write!(f, "{}/{}", ip, self.prefix_len - 96)
+            }
+            _ => write!(f, "{}/{}", self.address, self.prefix_len),
+        }
+    }
+}
+
 /// Information about a peer and its exclusion status
 #[derive(Clone)]
 struct Peer {
@@ -220,7 +386,7 @@ impl PeersOrderedByExclusionDate {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::net::Ipv6Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
 
     #[test]
     fn new_excluded_peers_excludes_nothing() {
@@ -331,6 +497,92 @@ mod tests {
         }
     }
 
+    mod banned_subnets {
+        use super::*;
+
+        #[test]
+        fn ban_subnet() {
+            let mut peers = PeerExclusion::new();
+            let subnet: IpSubnet = "10.0.0.0/24".parse().unwrap();
+            peers.ban(subnet, NOW + Duration::from_secs(60));
+
+            let banned = endpoint_v4(10, 0, 0, 42);
+            let not_banned = endpoint_v4(10, 0, 1, 42);
+            assert!(peers.is_excluded(&banned, NOW));
+            assert!(!peers.is_excluded(&not_banned, NOW));
+            assert_eq!(
+                peers.excluded_until(&banned),
+                Some(NOW + Duration::from_secs(60))
+            );
+        }
+
+        #[test]
+        fn ban_expires() {
+            let mut peers = PeerExclusion::new();
+            let endpoint = endpoint_v4(10, 0, 0, 1);
+            peers.ban(
+                IpSubnet::single(*endpoint.ip()),
+                NOW + Duration::from_secs(60),
+            );
+            assert!(!peers.is_excluded(&endpoint, NOW + Duration::from_secs(61)));
+            assert!(peers.banned_subnets().is_empty());
+        }
+
+        #[test]
+        fn unban_removes_ban_and_misbehavior() {
+            let mut peers = PeerExclusion::new();
+            let endpoint = endpoint_v4(10, 0, 0, 1);
+            peers.peer_misbehaved(&endpoint, NOW);
+            peers.peer_misbehaved(&endpoint, NOW);
+            let subnet = IpSubnet::single(*endpoint.ip());
+            peers.ban(subnet, Timestamp::MAX);
+
+            assert!(peers.unban(&subnet));
+
+            assert!(!peers.is_excluded(&endpoint, NOW));
+            assert!(peers.misbehaving_peers().is_empty());
+            assert!(!peers.unban(&subnet));
+        }
+
+        #[test]
+        fn list_misbehaving_peers() {
+            let mut peers = PeerExclusion::new();
+            let endpoint = test_endpoint(1);
+            peers.peer_misbehaved(&endpoint, NOW);
+            peers.peer_misbehaved(&endpoint, NOW);
+            assert_eq!(
+                peers.misbehaving_peers(),
+                vec![ExcludedPeer {
+                    address: endpoint,
+                    score: 2,
+                    exclude_until: NOW + Peer::EXCLUDE_TIME
+                }]
+            );
+        }
+
+        #[test]
+        fn parse_subnets() {
+            let ipv4: IpSubnet = "1.2.3.4/24".parse().unwrap();
+            assert_eq!(ipv4.prefix_len(), 120);
+            assert_eq!(ipv4.to_string(), "1.2.3.0/24");
+
+            let single: IpSubnet = "1.2.3.4".parse().unwrap();
+            assert_eq!(single.to_string(), "1.2.3.4/32");
+
+            let ipv6: IpSubnet = "2001:db8::1/32".parse().unwrap();
+            assert_eq!(ipv6.to_string(), "2001:db8::/32");
+            assert!(ipv6.contains(&"2001:db8:ffff::1".parse().unwrap()));
+            assert!(!ipv6.contains(&"2001:db9::1".parse().unwrap()));
+
+            assert!("1.2.3.4/33".parse::<IpSubnet>().is_err());
+            assert!("foo".parse::<IpSubnet>().is_err());
+        }
+
+        fn endpoint_v4(a: u8, b: u8, c: u8, d: u8) -> SocketAddrV6 {
+            SocketAddrV6::new(Ipv4Addr::new(a, b, c, d).to_ipv6_mapped(), 7075, 0, 0)
+        }
+    }
+
     fn test_endpoint(i: usize) -> SocketAddrV6 {
         SocketAddrV6::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, i as u16), 0, 0, 0)
     }
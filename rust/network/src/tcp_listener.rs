@@ -1,4 +1,7 @@
-use crate::{ChannelDirection, ChannelMode, Network, NetworkObserver, ResponseServerSpawner};
+use crate::{
+    utils::into_ipv6_socket_address, ChannelDirection, ChannelMode, Network, NetworkObserver,
+    ResponseServerSpawner,
+};
 use async_trait::async_trait;
 use rsnano_nullable_tcp::TcpStream;
 use std::{
@@ -126,7 +129,7 @@ impl TcpListenerExt for Arc<TcpListener> {
             loop {
                 self.network.wait_for_available_inbound_slot().await;
 
-                let Ok((stream, _)) = listener.accept().await else {
+                let Ok((stream, peer_addr)) = listener.accept().await else {
                     warn!("Could not accept incoming connection");
                     self.network_observer.accept_failure();
                     continue;
@@ -135,6 +138,7 @@ impl TcpListenerExt for Arc<TcpListener> {
                 let tcp_stream = TcpStream::new(stream);
                 match self.network.add(
                     tcp_stream,
+                    into_ipv6_socket_address(peer_addr),
                     ChannelDirection::Inbound,
                     ChannelMode::Undefined,
                 ) {
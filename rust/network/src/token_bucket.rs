@@ -25,7 +25,7 @@ pub struct TokenBucket {
     refill_rate: usize,
 }
 
-const UNLIMITED: usize = 1_000_000_000;
+pub(crate) const UNLIMITED: usize = 1_000_000_000;
 
 impl TokenBucket {
     /**
@@ -68,6 +68,20 @@ impl TokenBucket {
         possible || self.refill_rate == UNLIMITED
     }
 
+    /// Checks if `tokens_required` tokens are available without deducting them
+    pub fn can_consume(&mut self, tokens_required: usize) -> bool {
+        debug_assert!(tokens_required <= UNLIMITED);
+        self.refill();
+        self.current_size >= tokens_required || self.refill_rate == UNLIMITED
+    }
+
+    /// Deducts the tokens even if not enough are available
+    pub fn consume(&mut self, tokens: usize) {
+        self.refill();
+        self.current_size = self.current_size.saturating_sub(tokens);
+        self.smallest_size = std::cmp::min(self.smallest_size, self.current_size);
+    }
+
     /** Update the max_token_count and/or refill_rate_a parameters */
     pub fn reset(&mut self, mut max_token_count: usize, mut refill_rate: usize) {
         // A token count of 0 indicates unlimited capacity. We use 1e9 as
@@ -110,6 +124,21 @@ mod tests {
     use super::*;
     use mock_instant::MockClock;
 
+    #[test]
+    fn check_without_consuming() {
+        let mut bucket = TokenBucket::new(10, 10);
+        assert!(bucket.can_consume(10));
+        assert!(bucket.can_consume(10));
+        assert!(!bucket.can_consume(11));
+
+        bucket.consume(8);
+        assert!(bucket.can_consume(2));
+        assert!(!bucket.can_consume(3));
+
+        bucket.consume(8);
+        assert!(!bucket.can_consume(1));
+    }
+
     #[test]
     fn basic() {
         let mut bucket = TokenBucket::new(10, 10);
@@ -0,0 +1,69 @@
+use crate::utils::into_ipv6_socket_address;
+use rsnano_core::{Account, PublicKey};
+use std::{
+    fmt::Display,
+    net::{SocketAddr, SocketAddrV6},
+    str::FromStr,
+};
+
+/// A peer of the node operator that is always kept connected.
+/// Trusted peers are exempt from peer exclusion and from the connection limits
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TrustedPeer {
+    pub node_id: PublicKey,
+    pub endpoint: SocketAddrV6,
+}
+
+impl FromStr for TrustedPeer {
+    type Err = anyhow::Error;
+
+    /// Parses "node_id@endpoint", e.g. "node_1abc...@[::ffff:10.0.0.1]:7075"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((node_id, endpoint)) = s.split_once('@') else {
+            bail!("trusted peer must be in the format node_id@endpoint");
+        };
+        Ok(Self {
+            node_id: Account::decode_node_id(node_id)?.into(),
+            endpoint: into_ipv6_socket_address(endpoint.parse::<SocketAddr>()?),
+        })
+    }
+}
+
+impl Display for TrustedPeer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.node_id.to_node_id(), self.endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn parse() {
+        let node_id = PublicKey::from(42);
+        let peer: TrustedPeer = format!("{}@10.0.0.1:7075", node_id.to_node_id())
+            .parse()
+            .unwrap();
+        assert_eq!(
+            peer,
+            TrustedPeer {
+                node_id,
+                endpoint: SocketAddrV6::new(
+                    Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped(),
+                    7075,
+                    0,
+                    0
+                )
+            }
+        );
+        assert_eq!(peer.to_string().parse::<TrustedPeer>().unwrap(), peer);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("10.0.0.1:7075".parse::<TrustedPeer>().is_err());
+        assert!("foo@10.0.0.1:7075".parse::<TrustedPeer>().is_err());
+    }
+}
@@ -54,6 +54,8 @@ pub type BlockProcessorCallback = Box<dyn Fn(BlockStatus) + Send + Sync>;
 pub struct BlockProcessorContext {
     pub block: Arc<BlockEnum>,
     pub source: BlockSource,
+    /// The channel that sent the block. Loopback for local blocks
+    pub channel_id: ChannelId,
     callback: Option<BlockProcessorCallback>,
     pub arrival: Instant,
     waiter: Arc<BlockProcessorWaiter>,
@@ -63,11 +65,13 @@ impl BlockProcessorContext {
     pub fn new(
         block: Arc<BlockEnum>,
         source: BlockSource,
+        channel_id: ChannelId,
         callback: Option<BlockProcessorCallback>,
     ) -> Self {
         Self {
             block,
             source,
+            channel_id,
             arrival: Instant::now(),
             callback,
             waiter: Arc::new(BlockProcessorWaiter::new()),
@@ -448,7 +452,9 @@ impl BlockProcessorLoop {
         );
 
         self.add_impl(
-            Arc::new(BlockProcessorContext::new(block, source, callback)),
+            Arc::new(BlockProcessorContext::new(
+                block, source, channel_id, callback,
+            )),
             channel_id,
         )
     }
@@ -463,7 +469,12 @@ impl BlockProcessorLoop {
         );
 
         let hash = block.hash();
-        let ctx = Arc::new(BlockProcessorContext::new(block, source, None));
+        let ctx = Arc::new(BlockProcessorContext::new(
+            block,
+            source,
+            ChannelId::LOOPBACK,
+            None,
+        ));
         let waiter = ctx.get_waiter();
         self.add_impl(ctx, ChannelId::LOOPBACK);
 
@@ -481,7 +492,12 @@ impl BlockProcessorLoop {
     pub fn force(&self, block: Arc<BlockEnum>) {
         self.stats.inc(StatType::Blockprocessor, DetailType::Force);
         debug!("Forcing block: {}", block.hash());
-        let ctx = Arc::new(BlockProcessorContext::new(block, BlockSource::Forced, None));
+        let ctx = Arc::new(BlockProcessorContext::new(
+            block,
+            BlockSource::Forced,
+            ChannelId::LOOPBACK,
+            None,
+        ));
         self.add_impl(ctx, ChannelId::LOOPBACK);
     }
 
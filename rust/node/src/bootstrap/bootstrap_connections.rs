@@ -52,6 +52,7 @@ pub struct BootstrapConnections {
     pulls_cache: Arc<Mutex<PullsCache>>,
     message_publisher: MessagePublisher,
     clock: Arc<SteadyClock>,
+    tcp_stream_factory: TcpStreamFactory,
 }
 
 impl BootstrapConnections {
@@ -69,7 +70,12 @@ impl BootstrapConnections {
         message_publisher: MessagePublisher,
         clock: Arc<SteadyClock>,
     ) -> Self {
+        let tcp_stream_factory = match &config.socks5_proxy {
+            Some(proxy) => TcpStreamFactory::new_with_socks5_proxy(proxy.clone()),
+            None => TcpStreamFactory::new(),
+        };
         Self {
+            tcp_stream_factory,
             condition: Condvar::new(),
             populate_connections_started: AtomicBool::new(false),
             attempts,
@@ -98,6 +104,7 @@ impl BootstrapConnections {
 
     pub fn new_null(tokio_handle: tokio::runtime::Handle) -> Self {
         Self {
+            tcp_stream_factory: TcpStreamFactory::new(),
             condition: Condvar::new(),
             populate_connections_started: AtomicBool::new(false),
             attempts: Arc::new(Mutex::new(BootstrapAttempts::new())),
@@ -582,10 +589,9 @@ impl BootstrapConnectionsExt for Arc<BootstrapConnections> {
 
         self.network_stats.connection_attempt(&peer_addr);
 
-        let tcp_stream_factory = Arc::new(TcpStreamFactory::new());
         let tcp_stream = match tokio::time::timeout(
             self.config.tcp_io_timeout,
-            tcp_stream_factory.connect(peer_addr),
+            self.tcp_stream_factory.connect(peer_addr),
         )
         .await
         {
@@ -615,6 +621,7 @@ impl BootstrapConnectionsExt for Arc<BootstrapConnections> {
 
         let Ok(channel) = self.network.add(
             tcp_stream,
+            peer_addr,
             ChannelDirection::Outbound,
             ChannelMode::Bootstrap,
         ) else {
@@ -22,6 +22,7 @@ use rsnano_ledger::Ledger;
 use rsnano_messages::ProtocolInfo;
 use rsnano_network::{Network, NetworkInfo, NetworkObserver};
 use rsnano_nullable_clock::SteadyClock;
+use rsnano_nullable_tcp::Socks5Proxy;
 use std::{
     collections::{HashMap, VecDeque},
     net::SocketAddrV6,
@@ -52,6 +53,7 @@ pub struct BootstrapInitiatorConfig {
     pub disable_bulk_push_client: bool,
     pub bootstrap_initiator_threads: u32,
     pub receive_minimum: Amount,
+    pub socks5_proxy: Option<Socks5Proxy>,
 }
 
 impl BootstrapInitiatorConfig {
@@ -73,6 +75,7 @@ impl BootstrapInitiatorConfig {
             disable_bulk_push_client: false,
             bootstrap_initiator_threads: 1,
             receive_minimum: Amount::raw(*XRB_RATIO),
+            socks5_proxy: None,
         }
     }
 }
@@ -49,6 +49,7 @@ impl From<&GlobalConfig> for BootstrapInitiatorConfig {
             disable_bulk_push_client: value.flags.disable_bootstrap_bulk_push_client,
             bootstrap_initiator_threads: value.node_config.bootstrap_initiator_threads,
             receive_minimum: value.node_config.receive_minimum,
+            socks5_proxy: value.node_config.tcp.socks5_proxy.clone(),
         }
     }
 }
@@ -78,6 +79,7 @@ impl From<&GlobalConfig> for NetworkConfig {
             disable_network: value.flags.disable_tcp_realtime,
             min_protocol_version: value.network_params.network.protocol_info().version_min,
             listening_port: value.node_config.peering_port.unwrap_or(0),
+            trusted_peers: value.node_config.trusted_peers.clone(),
         }
     }
 }
@@ -89,6 +91,14 @@ impl From<&GlobalConfig> for OutboundBandwidthLimiterConfig {
             standard_burst_ratio: value.node_config.bandwidth_limit_burst_ratio,
             bootstrap_limit: value.node_config.bootstrap_bandwidth_limit,
             bootstrap_burst_ratio: value.node_config.bootstrap_bandwidth_burst_ratio,
+            vote_limit: value.node_config.vote_bandwidth_limit,
+            vote_burst_ratio: value.node_config.vote_bandwidth_burst_ratio,
+            block_limit: value.node_config.block_bandwidth_limit,
+            block_burst_ratio: value.node_config.block_bandwidth_burst_ratio,
+            telemetry_limit: value.node_config.telemetry_bandwidth_limit,
+            telemetry_burst_ratio: value.node_config.telemetry_bandwidth_burst_ratio,
+            peer_limit: value.node_config.peer_bandwidth_limit,
+            peer_burst_ratio: value.node_config.peer_bandwidth_burst_ratio,
         }
     }
 }
@@ -7,8 +7,11 @@ use crate::{
         ActiveElectionsConfig, HintedSchedulerConfig, OptimisticSchedulerConfig,
         PriorityBucketConfig, RequestAggregatorConfig, VoteCacheConfig, VoteProcessorConfig,
     },
+    ledger_backup::LedgerBackupConfig,
+    signing::RemoteSignerConfig,
     stats::StatsConfig,
-    transport::{MessageProcessorConfig, TcpConfig},
+    transport::{MessageProcessorConfig, PortMappingConfig, TcpConfig},
+    wallets::WalletBackupConfig,
     websocket::WebsocketConfig,
     IpcConfig, NetworkParams, DEV_NETWORK_PARAMS,
 };
@@ -18,8 +21,15 @@ use rsnano_core::{
     utils::{get_env_or_default_string, is_sanitizer_build},
     Account, Amount, PublicKey, GXRB_RATIO, XRB_RATIO,
 };
+use rsnano_network::TrustedPeer;
 use rsnano_store_lmdb::LmdbConfig;
-use std::{cmp::max, fmt, net::Ipv6Addr, str::FromStr, time::Duration};
+use std::{
+    cmp::max,
+    fmt,
+    net::{Ipv6Addr, SocketAddrV6},
+    str::FromStr,
+    time::Duration,
+};
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive)]
@@ -72,6 +82,15 @@ pub struct NodeConfig {
     pub bootstrap_server: BootstrapServerConfig,
     pub bootstrap_bandwidth_limit: usize,
     pub bootstrap_bandwidth_burst_ratio: f64,
+    // Outbound limits per message category and per peer. 0 means unlimited
+    pub vote_bandwidth_limit: usize,
+    pub vote_bandwidth_burst_ratio: f64,
+    pub block_bandwidth_limit: usize,
+    pub block_bandwidth_burst_ratio: f64,
+    pub telemetry_bandwidth_limit: usize,
+    pub telemetry_bandwidth_burst_ratio: f64,
+    pub peer_bandwidth_limit: usize,
+    pub peer_bandwidth_burst_ratio: f64,
     pub confirming_set_batch_time: Duration,
     pub backup_before_upgrade: bool,
     pub max_work_generate_multiplier: f64,
@@ -84,8 +103,16 @@ pub struct NodeConfig {
     pub secondary_work_peers: Vec<Peer>,
     pub preconfigured_peers: Vec<String>,
     pub preconfigured_representatives: Vec<PublicKey>,
+    /// Own nodes that are always kept connected and exempt from connection limits
+    pub trusted_peers: Vec<TrustedPeer>,
     pub max_pruning_age_s: i64,
     pub max_pruning_depth: u64,
+    /// Never prune the accounts of the local wallets
+    pub pruning_keep_wallet_accounts: bool,
+    /// Never prune accounts with delegated voting weight
+    pub pruning_keep_representatives: bool,
+    /// Accounts that never get pruned
+    pub pruning_keep_accounts: Vec<Account>,
     pub callback_address: String,
     pub callback_port: u16,
     pub callback_target: String,
@@ -110,6 +137,10 @@ pub struct NodeConfig {
     pub local_block_broadcaster: LocalBlockBroadcasterConfig,
     pub confirming_set: ConfirmingSetConfig,
     pub monitor: MonitorConfig,
+    pub ledger_backup: LedgerBackupConfig,
+    pub wallet_backup: WalletBackupConfig,
+    pub remote_signer: RemoteSignerConfig,
+    pub port_mapping: PortMappingConfig,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -303,6 +334,14 @@ impl NodeConfig {
             bootstrap_bandwidth_limit: 5 * 1024 * 1024,
             // Bootstrap traffic does not need bursts
             bootstrap_bandwidth_burst_ratio: 1.,
+            vote_bandwidth_limit: 0,
+            vote_bandwidth_burst_ratio: 3_f64,
+            block_bandwidth_limit: 0,
+            block_bandwidth_burst_ratio: 3_f64,
+            telemetry_bandwidth_limit: 0,
+            telemetry_bandwidth_burst_ratio: 3_f64,
+            peer_bandwidth_limit: 0,
+            peer_bandwidth_burst_ratio: 3_f64,
             bootstrap_ascending: Default::default(),
             bootstrap_server: Default::default(),
             confirming_set_batch_time: Duration::from_millis(250),
@@ -318,12 +357,16 @@ impl NodeConfig {
             secondary_work_peers: vec![Peer::new("127.0.0.1", 8076)],
             preconfigured_peers,
             preconfigured_representatives,
+            trusted_peers: Vec::new(),
             max_pruning_age_s: if !network_params.network.is_beta_network() {
                 24 * 60 * 60
             } else {
                 5 * 60
             }, // 1 day; 5 minutes for beta network
             max_pruning_depth: 0,
+            pruning_keep_wallet_accounts: false,
+            pruning_keep_representatives: false,
+            pruning_keep_accounts: Vec::new(),
             callback_address: String::new(),
             callback_port: 0,
             callback_target: String::new(),
@@ -363,6 +406,10 @@ impl NodeConfig {
             ),
             confirming_set: Default::default(),
             monitor: Default::default(),
+            ledger_backup: Default::default(),
+            wallet_backup: Default::default(),
+            remote_signer: Default::default(),
+            port_mapping: Default::default(),
         }
     }
 
@@ -374,6 +421,15 @@ impl NodeConfig {
         let i = thread_rng().gen_range(0..self.preconfigured_representatives.len());
         return self.preconfigured_representatives[i];
     }
+
+    /// The external address and port, if both are configured
+    pub fn configured_external_address(&self) -> Option<SocketAddrV6> {
+        let ip: Ipv6Addr = self.external_address.parse().ok()?;
+        if ip.is_unspecified() || self.external_port == 0 {
+            return None;
+        }
+        Some(SocketAddrV6::new(ip, self.external_port, 0, 0))
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 pub struct ActiveElectionsToml {
     pub confirmation_cache: Option<usize>,
     pub confirmation_history_size: Option<usize>,
+    pub fork_journal_size: Option<usize>,
     pub hinted_limit_percentage: Option<usize>,
     pub optimistic_limit_percentage: Option<usize>,
     pub size: Option<usize>,
@@ -36,6 +37,9 @@ impl From<&ActiveElectionsToml> for ActiveElectionsConfig {
         if let Some(confirmation_cache) = toml.confirmation_cache {
             config.confirmation_cache = confirmation_cache
         };
+        if let Some(fork_journal_size) = toml.fork_journal_size {
+            config.fork_journal_size = fork_journal_size
+        };
 
         config
     }
@@ -49,6 +53,7 @@ impl From<&ActiveElectionsConfig> for ActiveElectionsToml {
             optimistic_limit_percentage: Some(config.optimistic_limit_percentage),
             confirmation_history_size: Some(config.confirmation_history_size),
             confirmation_cache: Some(config.confirmation_cache),
+            fork_journal_size: Some(config.fork_journal_size),
         }
     }
 }
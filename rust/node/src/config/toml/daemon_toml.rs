@@ -76,6 +76,14 @@ mod tests {
         bandwidth_limit_burst_ratio = 999.9
         bootstrap_bandwidth_limit = 999
         bootstrap_bandwidth_burst_ratio = 999.9
+        vote_bandwidth_limit = 999
+        vote_bandwidth_burst_ratio = 999.9
+        block_bandwidth_limit = 999
+        block_bandwidth_burst_ratio = 999.9
+        telemetry_bandwidth_limit = 999
+        telemetry_bandwidth_burst_ratio = 999.9
+        peer_bandwidth_limit = 999
+        peer_bandwidth_burst_ratio = 999.9
         block_processor_batch_max_time = 999
         bootstrap_connections = 999
         bootstrap_connections_max = 999
@@ -103,6 +111,7 @@ mod tests {
         signature_checker_threads = 999
         tcp_incoming_connections_max = 999
         tcp_io_timeout = 999
+        trusted_peers = ["node_1111111111111111111111111111111111111111111111111113b8661hfk@[::ffff:10.0.0.1]:7075"]
         unchecked_cutoff_time = 999
         use_memory_pools = false
         vote_generator_delay = 999
@@ -130,6 +139,7 @@ mod tests {
         optimistic_limit_percentage = 90
         confirmation_history_size = 999
         confirmation_cache = 999
+        fork_journal_size = 999
 
         [node.diagnostics.txn_tracking]
         enable = true
@@ -154,6 +164,35 @@ mod tests {
         enable = false
         interval = 999
 
+        [node.ledger_backup]
+        enable = true
+        interval = 999
+        max_backups = 999
+        batch_size = 999
+        max_duration = 999
+        block_processor_threshold = 999
+
+        [node.wallet_backup]
+        max_backups = 999
+        passphrase = "secret"
+
+        [node.remote_signer]
+        enable = true
+        socket_path = "/tmp/signer"
+        timeout = 999
+        refresh_interval = 999
+
+        [node.port_mapping]
+        enable = true
+        natpmp_gateway = "192.168.0.1:5351"
+        ssdp_address = "127.0.0.1:1900"
+        timeout = 999
+
+        [node.socks5_proxy]
+        address = "127.0.0.1:1080"
+        username = "user"
+        password = "secret"
+
         [node.ipc.local]
         allow_unsafe = true
         enable = true
@@ -208,6 +247,9 @@ mod tests {
         secondary_work_peers = ["dev.org:998"]
         max_pruning_age = 999
         max_pruning_depth = 999
+        pruning_keep_wallet_accounts = true
+        pruning_keep_representatives = true
+        pruning_keep_accounts = ["nano_3arg3asgtigae3xckabaaewkx3bzsh7nwz7jkmjos79ihyaxwphhm6qgjps4"]
 
         [node.vote_cache]
         age_cutoff = 999
@@ -303,6 +345,38 @@ mod tests {
             deserialized.node.bootstrap_bandwidth_burst_ratio,
             default_cfg.node.bootstrap_bandwidth_burst_ratio
         );
+        assert_ne!(
+            deserialized.node.vote_bandwidth_limit,
+            default_cfg.node.vote_bandwidth_limit
+        );
+        assert_ne!(
+            deserialized.node.vote_bandwidth_burst_ratio,
+            default_cfg.node.vote_bandwidth_burst_ratio
+        );
+        assert_ne!(
+            deserialized.node.block_bandwidth_limit,
+            default_cfg.node.block_bandwidth_limit
+        );
+        assert_ne!(
+            deserialized.node.block_bandwidth_burst_ratio,
+            default_cfg.node.block_bandwidth_burst_ratio
+        );
+        assert_ne!(
+            deserialized.node.telemetry_bandwidth_limit,
+            default_cfg.node.telemetry_bandwidth_limit
+        );
+        assert_ne!(
+            deserialized.node.telemetry_bandwidth_burst_ratio,
+            default_cfg.node.telemetry_bandwidth_burst_ratio
+        );
+        assert_ne!(
+            deserialized.node.peer_bandwidth_limit,
+            default_cfg.node.peer_bandwidth_limit
+        );
+        assert_ne!(
+            deserialized.node.peer_bandwidth_burst_ratio,
+            default_cfg.node.peer_bandwidth_burst_ratio
+        );
         assert_ne!(
             deserialized.node.block_processor_batch_max_time_ms,
             default_cfg.node.block_processor_batch_max_time_ms
@@ -408,6 +482,10 @@ mod tests {
             deserialized.node.tcp_io_timeout_s,
             default_cfg.node.tcp_io_timeout_s
         );
+        assert_ne!(
+            deserialized.node.trusted_peers,
+            default_cfg.node.trusted_peers
+        );
         assert_ne!(
             deserialized.node.unchecked_cutoff_time_s,
             default_cfg.node.unchecked_cutoff_time_s
@@ -507,6 +585,10 @@ mod tests {
             deserialized.node.active_elections.confirmation_cache,
             default_cfg.node.active_elections.confirmation_cache
         );
+        assert_ne!(
+            deserialized.node.active_elections.fork_journal_size,
+            default_cfg.node.active_elections.fork_journal_size
+        );
 
         // Diagnostics section
         assert_ne!(
@@ -594,6 +676,58 @@ mod tests {
             default_cfg.node.monitor.interval
         );
 
+        // Experimental section
+        assert_ne!(
+            deserialized.node.max_pruning_age_s,
+            default_cfg.node.max_pruning_age_s
+        );
+        assert_ne!(
+            deserialized.node.max_pruning_depth,
+            default_cfg.node.max_pruning_depth
+        );
+        assert_ne!(
+            deserialized.node.pruning_keep_wallet_accounts,
+            default_cfg.node.pruning_keep_wallet_accounts
+        );
+        assert_ne!(
+            deserialized.node.pruning_keep_representatives,
+            default_cfg.node.pruning_keep_representatives
+        );
+        assert_ne!(
+            deserialized.node.pruning_keep_accounts,
+            default_cfg.node.pruning_keep_accounts
+        );
+
+        // Ledger backup section
+        assert_ne!(
+            deserialized.node.ledger_backup,
+            default_cfg.node.ledger_backup
+        );
+
+        // Wallet backup section
+        assert_ne!(
+            deserialized.node.wallet_backup,
+            default_cfg.node.wallet_backup
+        );
+
+        // Remote signer section
+        assert_ne!(
+            deserialized.node.remote_signer,
+            default_cfg.node.remote_signer
+        );
+
+        // Port mapping section
+        assert_ne!(
+            deserialized.node.port_mapping,
+            default_cfg.node.port_mapping
+        );
+
+        // SOCKS5 proxy section
+        assert_ne!(
+            deserialized.node.tcp.socks5_proxy,
+            default_cfg.node.tcp.socks5_proxy
+        );
+
         // IPC Local section
         assert_ne!(
             deserialized
@@ -996,6 +1130,30 @@ mod tests {
         assert_eq!(&deserialized_daemon_config, &default_daemon_config);
     }
 
+    #[test]
+    fn reject_invalid_socks5_proxy_address() {
+        let toml_str = r#"
+            [node.socks5_proxy]
+            address = "localhost:1080"
+        "#;
+        let result = toml::from_str::<DaemonToml>(toml_str);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn empty_socks5_proxy_address_disables_proxy() {
+        let toml_str = r#"
+            [node.socks5_proxy]
+            address = ""
+        "#;
+        let daemon_toml: DaemonToml = toml::from_str(toml_str).unwrap();
+
+        let mut config = create_default_daemon_config();
+        config.merge_toml(&daemon_toml);
+
+        assert_eq!(config.node.tcp.socks5_proxy, None);
+    }
+
     fn create_default_daemon_config() -> DaemonConfig {
         let mut config = DaemonConfig::new(&NetworkParams::new(Networks::NanoBetaNetwork), 8);
         config.rpc.child_process.rpc_path = PathBuf::from("/home/foo/nano_rpc");
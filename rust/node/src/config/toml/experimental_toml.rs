@@ -1,12 +1,16 @@
 use std::str::FromStr;
 
 use crate::config::{NodeConfig, Peer};
+use rsnano_core::Account;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
 pub struct ExperimentalToml {
     pub max_pruning_age: Option<u64>,
     pub max_pruning_depth: Option<u64>,
+    pub pruning_keep_wallet_accounts: Option<bool>,
+    pub pruning_keep_representatives: Option<bool>,
+    pub pruning_keep_accounts: Option<Vec<String>>,
     pub secondary_work_peers: Option<Vec<String>>,
 }
 
@@ -18,6 +22,20 @@ impl NodeConfig {
         if let Some(max_pruning_depth) = toml.max_pruning_depth {
             self.max_pruning_depth = max_pruning_depth;
         }
+        if let Some(keep_wallet_accounts) = toml.pruning_keep_wallet_accounts {
+            self.pruning_keep_wallet_accounts = keep_wallet_accounts;
+        }
+        if let Some(keep_representatives) = toml.pruning_keep_representatives {
+            self.pruning_keep_representatives = keep_representatives;
+        }
+        if let Some(keep_accounts) = &toml.pruning_keep_accounts {
+            self.pruning_keep_accounts = keep_accounts
+                .iter()
+                .map(|account| {
+                    Account::decode_account(account).expect("Invalid pruning keep account")
+                })
+                .collect();
+        }
         if let Some(secondary_work_peers) = &toml.secondary_work_peers {
             self.secondary_work_peers = secondary_work_peers
                 .iter()
@@ -39,6 +57,15 @@ impl From<&NodeConfig> for ExperimentalToml {
             ),
             max_pruning_age: Some(config.max_pruning_age_s as u64),
             max_pruning_depth: Some(config.max_pruning_depth),
+            pruning_keep_wallet_accounts: Some(config.pruning_keep_wallet_accounts),
+            pruning_keep_representatives: Some(config.pruning_keep_representatives),
+            pruning_keep_accounts: Some(
+                config
+                    .pruning_keep_accounts
+                    .iter()
+                    .map(|account| account.encode_account())
+                    .collect(),
+            ),
         }
     }
 }
@@ -0,0 +1,59 @@
+use crate::ledger_backup::LedgerBackupConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct LedgerBackupToml {
+    pub enable: Option<bool>,
+    pub interval: Option<u64>,
+    pub max_backups: Option<usize>,
+    pub batch_size: Option<usize>,
+    pub max_duration: Option<u64>,
+    pub block_processor_threshold: Option<usize>,
+}
+
+impl Default for LedgerBackupToml {
+    fn default() -> Self {
+        let config = LedgerBackupConfig::default();
+        (&config).into()
+    }
+}
+
+impl From<&LedgerBackupToml> for LedgerBackupConfig {
+    fn from(toml: &LedgerBackupToml) -> Self {
+        let mut config = LedgerBackupConfig::default();
+
+        if let Some(enabled) = toml.enable {
+            config.enabled = enabled;
+        }
+        if let Some(interval) = toml.interval {
+            config.interval = Duration::from_secs(interval);
+        }
+        if let Some(max_backups) = toml.max_backups {
+            config.max_backups = max_backups;
+        }
+        if let Some(batch_size) = toml.batch_size {
+            config.batch_size = batch_size;
+        }
+        if let Some(max_duration) = toml.max_duration {
+            config.max_duration = Duration::from_secs(max_duration);
+        }
+        if let Some(threshold) = toml.block_processor_threshold {
+            config.block_processor_threshold = threshold;
+        }
+        config
+    }
+}
+
+impl From<&LedgerBackupConfig> for LedgerBackupToml {
+    fn from(config: &LedgerBackupConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            interval: Some(config.interval.as_secs()),
+            max_backups: Some(config.max_backups),
+            batch_size: Some(config.batch_size),
+            max_duration: Some(config.max_duration.as_secs()),
+            block_processor_threshold: Some(config.block_processor_threshold),
+        }
+    }
+}
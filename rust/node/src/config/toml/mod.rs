@@ -8,6 +8,7 @@ mod experimental_toml;
 mod hinted_scheduler_toml;
 mod httpcallback_toml;
 mod ipc_toml;
+mod ledger_backup_toml;
 mod lmdb_toml;
 mod message_processor_toml;
 mod monitor_toml;
@@ -15,12 +16,16 @@ mod node_rpc_toml;
 mod node_toml;
 mod opencl_toml;
 mod optimistic_scheduler_toml;
+mod port_mapping_toml;
 mod priority_bucket_toml;
+mod remote_signer_toml;
 mod rep_crawler_toml;
 mod request_aggregator_toml;
+mod socks5_proxy_toml;
 mod stats_toml;
 mod vote_cache_toml;
 mod vote_processor_toml;
+mod wallet_backup_toml;
 mod websocket_toml;
 
 pub use active_elections_toml::*;
@@ -33,6 +38,7 @@ pub use experimental_toml::*;
 pub use hinted_scheduler_toml::*;
 pub use httpcallback_toml::*;
 pub use ipc_toml::*;
+pub use ledger_backup_toml::*;
 pub use lmdb_toml::*;
 pub use message_processor_toml::*;
 pub use monitor_toml::*;
@@ -40,10 +46,14 @@ pub use node_rpc_toml::*;
 pub use node_toml::*;
 pub use opencl_toml::*;
 pub use optimistic_scheduler_toml::*;
+pub use port_mapping_toml::*;
 pub use priority_bucket_toml::*;
+pub use remote_signer_toml::*;
 pub use rep_crawler_toml::*;
 pub use request_aggregator_toml::*;
+pub use socks5_proxy_toml::*;
 pub use stats_toml::*;
 pub use vote_cache_toml::*;
 pub use vote_processor_toml::*;
+pub use wallet_backup_toml::*;
 pub use websocket_toml::*;
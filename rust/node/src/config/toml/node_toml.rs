@@ -1,11 +1,13 @@
 use super::{
     ActiveElectionsToml, BlockProcessorToml, BootstrapAscendingToml, BootstrapServerToml,
-    DiagnosticsToml, ExperimentalToml, HintedSchedulerToml, HttpcallbackToml, IpcToml, LmdbToml,
-    MessageProcessorToml, MonitorToml, OptimisticSchedulerToml, PriorityBucketToml, RepCrawlerToml,
-    RequestAggregatorToml, StatsToml, VoteCacheToml, VoteProcessorToml, WebsocketToml,
+    DiagnosticsToml, ExperimentalToml, HintedSchedulerToml, HttpcallbackToml, IpcToml,
+    LedgerBackupToml, LmdbToml, MessageProcessorToml, MonitorToml, OptimisticSchedulerToml,
+    PortMappingToml, PriorityBucketToml, RemoteSignerToml, RepCrawlerToml, RequestAggregatorToml,
+    Socks5ProxyToml, StatsToml, VoteCacheToml, VoteProcessorToml, WalletBackupToml, WebsocketToml,
 };
 use crate::config::{FrontiersConfirmationMode, NodeConfig, Peer};
 use rsnano_core::{Account, Amount};
+use rsnano_network::TrustedPeer;
 use serde::{Deserialize, Serialize};
 use std::{str::FromStr, time::Duration};
 
@@ -18,6 +20,8 @@ pub struct NodeToml {
     pub backup_before_upgrade: Option<bool>,
     pub bandwidth_limit: Option<usize>,
     pub bandwidth_limit_burst_ratio: Option<f64>,
+    pub block_bandwidth_burst_ratio: Option<f64>,
+    pub block_bandwidth_limit: Option<usize>,
     pub block_processor_batch_max_time: Option<i64>,
     pub bootstrap_bandwidth_burst_ratio: Option<f64>,
     pub bootstrap_bandwidth_limit: Option<usize>,
@@ -39,6 +43,8 @@ pub struct NodeToml {
     pub network_threads: Option<u32>,
     pub online_weight_minimum: Option<String>,
     pub password_fanout: Option<u32>,
+    pub peer_bandwidth_burst_ratio: Option<f64>,
+    pub peer_bandwidth_limit: Option<usize>,
     pub peering_port: Option<u16>,
     pub pow_sleep_interval: Option<i64>,
     pub preconfigured_peers: Option<Vec<String>>,
@@ -50,8 +56,13 @@ pub struct NodeToml {
     pub signature_checker_threads: Option<u32>,
     pub tcp_incoming_connections_max: Option<u32>,
     pub tcp_io_timeout: Option<i64>,
+    pub telemetry_bandwidth_burst_ratio: Option<f64>,
+    pub telemetry_bandwidth_limit: Option<usize>,
+    pub trusted_peers: Option<Vec<String>>,
     pub unchecked_cutoff_time: Option<i64>,
     pub use_memory_pools: Option<bool>,
+    pub vote_bandwidth_burst_ratio: Option<f64>,
+    pub vote_bandwidth_limit: Option<usize>,
     pub vote_generator_delay: Option<i64>,
     pub vote_generator_threshold: Option<u32>,
     pub vote_minimum: Option<String>,
@@ -65,17 +76,22 @@ pub struct NodeToml {
     pub experimental: Option<ExperimentalToml>,
     pub httpcallback: Option<HttpcallbackToml>,
     pub ipc: Option<IpcToml>,
+    pub ledger_backup: Option<LedgerBackupToml>,
     pub lmdb: Option<LmdbToml>,
     pub message_processor: Option<MessageProcessorToml>,
     pub monitor: Option<MonitorToml>,
     pub optimistic_scheduler: Option<OptimisticSchedulerToml>,
     pub hinted_scheduler: Option<HintedSchedulerToml>,
     pub priority_bucket: Option<PriorityBucketToml>,
+    pub port_mapping: Option<PortMappingToml>,
+    pub remote_signer: Option<RemoteSignerToml>,
     pub rep_crawler: Option<RepCrawlerToml>,
     pub request_aggregator: Option<RequestAggregatorToml>,
+    pub socks5_proxy: Option<Socks5ProxyToml>,
     pub statistics: Option<StatsToml>,
     pub vote_cache: Option<VoteCacheToml>,
     pub vote_processor: Option<VoteProcessorToml>,
+    pub wallet_backup: Option<WalletBackupToml>,
     pub websocket: Option<WebsocketToml>,
 }
 
@@ -111,6 +127,30 @@ impl NodeConfig {
         if let Some(bootstrap_bandwidth_limit) = toml.bootstrap_bandwidth_limit {
             self.bootstrap_bandwidth_limit = bootstrap_bandwidth_limit;
         }
+        if let Some(vote_bandwidth_limit) = toml.vote_bandwidth_limit {
+            self.vote_bandwidth_limit = vote_bandwidth_limit;
+        }
+        if let Some(vote_bandwidth_burst_ratio) = toml.vote_bandwidth_burst_ratio {
+            self.vote_bandwidth_burst_ratio = vote_bandwidth_burst_ratio;
+        }
+        if let Some(block_bandwidth_limit) = toml.block_bandwidth_limit {
+            self.block_bandwidth_limit = block_bandwidth_limit;
+        }
+        if let Some(block_bandwidth_burst_ratio) = toml.block_bandwidth_burst_ratio {
+            self.block_bandwidth_burst_ratio = block_bandwidth_burst_ratio;
+        }
+        if let Some(telemetry_bandwidth_limit) = toml.telemetry_bandwidth_limit {
+            self.telemetry_bandwidth_limit = telemetry_bandwidth_limit;
+        }
+        if let Some(telemetry_bandwidth_burst_ratio) = toml.telemetry_bandwidth_burst_ratio {
+            self.telemetry_bandwidth_burst_ratio = telemetry_bandwidth_burst_ratio;
+        }
+        if let Some(peer_bandwidth_limit) = toml.peer_bandwidth_limit {
+            self.peer_bandwidth_limit = peer_bandwidth_limit;
+        }
+        if let Some(peer_bandwidth_burst_ratio) = toml.peer_bandwidth_burst_ratio {
+            self.peer_bandwidth_burst_ratio = peer_bandwidth_burst_ratio;
+        }
         if let Some(bootstrap_connections) = toml.bootstrap_connections {
             self.bootstrap_connections = bootstrap_connections;
         }
@@ -216,6 +256,12 @@ impl NodeConfig {
         if let Some(tcp_io_timeout_s) = toml.tcp_io_timeout {
             self.tcp_io_timeout_s = tcp_io_timeout_s;
         }
+        if let Some(trusted_peers) = &toml.trusted_peers {
+            self.trusted_peers = trusted_peers
+                .iter()
+                .map(|string| TrustedPeer::from_str(&string).expect("Invalid trusted peer"))
+                .collect();
+        }
         if let Some(unchecked_cutoff_time_s) = toml.unchecked_cutoff_time {
             self.unchecked_cutoff_time_s = unchecked_cutoff_time_s;
         }
@@ -336,6 +382,24 @@ impl NodeConfig {
         if let Some(monitor_toml) = &toml.monitor {
             self.monitor = monitor_toml.into();
         }
+        if let Some(experimental_toml) = &toml.experimental {
+            self.merge_experimental_toml(experimental_toml);
+        }
+        if let Some(ledger_backup_toml) = &toml.ledger_backup {
+            self.ledger_backup = ledger_backup_toml.into();
+        }
+        if let Some(wallet_backup_toml) = &toml.wallet_backup {
+            self.wallet_backup = wallet_backup_toml.into();
+        }
+        if let Some(port_mapping_toml) = &toml.port_mapping {
+            self.port_mapping = port_mapping_toml.into();
+        }
+        if let Some(socks5_proxy_toml) = &toml.socks5_proxy {
+            self.tcp.socks5_proxy = socks5_proxy_toml.to_proxy();
+        }
+        if let Some(remote_signer_toml) = &toml.remote_signer {
+            self.remote_signer = remote_signer_toml.into();
+        }
         if let Some(rep_crawler_weight_minimum) = &toml.rep_crawler_weight_minimum {
             self.rep_crawler_weight_minimum = Amount::decode_dec(&rep_crawler_weight_minimum)
                 .expect("Invalid rep crawler weight minimum");
@@ -367,6 +431,14 @@ impl From<&NodeConfig> for NodeToml {
             block_processor_batch_max_time: Some(config.block_processor_batch_max_time_ms),
             bootstrap_bandwidth_burst_ratio: Some(config.bootstrap_bandwidth_burst_ratio),
             bootstrap_bandwidth_limit: Some(config.bootstrap_bandwidth_limit),
+            vote_bandwidth_limit: Some(config.vote_bandwidth_limit),
+            vote_bandwidth_burst_ratio: Some(config.vote_bandwidth_burst_ratio),
+            block_bandwidth_limit: Some(config.block_bandwidth_limit),
+            block_bandwidth_burst_ratio: Some(config.block_bandwidth_burst_ratio),
+            telemetry_bandwidth_limit: Some(config.telemetry_bandwidth_limit),
+            telemetry_bandwidth_burst_ratio: Some(config.telemetry_bandwidth_burst_ratio),
+            peer_bandwidth_limit: Some(config.peer_bandwidth_limit),
+            peer_bandwidth_burst_ratio: Some(config.peer_bandwidth_burst_ratio),
             bootstrap_connections: Some(config.bootstrap_connections),
             bootstrap_connections_max: Some(config.bootstrap_connections_max),
             bootstrap_fraction_numerator: Some(config.bootstrap_fraction_numerator),
@@ -409,6 +481,13 @@ impl From<&NodeConfig> for NodeToml {
             signature_checker_threads: Some(config.signature_checker_threads),
             tcp_incoming_connections_max: Some(config.tcp_incoming_connections_max),
             tcp_io_timeout: Some(config.tcp_io_timeout_s),
+            trusted_peers: Some(
+                config
+                    .trusted_peers
+                    .iter()
+                    .map(|peer| peer.to_string())
+                    .collect(),
+            ),
             unchecked_cutoff_time: Some(config.unchecked_cutoff_time_s),
             use_memory_pools: Some(config.use_memory_pools),
             vote_generator_delay: Some(config.vote_generator_delay_ms),
@@ -439,6 +518,11 @@ impl From<&NodeConfig> for NodeToml {
             request_aggregator: Some((&config.request_aggregator).into()),
             message_processor: Some((&config.message_processor).into()),
             monitor: Some((&config.monitor).into()),
+            ledger_backup: Some((&config.ledger_backup).into()),
+            wallet_backup: Some((&config.wallet_backup).into()),
+            port_mapping: Some((&config.port_mapping).into()),
+            remote_signer: Some((&config.remote_signer).into()),
+            socks5_proxy: Some(config.tcp.socks5_proxy.as_ref().into()),
             httpcallback: Some(config.into()),
             rep_crawler: Some(config.into()),
             experimental: Some(config.into()),
@@ -0,0 +1,58 @@
+use crate::transport::PortMappingConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct PortMappingToml {
+    pub enable: Option<bool>,
+    pub natpmp_gateway: Option<String>,
+    pub ssdp_address: Option<String>,
+    pub timeout: Option<u64>,
+}
+
+impl Default for PortMappingToml {
+    fn default() -> Self {
+        let config = PortMappingConfig::default();
+        (&config).into()
+    }
+}
+
+impl From<&PortMappingToml> for PortMappingConfig {
+    fn from(toml: &PortMappingToml) -> Self {
+        let mut config = PortMappingConfig::default();
+
+        if let Some(enabled) = toml.enable {
+            config.enabled = enabled;
+        }
+        if let Some(gateway) = &toml.natpmp_gateway {
+            config.natpmp_gateway = if gateway.is_empty() {
+                None
+            } else {
+                Some(gateway.parse().expect("Invalid NAT-PMP gateway address"))
+            };
+        }
+        if let Some(ssdp_address) = &toml.ssdp_address {
+            config.ssdp_address = ssdp_address.parse().expect("Invalid SSDP address");
+        }
+        if let Some(timeout) = toml.timeout {
+            config.timeout = Duration::from_millis(timeout);
+        }
+        config
+    }
+}
+
+impl From<&PortMappingConfig> for PortMappingToml {
+    fn from(config: &PortMappingConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            natpmp_gateway: Some(
+                config
+                    .natpmp_gateway
+                    .map(|a| a.to_string())
+                    .unwrap_or_default(),
+            ),
+            ssdp_address: Some(config.ssdp_address.to_string()),
+            timeout: Some(config.timeout.as_millis() as u64),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::signing::RemoteSignerConfig;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Deserialize, Serialize)]
+pub struct RemoteSignerToml {
+    pub enable: Option<bool>,
+    pub socket_path: Option<PathBuf>,
+    pub timeout: Option<u64>,
+    pub refresh_interval: Option<u64>,
+}
+
+impl Default for RemoteSignerToml {
+    fn default() -> Self {
+        let config = RemoteSignerConfig::default();
+        (&config).into()
+    }
+}
+
+impl From<&RemoteSignerToml> for RemoteSignerConfig {
+    fn from(toml: &RemoteSignerToml) -> Self {
+        let mut config = RemoteSignerConfig::default();
+
+        if let Some(enabled) = toml.enable {
+            config.enabled = enabled;
+        }
+        if let Some(socket_path) = &toml.socket_path {
+            config.socket_path = socket_path.clone();
+        }
+        if let Some(timeout) = toml.timeout {
+            config.timeout = Duration::from_millis(timeout);
+        }
+        if let Some(refresh_interval) = toml.refresh_interval {
+            config.refresh_interval = Duration::from_secs(refresh_interval);
+        }
+        config
+    }
+}
+
+impl From<&RemoteSignerConfig> for RemoteSignerToml {
+    fn from(config: &RemoteSignerConfig) -> Self {
+        Self {
+            enable: Some(config.enabled),
+            socket_path: Some(config.socket_path.clone()),
+            timeout: Some(config.timeout.as_millis() as u64),
+            refresh_interval: Some(config.refresh_interval.as_secs()),
+        }
+    }
+}
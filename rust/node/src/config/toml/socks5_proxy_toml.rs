@@ -0,0 +1,55 @@
+use rsnano_nullable_tcp::Socks5Proxy;
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use std::net::SocketAddr;
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct Socks5ProxyToml {
+    /// Checked when the config is parsed, so that the node doesn't start with a broken proxy
+    #[serde(default, deserialize_with = "deserialize_address")]
+    pub address: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl Socks5ProxyToml {
+    /// An empty address disables the proxy
+    pub fn to_proxy(&self) -> Option<Socks5Proxy> {
+        let address = self.address.as_deref().unwrap_or_default();
+        if address.is_empty() {
+            return None;
+        }
+        let proxy = Socks5Proxy::new(address.parse().expect("Invalid SOCKS5 proxy address"));
+        match &self.username {
+            Some(username) if !username.is_empty() => {
+                Some(proxy.with_credentials(username, self.password.clone().unwrap_or_default()))
+            }
+            _ => Some(proxy),
+        }
+    }
+}
+
+impl From<Option<&Socks5Proxy>> for Socks5ProxyToml {
+    fn from(proxy: Option<&Socks5Proxy>) -> Self {
+        let credentials = proxy.and_then(|p| p.credentials.as_ref());
+        Self {
+            address: Some(proxy.map(|p| p.address.to_string()).unwrap_or_default()),
+            username: Some(credentials.map(|c| c.username.clone()).unwrap_or_default()),
+            password: Some(credentials.map(|c| c.password.clone()).unwrap_or_default()),
+        }
+    }
+}
+
+fn deserialize_address<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let address = Option::<String>::deserialize(deserializer)?;
+    if let Some(address) = &address {
+        if !address.is_empty() {
+            address.parse::<SocketAddr>().map_err(|_| {
+                D::Error::custom(format!("invalid SOCKS5 proxy address: {}", address))
+            })?;
+        }
+    }
+    Ok(address)
+}
@@ -0,0 +1,38 @@
+use crate::wallets::WalletBackupConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct WalletBackupToml {
+    pub max_backups: Option<usize>,
+    pub passphrase: Option<String>,
+}
+
+impl Default for WalletBackupToml {
+    fn default() -> Self {
+        let config = WalletBackupConfig::default();
+        (&config).into()
+    }
+}
+
+impl From<&WalletBackupToml> for WalletBackupConfig {
+    fn from(toml: &WalletBackupToml) -> Self {
+        let mut config = WalletBackupConfig::default();
+
+        if let Some(max_backups) = toml.max_backups {
+            config.max_backups = max_backups;
+        }
+        if let Some(passphrase) = &toml.passphrase {
+            config.passphrase = passphrase.clone();
+        }
+        config
+    }
+}
+
+impl From<&WalletBackupConfig> for WalletBackupToml {
+    fn from(config: &WalletBackupConfig) -> Self {
+        Self {
+            max_backups: Some(config.max_backups),
+            passphrase: Some(config.passphrase.clone()),
+        }
+    }
+}
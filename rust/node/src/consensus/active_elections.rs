@@ -1,8 +1,8 @@
 use super::{
     confirmation_solicitor::ConfirmationSolicitor, election_schedulers::ElectionSchedulers,
     Election, ElectionBehavior, ElectionData, ElectionState, ElectionStatus, ElectionStatusType,
-    RecentlyConfirmedCache, VoteApplier, VoteCache, VoteCacheProcessor, VoteGenerators, VoteRouter,
-    NEXT_ELECTION_ID,
+    ForkJournal, RecentlyConfirmedCache, VoteApplier, VoteCache, VoteCacheProcessor,
+    VoteGenerators, VoteRouter, NEXT_ELECTION_ID,
 };
 use crate::{
     block_processing::BlockProcessor,
@@ -59,6 +59,8 @@ pub struct ActiveElectionsConfig {
     pub confirmation_cache: usize,
     /// Maximum size of election winner details set
     pub max_election_winners: usize,
+    /// Maximum number of resolved forks kept in the fork journal
+    pub fork_journal_size: usize,
 }
 
 impl Default for ActiveElectionsConfig {
@@ -70,6 +72,7 @@ impl Default for ActiveElectionsConfig {
             confirmation_history_size: 2048,
             confirmation_cache: 65536,
             max_election_winners: 1024 * 16,
+            fork_journal_size: 1024,
         }
     }
 }
@@ -105,6 +108,7 @@ pub struct ActiveElections {
     pub vote_router: Arc<VoteRouter>,
     vote_cache_processor: Arc<VoteCacheProcessor>,
     message_publisher: Mutex<MessagePublisher>,
+    fork_journal: Arc<ForkJournal>,
 }
 
 impl ActiveElections {
@@ -130,6 +134,7 @@ impl ActiveElections {
         vote_cache_processor: Arc<VoteCacheProcessor>,
         steady_clock: Arc<SteadyClock>,
         message_publisher: MessagePublisher,
+        fork_journal: Arc<ForkJournal>,
     ) -> Self {
         Self {
             mutex: Mutex::new(ActiveElectionsState {
@@ -170,6 +175,7 @@ impl ActiveElections {
             steady_clock,
             message_publisher: Mutex::new(message_publisher),
             election_schedulers: RwLock::new(None),
+            fork_journal,
         }
     }
 
@@ -552,10 +558,12 @@ impl ActiveElections {
         election: &Election,
         election_guard: &mut MutexGuard<ElectionData>,
     ) {
-        if election_guard.last_vote_elapsed() >= self.network_params.network.vote_broadcast_interval
+        let now = self.steady_clock.now();
+        if election_guard.last_vote_elapsed(now)
+            >= self.network_params.network.vote_broadcast_interval
         {
             self.broadcast_vote_locked(election_guard, election);
-            election_guard.set_last_vote();
+            election_guard.set_last_vote(now);
         }
     }
 
@@ -590,11 +598,12 @@ impl ActiveElections {
         election_guard: &mut MutexGuard<ElectionData>,
         election: &Election,
     ) {
-        let last_vote_elapsed = election_guard.last_vote_elapsed();
+        let now = self.steady_clock.now();
+        let last_vote_elapsed = election_guard.last_vote_elapsed(now);
         if last_vote_elapsed < self.network_params.network.vote_broadcast_interval {
             return;
         }
-        election_guard.set_last_vote();
+        election_guard.set_last_vote(now);
         if self.node_config.enable_voting && self.wallets.voting_reps_count() > 0 {
             self.stats
                 .inc(StatType::Election, DetailType::BroadcastVote);
@@ -634,11 +643,13 @@ impl ActiveElections {
         let election_winner: BlockHash;
         let election_state;
         let blocks;
+        let tallies;
         {
             let election_guard = election.mutex.lock().unwrap();
             blocks = election_guard.last_blocks.clone();
             election_winner = election_guard.status.winner.as_ref().unwrap().hash();
             election_state = election_guard.state;
+            tallies = election_guard.last_tally.clone();
         }
 
         self.vote_router.disconnect_election(election);
@@ -693,6 +704,15 @@ impl ActiveElections {
 
         self.vacancy_updated();
 
+        self.fork_journal.election_ended(
+            &election.qualified_root,
+            || self.fork_account(&election.qualified_root, &blocks),
+            blocks.keys().copied(),
+            &tallies,
+            election_winner,
+            self.confirmed(election),
+        );
+
         for (hash, block) in blocks {
             // Notify observers about dropped elections & blocks lost confirmed elections
             if !self.confirmed(election) || hash != election_winner {
@@ -709,6 +729,29 @@ impl ActiveElections {
         }
     }
 
+    fn fork_account(
+        &self,
+        root: &QualifiedRoot,
+        blocks: &HashMap<BlockHash, Arc<BlockEnum>>,
+    ) -> Account {
+        blocks
+            .values()
+            .find_map(|b| {
+                b.account_field()
+                    .or_else(|| b.sideband().map(|s| s.account))
+            })
+            .or_else(|| {
+                if root.previous.is_zero() {
+                    // The root of an open block is its account
+                    return Some(root.root.into());
+                }
+                // Legacy blocks don't contain the account
+                let tx = self.ledger.read_txn();
+                self.ledger.any().block_account(&tx, &root.previous)
+            })
+            .unwrap_or_default()
+    }
+
     pub fn confirmed(&self, election: &Election) -> bool {
         election.mutex.lock().unwrap().is_confirmed()
     }
@@ -784,6 +827,13 @@ impl ActiveElections {
         result
     }
 
+    /// Runs a single iteration of the request loop.
+    /// Used for driving the elections manually when the request loop is disabled
+    pub fn tick(&self) {
+        let guard = self.mutex.lock().unwrap();
+        drop(self.request_confirm(guard));
+    }
+
     pub fn request_loop(&self) {
         let mut guard = self.mutex.lock().unwrap();
         while !guard.stopped {
@@ -871,9 +921,7 @@ impl ActiveElections {
         let mut result = false;
         match guard.state {
             ElectionState::Passive => {
-                if self.base_latency() * Election::PASSIVE_DURATION_FACTOR
-                    < election.election_start.elapsed()
-                {
+                if self.base_latency() * Election::PASSIVE_DURATION_FACTOR < election.duration() {
                     guard
                         .state_change(ElectionState::Passive, ElectionState::Active)
                         .unwrap();
@@ -899,7 +947,7 @@ impl ActiveElections {
             }
         }
 
-        if !guard.is_confirmed() && election.time_to_live() < election.election_start.elapsed() {
+        if !guard.is_confirmed() && election.time_to_live() < election.duration() {
             // It is possible the election confirmed while acquiring the mutex
             // state_change returning true would indicate it
             let state = guard.state;
@@ -1317,6 +1365,7 @@ impl ActiveElectionsExt for Arc<ActiveElections> {
                     election_behavior,
                     Box::new(|_| {}),
                     observer_rep_cb,
+                    self.steady_clock.clone(),
                 ));
                 guard.roots.insert(Entry {
                     root,
@@ -4,6 +4,7 @@ use crate::{
     utils::HardenedConstants,
 };
 use rsnano_core::{Amount, BlockEnum, BlockHash, PublicKey, QualifiedRoot, Root};
+use rsnano_nullable_clock::{SteadyClock, Timestamp};
 use std::{
     collections::HashMap,
     fmt::Debug,
@@ -25,10 +26,11 @@ pub struct Election {
     pub is_quorum: AtomicBool,
     pub confirmation_request_count: AtomicU32,
     // These are modified while not holding the mutex from transition_time only
-    last_block: RwLock<Instant>,
-    pub last_req: RwLock<Option<Instant>>,
+    last_block: RwLock<Timestamp>,
+    pub last_req: RwLock<Option<Timestamp>>,
     pub behavior: ElectionBehavior,
-    pub election_start: Instant,
+    pub election_start: Timestamp,
+    clock: Arc<SteadyClock>,
     pub confirmation_action: Box<dyn Fn(Arc<BlockEnum>) + Send + Sync>,
     pub live_vote_action: Box<dyn Fn(PublicKey) + Send + Sync>,
     height: u64,
@@ -43,7 +45,9 @@ impl Election {
         behavior: ElectionBehavior,
         confirmation_action: Box<dyn Fn(Arc<BlockEnum>) + Send + Sync>,
         live_vote_action: Box<dyn Fn(PublicKey) + Send + Sync>,
+        clock: Arc<SteadyClock>,
     ) -> Self {
+        let now = clock.now();
         let root = block.root();
         let qualified_root = block.qualified_root();
         let height = block.sideband().map(|s| s.height).unwrap_or_default();
@@ -76,9 +80,10 @@ impl Election {
             qualified_root,
             is_quorum: AtomicBool::new(false),
             confirmation_request_count: AtomicU32::new(0),
-            last_block: RwLock::new(Instant::now()),
+            last_block: RwLock::new(now),
             behavior,
-            election_start: Instant::now(),
+            election_start: now,
+            clock,
             last_req: RwLock::new(None),
             confirmation_action,
             live_vote_action,
@@ -87,7 +92,7 @@ impl Election {
     }
 
     pub fn duration(&self) -> Duration {
-        self.election_start.elapsed()
+        self.election_start.elapsed(self.clock.now())
     }
 
     pub fn state(&self) -> ElectionState {
@@ -109,22 +114,22 @@ impl Election {
     }
 
     pub fn set_last_req(&self) {
-        *self.last_req.write().unwrap() = Some(Instant::now());
+        *self.last_req.write().unwrap() = Some(self.clock.now());
     }
 
     pub fn last_req_elapsed(&self) -> Duration {
         match self.last_req.read().unwrap().as_ref() {
-            Some(i) => i.elapsed(),
+            Some(i) => i.elapsed(self.clock.now()),
             None => Duration::from_secs(60 * 60 * 24 * 365), // Duration::MAX caused problems with C++
         }
     }
 
     pub fn set_last_block(&self) {
-        *self.last_block.write().unwrap() = Instant::now();
+        *self.last_block.write().unwrap() = self.clock.now();
     }
 
     pub fn last_block_elapsed(&self) -> Duration {
-        self.last_block.read().unwrap().elapsed()
+        self.last_block.read().unwrap().elapsed(self.clock.now())
     }
 
     pub fn age(&self) -> Duration {
@@ -181,7 +186,7 @@ pub struct ElectionData {
     pub final_weight: Amount,
     pub last_tally: HashMap<BlockHash, Amount>,
     /** The last time vote for this election was generated */
-    pub last_vote: Option<Instant>,
+    pub last_vote: Option<Timestamp>,
     pub last_block_hash: BlockHash,
 }
 
@@ -195,7 +200,7 @@ impl ElectionData {
 
     pub fn update_status_to_confirmed(&mut self, election: &Election) {
         self.status.election_end = SystemTime::now();
-        self.status.election_duration = election.election_start.elapsed();
+        self.status.election_duration = election.duration();
         self.status.confirmation_request_count =
             election.confirmation_request_count.load(Ordering::SeqCst);
         self.status.block_count = self.last_blocks.len() as u32;
@@ -240,13 +245,13 @@ impl ElectionData {
         }
     }
 
-    pub fn set_last_vote(&mut self) {
-        self.last_vote = Some(Instant::now());
+    pub fn set_last_vote(&mut self, now: Timestamp) {
+        self.last_vote = Some(now);
     }
 
-    pub fn last_vote_elapsed(&self) -> Duration {
+    pub fn last_vote_elapsed(&self, now: Timestamp) -> Duration {
         match &self.last_vote {
-            Some(i) => i.elapsed(),
+            Some(i) => i.elapsed(now),
             None => Duration::from_secs(60 * 60 * 24 * 365), // Duration::MAX caused problems with C++
         }
     }
@@ -0,0 +1,506 @@
+use rsnano_core::{
+    utils::{ContainerInfo, ContainerInfoComponent},
+    Account, Amount, BlockHash, QualifiedRoot,
+};
+use rsnano_network::ChannelId;
+use rsnano_nullable_clock::SystemTimeFactory;
+use std::{
+    collections::{HashMap, VecDeque},
+    mem::size_of,
+    net::SocketAddrV6,
+    sync::Mutex,
+    time::SystemTime,
+};
+
+/// A competing block of a fork
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkBlock {
+    pub hash: BlockHash,
+    /// Vote tally of the block when the election ended
+    pub tally: Amount,
+    /// The channel that sent us the block, if it arrived from the network
+    pub channel_id: Option<ChannelId>,
+    pub peer_addr: Option<SocketAddrV6>,
+}
+
+/// An election that had to decide between competing blocks for the same root
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkRecord {
+    pub root: QualifiedRoot,
+    pub account: Account,
+    pub blocks: Vec<ForkBlock>,
+    pub winner: BlockHash,
+    /// True if the winner was confirmed. Otherwise the election was dropped
+    pub confirmed: bool,
+    /// Time the first competing block was seen
+    pub detected: SystemTime,
+    /// Time the election ended
+    pub resolved: SystemTime,
+}
+
+#[derive(Default, Clone)]
+pub struct ForkQuery {
+    pub account: Option<Account>,
+    pub from: Option<SystemTime>,
+    pub to: Option<SystemTime>,
+    pub count: Option<usize>,
+}
+
+type ForkObserver = Box<dyn Fn(&ForkRecord) + Send + Sync>;
+
+/// Bounded journal of the forks that were resolved by elections.
+/// Forks are only kept in memory; the oldest records are dropped when the journal is full.
+pub struct ForkJournal {
+    data: Mutex<ForkJournalData>,
+    observers: Mutex<Vec<ForkObserver>>,
+    time_factory: SystemTimeFactory,
+    max_records: usize,
+}
+
+impl ForkJournal {
+    /// How many block origins are remembered, so that they can be attributed
+    /// to a fork that is detected later
+    const MAX_ORIGINS: usize = 1024 * 16;
+
+    pub fn new(max_records: usize, time_factory: SystemTimeFactory) -> Self {
+        Self {
+            data: Mutex::new(ForkJournalData::default()),
+            observers: Mutex::new(Vec::new()),
+            time_factory,
+            max_records,
+        }
+    }
+
+    pub fn on_fork(&self, observer: ForkObserver) {
+        self.observers.lock().unwrap().push(observer);
+    }
+
+    /// Remember from which channel a block arrived
+    pub fn block_arrived(
+        &self,
+        hash: BlockHash,
+        channel_id: ChannelId,
+        peer_addr: Option<SocketAddrV6>,
+    ) {
+        self.blocks_processed([(hash, channel_id, peer_addr)], []);
+    }
+
+    /// A block was processed that competes with an existing block for the same root
+    pub fn fork_detected(&self, root: QualifiedRoot, hash: BlockHash) {
+        self.blocks_processed([], [(root, hash)]);
+    }
+
+    /// Remembers the origins of a batch of processed blocks and the forks among them.
+    /// The journal is locked only once for the whole batch
+    pub fn blocks_processed(
+        &self,
+        arrived: impl IntoIterator<Item = (BlockHash, ChannelId, Option<SocketAddrV6>)>,
+        forks: impl IntoIterator<Item = (QualifiedRoot, BlockHash)>,
+    ) {
+        let now = self.time_factory.now();
+        let mut guard = self.data.lock().unwrap();
+        for (hash, channel_id, peer_addr) in arrived {
+            guard.add_origin(hash, channel_id, peer_addr);
+        }
+        for (root, hash) in forks {
+            guard.add_pending(root, hash, now, self.max_records);
+        }
+    }
+
+    /// Creates a record if the election for `root` saw more than one block.
+    /// `account` only gets called when a record is created, and without holding the journal lock
+    pub fn election_ended(
+        &self,
+        root: &QualifiedRoot,
+        account: impl FnOnce() -> Account,
+        hashes: impl IntoIterator<Item = BlockHash>,
+        tallies: &HashMap<BlockHash, Amount>,
+        winner: BlockHash,
+        confirmed: bool,
+    ) -> Option<ForkRecord> {
+        let now = self.time_factory.now();
+        let (blocks, detected) = {
+            let mut guard = self.data.lock().unwrap();
+            let pending = guard.pending.remove(root);
+            if pending.is_some() {
+                guard.pending_sequence.retain(|r| r != root);
+            }
+
+            let mut all_hashes: Vec<BlockHash> = Vec::new();
+            for hash in hashes
+                .into_iter()
+                .chain(pending.iter().flat_map(|p| p.hashes.iter().copied()))
+            {
+                if !all_hashes.contains(&hash) {
+                    all_hashes.push(hash);
+                }
+            }
+            if all_hashes.len() < 2 {
+                return None;
+            }
+
+            let blocks: Vec<ForkBlock> = all_hashes
+                .into_iter()
+                .map(|hash| {
+                    let origin = guard.origins.get(&hash);
+                    ForkBlock {
+                        hash,
+                        tally: tallies.get(&hash).copied().unwrap_or_default(),
+                        channel_id: origin.map(|(id, _)| *id),
+                        peer_addr: origin.and_then(|(_, addr)| *addr),
+                    }
+                })
+                .collect();
+            (blocks, pending.map(|p| p.detected).unwrap_or(now))
+        };
+
+        let record = ForkRecord {
+            root: root.clone(),
+            account: account(),
+            blocks,
+            winner,
+            confirmed,
+            detected,
+            resolved: now,
+        };
+
+        {
+            let mut guard = self.data.lock().unwrap();
+            guard.records.push_back(record.clone());
+            while guard.records.len() > self.max_records {
+                guard.records.pop_front();
+            }
+        }
+
+        for observer in self.observers.lock().unwrap().iter() {
+            observer(&record);
+        }
+        Some(record)
+    }
+
+    /// Returns the matching records, newest first
+    pub fn forks(&self, query: &ForkQuery) -> Vec<ForkRecord> {
+        let guard = self.data.lock().unwrap();
+        guard
+            .records
+            .iter()
+            .rev()
+            .filter(|r| query.account.map(|a| a == r.account).unwrap_or(true))
+            .filter(|r| query.from.map(|from| r.resolved >= from).unwrap_or(true))
+            .filter(|r| query.to.map(|to| r.resolved <= to).unwrap_or(true))
+            .take(query.count.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.lock().unwrap().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
+        let guard = self.data.lock().unwrap();
+        ContainerInfoComponent::Composite(
+            name.into(),
+            vec![
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "records".to_string(),
+                    count: guard.records.len(),
+                    sizeof_element: size_of::<ForkRecord>(),
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "pending".to_string(),
+                    count: guard.pending.len(),
+                    sizeof_element: size_of::<(QualifiedRoot, PendingFork)>(),
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "origins".to_string(),
+                    count: guard.origins.len(),
+                    sizeof_element: size_of::<(BlockHash, (ChannelId, Option<SocketAddrV6>))>()
+                        + size_of::<BlockHash>(),
+                }),
+            ],
+        )
+    }
+}
+
+impl Default for ForkJournal {
+    fn default() -> Self {
+        Self::new(1024, SystemTimeFactory::default())
+    }
+}
+
+#[derive(Default)]
+struct ForkJournalData {
+    records: VecDeque<ForkRecord>,
+    pending: HashMap<QualifiedRoot, PendingFork>,
+    pending_sequence: VecDeque<QualifiedRoot>,
+    origins: HashMap<BlockHash, (ChannelId, Option<SocketAddrV6>)>,
+    origins_sequence: VecDeque<BlockHash>,
+}
+
+impl ForkJournalData {
+    fn add_origin(
+        &mut self,
+        hash: BlockHash,
+        channel_id: ChannelId,
+        peer_addr: Option<SocketAddrV6>,
+    ) {
+        if self.origins.contains_key(&hash) {
+            return;
+        }
+        self.origins.insert(hash, (channel_id, peer_addr));
+        self.origins_sequence.push_back(hash);
+        if self.origins_sequence.len() > ForkJournal::MAX_ORIGINS {
+            if let Some(oldest) = self.origins_sequence.pop_front() {
+                self.origins.remove(&oldest);
+            }
+        }
+    }
+
+    fn add_pending(
+        &mut self,
+        root: QualifiedRoot,
+        hash: BlockHash,
+        now: SystemTime,
+        max_records: usize,
+    ) {
+        if !self.pending.contains_key(&root) {
+            // Forks for roots without an election are never resolved, so evict the oldest
+            if self.pending_sequence.len() >= max_records {
+                if let Some(oldest) = self.pending_sequence.pop_front() {
+                    self.pending.remove(&oldest);
+                }
+            }
+            self.pending_sequence.push_back(root.clone());
+        }
+        let pending = self.pending.entry(root).or_insert_with(|| PendingFork {
+            detected: now,
+            hashes: Vec::new(),
+        });
+        if !pending.hashes.contains(&hash) {
+            pending.hashes.push(hash);
+        }
+    }
+}
+
+struct PendingFork {
+    detected: SystemTime,
+    hashes: Vec<BlockHash>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::{
+        utils::{new_test_timestamp, TEST_ENDPOINT_1},
+        Root,
+    };
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[test]
+    fn no_record_without_competing_blocks() {
+        let journal = ForkJournal::default();
+        let root = QualifiedRoot::new_test_instance();
+        let hash = BlockHash::from(1);
+
+        let record = journal.election_ended(
+            &root,
+            || Account::from(1),
+            [hash],
+            &HashMap::new(),
+            hash,
+            true,
+        );
+
+        assert_eq!(record, None);
+        assert_eq!(journal.len(), 0);
+    }
+
+    #[test]
+    fn record_fork() {
+        let now = new_test_timestamp();
+        let journal = ForkJournal::new(10, SystemTimeFactory::new_null_with(now));
+        let root = QualifiedRoot::new_test_instance();
+        let winner = BlockHash::from(1);
+        let loser = BlockHash::from(2);
+        journal.block_arrived(loser, ChannelId::from(7), Some(TEST_ENDPOINT_1));
+        journal.fork_detected(root.clone(), loser);
+        let tallies = HashMap::from([(winner, Amount::raw(100)), (loser, Amount::raw(5))]);
+
+        // The loser was already dropped from the election
+        let record = journal
+            .election_ended(&root, || Account::from(3), [winner], &tallies, winner, true)
+            .unwrap();
+
+        assert_eq!(
+            record,
+            ForkRecord {
+                root,
+                account: Account::from(3),
+                blocks: vec![
+                    ForkBlock {
+                        hash: winner,
+                        tally: Amount::raw(100),
+                        channel_id: None,
+                        peer_addr: None,
+                    },
+                    ForkBlock {
+                        hash: loser,
+                        tally: Amount::raw(5),
+                        channel_id: Some(ChannelId::from(7)),
+                        peer_addr: Some(TEST_ENDPOINT_1),
+                    }
+                ],
+                winner,
+                confirmed: true,
+                detected: now,
+                resolved: now,
+            }
+        );
+        assert_eq!(journal.forks(&ForkQuery::default()), vec![record]);
+    }
+
+    #[test]
+    fn notify_observers() {
+        let journal = ForkJournal::default();
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified2 = notified.clone();
+        journal.on_fork(Box::new(move |record| {
+            notified2.lock().unwrap().push(record.winner);
+        }));
+
+        add_fork(&journal, 1, Account::from(1));
+
+        assert_eq!(*notified.lock().unwrap(), vec![BlockHash::from(1)]);
+    }
+
+    #[test]
+    fn drop_oldest_records_when_full() {
+        let journal = ForkJournal::new(2, SystemTimeFactory::new_null());
+        add_fork(&journal, 1, Account::from(1));
+        add_fork(&journal, 2, Account::from(1));
+        add_fork(&journal, 3, Account::from(1));
+
+        let winners: Vec<_> = journal
+            .forks(&ForkQuery::default())
+            .iter()
+            .map(|r| r.winner)
+            .collect();
+        assert_eq!(winners, vec![BlockHash::from(3), BlockHash::from(2)]);
+    }
+
+    #[test]
+    fn filter_by_account_and_count() {
+        let journal = ForkJournal::default();
+        add_fork(&journal, 1, Account::from(1));
+        add_fork(&journal, 2, Account::from(2));
+        add_fork(&journal, 3, Account::from(1));
+
+        let winners: Vec<_> = journal
+            .forks(&ForkQuery {
+                account: Some(Account::from(1)),
+                count: Some(1),
+                ..Default::default()
+            })
+            .iter()
+            .map(|r| r.winner)
+            .collect();
+        assert_eq!(winners, vec![BlockHash::from(3)]);
+    }
+
+    #[test]
+    fn filter_by_time_range() {
+        let now = new_test_timestamp();
+        let journal = ForkJournal::new(10, SystemTimeFactory::new_null_with(now));
+        add_fork(&journal, 1, Account::from(1));
+
+        let query = |from: SystemTime, to: SystemTime| {
+            journal
+                .forks(&ForkQuery {
+                    from: Some(from),
+                    to: Some(to),
+                    ..Default::default()
+                })
+                .len()
+        };
+        assert_eq!(query(now - Duration::from_secs(1), now), 1);
+        assert_eq!(
+            query(now + Duration::from_secs(1), now + Duration::from_secs(2)),
+            0
+        );
+    }
+
+    #[test]
+    fn record_origins_and_forks_of_a_batch() {
+        let journal = ForkJournal::default();
+        let root = QualifiedRoot::new_test_instance();
+        let winner = BlockHash::from(1);
+        let loser = BlockHash::from(2);
+
+        journal.blocks_processed(
+            [
+                (winner, ChannelId::from(3), None),
+                (loser, ChannelId::from(7), Some(TEST_ENDPOINT_1)),
+            ],
+            [(root.clone(), loser)],
+        );
+
+        let record = journal
+            .election_ended(
+                &root,
+                || Account::from(3),
+                [winner],
+                &HashMap::new(),
+                winner,
+                true,
+            )
+            .unwrap();
+        let channels: Vec<_> = record.blocks.iter().map(|b| b.channel_id).collect();
+        assert_eq!(
+            channels,
+            vec![Some(ChannelId::from(3)), Some(ChannelId::from(7))]
+        );
+    }
+
+    #[test]
+    fn resolve_account_without_holding_the_lock() {
+        let journal = ForkJournal::default();
+        let root = QualifiedRoot::new_test_instance();
+        let winner = BlockHash::from(1);
+
+        let record = journal.election_ended(
+            &root,
+            || {
+                // Would deadlock if the journal was still locked
+                assert_eq!(journal.len(), 0);
+                Account::from(3)
+            },
+            [winner, BlockHash::from(2)],
+            &HashMap::new(),
+            winner,
+            true,
+        );
+
+        assert_eq!(record.unwrap().account, Account::from(3));
+    }
+
+    fn add_fork(journal: &ForkJournal, winner: u64, account: Account) {
+        let root = QualifiedRoot::new(Root::from(winner), BlockHash::from(1000 + winner));
+        let winner = BlockHash::from(winner);
+        journal.election_ended(
+            &root,
+            || account,
+            [winner, BlockHash::from(999)],
+            &HashMap::new(),
+            winner,
+            true,
+        );
+    }
+}
@@ -5,6 +5,7 @@ mod confirmation_solicitor;
 mod election;
 pub(crate) mod election_schedulers;
 mod election_status;
+mod fork_journal;
 mod hinted_scheduler;
 mod manual_scheduler;
 mod optimistic_scheduler;
@@ -27,6 +28,7 @@ pub use bucket::*;
 pub use confirmation_solicitor::ConfirmationSolicitor;
 pub use election::*;
 pub use election_status::{ElectionStatus, ElectionStatusType};
+pub use fork_journal::*;
 pub use hinted_scheduler::*;
 pub use manual_scheduler::*;
 pub use optimistic_scheduler::*;
@@ -188,6 +188,11 @@ impl PriorityScheduler {
         self.len() == 0
     }
 
+    /// True if blocks are waiting to get activated and there is room for their elections
+    pub fn activation_pending(&self) -> bool {
+        self.predicate()
+    }
+
     fn predicate(&self) -> bool {
         self.buckets.iter().any(|b| b.available())
     }
@@ -5,7 +5,7 @@ use crate::{
 };
 use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
-    Account, PublicKey,
+    Account, Amount, PublicKey,
 };
 use rsnano_ledger::RepWeightCache;
 use std::{
@@ -15,11 +15,12 @@ use std::{
     thread::JoinHandle,
     time::Duration,
 };
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use tracing::debug;
 
 // Higher number means higher priority
-#[derive(FromPrimitive, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, EnumIter)]
+#[derive(FromPrimitive, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Debug, EnumIter)]
 pub enum RepTier {
     None,  // Not a principal representatives
     Tier1, // (0.1-1%) of online stake
@@ -38,6 +39,13 @@ impl From<RepTier> for DetailType {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RepTierWeight {
+    pub tier: RepTier,
+    pub representatives: usize,
+    pub weight: Amount,
+}
+
 pub struct RepTiers {
     network_params: NetworkParams,
     thread: Mutex<Option<JoinHandle<()>>>,
@@ -103,16 +111,32 @@ impl RepTiers {
     }
 
     pub fn tier(&self, representative: &PublicKey) -> RepTier {
+        self.rep_tiers_impl
+            .tiers
+            .lock()
+            .unwrap()
+            .tier(representative)
+    }
+
+    /// Number of representatives and their summed weight for each tier.
+    /// Every representative is counted in its highest tier only
+    pub fn tier_weights(&self) -> Vec<RepTierWeight> {
+        let mut result: Vec<_> = RepTier::iter()
+            .map(|tier| RepTierWeight {
+                tier,
+                representatives: 0,
+                weight: Amount::zero(),
+            })
+            .collect();
+
         let tiers = self.rep_tiers_impl.tiers.lock().unwrap();
-        if tiers.representatives_3.contains(representative) {
-            RepTier::Tier3
-        } else if tiers.representatives_2.contains(representative) {
-            RepTier::Tier2
-        } else if tiers.representatives_1.contains(representative) {
-            RepTier::Tier1
-        } else {
-            RepTier::None
+        let rep_weights = self.rep_tiers_impl.rep_weights.read();
+        for (representative, weight) in rep_weights.iter() {
+            let entry = &mut result[tiers.tier(representative) as usize];
+            entry.representatives += 1;
+            entry.weight += *weight;
         }
+        result
     }
 
     pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
@@ -157,6 +181,20 @@ struct Tiers {
     representatives_3: HashSet<PublicKey>,
 }
 
+impl Tiers {
+    fn tier(&self, representative: &PublicKey) -> RepTier {
+        if self.representatives_3.contains(representative) {
+            RepTier::Tier3
+        } else if self.representatives_2.contains(representative) {
+            RepTier::Tier2
+        } else if self.representatives_1.contains(representative) {
+            RepTier::Tier1
+        } else {
+            RepTier::None
+        }
+    }
+}
+
 struct RepTiersImpl {
     stats: Arc<Stats>,
     online_reps: Arc<Mutex<OnlineReps>>,
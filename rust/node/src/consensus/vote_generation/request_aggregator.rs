@@ -4,7 +4,7 @@ use super::{
 };
 use crate::{
     stats::{DetailType, Direction, StatType, Stats},
-    transport::FairQueue,
+    transport::{FairQueue, TrustedPeers},
 };
 use rsnano_core::{utils::ContainerInfoComponent, BlockHash, Root};
 use rsnano_ledger::Ledger;
@@ -59,6 +59,7 @@ impl RequestAggregator {
         vote_generators: Arc<VoteGenerators>,
         ledger: Arc<Ledger>,
         network: Arc<RwLock<NetworkInfo>>,
+        trusted_peers: Arc<TrustedPeers>,
     ) -> Self {
         let max_queue = config.max_queue;
         Self {
@@ -68,7 +69,10 @@ impl RequestAggregator {
             config,
             condition: Arc::new(Condvar::new()),
             state: Arc::new(Mutex::new(RequestAggregatorState {
-                queue: FairQueue::new(Box::new(move |_| max_queue), Box::new(|_| 1)),
+                queue: FairQueue::new(
+                    Box::new(move |_| max_queue),
+                    Box::new(move |channel_id| trusted_peers.priority(channel_id)),
+                ),
                 stopped: false,
             })),
             threads: Mutex::new(Vec::new()),
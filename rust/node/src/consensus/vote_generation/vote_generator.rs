@@ -1,10 +1,10 @@
 use super::{LocalVoteHistory, VoteSpacing};
 use crate::{
     consensus::VoteBroadcaster,
+    signing::VoteSigner,
     stats::{DetailType, Direction, StatType, Stats},
     transport::MessagePublisher,
     utils::ProcessingQueue,
-    wallets::Wallets,
 };
 use rsnano_core::{
     utils::{milliseconds_since_epoch, ContainerInfo, ContainerInfoComponent},
@@ -39,7 +39,7 @@ impl VoteGenerator {
 
     pub(crate) fn new(
         ledger: Arc<Ledger>,
-        wallets: Arc<Wallets>,
+        signer: Arc<dyn VoteSigner>,
         history: Arc<LocalVoteHistory>,
         is_final: bool,
         stats: Arc<Stats>,
@@ -53,7 +53,7 @@ impl VoteGenerator {
             ledger: Arc::clone(&ledger),
             message_publisher: Mutex::new(message_publisher),
             history,
-            wallets,
+            signer,
             condition: Condvar::new(),
             queues: Mutex::new(Queues::default()),
             is_final,
@@ -142,6 +142,12 @@ impl VoteGenerator {
         result
     }
 
+    /// Number of votes that are queued but not broadcast yet
+    pub(crate) fn queue_len(&self) -> usize {
+        let queues = self.shared_state.queues.lock().unwrap();
+        self.vote_generation_queue.len() + queues.candidates.len() + queues.requests.len()
+    }
+
     pub(crate) fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
         let candidates_count;
         let requests_count;
@@ -176,7 +182,7 @@ impl Drop for VoteGenerator {
 
 struct SharedState {
     ledger: Arc<Ledger>,
-    wallets: Arc<Wallets>,
+    signer: Arc<dyn VoteSigner>,
     history: Arc<LocalVoteHistory>,
     message_publisher: Mutex<MessagePublisher>,
     is_final: bool,
@@ -267,25 +273,21 @@ impl SharedState {
         F: Fn(Arc<Vote>),
     {
         debug_assert_eq!(hashes.len(), roots.len());
-        let mut votes = Vec::new();
-        self.wallets.foreach_representative(|keys| {
-            let timestamp = if self.is_final {
-                Vote::TIMESTAMP_MAX
-            } else {
-                milliseconds_since_epoch()
-            };
-            let duration = if self.is_final {
-                Vote::DURATION_MAX
-            } else {
-                0x9 /*8192ms*/
-            };
-            votes.push(Arc::new(Vote::new(
-                keys,
-                timestamp,
-                duration,
-                hashes.clone(),
-            )));
-        });
+        let timestamp = if self.is_final {
+            Vote::TIMESTAMP_MAX
+        } else {
+            milliseconds_since_epoch()
+        };
+        let duration = if self.is_final {
+            Vote::DURATION_MAX
+        } else {
+            0x9 /*8192ms*/
+        };
+        let votes = self
+            .signer
+            .sign_votes(timestamp, duration, hashes)
+            .into_iter()
+            .map(Arc::new);
 
         for vote in votes {
             {
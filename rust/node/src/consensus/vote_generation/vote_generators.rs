@@ -1,7 +1,7 @@
 use super::{vote_generator::VoteGenerator, LocalVoteHistory};
 use crate::{
-    config::NodeConfig, consensus::VoteBroadcaster, stats::Stats, transport::MessagePublisher,
-    wallets::Wallets, NetworkParams,
+    config::NodeConfig, consensus::VoteBroadcaster, signing::VoteSigner, stats::Stats,
+    transport::MessagePublisher, NetworkParams,
 };
 use rsnano_core::{utils::ContainerInfoComponent, BlockEnum, BlockHash, Root};
 use rsnano_ledger::Ledger;
@@ -16,7 +16,7 @@ pub struct VoteGenerators {
 impl VoteGenerators {
     pub(crate) fn new(
         ledger: Arc<Ledger>,
-        wallets: Arc<Wallets>,
+        signer: Arc<dyn VoteSigner>,
         history: Arc<LocalVoteHistory>,
         stats: Arc<Stats>,
         config: &NodeConfig,
@@ -26,7 +26,7 @@ impl VoteGenerators {
     ) -> Self {
         let non_final_vote_generator = VoteGenerator::new(
             ledger.clone(),
-            wallets.clone(),
+            signer.clone(),
             history.clone(),
             false, //none-final
             stats.clone(),
@@ -39,7 +39,7 @@ impl VoteGenerators {
 
         let final_vote_generator = VoteGenerator::new(
             ledger,
-            wallets,
+            signer,
             history,
             true, //final
             stats,
@@ -90,6 +90,10 @@ impl VoteGenerators {
         self.non_final_vote_generator.generate(blocks, channel_id)
     }
 
+    pub fn queue_len(&self) -> usize {
+        self.non_final_vote_generator.queue_len() + self.final_vote_generator.queue_len()
+    }
+
     pub(crate) fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
         ContainerInfoComponent::Composite(
             name.into(),
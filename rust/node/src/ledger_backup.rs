@@ -0,0 +1,444 @@
+use crate::{
+    block_processing::BlockProcessor,
+    utils::{CancellationToken, Runnable},
+};
+use rsnano_core::utils::seconds_since_epoch;
+use rsnano_store_lmdb::LmdbStore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{error, info};
+
+pub const LEDGER_BACKUP_FILE_NAME: &str = "data.ldb";
+pub const LEDGER_BACKUP_MANIFEST_NAME: &str = "manifest.json";
+const LEDGER_BACKUP_DIR_PREFIX: &str = "ledger_";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LedgerBackupConfig {
+    /// Create backups periodically
+    pub enabled: bool,
+    pub interval: Duration,
+    /// Number of scheduled backups to keep. Older ones get deleted
+    pub max_backups: usize,
+    /// Number of database entries that get copied between two cancellation checks
+    pub batch_size: usize,
+    /// The backup is cancelled when copying takes longer than this, so that the
+    /// read transaction doesn't keep old database pages alive for too long
+    pub max_duration: Duration,
+    /// The backup doesn't start, and pauses between two batches, while the block processor
+    /// queue holds more than this many blocks
+    pub block_processor_threshold: usize,
+}
+
+impl Default for LedgerBackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(60 * 60 * 24),
+            max_backups: 3,
+            batch_size: 64 * 1024,
+            max_duration: Duration::from_secs(60 * 60),
+            block_processor_threshold: 1000,
+        }
+    }
+}
+
+/// Describes the content of a ledger backup
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerBackupManifest {
+    pub created: u64,
+    pub block_count: u64,
+    pub cemented_count: u64,
+    pub account_count: u64,
+    pub store_version: i32,
+    /// Hex encoded SHA-256 of the backup file
+    pub sha256: String,
+}
+
+impl LedgerBackupManifest {
+    pub fn load(backup_dir: &Path) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(backup_dir.join(LEDGER_BACKUP_MANIFEST_NAME))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    fn save(&self, backup_dir: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(backup_dir.join(LEDGER_BACKUP_MANIFEST_NAME), json)?;
+        Ok(())
+    }
+}
+
+/// Creates consistent copies of the ledger while the node is running.
+/// The copy is read from a single read transaction, so that writers are never blocked.
+/// That transaction is only opened once the node is idle and is never held longer
+/// than `max_duration`.
+pub struct LedgerBackup {
+    config: LedgerBackupConfig,
+    store: Arc<LmdbStore>,
+    block_processor: Option<Arc<BlockProcessor>>,
+    backups_path: PathBuf,
+    stopped: AtomicBool,
+}
+
+impl LedgerBackup {
+    pub fn new(config: LedgerBackupConfig, store: Arc<LmdbStore>, backups_path: PathBuf) -> Self {
+        Self {
+            config,
+            store,
+            block_processor: None,
+            backups_path,
+            stopped: AtomicBool::new(false),
+        }
+    }
+
+    /// Pause the backup while the block processor is busy
+    pub fn set_block_processor(&mut self, block_processor: Arc<BlockProcessor>) {
+        self.block_processor = Some(block_processor);
+    }
+
+    pub fn backups_path(&self) -> &Path {
+        &self.backups_path
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// Creates a new backup directory inside `backups_path` and deletes old
+    /// backups as configured by `max_backups`
+    pub fn backup_with_retention(&self) -> anyhow::Result<(PathBuf, LedgerBackupManifest)> {
+        let backup_dir = self.create_unique_backup_dir()?;
+        let manifest = match self.backup(&backup_dir) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&backup_dir);
+                return Err(e);
+            }
+        };
+        self.delete_old_backups()?;
+        Ok((backup_dir, manifest))
+    }
+
+    /// Writes a copy of the ledger and its manifest into `backup_dir`.
+    /// On failure only the files that were written by this call get deleted,
+    /// because `backup_dir` may be an existing directory
+    pub fn backup(&self, backup_dir: &Path) -> anyhow::Result<LedgerBackupManifest> {
+        let backup_file = backup_dir.join(LEDGER_BACKUP_FILE_NAME);
+        let manifest_file = backup_dir.join(LEDGER_BACKUP_MANIFEST_NAME);
+        if backup_file.exists() || manifest_file.exists() {
+            bail!("backup {:?} already exists", backup_dir);
+        }
+        let created_dir = !backup_dir.exists();
+        fs::create_dir_all(backup_dir)?;
+
+        let result = self.write_backup(backup_dir, &backup_file);
+        if result.is_err() {
+            let mut lock_file = backup_file.clone().into_os_string();
+            lock_file.push("-lock");
+            for file in [
+                backup_file.as_os_str(),
+                &lock_file,
+                manifest_file.as_os_str(),
+            ] {
+                let _ = fs::remove_file(file);
+            }
+            if created_dir {
+                // Only removes the directory if it is empty
+                let _ = fs::remove_dir(backup_dir);
+            }
+        }
+        result
+    }
+
+    fn write_backup(
+        &self,
+        backup_dir: &Path,
+        backup_file: &Path,
+    ) -> anyhow::Result<LedgerBackupManifest> {
+        if !self.wait_until_idle(None) {
+            bail!("backup cancelled");
+        }
+
+        info!("Creating ledger backup in {:?}...", backup_dir);
+        let txn = self.store.tx_begin_read();
+        let mut manifest = LedgerBackupManifest {
+            created: seconds_since_epoch(),
+            block_count: self.store.block.count(&txn),
+            cemented_count: self.store.cache.cemented_count.load(Ordering::SeqCst),
+            account_count: self.store.account.count(&txn),
+            store_version: self.store.version.get(&txn).unwrap_or_default(),
+            sha256: String::new(),
+        };
+
+        // Between two batches the copy pauses while the block processor is busy
+        let deadline = Instant::now() + self.config.max_duration;
+        let result =
+            self.store
+                .copy_db_consistent(&txn, backup_file, self.config.batch_size, || {
+                    self.wait_until_idle(Some(deadline))
+                });
+        drop(txn);
+        result?;
+
+        manifest.sha256 = file_sha256(backup_file)?;
+        manifest.save(backup_dir)?;
+        info!(
+            "Ledger backup created in {:?} (blocks: {}, cemented: {})",
+            backup_dir, manifest.block_count, manifest.cemented_count
+        );
+        Ok(manifest)
+    }
+
+    /// Returns the backup directories created by `backup_with_retention`, oldest first
+    pub fn list_backups(&self) -> anyhow::Result<Vec<PathBuf>> {
+        if !self.backups_path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut backups: Vec<(u64, PathBuf)> = fs::read_dir(&self.backups_path)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let timestamp = name
+                    .to_str()?
+                    .strip_prefix(LEDGER_BACKUP_DIR_PREFIX)?
+                    .parse()
+                    .ok()?;
+                Some((timestamp, entry.path()))
+            })
+            .collect();
+        backups.sort();
+        Ok(backups.into_iter().map(|(_, path)| path).collect())
+    }
+
+    fn delete_old_backups(&self) -> anyhow::Result<()> {
+        let backups = self.list_backups()?;
+        let to_delete = backups.len().saturating_sub(self.config.max_backups.max(1));
+        for backup in &backups[..to_delete] {
+            info!("Deleting old ledger backup {:?}", backup);
+            fs::remove_dir_all(backup)?;
+        }
+        Ok(())
+    }
+
+    /// The directory name is the creation time in milliseconds. A counter gets
+    /// added when two backups are created within the same millisecond
+    fn create_unique_backup_dir(&self) -> anyhow::Result<PathBuf> {
+        fs::create_dir_all(&self.backups_path)?;
+        let mut timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        loop {
+            let backup_dir = self
+                .backups_path
+                .join(format!("{}{}", LEDGER_BACKUP_DIR_PREFIX, timestamp));
+            match fs::create_dir(&backup_dir) {
+                Ok(()) => return Ok(backup_dir),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => timestamp += 1,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Waits until the block processor is not busy anymore. Returns false if the backup
+    /// should be cancelled, because the node stopped or the deadline passed
+    fn wait_until_idle(&self, deadline: Option<Instant>) -> bool {
+        loop {
+            if self.stopped.load(Ordering::SeqCst)
+                || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return false;
+            }
+            let block_processor_busy = self
+                .block_processor
+                .as_ref()
+                .is_some_and(|bp| bp.total_queue_len() > self.config.block_processor_threshold);
+            if !block_processor_busy {
+                return true;
+            }
+            sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+fn file_sha256(path: &Path) -> anyhow::Result<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 64 * 1024];
+    loop {
+        let len = reader.read(&mut buffer)?;
+        if len == 0 {
+            break;
+        }
+        hasher.update(&buffer[..len]);
+    }
+    Ok(hex::encode_upper(hasher.finalize()))
+}
+
+/// Runs ledger backups periodically
+pub struct ScheduledLedgerBackup {
+    backup: Arc<LedgerBackup>,
+}
+
+impl ScheduledLedgerBackup {
+    pub fn new(backup: Arc<LedgerBackup>) -> Self {
+        Self { backup }
+    }
+}
+
+impl Runnable for ScheduledLedgerBackup {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        if let Err(e) = self.backup.backup_with_retention() {
+            error!(error = ?e, "Could not create ledger backup");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn create_backup_with_manifest() {
+        let fixture = Fixture::new(LedgerBackupConfig::default());
+        fixture
+            .backup
+            .store
+            .cache
+            .cemented_count
+            .store(3, Ordering::SeqCst);
+        let backup_dir = fixture.dir.join("my_backup");
+
+        let manifest = fixture.backup.backup(&backup_dir).unwrap();
+
+        assert_eq!(manifest.cemented_count, 3);
+        assert_eq!(manifest.block_count, 0);
+        assert_eq!(manifest.sha256.len(), 64);
+        assert_eq!(
+            manifest.sha256,
+            file_sha256(&backup_dir.join(LEDGER_BACKUP_FILE_NAME)).unwrap()
+        );
+        assert_eq!(LedgerBackupManifest::load(&backup_dir).unwrap(), manifest);
+    }
+
+    #[test]
+    fn refuse_to_overwrite_existing_backup() {
+        let fixture = Fixture::new(LedgerBackupConfig::default());
+        let backup_dir = fixture.dir.join("my_backup");
+        fixture.backup.backup(&backup_dir).unwrap();
+
+        assert!(fixture.backup.backup(&backup_dir).is_err());
+    }
+
+    #[test]
+    fn delete_old_backups() {
+        let fixture = Fixture::new(LedgerBackupConfig {
+            max_backups: 2,
+            ..Default::default()
+        });
+        for timestamp in [100, 200, 300] {
+            fs::create_dir_all(
+                fixture
+                    .backup
+                    .backups_path()
+                    .join(format!("{}{}", LEDGER_BACKUP_DIR_PREFIX, timestamp)),
+            )
+            .unwrap();
+        }
+
+        let (created, _) = fixture.backup.backup_with_retention().unwrap();
+
+        let backups = fixture.backup.list_backups().unwrap();
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].ends_with(format!("{}300", LEDGER_BACKUP_DIR_PREFIX)));
+        assert_eq!(backups[1], created);
+    }
+
+    #[test]
+    fn backups_in_quick_succession_get_unique_directories() {
+        let fixture = Fixture::new(LedgerBackupConfig {
+            max_backups: 10,
+            ..Default::default()
+        });
+
+        let (first, _) = fixture.backup.backup_with_retention().unwrap();
+        let (second, _) = fixture.backup.backup_with_retention().unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(fixture.backup.list_backups().unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn backup_gets_cancelled_after_max_duration() {
+        let fixture = Fixture::new(LedgerBackupConfig {
+            batch_size: 1,
+            max_duration: Duration::ZERO,
+            ..Default::default()
+        });
+        let backup_dir = fixture.dir.join("my_backup");
+
+        assert!(fixture.backup.backup(&backup_dir).is_err());
+        assert!(!backup_dir.exists());
+    }
+
+    #[test]
+    fn failed_backup_keeps_existing_directory() {
+        let fixture = Fixture::new(LedgerBackupConfig {
+            batch_size: 1,
+            max_duration: Duration::ZERO,
+            ..Default::default()
+        });
+        let backup_dir = fixture.dir.join("existing");
+        fs::create_dir_all(&backup_dir).unwrap();
+        fs::write(backup_dir.join("other_file"), "keep me").unwrap();
+
+        assert!(fixture.backup.backup(&backup_dir).is_err());
+
+        let files: Vec<_> = fs::read_dir(&backup_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, vec!["other_file"]);
+    }
+
+    #[test]
+    fn stopped_backup_gets_cancelled() {
+        let fixture = Fixture::new(LedgerBackupConfig {
+            batch_size: 1,
+            ..Default::default()
+        });
+        fixture.backup.stop();
+        let backup_dir = fixture.dir.join("my_backup");
+
+        assert!(fixture.backup.backup(&backup_dir).is_err());
+        assert!(!backup_dir.exists());
+    }
+
+    struct Fixture {
+        dir: PathBuf,
+        backup: LedgerBackup,
+    }
+
+    impl Fixture {
+        fn new(config: LedgerBackupConfig) -> Self {
+            let dir = std::env::temp_dir().join(format!("rsnano-test-{}", Uuid::new_v4().simple()));
+            let store = Arc::new(LmdbStore::open(&dir.join("data.ldb")).build().unwrap());
+            let backup = LedgerBackup::new(config, store, dir.join("backup"));
+            Self { dir, backup }
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
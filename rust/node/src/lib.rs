@@ -13,6 +13,7 @@ pub mod cementation;
 pub mod config;
 pub mod consensus;
 mod ipc;
+pub mod ledger_backup;
 mod monitor;
 mod node;
 mod node_builder;
@@ -20,8 +21,10 @@ mod node_id_key_file;
 pub mod pruning;
 pub mod representatives;
 mod secure;
+pub mod signing;
 pub mod stats;
 mod telemetry;
+mod telemetry_census;
 pub mod transport;
 pub mod utils;
 pub mod wallets;
@@ -34,3 +37,4 @@ pub use node_builder::*;
 pub use representatives::OnlineWeightSampler;
 pub use secure::*;
 pub use telemetry::*;
+pub use telemetry_census::*;
@@ -11,16 +11,20 @@ use crate::{
     config::{GlobalConfig, NodeConfig, NodeFlags},
     consensus::{
         election_schedulers::ElectionSchedulers, get_bootstrap_weights, log_bootstrap_weights,
-        ActiveElections, ActiveElectionsExt, ElectionStatusType, LocalVoteHistory,
+        ActiveElections, ActiveElectionsExt, ElectionStatusType, ForkJournal, LocalVoteHistory,
         ProcessLiveDispatcher, ProcessLiveDispatcherExt, RecentlyConfirmedCache, RepTiers,
         RequestAggregator, RequestAggregatorCleanup, VoteApplier, VoteBroadcaster, VoteCache,
         VoteCacheProcessor, VoteGenerators, VoteProcessor, VoteProcessorExt, VoteProcessorQueue,
         VoteProcessorQueueCleanup, VoteRouter,
     },
+    ledger_backup::{LedgerBackup, ScheduledLedgerBackup},
     monitor::Monitor,
     node_id_key_file::NodeIdKeyFile,
-    pruning::{LedgerPruning, LedgerPruningExt},
-    representatives::{OnlineReps, OnlineRepsCleanup, RepCrawler, RepCrawlerExt},
+    pruning::{LedgerPruning, LedgerPruningExt, PruningStatus},
+    representatives::{
+        OnlineReps, OnlineRepsCleanup, RepCrawler, RepCrawlerExt, RepresentativeCacheUpdater,
+    },
+    signing::{ThresholdSigningSessions, VoteSigner, WalletVoteSigner},
     stats::{
         adapters::{LedgerStats, NetworkStats},
         DetailType, Direction, StatType, Stats,
@@ -28,13 +32,14 @@ use crate::{
     transport::{
         InboundMessageQueue, InboundMessageQueueCleanup, KeepaliveFactory, LatestKeepalives,
         LatestKeepalivesCleanup, MessageProcessor, MessagePublisher, NanoResponseServerSpawner,
-        NetworkFilter, NetworkThreads, PeerCacheConnector, PeerCacheUpdater,
-        RealtimeMessageHandler, SynCookies,
+        NetworkFilter, NetworkThreads, PeerBans, PeerCacheConnector, PeerCacheUpdater, PortMapping,
+        PortMappingRefresh, RealtimeMessageHandler, SynCookies, TrustedPeerConnector, TrustedPeers,
+        TrustedPeersCleanup,
     },
     utils::{
         LongRunningTransactionLogger, ThreadPool, ThreadPoolImpl, TimerThread, TxnTrackingConfig,
     },
-    wallets::{Wallets, WalletsExt},
+    wallets::{backup_wallets_with_retention, Wallets, WalletsExt},
     websocket::{create_websocket_server, WebsocketListenerExt},
     work::DistributedWorkFactory,
     NetworkParams, NodeCallbacks, OnlineWeightSampler, TelementryConfig, TelementryExt, Telemetry,
@@ -92,10 +97,12 @@ pub struct Node {
     pub ledger: Arc<Ledger>,
     pub syn_cookies: Arc<SynCookies>,
     pub network_info: Arc<RwLock<NetworkInfo>>,
+    pub peer_bans: Arc<PeerBans>,
+    pub trusted_peers: Arc<TrustedPeers>,
     pub network: Arc<Network>,
     pub telemetry: Arc<Telemetry>,
     pub bootstrap_server: Arc<BootstrapServer>,
-    online_weight_sampler: Arc<OnlineWeightSampler>,
+    pub online_weight_sampler: Arc<OnlineWeightSampler>,
     pub online_reps: Arc<Mutex<OnlineReps>>,
     pub rep_tiers: Arc<RepTiers>,
     pub vote_processor_queue: Arc<VoteProcessorQueue>,
@@ -104,8 +111,10 @@ pub struct Node {
     pub vote_cache: Arc<Mutex<VoteCache>>,
     pub block_processor: Arc<BlockProcessor>,
     pub wallets: Arc<Wallets>,
+    pub vote_signer: Arc<dyn VoteSigner>,
     pub vote_generators: Arc<VoteGenerators>,
     pub active: Arc<ActiveElections>,
+    pub fork_journal: Arc<ForkJournal>,
     pub vote_router: Arc<VoteRouter>,
     pub vote_processor: Arc<VoteProcessor>,
     vote_cache_processor: Arc<VoteCacheProcessor>,
@@ -125,9 +134,16 @@ pub struct Node {
     pub peer_connector: Arc<PeerConnector>,
     ongoing_bootstrap: Arc<OngoingBootstrap>,
     peer_cache_updater: TimerThread<PeerCacheUpdater>,
+    rep_cache_updater: TimerThread<RepresentativeCacheUpdater>,
     peer_cache_connector: TimerThread<PeerCacheConnector>,
+    trusted_peer_connector: TimerThread<TrustedPeerConnector>,
     pub inbound_message_queue: Arc<InboundMessageQueue>,
     monitor: TimerThread<Monitor>,
+    pub ledger_backup: Arc<LedgerBackup>,
+    pub threshold_signing: ThresholdSigningSessions,
+    ledger_backup_scheduler: TimerThread<ScheduledLedgerBackup>,
+    pub port_mapping: Arc<PortMapping>,
+    port_mapping_refresh: TimerThread<PortMappingRefresh>,
     stopped: AtomicBool,
     pub publish_filter: Arc<NetworkFilter>,
     pub message_publisher: Arc<Mutex<MessagePublisher>>, // TODO remove this. It is needed right now
@@ -143,6 +159,7 @@ pub(crate) struct NodeArgs {
     pub flags: NodeFlags,
     pub work: Arc<WorkPoolImpl>,
     pub callbacks: NodeCallbacks,
+    pub clock: Arc<SteadyClock>,
 }
 
 impl NodeArgs {
@@ -157,6 +174,7 @@ impl NodeArgs {
             flags: Default::default(),
             callbacks: Default::default(),
             work: Arc::new(WorkPoolImpl::new_null(123)),
+            clock: Arc::new(SteadyClock::default()),
         }
     }
 }
@@ -182,7 +200,7 @@ impl Node {
         let work = args.work;
         // Time relative to the start of the node. This makes time exlicit and enables us to
         // write time relevant unit tests with ease.
-        let steady_clock = Arc::new(SteadyClock::default());
+        let steady_clock = args.clock;
 
         let network_label = network_params.network.get_current_network_as_string();
         let global_config = GlobalConfig {
@@ -291,8 +309,23 @@ impl Node {
 
         dead_channel_cleanup.add_step(NetworkCleanup::new(network.clone()));
 
-        let mut inbound_message_queue =
-            InboundMessageQueue::new(config.message_processor.max_queue, stats.clone());
+        let trusted_peers = Arc::new(TrustedPeers::new(config.trusted_peers.clone()));
+        dead_channel_cleanup.add_step(TrustedPeersCleanup::new(trusted_peers.clone()));
+        let trusted_peers_w = Arc::downgrade(&trusted_peers);
+        network_info
+            .write()
+            .unwrap()
+            .on_new_realtime_channel(Arc::new(move |channel| {
+                if let Some(trusted_peers) = trusted_peers_w.upgrade() {
+                    trusted_peers.channel_added(&channel);
+                }
+            }));
+
+        let mut inbound_message_queue = InboundMessageQueue::new(
+            config.message_processor.max_queue,
+            stats.clone(),
+            trusted_peers.clone(),
+        );
         if let Some(cb) = args.callbacks.on_inbound {
             inbound_message_queue.set_inbound_callback(cb);
         }
@@ -451,9 +484,10 @@ impl Node {
             message_publisher.clone(),
         ));
 
+        let vote_signer = create_vote_signer(&config, &wallets, &ledger);
         let vote_generators = Arc::new(VoteGenerators::new(
             ledger.clone(),
-            wallets.clone(),
+            vote_signer.clone(),
             history.clone(),
             stats.clone(),
             &config,
@@ -511,6 +545,35 @@ impl Node {
             .on_balance_changed
             .unwrap_or_else(|| Box::new(|_, _| {}));
 
+        let fork_journal = Arc::new(ForkJournal::new(
+            config.active_elections.fork_journal_size,
+            SystemTimeFactory::default(),
+        ));
+        let fork_journal_l = fork_journal.clone();
+        let network_info_l = network_info.clone();
+        block_processor.add_batch_processed_observer(Box::new(move |batch| {
+            let forks: Vec<_> = batch
+                .iter()
+                .filter(|(status, _)| *status == BlockStatus::Fork)
+                .map(|(_, context)| (context.block.qualified_root(), context.block.hash()))
+                .collect();
+            let live = batch
+                .iter()
+                .filter(|(_, context)| context.source == BlockSource::Live);
+            if forks.is_empty() && live.clone().next().is_none() {
+                return;
+            }
+            let arrived: Vec<_> = {
+                let network = network_info_l.read().unwrap();
+                live.map(|(_, context)| {
+                    let peer_addr = network.get(context.channel_id).map(|c| c.peer_addr());
+                    (context.block.hash(), context.channel_id, peer_addr)
+                })
+                .collect()
+            };
+            fork_journal_l.blocks_processed(arrived, forks);
+        }));
+
         let active_elections = Arc::new(ActiveElections::new(
             network_params.clone(),
             wallets.clone(),
@@ -533,6 +596,7 @@ impl Node {
             vote_cache_processor.clone(),
             steady_clock.clone(),
             message_publisher.clone(),
+            fork_journal.clone(),
         ));
 
         active_elections.initialize();
@@ -543,6 +607,7 @@ impl Node {
             &active_elections,
             &telemetry,
             &vote_processor,
+            &fork_journal,
         );
 
         let mut bootstrap_publisher = MessagePublisher::new_with_buffer_size(
@@ -603,6 +668,7 @@ impl Node {
             runtime.clone(),
             response_server_spawner.clone(),
             steady_clock.clone(),
+            Arc::new(config.tcp.outbound_stream_factory()),
         ));
 
         let rep_crawler = Arc::new(RepCrawler::new(
@@ -617,6 +683,7 @@ impl Node {
             active_elections.clone(),
             peer_connector.clone(),
             steady_clock.clone(),
+            SystemTimeFactory::default(),
             message_publisher.clone(),
         ));
 
@@ -655,6 +722,7 @@ impl Node {
             vote_generators.clone(),
             ledger.clone(),
             network_info.clone(),
+            trusted_peers.clone(),
         ));
         dead_channel_cleanup.add_step(RequestAggregatorCleanup::new(
             request_aggregator.state.clone(),
@@ -708,6 +776,19 @@ impl Node {
             ascendboot.clone(),
         ));
 
+        let port_mapping = Arc::new(PortMapping::new(
+            config.port_mapping.clone(),
+            &network_params.portmapping,
+            network_info.clone(),
+            stats.clone(),
+            steady_clock.clone(),
+            config.configured_external_address(),
+        ));
+        let port_mapping_refresh = TimerThread::new_run_immedately(
+            "Port mapping",
+            PortMappingRefresh::new(port_mapping.clone()),
+        );
+
         let keepalive_factory = Arc::new(KeepaliveFactory {
             network: network_info.clone(),
             config: config.clone(),
@@ -1034,6 +1115,13 @@ impl Node {
 
         let time_factory = SystemTimeFactory::default();
 
+        let peer_bans = Arc::new(PeerBans::new(
+            network_info.clone(),
+            ledger.clone(),
+            steady_clock.clone(),
+            SystemTimeFactory::default(),
+        ));
+
         let peer_cache_updater = PeerCacheUpdater::new(
             network_info.clone(),
             ledger.clone(),
@@ -1046,6 +1134,20 @@ impl Node {
             },
         );
 
+        let rep_cache_updater = RepresentativeCacheUpdater::new(
+            online_reps.clone(),
+            network_info.clone(),
+            ledger.clone(),
+            steady_clock.clone(),
+            SystemTimeFactory::default(),
+            stats.clone(),
+            if network_params.network.is_dev_network() {
+                Duration::from_secs(10)
+            } else {
+                Duration::from_secs(60 * 60 * 24)
+            },
+        );
+
         let peer_cache_connector = PeerCacheConnector::new(
             ledger.clone(),
             peer_connector.clone(),
@@ -1053,10 +1155,19 @@ impl Node {
             network_params.network.merge_period,
         );
 
+        let trusted_peer_connector = TrustedPeerConnector::new(
+            trusted_peers.clone(),
+            network_info.clone(),
+            peer_connector.clone(),
+            stats.clone(),
+        );
+
         let ledger_pruning = Arc::new(LedgerPruning::new(
             config.clone(),
             flags.clone(),
             ledger.clone(),
+            wallets.clone(),
+            stats.clone(),
             workers.clone(),
         ));
 
@@ -1070,14 +1181,31 @@ impl Node {
             ),
         );
 
+        let mut ledger_backup = LedgerBackup::new(
+            config.ledger_backup.clone(),
+            store.clone(),
+            application_path.join("backup").join("ledger"),
+        );
+        ledger_backup.set_block_processor(block_processor.clone());
+        let ledger_backup = Arc::new(ledger_backup);
+        let ledger_backup_scheduler = TimerThread::new(
+            "Ledger backup",
+            ScheduledLedgerBackup::new(ledger_backup.clone()),
+        );
+
         Self {
             is_nulled,
             steady_clock,
             peer_cache_updater: TimerThread::new("Peer history", peer_cache_updater),
+            rep_cache_updater: TimerThread::new("Rep history", rep_cache_updater),
             peer_cache_connector: TimerThread::new_run_immedately(
                 "Net reachout",
                 peer_cache_connector,
             ),
+            trusted_peer_connector: TimerThread::new_run_immedately(
+                "Trusted reachout",
+                trusted_peer_connector,
+            ),
             ongoing_bootstrap,
             peer_connector,
             node_id,
@@ -1091,6 +1219,8 @@ impl Node {
             syn_cookies,
             network,
             network_info,
+            peer_bans,
+            trusted_peers,
             ledger,
             store,
             stats,
@@ -1111,8 +1241,10 @@ impl Node {
             vote_cache,
             block_processor,
             wallets,
+            vote_signer,
             vote_generators,
             active: active_elections,
+            fork_journal,
             vote_processor,
             vote_cache_processor,
             websocket,
@@ -1130,6 +1262,11 @@ impl Node {
             message_processor,
             inbound_message_queue,
             monitor,
+            ledger_backup,
+            threshold_signing: ThresholdSigningSessions::default(),
+            ledger_backup_scheduler,
+            port_mapping,
+            port_mapping_refresh,
             message_publisher: message_publisher_l,
             publish_filter,
             stopped: AtomicBool::new(false),
@@ -1197,6 +1334,7 @@ impl Node {
                 self.local_block_broadcaster
                     .collect_container_info("local_block_broadcaster"),
                 self.rep_tiers.collect_container_info("rep_tiers"),
+                self.fork_journal.collect_container_info("fork_journal"),
                 self.inbound_message_queue
                     .collect_container_info("message_processor"),
             ],
@@ -1220,6 +1358,7 @@ impl Node {
         if perform_cleanup {
             self.ledger.store.online_weight.clear(&mut tx);
             self.ledger.store.peer.clear(&mut tx);
+            self.ledger.store.representative.clear(&mut tx);
             info!("records of peers and online weight after a long period of inactivity");
         }
     }
@@ -1233,6 +1372,10 @@ impl Node {
             .ledger_pruning(batch_size, bootstrap_weight_reached)
     }
 
+    pub fn pruning_status(&self) -> PruningStatus {
+        self.ledger_pruning.status()
+    }
+
     pub fn process_local(&self, block: BlockEnum) -> Option<BlockStatus> {
         self.block_processor
             .add_blocking(Arc::new(block), BlockSource::Local)
@@ -1345,6 +1488,7 @@ pub trait NodeExt {
     fn ongoing_online_weight_calculation_queue(&self);
     fn ongoing_online_weight_calculation(&self);
     fn backup_wallet(&self);
+    fn refresh_remote_signer_representatives(&self);
     fn search_receivable_all(&self);
     fn bootstrap_wallet(&self);
     fn flood_block_many(
@@ -1376,6 +1520,7 @@ impl NodeExt for Arc<Node> {
         }
 
         self.long_inactivity_cleanup();
+        self.peer_bans.load();
         self.network_threads.lock().unwrap().start();
         self.message_processor.lock().unwrap().start();
 
@@ -1401,7 +1546,15 @@ impl NodeExt for Arc<Node> {
         }
 
         if !self.flags.disable_backup {
-            self.backup_wallet();
+            if self.config.wallet_backup.is_enabled() {
+                self.backup_wallet();
+            } else {
+                warn!("Automatic wallet backups are disabled, because no backup passphrase is configured");
+            }
+        }
+
+        if self.config.remote_signer.enabled && cfg!(unix) {
+            self.refresh_remote_signer_representatives();
         }
 
         if !self.flags.disable_search_pending {
@@ -1453,16 +1606,34 @@ impl NodeExt for Arc<Node> {
             Duration::from_secs(15)
         };
         self.peer_cache_updater.start(peer_cache_update_interval);
+        if !self.flags.disable_rep_crawler {
+            self.rep_cache_updater.start(peer_cache_update_interval);
+        }
 
         if !self.network_params.network.merge_period.is_zero() {
             self.peer_cache_connector
                 .start(self.network_params.network.merge_period);
         }
+        if !self.trusted_peers.peers().is_empty() {
+            self.trusted_peer_connector
+                .start(self.network_params.network.keepalive_period);
+        }
         self.vote_router.start();
 
         if self.config.monitor.enabled {
             self.monitor.start(self.config.monitor.interval);
         }
+
+        if self.config.ledger_backup.enabled {
+            self.ledger_backup_scheduler
+                .start(self.config.ledger_backup.interval);
+        }
+
+        if self.config.port_mapping.enabled {
+            self.port_mapping_refresh.start(Duration::from_secs(
+                self.network_params.portmapping.health_check_period_s as u64,
+            ));
+        }
     }
 
     fn stop(&self) {
@@ -1484,8 +1655,14 @@ impl NodeExt for Arc<Node> {
         self.vote_router.stop();
         self.peer_connector.stop();
         self.ledger_pruning.stop();
+        self.ledger_backup.stop();
+        self.ledger_backup_scheduler.stop();
+        self.port_mapping_refresh.stop();
+        self.port_mapping.stop();
         self.peer_cache_connector.stop();
+        self.trusted_peer_connector.stop();
         self.peer_cache_updater.stop();
+        self.rep_cache_updater.stop();
         // Cancels ongoing work generation tasks, which may be blocking other threads
         // No tasks may wait for work generation in I/O threads, or termination signal capturing will be unable to call node::stop()
         self.distributed_work.stop();
@@ -1534,16 +1711,21 @@ impl NodeExt for Arc<Node> {
     }
 
     fn ongoing_online_weight_calculation(&self) {
-        let online = self.online_reps.lock().unwrap().online_weight();
+        let online = {
+            let mut online_reps = self.online_reps.lock().unwrap();
+            online_reps.trim(self.steady_clock.now());
+            online_reps.online_weight()
+        };
         self.online_weight_sampler.sample(online);
         let trend = self.online_weight_sampler.calculate_trend();
         self.online_reps.lock().unwrap().set_trended(trend);
     }
 
     fn backup_wallet(&self) {
-        let mut backup_path = self.data_path.clone();
-        backup_path.push("backup");
-        if let Err(e) = self.wallets.backup(&backup_path) {
+        let backup_path = self.data_path.join("backup").join("wallets");
+        if let Err(e) =
+            backup_wallets_with_retention(&self.wallets, &backup_path, &self.config.wallet_backup)
+        {
             error!(error = ?e, "Could not create backup of wallets");
         }
 
@@ -1558,6 +1740,24 @@ impl NodeExt for Arc<Node> {
         )
     }
 
+    fn refresh_remote_signer_representatives(&self) {
+        match self.vote_signer.reload_representatives() {
+            Ok(representatives) => self.wallets.set_external_representatives(representatives),
+            // The representatives of the last successful request are kept
+            Err(e) => warn!(error = ?e, "Could not refresh remote signer representatives"),
+        }
+
+        let node_w = Arc::downgrade(self);
+        self.workers.add_delayed_task(
+            self.config.remote_signer.refresh_interval,
+            Box::new(move || {
+                if let Some(node) = node_w.upgrade() {
+                    node.refresh_remote_signer_representatives();
+                }
+            }),
+        )
+    }
+
     fn search_receivable_all(&self) {
         // Reload wallets from disk
         self.wallets.reload();
@@ -1645,6 +1845,44 @@ fn make_store(
     Ok(Arc::new(store))
 }
 
+fn create_vote_signer(
+    config: &NodeConfig,
+    wallets: &Arc<Wallets>,
+    ledger: &Arc<Ledger>,
+) -> Arc<dyn VoteSigner> {
+    if config.remote_signer.enabled {
+        #[cfg(unix)]
+        {
+            let signer = crate::signing::RemoteSigner::new(
+                &config.remote_signer,
+                Arc::clone(ledger),
+                config.vote_minimum,
+            );
+            match signer.fetch_representatives() {
+                Ok(representatives) => {
+                    info!(
+                        "Using remote signer at {:?} for {} representative(s)",
+                        config.remote_signer.socket_path,
+                        representatives.len()
+                    );
+                    wallets.set_external_representatives(representatives);
+                }
+                Err(e) => warn!(
+                    "Remote signer is not available, node won't vote until it is: {:?}",
+                    e
+                ),
+            }
+            return Arc::new(signer);
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = ledger;
+            warn!("Remote signer is only supported on Unix, using wallet keys instead");
+        }
+    }
+    Arc::new(WalletVoteSigner::new(Arc::clone(wallets)))
+}
+
 #[derive(Serialize)]
 struct RpcCallbackMessage {
     account: String,
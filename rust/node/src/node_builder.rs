@@ -12,6 +12,7 @@ use rsnano_core::{
 };
 use rsnano_messages::Message;
 use rsnano_network::ChannelId;
+use rsnano_nullable_clock::SteadyClock;
 use std::{path::PathBuf, sync::Arc, time::Duration};
 
 #[derive(Default)]
@@ -102,6 +103,7 @@ pub struct NodeBuilder {
     flags: Option<NodeFlags>,
     work: Option<Arc<WorkPoolImpl>>,
     callbacks: Option<NodeCallbacks>,
+    clock: Option<Arc<SteadyClock>>,
 }
 
 impl NodeBuilder {
@@ -115,6 +117,7 @@ impl NodeBuilder {
             flags: None,
             work: None,
             callbacks: None,
+            clock: None,
         }
     }
 
@@ -153,6 +156,12 @@ impl NodeBuilder {
         self
     }
 
+    /// Lets several nodes share a clock, for example a nulled clock that is advanced by a simulation
+    pub fn clock(mut self, clock: Arc<SteadyClock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
     pub fn finish(self) -> anyhow::Result<Node> {
         let runtime = self
             .runtime
@@ -187,6 +196,7 @@ impl NodeBuilder {
         });
 
         let callbacks = self.callbacks.unwrap_or_default();
+        let clock = self.clock.unwrap_or_default();
 
         let args = NodeArgs {
             runtime,
@@ -196,6 +206,7 @@ impl NodeBuilder {
             flags,
             work,
             callbacks,
+            clock,
         };
 
         Ok(Node::new_with_args(args))
@@ -1,26 +1,49 @@
 use crate::{
     config::{NodeConfig, NodeFlags},
+    stats::{DetailType, StatType, Stats},
     utils::ThreadPool,
+    wallets::Wallets,
 };
-use rsnano_core::{Account, BlockHash};
+use rsnano_core::{utils::seconds_since_epoch, Account, BlockHash};
 use rsnano_ledger::{Ledger, Writer};
 use rsnano_store_lmdb::Transaction;
 use std::{
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tracing::debug;
 
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct PruningStatus {
+    /// Number of finished pruning runs since the node started
+    pub runs: u64,
+    /// Unix timestamp of the last finished run
+    pub last_run: u64,
+    pub last_run_pruned: u64,
+    /// Number of accounts that were skipped in the last run because of the keep-lists
+    pub last_run_kept_accounts: u64,
+    pub total_pruned: u64,
+}
+
+/// Accounts that are excluded from pruning
+pub struct PruningKeepList {
+    accounts: HashSet<Account>,
+    kept_accounts: u64,
+}
+
 pub struct LedgerPruning {
     config: NodeConfig,
     flags: NodeFlags,
     ledger: Arc<Ledger>,
+    wallets: Arc<Wallets>,
+    stats: Arc<Stats>,
     stopped: AtomicBool,
     workers: Arc<dyn ThreadPool>,
+    status: Mutex<PruningStatus>,
 }
 
 impl LedgerPruning {
@@ -28,14 +51,19 @@ impl LedgerPruning {
         config: NodeConfig,
         flags: NodeFlags,
         ledger: Arc<Ledger>,
+        wallets: Arc<Wallets>,
+        stats: Arc<Stats>,
         workers: Arc<dyn ThreadPool>,
     ) -> Self {
         Self {
             config,
             flags,
             ledger,
+            wallets,
+            stats,
             workers,
             stopped: AtomicBool::new(false),
+            status: Mutex::new(PruningStatus::default()),
         }
     }
 
@@ -43,6 +71,29 @@ impl LedgerPruning {
         self.stopped.store(true, Ordering::SeqCst);
     }
 
+    pub fn status(&self) -> PruningStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Accounts whose blocks must not be pruned
+    fn keep_list(&self) -> PruningKeepList {
+        let mut keep: HashSet<Account> =
+            self.config.pruning_keep_accounts.iter().cloned().collect();
+        if self.config.pruning_keep_wallet_accounts {
+            keep.extend(self.wallets.get_accounts(usize::MAX));
+        }
+        PruningKeepList {
+            accounts: keep,
+            kept_accounts: 0,
+        }
+    }
+
+    fn should_keep(&self, keep_list: &PruningKeepList, account: &Account) -> bool {
+        keep_list.accounts.contains(account)
+            || (self.config.pruning_keep_representatives
+                && !self.ledger.weight(&account.into()).is_zero())
+    }
+
     pub fn ledger_pruning(&self, batch_size_a: u64, bootstrap_weight_reached_a: bool) {
         let max_depth = if self.config.max_pruning_depth != 0 {
             self.config.max_pruning_depth
@@ -57,6 +108,7 @@ impl LedgerPruning {
         } else {
             u64::MAX
         };
+        let mut keep_list = self.keep_list();
         let mut pruned_count = 0;
         let mut transaction_write_count = 0;
         let mut last_account = Account::from(1); // 0 Burn account is never opened. So it can be used to break loop
@@ -76,6 +128,7 @@ impl LedgerPruning {
                     batch_size_a * 2,
                     max_depth,
                     cutoff_time,
+                    &mut keep_list,
                 );
             }
             // Pruning write operation
@@ -101,6 +154,22 @@ impl LedgerPruning {
         }
 
         debug!("Total recently pruned block count: {}", pruned_count);
+
+        self.stats.inc(StatType::Pruning, DetailType::Loop);
+        self.stats
+            .add(StatType::Pruning, DetailType::Pruned, pruned_count);
+        self.stats.add(
+            StatType::Pruning,
+            DetailType::KeptAccount,
+            keep_list.kept_accounts,
+        );
+
+        let mut status = self.status.lock().unwrap();
+        status.runs += 1;
+        status.last_run = seconds_since_epoch();
+        status.last_run_pruned = pruned_count;
+        status.last_run_kept_accounts = keep_list.kept_accounts;
+        status.total_pruned += pruned_count;
     }
 
     pub fn collect_ledger_pruning_targets(
@@ -110,6 +179,7 @@ impl LedgerPruning {
         batch_read_size_a: u64,
         max_depth_a: u64,
         cutoff_time_a: u64,
+        keep_list: &mut PruningKeepList,
     ) -> bool {
         let mut read_operations = 0;
         let mut finish_transaction = false;
@@ -124,34 +194,38 @@ impl LedgerPruning {
                 break;
             }
             read_operations += 1;
-            let mut hash = info.frontier;
-            let mut depth = 0;
-            while !hash.is_zero() && depth < max_depth_a {
-                if let Some(block) = self.ledger.any().get_block(&tx, &hash) {
-                    if block.sideband().unwrap().timestamp > cutoff_time_a || depth == 0 {
-                        hash = block.previous();
+            if self.should_keep(keep_list, &account) {
+                keep_list.kept_accounts += 1;
+            } else {
+                let mut hash = info.frontier;
+                let mut depth = 0;
+                while !hash.is_zero() && depth < max_depth_a {
+                    if let Some(block) = self.ledger.any().get_block(&tx, &hash) {
+                        if block.sideband().unwrap().timestamp > cutoff_time_a || depth == 0 {
+                            hash = block.previous();
+                        } else {
+                            break;
+                        }
                     } else {
-                        break;
+                        assert!(depth != 0);
+                        hash = BlockHash::zero();
+                    }
+                    depth += 1;
+                    if depth % batch_read_size_a == 0 {
+                        drop(it);
+                        tx.refresh();
+                        it = self
+                            .ledger
+                            .store
+                            .confirmation_height
+                            .begin_at_account(&tx, &account);
                     }
-                } else {
-                    assert!(depth != 0);
-                    hash = BlockHash::zero();
                 }
-                depth += 1;
-                if depth % batch_read_size_a == 0 {
-                    drop(it);
-                    tx.refresh();
-                    it = self
-                        .ledger
-                        .store
-                        .confirmation_height
-                        .begin_at_account(&tx, &account);
+                if !hash.is_zero() {
+                    pruning_targets_a.push_back(hash);
                 }
+                read_operations += depth;
             }
-            if !hash.is_zero() {
-                pruning_targets_a.push_back(hash);
-            }
-            read_operations += depth;
             if read_operations >= batch_read_size_a {
                 *last_account_a = account.inc().unwrap_or_default();
                 finish_transaction = true;
@@ -140,6 +140,15 @@ impl OnlineReps {
             .map(|rep| rep.last_request.elapsed(now))
     }
 
+    /// Time of the last vote that was observed from the given representative
+    pub fn last_vote(&self, rep: &PublicKey) -> Option<Timestamp> {
+        self.online_reps.last_vote(rep)
+    }
+
+    pub fn weight_period(&self) -> Duration {
+        self.weight_period
+    }
+
     /// List of online representatives, both the currently sampling ones and the ones observed in the previous sampling period
     pub fn online_reps(&self) -> impl Iterator<Item = &PublicKey> {
         self.online_reps.iter()
@@ -177,9 +186,7 @@ impl OnlineReps {
     pub fn vote_observed(&mut self, rep_account: PublicKey, now: Timestamp) {
         if self.rep_weights.weight(&rep_account) > Amount::zero() {
             let new_insert = self.online_reps.insert(rep_account, now);
-            let trimmed = self
-                .online_reps
-                .trim(now.checked_sub(self.weight_period).unwrap_or_default());
+            let trimmed = self.trim_online_reps(now);
 
             if new_insert || trimmed {
                 self.calculate_online_weight();
@@ -187,6 +194,20 @@ impl OnlineReps {
         }
     }
 
+    /// Removes the representatives that didn't vote within the weight period.
+    /// This must be called periodically, because representatives restored from
+    /// the last run would stay online forever if no votes arrive.
+    pub fn trim(&mut self, now: Timestamp) {
+        if self.trim_online_reps(now) {
+            self.calculate_online_weight();
+        }
+    }
+
+    fn trim_online_reps(&mut self, now: Timestamp) -> bool {
+        self.online_reps
+            .trim(now.checked_sub(self.weight_period).unwrap_or_default())
+    }
+
     fn calculate_online_weight(&mut self) {
         let mut current = Amount::zero();
         for account in self.online_reps.iter() {
@@ -283,6 +304,24 @@ mod tests {
         assert_eq!(online_reps.peered_weight(), Amount::zero(), "peered");
     }
 
+    #[test]
+    fn trim_reps_without_recent_votes() {
+        let clock = SteadyClock::new_null();
+        let account = PublicKey::from(1);
+        let weights = Arc::new(RepWeightCache::new());
+        weights.set(account, Amount::nano(100_000));
+        let mut online_reps = OnlineReps::builder().rep_weights(weights).finish();
+        let now = clock.now();
+        online_reps.vote_observed(account, now);
+
+        online_reps.trim(now + online_reps.weight_period());
+        assert_eq!(online_reps.online_weight(), Amount::nano(100_000));
+
+        online_reps.trim(now + online_reps.weight_period() + Duration::from_millis(1));
+        assert_eq!(online_reps.online_weight(), Amount::zero());
+        assert_eq!(online_reps.last_vote(&account), None);
+    }
+
     #[test]
     fn observe_direct_vote() {
         let clock = SteadyClock::new_null();
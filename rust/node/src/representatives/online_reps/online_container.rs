@@ -65,6 +65,10 @@ impl OnlineContainer {
         trimmed
     }
 
+    pub fn last_vote(&self, rep: &PublicKey) -> Option<Timestamp> {
+        self.by_account.get(rep).copied()
+    }
+
     pub fn len(&self) -> usize {
         self.by_account.len()
     }
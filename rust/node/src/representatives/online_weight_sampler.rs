@@ -18,15 +18,21 @@ impl OnlineWeightSampler {
     }
 
     pub fn calculate_trend(&self) -> Amount {
-        self.medium_weight(self.load_samples())
+        self.medium_weight(
+            self.samples()
+                .into_iter()
+                .map(|(_, weight)| weight)
+                .collect(),
+        )
     }
 
-    fn load_samples(&self) -> Vec<Amount> {
+    /// The stored samples, oldest first. The timestamp is in nanoseconds since the epoch
+    pub fn samples(&self) -> Vec<(u64, Amount)> {
         let txn = self.ledger.read_txn();
-        let mut items = Vec::with_capacity(self.max_samples as usize + 1);
+        let mut items = Vec::with_capacity(self.max_samples + 1);
         let mut it = self.ledger.store.online_weight.begin(&txn);
-        while !it.is_end() {
-            items.push(*it.current().unwrap().1);
+        while let Some((&timestamp, &weight)) = it.current() {
+            items.push((timestamp, weight));
             it.next();
         }
         items
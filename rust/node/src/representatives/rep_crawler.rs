@@ -1,4 +1,4 @@
-use super::{InsertResult, OnlineReps};
+use super::{representative_cache::warm_start_online_reps, InsertResult, OnlineReps};
 use crate::{
     config::NodeConfig,
     consensus::ActiveElections,
@@ -17,10 +17,11 @@ use rsnano_network::{
     utils::into_ipv6_socket_address, ChannelId, ChannelInfo, DropPolicy, NetworkInfo,
     PeerConnector, TrafficType,
 };
-use rsnano_nullable_clock::{SteadyClock, Timestamp};
+use rsnano_nullable_clock::{SteadyClock, SystemTimeFactory, Timestamp};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     mem::size_of,
+    net::SocketAddrV6,
     ops::DerefMut,
     sync::{Arc, Condvar, Mutex, MutexGuard, RwLock},
     thread::JoinHandle,
@@ -44,6 +45,7 @@ pub struct RepCrawler {
     active: Arc<ActiveElections>,
     thread: Mutex<Option<JoinHandle<()>>>,
     steady_clock: Arc<SteadyClock>,
+    time_factory: SystemTimeFactory,
     message_publisher: Arc<Mutex<MessagePublisher>>,
 }
 
@@ -62,6 +64,7 @@ impl RepCrawler {
         active: Arc<ActiveElections>,
         peer_connector: Arc<PeerConnector>,
         steady_clock: Arc<SteadyClock>,
+        time_factory: SystemTimeFactory,
         message_publisher: MessagePublisher,
     ) -> Self {
         let is_dev_network = network_params.network.is_dev_network();
@@ -78,6 +81,7 @@ impl RepCrawler {
             thread: Mutex::new(None),
             peer_connector,
             steady_clock,
+            time_factory,
             message_publisher: Arc::new(Mutex::new(message_publisher)),
             rep_crawler_impl: Mutex::new(RepCrawlerImpl {
                 is_dev_network,
@@ -88,10 +92,53 @@ impl RepCrawler {
                 stopped: false,
                 last_query: None,
                 responses: BoundedVecDeque::new(Self::MAX_RESPONSES),
+                warm_start_endpoints: Vec::new(),
             }),
         }
     }
 
+    /// Loads the representatives that were persisted during the last run. Recently seen
+    /// representatives count as online right away and their nodes get queried first
+    pub fn warm_start(&self) {
+        let stored: Vec<_> = {
+            let tx = self.ledger.read_txn();
+            self.ledger.store.representative.iter(&tx).collect()
+        };
+        if stored.is_empty() {
+            return;
+        }
+
+        let endpoints = warm_start_online_reps(
+            stored,
+            &mut self.online_reps.lock().unwrap(),
+            self.time_factory.now(),
+            self.steady_clock.now(),
+        );
+        info!(
+            "Warm starting rep crawler with {} known representative endpoints",
+            endpoints.len()
+        );
+        self.stats.add(
+            StatType::RepHistory,
+            DetailType::WarmStarted,
+            endpoints.len() as u64,
+        );
+
+        for endpoint in &endpoints {
+            let connected = self
+                .network_info
+                .read()
+                .unwrap()
+                .find_realtime_channel_by_peering_addr(endpoint)
+                .is_some();
+            if !connected {
+                self.peer_connector.connect_to(*endpoint);
+            }
+        }
+
+        self.rep_crawler_impl.lock().unwrap().warm_start_endpoints = endpoints;
+    }
+
     pub fn stop(&self) {
         {
             let mut guard = self.rep_crawler_impl.lock().unwrap();
@@ -216,7 +263,8 @@ impl RepCrawler {
             let current_total_weight;
             let sufficient_weight;
             {
-                let reps = self.online_reps.lock().unwrap();
+                let mut reps = self.online_reps.lock().unwrap();
+                reps.trim(self.steady_clock.now());
                 current_total_weight = reps.peered_weight();
                 sufficient_weight = current_total_weight > reps.quorum_delta();
             }
@@ -268,13 +316,19 @@ impl RepCrawler {
                 };
 
                 /* include channels with ephemeral remote ports */
-                let random_peers = self
+                let mut random_peers = self
                     .network_info
                     .read()
                     .unwrap()
                     .random_realtime_channels(required_peer_count, 0);
 
                 guard = self.rep_crawler_impl.lock().unwrap();
+                if sufficient_weight {
+                    guard.warm_start_endpoints.clear();
+                } else {
+                    // Query the nodes of representatives known from the last run first
+                    random_peers = self.prioritize_known_reps(&guard, random_peers);
+                }
                 let targets = guard.prepare_crawl_targets(
                     sufficient_weight,
                     random_peers,
@@ -287,6 +341,34 @@ impl RepCrawler {
         }
     }
 
+    fn prioritize_known_reps(
+        &self,
+        guard: &RepCrawlerImpl,
+        random_peers: Vec<Arc<ChannelInfo>>,
+    ) -> Vec<Arc<ChannelInfo>> {
+        if guard.warm_start_endpoints.is_empty() {
+            return random_peers;
+        }
+
+        let network_info = self.network_info.read().unwrap();
+        let mut result: Vec<Arc<ChannelInfo>> = guard
+            .warm_start_endpoints
+            .iter()
+            .filter_map(|endpoint| {
+                let channel_id = network_info.find_realtime_channel_by_peering_addr(endpoint)?;
+                network_info.get(channel_id).cloned()
+            })
+            .collect();
+
+        let known: HashSet<ChannelId> = result.iter().map(|c| c.channel_id()).collect();
+        result.extend(
+            random_peers
+                .into_iter()
+                .filter(|c| !known.contains(&c.channel_id())),
+        );
+        result
+    }
+
     fn validate_and_process<'a>(&self, mut guard: MutexGuard<RepCrawlerImpl>) {
         let mut responses = BoundedVecDeque::new(Self::MAX_RESPONSES);
         std::mem::swap(&mut guard.responses, &mut responses);
@@ -482,6 +564,8 @@ struct RepCrawlerImpl {
     last_query: Option<Instant>,
     responses: BoundedVecDeque<(ChannelId, Arc<Vote>)>,
     is_dev_network: bool,
+    /// Peering addresses of representatives that were persisted during the last run
+    warm_start_endpoints: Vec<SocketAddrV6>,
 }
 
 impl RepCrawlerImpl {
@@ -710,6 +794,7 @@ pub trait RepCrawlerExt {
 impl RepCrawlerExt for Arc<RepCrawler> {
     fn start(&self) {
         debug_assert!(self.thread.lock().unwrap().is_none());
+        self.warm_start();
         let self_l = Arc::clone(self);
         *self.thread.lock().unwrap() = Some(
             std::thread::Builder::new()
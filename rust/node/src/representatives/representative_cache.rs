@@ -0,0 +1,332 @@
+use super::OnlineReps;
+use crate::{
+    stats::{DetailType, StatType, Stats},
+    utils::{CancellationToken, Runnable},
+};
+use rsnano_core::PublicKey;
+use rsnano_ledger::Ledger;
+use rsnano_network::NetworkInfo;
+use rsnano_nullable_clock::{SteadyClock, SystemTimeFactory, Timestamp};
+use rsnano_store_lmdb::{LmdbWriteTransaction, StoredRepresentative};
+use std::{
+    cmp::Reverse,
+    net::SocketAddrV6,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
+};
+use tracing::debug;
+
+/// Writes the representatives that were found by the rep crawler to the database,
+/// so that they are known right away when the node is restarted
+pub struct RepresentativeCacheUpdater {
+    online_reps: Arc<Mutex<OnlineReps>>,
+    network_info: Arc<RwLock<NetworkInfo>>,
+    ledger: Arc<Ledger>,
+    steady_clock: Arc<SteadyClock>,
+    time_factory: SystemTimeFactory,
+    stats: Arc<Stats>,
+    erase_cutoff: Duration,
+}
+
+impl RepresentativeCacheUpdater {
+    pub fn new(
+        online_reps: Arc<Mutex<OnlineReps>>,
+        network_info: Arc<RwLock<NetworkInfo>>,
+        ledger: Arc<Ledger>,
+        steady_clock: Arc<SteadyClock>,
+        time_factory: SystemTimeFactory,
+        stats: Arc<Stats>,
+        erase_cutoff: Duration,
+    ) -> Self {
+        Self {
+            online_reps,
+            network_info,
+            ledger,
+            steady_clock,
+            time_factory,
+            stats,
+            erase_cutoff,
+        }
+    }
+
+    fn save_reps(&self, tx: &mut LmdbWriteTransaction) {
+        for (account, rep) in self.current_reps() {
+            let exists = self.ledger.store.representative.exists(tx, &account);
+            self.ledger.store.representative.put(tx, &account, &rep);
+
+            if !exists {
+                self.stats.inc(StatType::RepHistory, DetailType::Inserted);
+                debug!(
+                    "Saved new representative: {} at {}",
+                    account.as_account().encode_account(),
+                    rep.endpoint
+                );
+            } else {
+                self.stats.inc(StatType::RepHistory, DetailType::Updated);
+            }
+        }
+    }
+
+    fn current_reps(&self) -> Vec<(PublicKey, StoredRepresentative)> {
+        let now = self.time_factory.now();
+        let steady_now = self.steady_clock.now();
+        let online_reps = self.online_reps.lock().unwrap();
+        let network_info = self.network_info.read().unwrap();
+
+        online_reps
+            .peered_reps()
+            .iter()
+            .filter_map(|peered| {
+                let channel = network_info.get(peered.channel_id)?;
+                let last_vote = online_reps.last_vote(&peered.account)?;
+                Some((
+                    peered.account,
+                    StoredRepresentative {
+                        node_id: channel.node_id()?,
+                        endpoint: channel.peering_addr()?,
+                        last_vote: now - last_vote.elapsed(steady_now),
+                        weight: self.ledger.weight(&peered.account),
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    fn delete_old_reps(&self, tx: &mut LmdbWriteTransaction) {
+        let now = self.time_factory.now();
+        let cutoff = now - self.erase_cutoff;
+        let old_reps: Vec<_> = self
+            .ledger
+            .store
+            .representative
+            .iter(tx)
+            .filter(|(_, rep)| rep.last_vote < cutoff || rep.last_vote > now)
+            .map(|(account, _)| account)
+            .collect();
+
+        for account in old_reps {
+            self.ledger.store.representative.del(tx, &account);
+            self.stats.inc(StatType::RepHistory, DetailType::Erased);
+        }
+    }
+}
+
+impl Runnable for RepresentativeCacheUpdater {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.stats.inc(StatType::RepHistory, DetailType::Loop);
+        let mut tx = self.ledger.rw_txn();
+        self.save_reps(&mut tx);
+        self.delete_old_reps(&mut tx);
+    }
+}
+
+/// Restores the representatives of the last run. Representatives that voted within
+/// the weight period are marked as online again. Returns the endpoints of all
+/// stored representatives, heaviest first.
+pub(crate) fn warm_start_online_reps(
+    stored: impl IntoIterator<Item = (PublicKey, StoredRepresentative)>,
+    online_reps: &mut OnlineReps,
+    now: SystemTime,
+    steady_now: Timestamp,
+) -> Vec<SocketAddrV6> {
+    let mut stored: Vec<_> = stored.into_iter().collect();
+    stored.sort_by_key(|(_, rep)| Reverse(rep.weight));
+
+    for (account, rep) in &stored {
+        let Ok(age) = now.duration_since(rep.last_vote) else {
+            continue;
+        };
+        if age >= online_reps.weight_period() {
+            continue;
+        }
+        if let Some(last_vote) = steady_now.checked_sub(age) {
+            online_reps.vote_observed(*account, last_vote);
+        }
+    }
+
+    let mut endpoints: Vec<SocketAddrV6> = Vec::with_capacity(stored.len());
+    for (_, rep) in stored {
+        if !endpoints.contains(&rep.endpoint) {
+            endpoints.push(rep.endpoint);
+        }
+    }
+    endpoints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::Direction;
+    use rsnano_core::{
+        utils::{new_test_timestamp, NULL_ENDPOINT, TEST_ENDPOINT_1, TEST_ENDPOINT_2},
+        Amount,
+    };
+    use rsnano_ledger::RepWeightCache;
+    use rsnano_network::{ChannelDirection, ChannelMode};
+
+    #[test]
+    fn save_peered_rep() {
+        let now = new_test_timestamp();
+        let steady_clock = Arc::new(SteadyClock::new_null());
+        let account = PublicKey::from(1);
+        let node_id = PublicKey::from(2);
+
+        let mut network = NetworkInfo::new_test_instance();
+        let channel = network
+            .add(
+                NULL_ENDPOINT,
+                TEST_ENDPOINT_1,
+                ChannelDirection::Outbound,
+                ChannelMode::Realtime,
+                steady_clock.now(),
+            )
+            .unwrap();
+        channel.set_node_id(node_id);
+        channel.set_peering_addr(TEST_ENDPOINT_1);
+
+        let weights = Arc::new(RepWeightCache::new());
+        weights.set(account, Amount::nano(100_000));
+        let mut online_reps = OnlineReps::builder().rep_weights(weights).finish();
+        online_reps.vote_observed_directly(account, channel.channel_id(), steady_clock.now());
+        steady_clock.advance(Duration::from_secs(3));
+
+        let ledger = Arc::new(Ledger::new_null_builder().finish());
+        let put_tracker = ledger.store.representative.track_puts();
+        let stats = Arc::new(Stats::default());
+        let mut updater = RepresentativeCacheUpdater::new(
+            Arc::new(Mutex::new(online_reps)),
+            Arc::new(RwLock::new(network)),
+            ledger.clone(),
+            steady_clock,
+            SystemTimeFactory::new_null_with(now),
+            stats.clone(),
+            Duration::from_secs(60 * 60),
+        );
+
+        updater.run(&CancellationToken::new());
+
+        let written = put_tracker.output();
+        assert_eq!(written.len(), 1);
+        assert_eq!(written[0].0, account);
+        assert_eq!(
+            written[0].1,
+            StoredRepresentative {
+                node_id,
+                endpoint: TEST_ENDPOINT_1,
+                last_vote: now - Duration::from_secs(3),
+                weight: Amount::zero(),
+            }
+        );
+        assert_eq!(
+            stats.count(StatType::RepHistory, DetailType::Inserted, Direction::In),
+            1
+        );
+    }
+
+    #[test]
+    fn erase_entries_older_than_cutoff() {
+        let now = new_test_timestamp();
+        let account = PublicKey::from(1);
+        let ledger = Arc::new(
+            Ledger::new_null_builder()
+                .representatives([(
+                    account,
+                    stored_rep(TEST_ENDPOINT_1, now - Duration::from_secs(60 * 61), 1),
+                )])
+                .finish(),
+        );
+        let delete_tracker = ledger.store.representative.track_deletions();
+        let mut updater = RepresentativeCacheUpdater::new(
+            Arc::new(Mutex::new(OnlineReps::default())),
+            Arc::new(RwLock::new(NetworkInfo::new_test_instance())),
+            ledger.clone(),
+            Arc::new(SteadyClock::new_null()),
+            SystemTimeFactory::new_null_with(now),
+            Arc::new(Stats::default()),
+            Duration::from_secs(60 * 60),
+        );
+
+        updater.run(&CancellationToken::new());
+
+        assert_eq!(delete_tracker.output(), vec![account]);
+    }
+
+    #[test]
+    fn warm_start_marks_recent_reps_online() {
+        let now = new_test_timestamp();
+        let steady_now = Timestamp::new_test_instance();
+        let weights = Arc::new(RepWeightCache::new());
+        let recent = PublicKey::from(1);
+        let old = PublicKey::from(2);
+        weights.set(recent, Amount::nano(100_000));
+        weights.set(old, Amount::nano(200_000));
+        let mut online_reps = OnlineReps::builder()
+            .rep_weights(weights)
+            .weight_period(Duration::from_secs(60))
+            .finish();
+
+        let endpoints = warm_start_online_reps(
+            [
+                (
+                    recent,
+                    stored_rep(TEST_ENDPOINT_1, now - Duration::from_secs(10), 1),
+                ),
+                (
+                    old,
+                    stored_rep(TEST_ENDPOINT_2, now - Duration::from_secs(120), 2),
+                ),
+            ],
+            &mut online_reps,
+            now,
+            steady_now,
+        );
+
+        assert_eq!(online_reps.online_weight(), Amount::nano(100_000));
+        assert_eq!(
+            online_reps.last_vote(&recent),
+            steady_now.checked_sub(Duration::from_secs(10))
+        );
+        assert_eq!(online_reps.last_vote(&old), None);
+        // heaviest representative first
+        assert_eq!(endpoints, vec![TEST_ENDPOINT_2, TEST_ENDPOINT_1]);
+    }
+
+    #[test]
+    fn warm_started_reps_expire_without_votes() {
+        let now = new_test_timestamp();
+        let steady_now = Timestamp::new_test_instance();
+        let weights = Arc::new(RepWeightCache::new());
+        let rep = PublicKey::from(1);
+        weights.set(rep, Amount::nano(100_000));
+        let mut online_reps = OnlineReps::builder()
+            .rep_weights(weights)
+            .weight_period(Duration::from_secs(60))
+            .finish();
+        warm_start_online_reps(
+            [(
+                rep,
+                stored_rep(TEST_ENDPOINT_1, now - Duration::from_secs(10), 1),
+            )],
+            &mut online_reps,
+            now,
+            steady_now,
+        );
+
+        online_reps.trim(steady_now + Duration::from_secs(51));
+
+        assert_eq!(online_reps.online_weight(), Amount::zero());
+    }
+
+    fn stored_rep(
+        endpoint: SocketAddrV6,
+        last_vote: SystemTime,
+        weight: u128,
+    ) -> StoredRepresentative {
+        StoredRepresentative {
+            node_id: PublicKey::from(42),
+            endpoint,
+            last_vote,
+            weight: Amount::nano(weight),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(unix)]
+mod remote_signer;
+mod remote_signer_config;
+mod signer_protocol;
+mod signer_service;
+mod slashing_protection;
+mod threshold_sessions;
+mod wallet_vote_signer;
+
+#[cfg(unix)]
+pub use remote_signer::*;
+pub use remote_signer_config::*;
+pub use signer_protocol::*;
+pub use signer_service::*;
+pub use slashing_protection::*;
+pub use threshold_sessions::*;
+pub use wallet_vote_signer::*;
+
+use rsnano_core::{BlockHash, PublicKey, Vote};
+
+/// Creates the votes of the representatives that this node votes for
+pub trait VoteSigner: Send + Sync {
+    /// Returns one signed vote per representative.
+    /// Representatives whose vote can't be signed are left out
+    fn sign_votes(&self, timestamp: u64, duration: u8, hashes: &[BlockHash]) -> Vec<Vote>;
+
+    /// Asks the key store again which representatives it holds the keys for.
+    /// Signers that use the wallet keys have nothing to reload
+    fn reload_representatives(&self) -> anyhow::Result<Vec<PublicKey>> {
+        Ok(Vec::new())
+    }
+}
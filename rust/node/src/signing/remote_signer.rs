@@ -0,0 +1,363 @@
+use super::{
+    read_signer_message, write_signer_message, RemoteSignerConfig, SignerRequest, SignerResponse,
+    VoteSigner,
+};
+use rsnano_core::{Amount, BlockEnum, BlockHash, PublicKey, Signature, Vote};
+use rsnano_ledger::Ledger;
+use std::{
+    io::BufReader,
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing::warn;
+
+/// Lets a separate signer process sign votes and blocks, so that the
+/// representative keys don't have to be stored in the node
+pub struct RemoteSigner {
+    socket_path: PathBuf,
+    timeout: Duration,
+    connection: Mutex<Option<Connection>>,
+    representatives: Mutex<Vec<PublicKey>>,
+    /// Provides the rep weights and the blocks of final votes
+    ledger: Arc<Ledger>,
+    /// Representatives with less weight don't vote, just like the representatives in the wallets
+    vote_minimum: Amount,
+}
+
+struct Connection {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl RemoteSigner {
+    pub fn new(config: &RemoteSignerConfig, ledger: Arc<Ledger>, vote_minimum: Amount) -> Self {
+        Self {
+            socket_path: config.socket_path.clone(),
+            timeout: config.timeout,
+            connection: Mutex::new(None),
+            representatives: Mutex::new(Vec::new()),
+            ledger,
+            vote_minimum,
+        }
+    }
+
+    /// Asks the signer which representatives it holds the keys for. Only these
+    /// representatives are used for voting
+    pub fn fetch_representatives(&self) -> anyhow::Result<Vec<PublicKey>> {
+        match self.request(&SignerRequest::Representatives)? {
+            SignerResponse::Representatives { representatives } => {
+                *self.representatives.lock().unwrap() = representatives.clone();
+                Ok(representatives)
+            }
+            response => Err(unexpected_response(response)),
+        }
+    }
+
+    pub fn representatives(&self) -> Vec<PublicKey> {
+        self.representatives.lock().unwrap().clone()
+    }
+
+    pub fn sign_block(&self, account: PublicKey, block: &BlockEnum) -> anyhow::Result<Signature> {
+        let request = SignerRequest::SignBlock {
+            account,
+            block: Box::new(block.clone()),
+        };
+        match self.request(&request)? {
+            SignerResponse::Signature { signature } => {
+                rsnano_core::validate_message(&account, block.hash().as_bytes(), &signature)
+                    .map_err(|_| anyhow!("signer returned an invalid signature"))?;
+                Ok(signature)
+            }
+            response => Err(unexpected_response(response)),
+        }
+    }
+
+    fn has_voting_weight(&self, representative: &PublicKey) -> bool {
+        let weight = self.ledger.rep_weights.weight(representative);
+        !weight.is_zero() && weight >= self.vote_minimum
+    }
+
+    fn sign_vote(
+        &self,
+        representative: PublicKey,
+        timestamp: u64,
+        duration: u8,
+        hashes: &[BlockHash],
+        blocks: &[BlockEnum],
+    ) -> anyhow::Result<Vote> {
+        let request = SignerRequest::SignVote {
+            representative,
+            timestamp,
+            duration,
+            hashes: hashes.to_vec(),
+            blocks: blocks.to_vec(),
+        };
+        let SignerResponse::Signature { signature } = self.request(&request)? else {
+            bail!("signer refused to sign");
+        };
+
+        let mut vote = Vote::new_unsigned(representative, timestamp, duration, hashes.to_vec());
+        vote.signature = signature;
+        vote.validate()
+            .map_err(|_| anyhow!("signer returned an invalid signature"))?;
+        Ok(vote)
+    }
+
+    /// The signer derives the roots of a final vote from its blocks.
+    /// Non-final votes don't need the blocks
+    fn final_vote_blocks(
+        &self,
+        timestamp: u64,
+        duration: u8,
+        hashes: &[BlockHash],
+    ) -> anyhow::Result<Vec<BlockEnum>> {
+        if !Vote::new_unsigned(PublicKey::zero(), timestamp, duration, Vec::new()).is_final() {
+            return Ok(Vec::new());
+        }
+        let tx = self.ledger.read_txn();
+        hashes
+            .iter()
+            .map(|hash| {
+                self.ledger
+                    .any()
+                    .get_block(&tx, hash)
+                    .ok_or_else(|| anyhow!("block {} not found", hash))
+            })
+            .collect()
+    }
+
+    /// Sends a request and waits for the response. A broken connection gets reopened once
+    fn request(&self, request: &SignerRequest) -> anyhow::Result<SignerResponse> {
+        let mut connection = self.connection.lock().unwrap();
+        if connection.is_some() {
+            match Self::send(connection.as_mut().unwrap(), request) {
+                Ok(response) => return Ok(response),
+                Err(_) => *connection = None,
+            }
+        }
+
+        let mut new_connection = self.connect()?;
+        let response = Self::send(&mut new_connection, request)?;
+        *connection = Some(new_connection);
+        Ok(response)
+    }
+
+    fn connect(&self) -> anyhow::Result<Connection> {
+        let stream = UnixStream::connect(&self.socket_path).map_err(|e| {
+            anyhow!(
+                "Could not connect to signer at {:?}: {}",
+                self.socket_path,
+                e
+            )
+        })?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        Ok(Connection {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    fn send(
+        connection: &mut Connection,
+        request: &SignerRequest,
+    ) -> anyhow::Result<SignerResponse> {
+        write_signer_message(&mut connection.writer, request)?;
+        read_signer_message(&mut connection.reader)?
+            .ok_or_else(|| anyhow!("signer closed the connection"))
+    }
+}
+
+impl VoteSigner for RemoteSigner {
+    fn sign_votes(&self, timestamp: u64, duration: u8, hashes: &[BlockHash]) -> Vec<Vote> {
+        let blocks = match self.final_vote_blocks(timestamp, duration, hashes) {
+            Ok(blocks) => blocks,
+            Err(e) => {
+                warn!("Could not create final vote: {:?}", e);
+                return Vec::new();
+            }
+        };
+        let mut votes = Vec::new();
+        for representative in self.representatives() {
+            if !self.has_voting_weight(&representative) {
+                continue;
+            }
+            match self.sign_vote(representative, timestamp, duration, hashes, &blocks) {
+                Ok(vote) => votes.push(vote),
+                Err(e) => warn!(
+                    "Remote signer did not sign vote for {}: {:?}",
+                    representative.as_account(),
+                    e
+                ),
+            }
+        }
+        votes
+    }
+
+    fn reload_representatives(&self) -> anyhow::Result<Vec<PublicKey>> {
+        self.fetch_representatives()
+    }
+}
+
+fn unexpected_response(response: SignerResponse) -> anyhow::Error {
+    match response {
+        SignerResponse::Error { error } => anyhow!("signer error: {}", error),
+        response => anyhow!("unexpected signer response: {:?}", response),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        signing::{SignerService, SlashingProtection},
+        unique_path,
+    };
+    use rsnano_core::{Account, BlockSideband, KeyPair, Link, StateBlock};
+    use std::{os::unix::net::UnixListener, sync::Arc};
+
+    #[test]
+    fn sign_votes_with_remote_signer() {
+        let keys = KeyPair::from(1);
+        let (signer, dir) = start_signer(&keys);
+
+        assert_eq!(
+            signer.fetch_representatives().unwrap(),
+            vec![keys.public_key()]
+        );
+        let votes = signer.sign_votes(1000, 2, &[BlockHash::from(3)]);
+
+        assert_eq!(
+            votes,
+            vec![Vote::new(&keys, 1000, 2, vec![BlockHash::from(3)])]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn skip_refused_final_votes() {
+        let keys = KeyPair::from(1);
+        let (signer, dir) = start_signer(&keys);
+        signer.fetch_representatives().unwrap();
+
+        let final_vote = |block: &BlockEnum| {
+            signer.sign_votes(Vote::TIMESTAMP_MAX, Vote::DURATION_MAX, &[block.hash()])
+        };
+
+        assert_eq!(final_vote(&fork(3)).len(), 1);
+        assert_eq!(final_vote(&fork(4)).len(), 0);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dont_sign_final_vote_for_unknown_block() {
+        let keys = KeyPair::from(1);
+        let (signer, dir) = start_signer(&keys);
+        signer.fetch_representatives().unwrap();
+
+        let votes = signer.sign_votes(
+            Vote::TIMESTAMP_MAX,
+            Vote::DURATION_MAX,
+            &[BlockHash::from(3)],
+        );
+
+        assert!(votes.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dont_vote_without_enough_weight() {
+        let keys = KeyPair::from(1);
+        let (signer, dir) = start_signer_with_weight(&keys, VOTE_MINIMUM - Amount::raw(1));
+        signer.fetch_representatives().unwrap();
+
+        let votes = signer.sign_votes(1000, 2, &[BlockHash::from(3)]);
+
+        assert!(votes.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sign_block_with_remote_signer() {
+        let keys = KeyPair::from(1);
+        let (signer, dir) = start_signer(&keys);
+        let block = BlockEnum::new_test_instance_with_key(keys.clone());
+
+        let signature = signer.sign_block(keys.public_key(), &block).unwrap();
+
+        assert!(rsnano_core::validate_message(
+            &keys.public_key(),
+            block.hash().as_bytes(),
+            &signature
+        )
+        .is_ok());
+        assert!(signer.sign_block(PublicKey::from(9), &block).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn fail_when_signer_is_not_running() {
+        let signer = RemoteSigner::new(
+            &RemoteSignerConfig {
+                enabled: true,
+                socket_path: unique_path().unwrap().join("signer"),
+                ..Default::default()
+            },
+            Arc::new(Ledger::new_null()),
+            VOTE_MINIMUM,
+        );
+        assert!(signer.fetch_representatives().is_err());
+    }
+
+    const VOTE_MINIMUM: Amount = Amount::raw(1000);
+
+    fn start_signer(keys: &KeyPair) -> (RemoteSigner, PathBuf) {
+        start_signer_with_weight(keys, VOTE_MINIMUM)
+    }
+
+    fn start_signer_with_weight(keys: &KeyPair, weight: Amount) -> (RemoteSigner, PathBuf) {
+        let dir = unique_path().unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("signer");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let service = Arc::new(SignerService::new(
+            [keys.clone()],
+            SlashingProtection::new_in_memory(),
+        ));
+        std::thread::spawn(move || service.serve(listener));
+
+        let ledger = Ledger::new_null_builder()
+            .blocks([&fork(3), &fork(4)])
+            .finish();
+        ledger.rep_weights.set(keys.public_key(), weight);
+        let signer = RemoteSigner::new(
+            &RemoteSignerConfig {
+                enabled: true,
+                socket_path,
+                ..Default::default()
+            },
+            Arc::new(ledger),
+            VOTE_MINIMUM,
+        );
+        (signer, dir)
+    }
+
+    /// Blocks with the same root
+    fn fork(balance: u128) -> BlockEnum {
+        let mut block = BlockEnum::State(StateBlock::new(
+            Account::from(1),
+            BlockHash::from(2),
+            PublicKey::from(3),
+            Amount::raw(balance),
+            Link::from(4),
+            &KeyPair::from(5),
+            0,
+        ));
+        block
+            .as_block_mut()
+            .set_sideband(BlockSideband::new_test_instance());
+        block
+    }
+}
@@ -0,0 +1,25 @@
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoteSignerConfig {
+    /// Sign votes with a signer process instead of the wallet keys
+    pub enabled: bool,
+    /// Unix socket of the signer process
+    pub socket_path: PathBuf,
+    /// Read and write timeout for signer requests
+    pub timeout: Duration,
+    /// How often the node asks the signer for its representatives, so that keys
+    /// can be added to or removed from the signer while the node is running
+    pub refresh_interval: Duration,
+}
+
+impl Default for RemoteSignerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            socket_path: PathBuf::from("/tmp/rsnano_signer"),
+            timeout: Duration::from_secs(5),
+            refresh_interval: Duration::from_secs(60),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use rsnano_core::{BlockEnum, BlockHash, PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// Request that the node sends to a remote signer.
+/// Requests and responses are JSON objects, one per line.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// Lists the representatives whose keys are held by the signer
+    Representatives,
+    /// Final votes carry the block of every hash in `blocks`. The signer derives the roots
+    /// from them, so that the node can't trick it into signing two different final votes
+    /// for the same root. Non-final votes don't need the blocks
+    SignVote {
+        representative: PublicKey,
+        timestamp: u64,
+        duration: u8,
+        hashes: Vec<BlockHash>,
+        #[serde(default)]
+        blocks: Vec<BlockEnum>,
+    },
+    /// Carries the whole block and not just its hash. The signer hashes the block itself,
+    /// so that it can't be tricked into signing a vote hash
+    SignBlock {
+        account: PublicKey,
+        block: Box<BlockEnum>,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SignerResponse {
+    Representatives { representatives: Vec<PublicKey> },
+    Signature { signature: Signature },
+    Error { error: String },
+}
+
+impl SignerResponse {
+    pub fn error(error: impl Into<String>) -> Self {
+        Self::Error {
+            error: error.into(),
+        }
+    }
+}
+
+pub fn write_signer_message(
+    writer: &mut impl Write,
+    message: &impl Serialize,
+) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns None if the other side closed the connection
+pub fn read_signer_message<T: for<'de> Deserialize<'de>>(
+    reader: &mut impl BufRead,
+) -> anyhow::Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::{KeyPair, Vote};
+    use std::io::Cursor;
+
+    #[test]
+    fn serialize_representatives_request() {
+        assert_eq!(
+            serde_json::to_string(&SignerRequest::Representatives).unwrap(),
+            r#"{"action":"representatives"}"#
+        );
+    }
+
+    #[test]
+    fn serialize_sign_vote_request() {
+        let request = SignerRequest::SignVote {
+            representative: PublicKey::from(1),
+            timestamp: 2,
+            duration: 3,
+            hashes: vec![BlockHash::from(4)],
+            blocks: Vec::new(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"action":"sign_vote","representative":"0000000000000000000000000000000000000000000000000000000000000001","timestamp":2,"duration":3,"hashes":["0000000000000000000000000000000000000000000000000000000000000004"],"blocks":[]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<SignerRequest>(&json).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn sign_block_request_needs_the_block() {
+        let vote = Vote::new(&KeyPair::from(1), 1000, 2, vec![BlockHash::from(3)]);
+        let json = format!(
+            r#"{{"action":"sign_block","account":"{}","hash":"{}"}}"#,
+            PublicKey::from(1).encode_hex(),
+            vote.hash().encode_hex()
+        );
+        assert!(serde_json::from_str::<SignerRequest>(&json).is_err());
+    }
+
+    #[test]
+    fn deserialize_responses() {
+        assert_eq!(
+            serde_json::from_str::<SignerResponse>(r#"{"error":"locked"}"#).unwrap(),
+            SignerResponse::error("locked")
+        );
+        assert_eq!(
+            serde_json::from_str::<SignerResponse>(r#"{"representatives":[]}"#).unwrap(),
+            SignerResponse::Representatives {
+                representatives: Vec::new()
+            }
+        );
+        let signature = SignerResponse::Signature {
+            signature: Signature::from_bytes([7; 64]),
+        };
+        let json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SignerResponse>(&json).unwrap(),
+            signature
+        );
+    }
+
+    #[test]
+    fn one_message_per_line() {
+        let mut buffer = Vec::new();
+        write_signer_message(&mut buffer, &SignerRequest::Representatives).unwrap();
+        write_signer_message(&mut buffer, &SignerRequest::Representatives).unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        for _ in 0..2 {
+            let request: Option<SignerRequest> = read_signer_message(&mut reader).unwrap();
+            assert_eq!(request, Some(SignerRequest::Representatives));
+        }
+        let request: Option<SignerRequest> = read_signer_message(&mut reader).unwrap();
+        assert_eq!(request, None);
+    }
+}
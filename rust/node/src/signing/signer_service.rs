@@ -0,0 +1,386 @@
+use super::{SignerRequest, SignerResponse, SlashingProtection};
+use rsnano_core::{sign_message, BlockEnum, BlockHash, KeyPair, PublicKey, Vote};
+use std::{collections::HashMap, path::Path, sync::Mutex};
+
+/// The signing side of the remote signer protocol. It holds the representative keys
+/// and refuses to sign conflicting final votes.
+pub struct SignerService {
+    keys: HashMap<PublicKey, KeyPair>,
+    protection: Mutex<SlashingProtection>,
+}
+
+impl SignerService {
+    pub fn new(keys: impl IntoIterator<Item = KeyPair>, protection: SlashingProtection) -> Self {
+        Self {
+            keys: keys.into_iter().map(|k| (k.public_key(), k)).collect(),
+            protection: Mutex::new(protection),
+        }
+    }
+
+    /// Reads one private key in hex per line. Empty lines and lines starting with '#' are ignored
+    pub fn load_keys(path: impl AsRef<Path>) -> anyhow::Result<Vec<KeyPair>> {
+        let content = std::fs::read_to_string(path)?;
+        content
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(KeyPair::from_priv_key_hex)
+            .collect()
+    }
+
+    pub fn representatives(&self) -> Vec<PublicKey> {
+        let mut representatives: Vec<_> = self.keys.keys().cloned().collect();
+        representatives.sort();
+        representatives
+    }
+
+    pub fn handle(&self, request: SignerRequest) -> SignerResponse {
+        match request {
+            SignerRequest::Representatives => SignerResponse::Representatives {
+                representatives: self.representatives(),
+            },
+            SignerRequest::SignVote {
+                representative,
+                timestamp,
+                duration,
+                hashes,
+                blocks,
+            } => self.sign_vote(representative, timestamp, duration, hashes, blocks),
+            SignerRequest::SignBlock { account, block } => self.sign_block(account, block),
+        }
+    }
+
+    fn sign_block(&self, account: PublicKey, block: Box<BlockEnum>) -> SignerResponse {
+        let Some(keys) = self.keys.get(&account) else {
+            return SignerResponse::error("unknown account");
+        };
+        if let Some(block_account) = block.account_field() {
+            if PublicKey::from(block_account) != account {
+                return SignerResponse::error("block belongs to another account");
+            }
+        }
+
+        SignerResponse::Signature {
+            signature: sign_message(&keys.private_key(), block.hash().as_bytes()),
+        }
+    }
+
+    fn sign_vote(
+        &self,
+        representative: PublicKey,
+        timestamp: u64,
+        duration: u8,
+        hashes: Vec<BlockHash>,
+        blocks: Vec<BlockEnum>,
+    ) -> SignerResponse {
+        let Some(keys) = self.keys.get(&representative) else {
+            return SignerResponse::error("unknown representative");
+        };
+        if hashes.is_empty() || hashes.len() > Vote::MAX_HASHES {
+            return SignerResponse::error("invalid number of hashes");
+        }
+
+        let vote = Vote::new_unsigned(representative, timestamp, duration, hashes);
+        if vote.is_final() {
+            if blocks.len() != vote.hashes.len() {
+                return SignerResponse::error("every hash of a final vote needs its block");
+            }
+            // The roots are taken from the blocks and not from the node, because
+            // a root is part of the block hash and therefore can't be faked
+            let mut roots = Vec::with_capacity(blocks.len());
+            for (block, hash) in blocks.iter().zip(&vote.hashes) {
+                if block.hash() != *hash {
+                    return SignerResponse::error("block does not match hash");
+                }
+                roots.push(block.root());
+            }
+            let result = self.protection.lock().unwrap().check_and_record(
+                &representative,
+                &roots,
+                &vote.hashes,
+            );
+            if let Err(e) = result {
+                return SignerResponse::error(e.to_string());
+            }
+        }
+
+        SignerResponse::Signature {
+            signature: sign_message(&keys.private_key(), vote.hash().as_bytes()),
+        }
+    }
+}
+
+#[cfg(unix)]
+mod unix_socket {
+    use super::SignerService;
+    use crate::signing::{read_signer_message, write_signer_message, SignerRequest};
+    use std::{
+        fs::{DirBuilder, Permissions},
+        io::BufReader,
+        os::unix::{
+            fs::{DirBuilderExt, PermissionsExt},
+            net::{UnixListener, UnixStream},
+        },
+        path::Path,
+        sync::Arc,
+    };
+    use tracing::{debug, warn};
+
+    /// Creates a socket that only the current user can connect to.
+    /// The socket is bound in a private directory and moved into place once
+    /// its permissions are restricted, so no other user can connect in between
+    pub fn bind_private_socket(path: impl AsRef<Path>) -> anyhow::Result<UnixListener> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("Invalid socket path {:?}", path))?;
+        let private_dir = path.with_file_name(format!(
+            ".{}.{}",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+        DirBuilder::new().mode(0o700).create(&private_dir)?;
+
+        let result = (|| {
+            let private_path = private_dir.join(file_name);
+            let listener = UnixListener::bind(&private_path)?;
+            std::fs::set_permissions(&private_path, Permissions::from_mode(0o600))?;
+            // Replaces the socket of a previous run
+            std::fs::rename(&private_path, path)?;
+            Ok(listener)
+        })();
+        let _ = std::fs::remove_dir_all(&private_dir);
+        result
+    }
+
+    impl SignerService {
+        /// Accepts connections until the listener fails. Every connection is served by its own thread
+        pub fn serve(self: &Arc<Self>, listener: UnixListener) -> anyhow::Result<()> {
+            loop {
+                let (stream, _) = listener.accept()?;
+                let service = Arc::clone(self);
+                std::thread::Builder::new()
+                    .name("signer conn".to_owned())
+                    .spawn(move || {
+                        if let Err(e) = service.serve_connection(stream) {
+                            warn!("Signer connection failed: {:?}", e);
+                        }
+                    })?;
+            }
+        }
+
+        fn serve_connection(&self, stream: UnixStream) -> anyhow::Result<()> {
+            let mut reader = BufReader::new(stream.try_clone()?);
+            let mut writer = stream;
+            while let Some(request) = read_signer_message::<SignerRequest>(&mut reader)? {
+                debug!(?request, "Signer request");
+                let response = self.handle(request);
+                write_signer_message(&mut writer, &response)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(unix)]
+pub use unix_socket::bind_private_socket;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::{Account, Amount, Link, Signature, StateBlock};
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_is_only_accessible_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = crate::unique_path().unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("signer");
+
+        let _listener = bind_private_socket(&socket_path).unwrap();
+
+        let mode = std::fs::metadata(&socket_path)
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn list_representatives() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+        assert_eq!(
+            service.handle(SignerRequest::Representatives),
+            SignerResponse::Representatives {
+                representatives: vec![keys.public_key()]
+            }
+        );
+    }
+
+    #[test]
+    fn sign_vote() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+        let hashes = vec![BlockHash::from(3)];
+
+        let response = service.handle(SignerRequest::SignVote {
+            representative: keys.public_key(),
+            timestamp: 1000,
+            duration: 2,
+            hashes: hashes.clone(),
+            blocks: Vec::new(),
+        });
+
+        let expected = Vote::new(&keys, 1000, 2, hashes);
+        assert_eq!(
+            response,
+            SignerResponse::Signature {
+                signature: expected.signature
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_representative() {
+        let service = create_service(&KeyPair::from(1));
+        let response = service.handle(SignerRequest::SignVote {
+            representative: PublicKey::from(42),
+            timestamp: 1000,
+            duration: 2,
+            hashes: vec![BlockHash::from(3)],
+            blocks: Vec::new(),
+        });
+        assert_eq!(response, SignerResponse::error("unknown representative"));
+    }
+
+    #[test]
+    fn non_final_votes_can_change() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+        for balance in [3, 4] {
+            let response = service.handle(sign_request(&keys, 1000, &fork(balance)));
+            assert!(matches!(response, SignerResponse::Signature { .. }));
+        }
+    }
+
+    #[test]
+    fn never_sign_conflicting_final_votes() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+
+        let first = service.handle(sign_request(&keys, Vote::FINAL_TIMESTAMP, &fork(3)));
+        let same = service.handle(sign_request(&keys, Vote::FINAL_TIMESTAMP, &fork(3)));
+        let conflicting = service.handle(sign_request(&keys, Vote::FINAL_TIMESTAMP, &fork(4)));
+
+        assert!(matches!(first, SignerResponse::Signature { .. }));
+        assert_eq!(first, same);
+        assert!(matches!(conflicting, SignerResponse::Error { .. }));
+    }
+
+    #[test]
+    fn final_vote_needs_blocks() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+        let response = service.handle(SignerRequest::SignVote {
+            representative: keys.public_key(),
+            timestamp: Vote::FINAL_TIMESTAMP,
+            duration: Vote::DURATION_MAX,
+            hashes: vec![fork(3).hash()],
+            blocks: Vec::new(),
+        });
+        assert_eq!(
+            response,
+            SignerResponse::error("every hash of a final vote needs its block")
+        );
+    }
+
+    #[test]
+    fn node_cannot_lie_about_roots() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+        let first = service.handle(sign_request(&keys, Vote::FINAL_TIMESTAMP, &fork(3)));
+        assert!(matches!(first, SignerResponse::Signature { .. }));
+
+        // The node passes a block with another root for the conflicting hash
+        let other_root = BlockEnum::new_test_instance_with_key(KeyPair::from(9));
+        let response = service.handle(SignerRequest::SignVote {
+            representative: keys.public_key(),
+            timestamp: Vote::FINAL_TIMESTAMP,
+            duration: Vote::DURATION_MAX,
+            hashes: vec![fork(4).hash()],
+            blocks: vec![other_root],
+        });
+
+        assert_eq!(response, SignerResponse::error("block does not match hash"));
+    }
+
+    #[test]
+    fn sign_block() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+        let block = BlockEnum::new_test_instance_with_key(keys.clone());
+        let response = service.handle(SignerRequest::SignBlock {
+            account: keys.public_key(),
+            block: Box::new(block.clone()),
+        });
+        let SignerResponse::Signature { signature } = response else {
+            panic!("no signature");
+        };
+        assert_ne!(signature, Signature::new());
+        assert!(rsnano_core::validate_message(
+            &keys.public_key(),
+            block.hash().as_bytes(),
+            &signature
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn dont_sign_block_of_another_account() {
+        let keys = KeyPair::from(1);
+        let service = create_service(&keys);
+        let response = service.handle(SignerRequest::SignBlock {
+            account: keys.public_key(),
+            block: Box::new(BlockEnum::new_test_instance_with_key(KeyPair::from(2))),
+        });
+        assert_eq!(
+            response,
+            SignerResponse::error("block belongs to another account")
+        );
+    }
+
+    fn create_service(keys: &KeyPair) -> SignerService {
+        SignerService::new([keys.clone()], SlashingProtection::new_in_memory())
+    }
+
+    fn sign_request(keys: &KeyPair, timestamp: u64, block: &BlockEnum) -> SignerRequest {
+        let is_final = timestamp == Vote::FINAL_TIMESTAMP;
+        SignerRequest::SignVote {
+            representative: keys.public_key(),
+            timestamp,
+            duration: if is_final { Vote::DURATION_MAX } else { 2 },
+            hashes: vec![block.hash()],
+            blocks: if is_final {
+                vec![block.clone()]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// Blocks with the same root
+    fn fork(balance: u128) -> BlockEnum {
+        BlockEnum::State(StateBlock::new(
+            Account::from(1),
+            BlockHash::from(2),
+            PublicKey::from(3),
+            Amount::raw(balance),
+            Link::from(4),
+            &KeyPair::from(5),
+            0,
+        ))
+    }
+}
@@ -0,0 +1,200 @@
+use rsnano_core::{BlockHash, PublicKey, Root};
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+};
+
+/// Remembers every final vote a signer has signed, so that it never signs
+/// a final vote for a different block with the same root.
+///
+/// The records are appended to a file, one line per (representative, root, hash),
+/// and the file is synced before the signature is handed out.
+pub struct SlashingProtection {
+    final_votes: HashMap<(PublicKey, Root), BlockHash>,
+    file: Option<File>,
+}
+
+impl SlashingProtection {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let mut final_votes = HashMap::new();
+        if path.exists() {
+            let reader = BufReader::new(File::open(path)?);
+            for (line_no, line) in reader.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let (representative, root, hash) = parse_record(&line).ok_or_else(|| {
+                    anyhow!("invalid slashing protection record in line {}", line_no + 1)
+                })?;
+                final_votes.insert((representative, root), hash);
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            final_votes,
+            file: Some(file),
+        })
+    }
+
+    /// Keeps the records in memory only
+    pub fn new_in_memory() -> Self {
+        Self {
+            final_votes: HashMap::new(),
+            file: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.final_votes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.final_votes.is_empty()
+    }
+
+    pub fn signed_final_vote(&self, representative: &PublicKey, root: &Root) -> Option<BlockHash> {
+        self.final_votes.get(&(*representative, *root)).cloned()
+    }
+
+    /// Records a final vote. It fails without recording anything if any of the
+    /// roots already got a final vote for another block
+    pub fn check_and_record(
+        &mut self,
+        representative: &PublicKey,
+        roots: &[Root],
+        hashes: &[BlockHash],
+    ) -> anyhow::Result<()> {
+        if roots.len() != hashes.len() {
+            bail!("the number of roots and hashes differ");
+        }
+
+        let mut new_records = Vec::new();
+        for (root, hash) in roots.iter().zip(hashes) {
+            match self.signed_final_vote(representative, root) {
+                Some(signed) if signed != *hash => {
+                    bail!(
+                        "refusing to sign final vote for {}: already signed {} for root {}",
+                        hash,
+                        signed,
+                        root
+                    );
+                }
+                Some(_) => {}
+                None => new_records.push((*root, *hash)),
+            }
+        }
+
+        if let Some(file) = &mut self.file {
+            let mut lines = String::new();
+            for (root, hash) in &new_records {
+                lines.push_str(&format!("{} {} {}\n", representative, root, hash));
+            }
+            file.write_all(lines.as_bytes())?;
+            file.sync_data()?;
+        }
+
+        for (root, hash) in new_records {
+            self.final_votes.insert((*representative, root), hash);
+        }
+        Ok(())
+    }
+}
+
+fn parse_record(line: &str) -> Option<(PublicKey, Root, BlockHash)> {
+    let mut parts = line.split_whitespace();
+    let representative = PublicKey::decode_hex(parts.next()?).ok()?;
+    let root = Root::decode_hex(parts.next()?).ok()?;
+    let hash = BlockHash::decode_hex(parts.next()?).ok()?;
+    Some((representative, root, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unique_path;
+
+    #[test]
+    fn record_final_vote() {
+        let mut protection = SlashingProtection::new_in_memory();
+        let rep = PublicKey::from(1);
+        protection
+            .check_and_record(&rep, &[Root::from(2)], &[BlockHash::from(3)])
+            .unwrap();
+        assert_eq!(
+            protection.signed_final_vote(&rep, &Root::from(2)),
+            Some(BlockHash::from(3))
+        );
+        assert_eq!(protection.len(), 1);
+    }
+
+    #[test]
+    fn allow_same_final_vote_again() {
+        let mut protection = SlashingProtection::new_in_memory();
+        let rep = PublicKey::from(1);
+        for _ in 0..2 {
+            protection
+                .check_and_record(&rep, &[Root::from(2)], &[BlockHash::from(3)])
+                .unwrap();
+        }
+        assert_eq!(protection.len(), 1);
+    }
+
+    #[test]
+    fn reject_conflicting_final_vote() {
+        let mut protection = SlashingProtection::new_in_memory();
+        let rep = PublicKey::from(1);
+        protection
+            .check_and_record(&rep, &[Root::from(2)], &[BlockHash::from(3)])
+            .unwrap();
+
+        let result = protection.check_and_record(
+            &rep,
+            &[Root::from(5), Root::from(2)],
+            &[BlockHash::from(6), BlockHash::from(4)],
+        );
+
+        assert!(result.is_err());
+        // Nothing of the rejected vote was recorded
+        assert_eq!(protection.signed_final_vote(&rep, &Root::from(5)), None);
+    }
+
+    #[test]
+    fn other_representative_can_vote_for_other_block() {
+        let mut protection = SlashingProtection::new_in_memory();
+        protection
+            .check_and_record(&PublicKey::from(1), &[Root::from(2)], &[BlockHash::from(3)])
+            .unwrap();
+        protection
+            .check_and_record(&PublicKey::from(9), &[Root::from(2)], &[BlockHash::from(4)])
+            .unwrap();
+    }
+
+    #[test]
+    fn records_survive_restart() {
+        let dir = unique_path().unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("slashing_protection");
+        let rep = PublicKey::from(1);
+        {
+            let mut protection = SlashingProtection::open(&path).unwrap();
+            protection
+                .check_and_record(&rep, &[Root::from(2)], &[BlockHash::from(3)])
+                .unwrap();
+        }
+
+        let mut protection = SlashingProtection::open(&path).unwrap();
+        assert_eq!(
+            protection.signed_final_vote(&rep, &Root::from(2)),
+            Some(BlockHash::from(3))
+        );
+        assert!(protection
+            .check_and_record(&rep, &[Root::from(2)], &[BlockHash::from(4)])
+            .is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
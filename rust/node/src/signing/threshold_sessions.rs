@@ -0,0 +1,495 @@
+#[cfg(test)]
+use mock_instant::Instant;
+use rsnano_core::{
+    threshold::{PartialSignature, ParticipantId, SigningCommitment, ThresholdGroup},
+    BlockEnum, BlockHash, Signature,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(not(test))]
+use std::time::Instant;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ThresholdSigningError {
+    SessionNotFound,
+    SessionExists,
+    AccountMismatch,
+    NotAParticipant,
+    InvalidCommitment,
+    DuplicateCommitment,
+    SignersComplete,
+    SignersIncomplete,
+    NotASigner,
+    DuplicatePartialSignature,
+    InvalidPartialSignature,
+}
+
+impl fmt::Display for ThresholdSigningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error_message = match self {
+            ThresholdSigningError::SessionNotFound => "Signing session not found",
+            ThresholdSigningError::SessionExists => "Signing session already exists",
+            ThresholdSigningError::AccountMismatch => "Block account is not the group account",
+            ThresholdSigningError::NotAParticipant => "Not a participant of the group",
+            ThresholdSigningError::InvalidCommitment => {
+                "Commitment is not signed by the participant"
+            }
+            ThresholdSigningError::DuplicateCommitment => "Participant already committed",
+            ThresholdSigningError::SignersComplete => "Enough signers already committed",
+            ThresholdSigningError::SignersIncomplete => "Not enough signers committed yet",
+            ThresholdSigningError::NotASigner => "Participant is not a signer of this session",
+            ThresholdSigningError::DuplicatePartialSignature => {
+                "Participant already submitted a partial signature"
+            }
+            ThresholdSigningError::InvalidPartialSignature => "Invalid partial signature",
+        };
+        write!(f, "{}", error_message)
+    }
+}
+
+impl std::error::Error for ThresholdSigningError {}
+
+/// The state of a block that is being signed by a threshold group
+#[derive(Clone, Debug)]
+pub struct ThresholdSigningSession {
+    pub block: BlockEnum,
+    pub group: ThresholdGroup,
+    /// The first `threshold` participants that commit become the signers
+    pub commitments: Vec<SigningCommitment>,
+    pub partial_signatures: Vec<(ParticipantId, PartialSignature)>,
+    /// Set as soon as all signers submitted their partial signature
+    pub signature: Option<Signature>,
+    started: Instant,
+}
+
+impl ThresholdSigningSession {
+    pub fn hash(&self) -> BlockHash {
+        self.block.hash()
+    }
+
+    pub fn signers_complete(&self) -> bool {
+        self.commitments.len() == self.group.threshold() as usize
+    }
+
+    /// The block with the aggregated signature, once the session is complete
+    pub fn signed_block(&self) -> Option<BlockEnum> {
+        self.signature.as_ref().map(|signature| {
+            let mut block = self.block.clone();
+            block.set_block_signature(signature);
+            block
+        })
+    }
+}
+
+/// Coordinates the two signing rounds of threshold accounts: collects the
+/// commitments of the signers, then their partial signatures and aggregates them.
+/// Sessions are identified by the block hash and only kept in memory.
+/// A session that isn't signed within the timeout gets aborted, so the coordinator
+/// can start it again with a quorum that excludes the signers that stopped responding
+pub struct ThresholdSigningSessions {
+    data: Mutex<SessionsData>,
+    max_sessions: usize,
+    timeout: Duration,
+}
+
+#[derive(Default)]
+struct SessionsData {
+    sessions: HashMap<BlockHash, ThresholdSigningSession>,
+    /// Oldest first
+    order: VecDeque<BlockHash>,
+}
+
+impl SessionsData {
+    /// Aborts the unsigned sessions that are older than the timeout
+    fn abort_expired(&mut self, timeout: Duration, now: Instant) {
+        let sessions = &mut self.sessions;
+        self.order.retain(|hash| {
+            let expired = sessions.get(hash).is_some_and(|session| {
+                session.signature.is_none() && now.duration_since(session.started) >= timeout
+            });
+            if expired {
+                sessions.remove(hash);
+            }
+            !expired
+        });
+    }
+
+    fn remove(&mut self, hash: &BlockHash) -> bool {
+        if self.sessions.remove(hash).is_none() {
+            return false;
+        }
+        self.order.retain(|h| h != hash);
+        true
+    }
+}
+
+impl ThresholdSigningSessions {
+    pub const DEFAULT_MAX_SESSIONS: usize = 256;
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// `max_sessions` must be at least 1
+    pub fn new(max_sessions: usize, timeout: Duration) -> Self {
+        assert!(max_sessions > 0, "max_sessions must be at least 1");
+        Self {
+            data: Mutex::new(SessionsData::default()),
+            max_sessions,
+            timeout,
+        }
+    }
+
+    fn lock_data(&self) -> MutexGuard<'_, SessionsData> {
+        let mut data = self.data.lock().unwrap();
+        data.abort_expired(self.timeout, Instant::now());
+        data
+    }
+
+    /// Starts a session for the block. The oldest session is dropped if there are too many.
+    /// A block whose session was aborted can be started again
+    pub fn start(
+        &self,
+        block: BlockEnum,
+        group: ThresholdGroup,
+    ) -> Result<BlockHash, ThresholdSigningError> {
+        if let Some(account) = block.account_field() {
+            if account != group.account() {
+                return Err(ThresholdSigningError::AccountMismatch);
+            }
+        }
+
+        let hash = block.hash();
+        let mut data = self.lock_data();
+        if data.sessions.contains_key(&hash) {
+            return Err(ThresholdSigningError::SessionExists);
+        }
+        while data.order.len() >= self.max_sessions {
+            let Some(oldest) = data.order.pop_front() else {
+                break;
+            };
+            data.sessions.remove(&oldest);
+        }
+        data.sessions.insert(
+            hash,
+            ThresholdSigningSession {
+                block,
+                group,
+                commitments: Vec::new(),
+                partial_signatures: Vec::new(),
+                signature: None,
+                started: Instant::now(),
+            },
+        );
+        data.order.push_back(hash);
+        Ok(hash)
+    }
+
+    /// First round: adds the commitment of a signer
+    pub fn commit(
+        &self,
+        hash: &BlockHash,
+        commitment: SigningCommitment,
+    ) -> Result<ThresholdSigningSession, ThresholdSigningError> {
+        let mut data = self.lock_data();
+        let session = data
+            .sessions
+            .get_mut(hash)
+            .ok_or(ThresholdSigningError::SessionNotFound)?;
+        if !session.group.contains(commitment.participant()) {
+            return Err(ThresholdSigningError::NotAParticipant);
+        }
+        if session.group.verify_commitment(&commitment).is_err() {
+            return Err(ThresholdSigningError::InvalidCommitment);
+        }
+        if session
+            .commitments
+            .iter()
+            .any(|c| c.participant() == commitment.participant())
+        {
+            return Err(ThresholdSigningError::DuplicateCommitment);
+        }
+        if session.signers_complete() {
+            return Err(ThresholdSigningError::SignersComplete);
+        }
+        session.commitments.push(commitment);
+        Ok(session.clone())
+    }
+
+    /// Second round: adds the partial signature of a signer. The signature gets
+    /// aggregated when the last partial signature arrives
+    pub fn submit(
+        &self,
+        hash: &BlockHash,
+        participant: ParticipantId,
+        partial: PartialSignature,
+    ) -> Result<ThresholdSigningSession, ThresholdSigningError> {
+        let mut data = self.lock_data();
+        let session = data
+            .sessions
+            .get_mut(hash)
+            .ok_or(ThresholdSigningError::SessionNotFound)?;
+        if !session.signers_complete() {
+            return Err(ThresholdSigningError::SignersIncomplete);
+        }
+        if !session
+            .commitments
+            .iter()
+            .any(|c| c.participant() == participant)
+        {
+            return Err(ThresholdSigningError::NotASigner);
+        }
+        if session
+            .partial_signatures
+            .iter()
+            .any(|(id, _)| *id == participant)
+        {
+            return Err(ThresholdSigningError::DuplicatePartialSignature);
+        }
+        session
+            .group
+            .verify_partial(hash.as_bytes(), &session.commitments, participant, &partial)
+            .map_err(|_| ThresholdSigningError::InvalidPartialSignature)?;
+        session.partial_signatures.push((participant, partial));
+
+        if session.partial_signatures.len() == session.commitments.len() {
+            let signature = session
+                .group
+                .aggregate(
+                    hash.as_bytes(),
+                    &session.commitments,
+                    &session.partial_signatures,
+                )
+                .map_err(|_| ThresholdSigningError::InvalidPartialSignature)?;
+            session.signature = Some(signature);
+        }
+        Ok(session.clone())
+    }
+
+    /// Aborts the session before its timeout, e.g. when a signer stopped responding
+    pub fn abort(&self, hash: &BlockHash) -> Result<(), ThresholdSigningError> {
+        if self.lock_data().remove(hash) {
+            Ok(())
+        } else {
+            Err(ThresholdSigningError::SessionNotFound)
+        }
+    }
+
+    pub fn get(&self, hash: &BlockHash) -> Option<ThresholdSigningSession> {
+        self.lock_data().sessions.get(hash).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock_data().sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for ThresholdSigningSessions {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_SESSIONS, Self::DEFAULT_TIMEOUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_instant::MockClock;
+    use rsnano_core::{
+        threshold::{create_key_shares, SigningNonces},
+        Amount, BlockHash, StateBlock,
+    };
+
+    #[test]
+    fn start_rejects_block_of_other_account() {
+        let sessions = ThresholdSigningSessions::default();
+        let group = create_key_shares(2, 3)[0].group().clone();
+
+        let result = sessions.start(BlockEnum::new_test_instance(), group);
+
+        assert_eq!(result, Err(ThresholdSigningError::AccountMismatch));
+    }
+
+    #[test]
+    fn commit_accepts_threshold_signers_once() {
+        let sessions = ThresholdSigningSessions::default();
+        let shares = create_key_shares(2, 3);
+        let group = shares[0].group().clone();
+        let hash = sessions.start(group_block(&group, 1), group).unwrap();
+        let mut nonces: Vec<_> = shares.iter().map(SigningNonces::new).collect();
+        let commitments: Vec<_> = nonces.iter().map(|n| n.commitment()).collect();
+        let partial = shares[0]
+            .sign(nonces.remove(0), hash.as_bytes(), &commitments[..2])
+            .unwrap();
+
+        sessions.commit(&hash, commitments[0].clone()).unwrap();
+
+        assert_eq!(
+            sessions.commit(&hash, commitments[0].clone()).unwrap_err(),
+            ThresholdSigningError::DuplicateCommitment
+        );
+        assert_eq!(
+            sessions.submit(&hash, 1, partial).unwrap_err(),
+            ThresholdSigningError::SignersIncomplete
+        );
+        assert!(sessions
+            .commit(&hash, commitments[1].clone())
+            .unwrap()
+            .signers_complete());
+        assert_eq!(
+            sessions.commit(&hash, commitments[2].clone()).unwrap_err(),
+            ThresholdSigningError::SignersComplete
+        );
+    }
+
+    #[test]
+    fn commit_rejects_commitment_of_other_group() {
+        let sessions = ThresholdSigningSessions::default();
+        let group = create_key_shares(2, 3)[0].group().clone();
+        let hash = sessions.start(group_block(&group, 1), group).unwrap();
+        let other_shares = create_key_shares(2, 3);
+
+        let result = sessions.commit(&hash, SigningNonces::new(&other_shares[0]).commitment());
+
+        assert_eq!(
+            result.unwrap_err(),
+            ThresholdSigningError::InvalidCommitment
+        );
+        assert!(sessions.get(&hash).unwrap().commitments.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "max_sessions must be at least 1")]
+    fn reject_zero_max_sessions() {
+        ThresholdSigningSessions::new(0, ThresholdSigningSessions::DEFAULT_TIMEOUT);
+    }
+
+    #[test]
+    fn aggregates_signature_when_all_signers_submitted() {
+        let sessions = ThresholdSigningSessions::default();
+        let shares = create_key_shares(2, 3);
+        let group = shares[0].group().clone();
+        let hash = sessions.start(group_block(&group, 1), group).unwrap();
+        let nonces: Vec<_> = [&shares[1], &shares[2]]
+            .into_iter()
+            .map(SigningNonces::new)
+            .collect();
+        for nonce in &nonces {
+            sessions.commit(&hash, nonce.commitment()).unwrap();
+        }
+        let commitments = sessions.get(&hash).unwrap().commitments;
+
+        for (share, nonce) in shares[1..].iter().zip(nonces) {
+            let partial = share.sign(nonce, hash.as_bytes(), &commitments).unwrap();
+            sessions
+                .submit(&hash, share.participant(), partial)
+                .unwrap();
+        }
+
+        let session = sessions.get(&hash).unwrap();
+        let BlockEnum::State(block) = session.signed_block().unwrap() else {
+            panic!("not a state block")
+        };
+        assert!(rsnano_core::validate_block_signature(&block).is_ok());
+    }
+
+    #[test]
+    fn evicts_oldest_session() {
+        let sessions = ThresholdSigningSessions::new(2, ThresholdSigningSessions::DEFAULT_TIMEOUT);
+        let group = create_key_shares(1, 1)[0].group().clone();
+        let first = sessions
+            .start(group_block(&group, 1), group.clone())
+            .unwrap();
+        let second = sessions
+            .start(group_block(&group, 2), group.clone())
+            .unwrap();
+
+        let third = sessions.start(group_block(&group, 3), group).unwrap();
+
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions.get(&first).is_none());
+        assert!(sessions.get(&second).is_some());
+        assert!(sessions.get(&third).is_some());
+    }
+
+    #[test]
+    fn aborts_stale_session_after_timeout() {
+        let sessions = ThresholdSigningSessions::new(2, Duration::from_secs(60));
+        let shares = create_key_shares(2, 3);
+        let group = shares[0].group().clone();
+        let block = group_block(&group, 1);
+        let hash = sessions.start(block.clone(), group.clone()).unwrap();
+        sessions
+            .commit(&hash, SigningNonces::new(&shares[0]).commitment())
+            .unwrap();
+
+        MockClock::advance(Duration::from_secs(59));
+        assert!(sessions.get(&hash).is_some());
+
+        MockClock::advance(Duration::from_secs(1));
+        assert!(sessions.get(&hash).is_none());
+        assert_eq!(
+            sessions
+                .commit(&hash, SigningNonces::new(&shares[1]).commitment())
+                .unwrap_err(),
+            ThresholdSigningError::SessionNotFound
+        );
+
+        // The coordinator can retry with another quorum
+        assert_eq!(sessions.start(block, group), Ok(hash));
+        assert!(sessions.get(&hash).unwrap().commitments.is_empty());
+    }
+
+    #[test]
+    fn keeps_signed_session_after_timeout() {
+        let sessions = ThresholdSigningSessions::new(2, Duration::from_secs(60));
+        let shares = create_key_shares(1, 1);
+        let group = shares[0].group().clone();
+        let hash = sessions.start(group_block(&group, 1), group).unwrap();
+        let nonces = SigningNonces::new(&shares[0]);
+        let commitments = sessions
+            .commit(&hash, nonces.commitment())
+            .unwrap()
+            .commitments;
+        let partial = shares[0]
+            .sign(nonces, hash.as_bytes(), &commitments)
+            .unwrap();
+        sessions.submit(&hash, 1, partial).unwrap();
+
+        MockClock::advance(Duration::from_secs(60));
+
+        assert!(sessions.get(&hash).unwrap().signature.is_some());
+    }
+
+    #[test]
+    fn abort_session() {
+        let sessions = ThresholdSigningSessions::default();
+        let group = create_key_shares(1, 1)[0].group().clone();
+        let hash = sessions.start(group_block(&group, 1), group).unwrap();
+
+        assert_eq!(sessions.abort(&hash), Ok(()));
+
+        assert!(sessions.is_empty());
+        assert_eq!(
+            sessions.abort(&hash),
+            Err(ThresholdSigningError::SessionNotFound)
+        );
+    }
+
+    fn group_block(group: &ThresholdGroup, balance: u128) -> BlockEnum {
+        BlockEnum::State(StateBlock::with_signature(
+            group.account(),
+            BlockHash::zero(),
+            group.public_key(),
+            Amount::raw(balance),
+            BlockHash::from(1).into(),
+            Signature::new(),
+            0,
+        ))
+    }
+}
@@ -0,0 +1,25 @@
+use super::VoteSigner;
+use crate::wallets::Wallets;
+use rsnano_core::{BlockHash, Vote};
+use std::sync::Arc;
+
+/// Signs votes with the representative keys that are stored in the local wallets
+pub struct WalletVoteSigner {
+    wallets: Arc<Wallets>,
+}
+
+impl WalletVoteSigner {
+    pub fn new(wallets: Arc<Wallets>) -> Self {
+        Self { wallets }
+    }
+}
+
+impl VoteSigner for WalletVoteSigner {
+    fn sign_votes(&self, timestamp: u64, duration: u8, hashes: &[BlockHash]) -> Vec<Vote> {
+        let mut votes = Vec::new();
+        self.wallets.foreach_representative(|keys| {
+            votes.push(Vote::new(keys, timestamp, duration, hashes.to_vec()));
+        });
+        votes
+    }
+}
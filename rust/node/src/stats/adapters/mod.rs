@@ -7,6 +7,7 @@ pub use network_stats::*;
 use rsnano_core::VoteSource;
 use rsnano_ledger::BlockStatus;
 use rsnano_messages::Message;
+use rsnano_network::bandwidth_limiter::BandwidthLimitType;
 
 use super::DetailType;
 
@@ -46,3 +47,16 @@ impl From<VoteSource> for DetailType {
         }
     }
 }
+
+impl From<BandwidthLimitType> for DetailType {
+    fn from(value: BandwidthLimitType) -> Self {
+        match value {
+            BandwidthLimitType::Standard => Self::Standard,
+            BandwidthLimitType::Bootstrap => Self::Bootstrap,
+            BandwidthLimitType::Vote => Self::Vote,
+            BandwidthLimitType::Block => Self::Block,
+            BandwidthLimitType::Telemetry => Self::Telemetry,
+            BandwidthLimitType::Peer => Self::Peer,
+        }
+    }
+}
@@ -85,6 +85,10 @@ pub enum StatType {
     MessageProcessor,
     MessageProcessorOverfill,
     MessageProcessorType,
+    Pruning,
+    PortMapping,
+    RepHistory,
+    BandwidthLimiter,
 }
 
 impl StatType {
@@ -270,9 +274,11 @@ pub enum DetailType {
     LoopKeepalive,
     LoopReachout,
     LoopReachoutCached,
+    LoopReachoutTrusted,
     MergePeer,
     ReachoutLive,
     ReachoutCached,
+    ReachoutTrusted,
 
     // tcp
     TcpWriteDrop,
@@ -509,6 +515,24 @@ pub enum DetailType {
     BlocksByHash,
     BlocksByAccount,
     AccountInfoByHash,
+
+    // ledger pruning
+    Pruned,
+    KeptAccount,
+
+    // port mapping
+    Mapped,
+    Renewed,
+    HealthCheckFailed,
+    MappingFailed,
+
+    // rep history
+    WarmStarted,
+
+    // bandwidth limiter
+    Standard,
+    Telemetry,
+    Peer,
 }
 
 impl DetailType {
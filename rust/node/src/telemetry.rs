@@ -53,6 +53,7 @@ pub struct Telemetry {
 
 impl Telemetry {
     const MAX_SIZE: usize = 1024;
+    const MAX_HISTORY: usize = 64;
 
     pub(crate) fn new(
         config: TelementryConfig,
@@ -156,12 +157,19 @@ impl Telemetry {
             self.stats.inc(StatType::Telemetry, DetailType::Update);
             entry.data = data.clone();
             entry.last_updated = Instant::now();
+            if entry.history.len() >= Self::MAX_HISTORY {
+                entry.history.pop_front();
+            }
+            entry
+                .history
+                .push_back(TelemetrySample::new(data, SystemTime::now()));
         } else {
             self.stats.inc(StatType::Telemetry, DetailType::Insert);
             guard.telemetries.push_back(Entry {
                 endpoint: peer_addr,
                 data: data.clone(),
                 last_updated: Instant::now(),
+                history: VecDeque::from([TelemetrySample::new(data, SystemTime::now())]),
             });
 
             if guard.telemetries.len() > Self::MAX_SIZE {
@@ -332,15 +340,31 @@ impl Telemetry {
         result
     }
 
+    /// Returns the most recent telemetry samples of the given endpoint, oldest first
+    pub fn history(&self, endpoint: &SocketAddrV6) -> Vec<TelemetrySample> {
+        let guard = self.mutex.lock().unwrap();
+        match guard.telemetries.get(endpoint) {
+            Some(entry) => entry.history.iter().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
     pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
         let guard = self.mutex.lock().unwrap();
         ContainerInfoComponent::Composite(
             name.into(),
-            vec![ContainerInfoComponent::Leaf(ContainerInfo {
-                name: "telemetries".to_string(),
-                count: guard.telemetries.len(),
-                sizeof_element: OrderedTelemetries::ELEMENT_SIZE,
-            })],
+            vec![
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "telemetries".to_string(),
+                    count: guard.telemetries.len(),
+                    sizeof_element: OrderedTelemetries::ELEMENT_SIZE,
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "history".to_string(),
+                    count: guard.telemetries.iter().map(|e| e.history.len()).sum(),
+                    sizeof_element: size_of::<TelemetrySample>(),
+                }),
+            ],
         )
     }
 
@@ -428,6 +452,47 @@ struct Entry {
     endpoint: SocketAddrV6,
     data: TelemetryData,
     last_updated: Instant,
+    history: VecDeque<TelemetrySample>,
+}
+
+/// The fields of a received telemetry message that are worth tracking over time
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TelemetrySample {
+    pub received: SystemTime,
+    pub block_count: u64,
+    pub cemented_count: u64,
+    pub bandwidth_cap: u64,
+    pub active_difficulty: u64,
+    pub major_version: u8,
+    pub minor_version: u8,
+    pub patch_version: u8,
+    pub pre_release_version: u8,
+    pub maker: u8,
+}
+
+impl TelemetrySample {
+    pub fn new(data: &TelemetryData, received: SystemTime) -> Self {
+        Self {
+            received,
+            block_count: data.block_count,
+            cemented_count: data.cemented_count,
+            bandwidth_cap: data.bandwidth_cap,
+            active_difficulty: data.active_difficulty,
+            major_version: data.major_version,
+            minor_version: data.minor_version,
+            patch_version: data.patch_version,
+            pre_release_version: data.pre_release_version,
+            maker: data.maker,
+        }
+    }
+
+    /// major.minor.patch.pre_release
+    pub fn version(&self) -> String {
+        format!(
+            "{}.{}.{}.{}",
+            self.major_version, self.minor_version, self.patch_version, self.pre_release_version
+        )
+    }
 }
 
 #[derive(Default)]
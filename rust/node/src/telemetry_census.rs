@@ -0,0 +1,212 @@
+use num_traits::FromPrimitive;
+use rsnano_core::Amount;
+use rsnano_messages::{TelemetryData, TelemetryMaker};
+use std::{collections::HashMap, net::SocketAddrV6};
+
+/// Summary of the telemetry data of all peers. Shows which node versions and
+/// implementations are running in the network and which peers fall behind
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct TelemetryCensus {
+    pub peers: usize,
+    pub versions: Vec<CensusBucket>,
+    pub makers: Vec<CensusBucket>,
+    pub median_cemented_count: u64,
+    pub lagging_peers: Vec<LaggingPeer>,
+}
+
+/// Number of peers and the representative weight behind a version or maker
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CensusBucket {
+    pub name: String,
+    pub peers: usize,
+    pub weight: Amount,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LaggingPeer {
+    pub endpoint: SocketAddrV6,
+    pub cemented_count: u64,
+    /// How many blocks the peer is behind the median cemented count
+    pub behind: u64,
+}
+
+impl TelemetryCensus {
+    pub const DEFAULT_LAG_THRESHOLD: u64 = 10_000;
+
+    /// A peer is lagging if its cemented count is more than `lag_threshold`
+    /// blocks behind the median. `weights` maps peers to the weight of the
+    /// representatives reachable through them
+    pub fn new(
+        telemetries: &HashMap<SocketAddrV6, TelemetryData>,
+        weights: &HashMap<SocketAddrV6, Amount>,
+        lag_threshold: u64,
+    ) -> Self {
+        let mut versions = HashMap::new();
+        let mut makers = HashMap::new();
+        for (endpoint, data) in telemetries {
+            let weight = weights.get(endpoint).cloned().unwrap_or_default();
+            add_to_bucket(&mut versions, version_name(data), weight);
+            add_to_bucket(&mut makers, maker_name(data.maker), weight);
+        }
+
+        let median_cemented_count = median_cemented_count(telemetries);
+        let mut lagging_peers: Vec<_> = telemetries
+            .iter()
+            .filter_map(|(endpoint, data)| {
+                let behind = median_cemented_count.saturating_sub(data.cemented_count);
+                (behind > lag_threshold).then_some(LaggingPeer {
+                    endpoint: *endpoint,
+                    cemented_count: data.cemented_count,
+                    behind,
+                })
+            })
+            .collect();
+        lagging_peers.sort_by(|a, b| b.behind.cmp(&a.behind).then(a.endpoint.cmp(&b.endpoint)));
+
+        Self {
+            peers: telemetries.len(),
+            versions: sorted_buckets(versions),
+            makers: sorted_buckets(makers),
+            median_cemented_count,
+            lagging_peers,
+        }
+    }
+}
+
+fn add_to_bucket(buckets: &mut HashMap<String, (usize, Amount)>, name: String, weight: Amount) {
+    let bucket = buckets.entry(name).or_default();
+    bucket.0 += 1;
+    bucket.1 += weight;
+}
+
+/// Heaviest buckets first
+fn sorted_buckets(buckets: HashMap<String, (usize, Amount)>) -> Vec<CensusBucket> {
+    let mut result: Vec<_> = buckets
+        .into_iter()
+        .map(|(name, (peers, weight))| CensusBucket {
+            name,
+            peers,
+            weight,
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        b.weight
+            .cmp(&a.weight)
+            .then(b.peers.cmp(&a.peers))
+            .then(a.name.cmp(&b.name))
+    });
+    result
+}
+
+fn median_cemented_count(telemetries: &HashMap<SocketAddrV6, TelemetryData>) -> u64 {
+    let mut counts: Vec<_> = telemetries.values().map(|t| t.cemented_count).collect();
+    if counts.is_empty() {
+        return 0;
+    }
+    counts.sort_unstable();
+    counts[counts.len() / 2]
+}
+
+fn version_name(data: &TelemetryData) -> String {
+    format!(
+        "{}.{}.{}.{}",
+        data.major_version, data.minor_version, data.patch_version, data.pre_release_version
+    )
+}
+
+fn maker_name(maker: u8) -> String {
+    match TelemetryMaker::from_u8(maker) {
+        Some(TelemetryMaker::NfNode) => "nf_node".to_owned(),
+        Some(TelemetryMaker::NfPrunedNode) => "nf_pruned_node".to_owned(),
+        Some(TelemetryMaker::NanoNodeLight) => "nano_node_light".to_owned(),
+        Some(TelemetryMaker::RsNano) => "rsnano".to_owned(),
+        None => format!("unknown_{}", maker),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::utils::TEST_ENDPOINT_1;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn empty() {
+        let census = TelemetryCensus::new(&HashMap::new(), &HashMap::new(), 100);
+        assert_eq!(census, TelemetryCensus::default());
+    }
+
+    #[test]
+    fn group_versions_and_makers_by_weight() {
+        let telemetries = HashMap::from([
+            (endpoint(1), telemetry(2, TelemetryMaker::RsNano, 1000)),
+            (endpoint(2), telemetry(2, TelemetryMaker::RsNano, 1000)),
+            (endpoint(3), telemetry(1, TelemetryMaker::NfNode, 1000)),
+        ]);
+        let weights = HashMap::from([
+            (endpoint(1), Amount::raw(10)),
+            (endpoint(3), Amount::raw(50)),
+        ]);
+
+        let census = TelemetryCensus::new(&telemetries, &weights, 100);
+
+        assert_eq!(census.peers, 3);
+        assert_eq!(
+            census.versions,
+            vec![
+                CensusBucket {
+                    name: "1.0.0.0".to_owned(),
+                    peers: 1,
+                    weight: Amount::raw(50)
+                },
+                CensusBucket {
+                    name: "2.0.0.0".to_owned(),
+                    peers: 2,
+                    weight: Amount::raw(10)
+                },
+            ]
+        );
+        assert_eq!(census.makers[0].name, "nf_node");
+        assert_eq!(census.makers[1].name, "rsnano");
+        assert_eq!(census.makers[1].peers, 2);
+    }
+
+    #[test]
+    fn detect_lagging_peers() {
+        let telemetries = HashMap::from([
+            (endpoint(1), telemetry(2, TelemetryMaker::RsNano, 1000)),
+            (endpoint(2), telemetry(2, TelemetryMaker::RsNano, 990)),
+            (endpoint(3), telemetry(2, TelemetryMaker::RsNano, 500)),
+        ]);
+
+        let census = TelemetryCensus::new(&telemetries, &HashMap::new(), 100);
+
+        assert_eq!(census.median_cemented_count, 990);
+        assert_eq!(
+            census.lagging_peers,
+            vec![LaggingPeer {
+                endpoint: endpoint(3),
+                cemented_count: 500,
+                behind: 490
+            }]
+        );
+    }
+
+    #[test]
+    fn unknown_maker() {
+        assert_eq!(maker_name(42), "unknown_42");
+    }
+
+    fn endpoint(i: u16) -> SocketAddrV6 {
+        SocketAddrV6::new(Ipv6Addr::LOCALHOST, TEST_ENDPOINT_1.port() + i, 0, 0)
+    }
+
+    fn telemetry(major_version: u8, maker: TelemetryMaker, cemented_count: u64) -> TelemetryData {
+        TelemetryData {
+            major_version,
+            maker: maker as u8,
+            cemented_count,
+            ..TelemetryData::default()
+        }
+    }
+}
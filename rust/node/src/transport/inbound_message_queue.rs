@@ -1,4 +1,4 @@
-use super::{FairQueue, MessageCallback};
+use super::{FairQueue, MessageCallback, TrustedPeers};
 use crate::stats::{DetailType, StatType, Stats};
 use rsnano_core::utils::ContainerInfoComponent;
 use rsnano_messages::Message;
@@ -17,10 +17,13 @@ pub struct InboundMessageQueue {
 }
 
 impl InboundMessageQueue {
-    pub fn new(max_queue: usize, stats: Arc<Stats>) -> Self {
+    pub fn new(max_queue: usize, stats: Arc<Stats>, trusted_peers: Arc<TrustedPeers>) -> Self {
         Self {
             state: Mutex::new(State {
-                queue: FairQueue::new(Box::new(move |_| max_queue), Box::new(|_| 1)),
+                queue: FairQueue::new(
+                    Box::new(move |_| max_queue),
+                    Box::new(move |channel_id| trusted_peers.priority(channel_id)),
+                ),
                 stopped: false,
             }),
             condition: Condvar::new(),
@@ -116,7 +119,11 @@ impl InboundMessageQueue {
 
 impl Default for InboundMessageQueue {
     fn default() -> Self {
-        Self::new(64, Arc::new(Stats::default()))
+        Self::new(
+            64,
+            Arc::new(Stats::default()),
+            Arc::new(TrustedPeers::default()),
+        )
     }
 }
 
@@ -149,7 +156,11 @@ mod tests {
 
     #[test]
     fn put_and_get_one_message() {
-        let manager = InboundMessageQueue::new(1, Arc::new(Stats::default()));
+        let manager = InboundMessageQueue::new(
+            1,
+            Arc::new(Stats::default()),
+            Arc::new(TrustedPeers::default()),
+        );
         assert_eq!(manager.size(), 0);
         manager.put(
             Message::BulkPush,
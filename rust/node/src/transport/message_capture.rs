@@ -0,0 +1,248 @@
+use super::{MessageDeserializer, NetworkFilter, VecBufferReader};
+use rsnano_core::work::WorkThresholds;
+use rsnano_messages::{
+    DeserializedMessage, Message, MessageHeader, MessageSerializer, ProtocolInfo,
+};
+use rsnano_network::{ChannelDirection, ChannelId};
+use std::{
+    io::{ErrorKind, Read, Write},
+    sync::Arc,
+};
+
+/// Every capture file starts with these bytes
+pub const MESSAGE_CAPTURE_MAGIC: [u8; 8] = *b"RSNCAP01";
+
+/// A network message as it was sent or received over a channel
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CapturedMessage {
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: i64,
+    pub channel_id: ChannelId,
+    pub direction: ChannelDirection,
+    /// Message header and payload exactly as they are sent over the wire
+    pub bytes: Vec<u8>,
+}
+
+impl CapturedMessage {
+    pub fn new(
+        timestamp: i64,
+        channel_id: ChannelId,
+        direction: ChannelDirection,
+        message: &Message,
+        protocol: ProtocolInfo,
+    ) -> Self {
+        let mut serializer = MessageSerializer::new(protocol);
+        Self {
+            timestamp,
+            channel_id,
+            direction,
+            bytes: serializer.serialize(message).to_vec(),
+        }
+    }
+
+    /// Parses the raw bytes with the same deserializer that is used for live channels
+    pub async fn deserialize(
+        &self,
+        protocol: ProtocolInfo,
+        work_thresholds: WorkThresholds,
+        network_filter: Arc<NetworkFilter>,
+    ) -> anyhow::Result<DeserializedMessage> {
+        let mut deserializer = MessageDeserializer::new(
+            protocol,
+            work_thresholds,
+            network_filter,
+            VecBufferReader::new(self.bytes.clone()),
+        );
+        deserializer
+            .read()
+            .await
+            .map_err(|e| anyhow!("invalid captured message: {:?}", e))
+    }
+
+    pub fn new_test_instance() -> Self {
+        Self::new(
+            1_700_000_000_000,
+            ChannelId::from(42),
+            ChannelDirection::Inbound,
+            &Message::TelemetryReq,
+            ProtocolInfo::default(),
+        )
+    }
+}
+
+/// Writes network messages to a capture file.
+///
+/// The file starts with `MESSAGE_CAPTURE_MAGIC`, followed by one record per message.
+/// All integers are little endian:
+///
+/// | field      | size   | content                                     |
+/// |------------|--------|---------------------------------------------|
+/// | timestamp  | 8      | i64, milliseconds since the UNIX epoch      |
+/// | channel id | 8      | u64                                         |
+/// | direction  | 1      | 0 = inbound, 1 = outbound                   |
+/// | length     | 4      | u32, size of the message bytes              |
+/// | message    | length | message header and payload as on the wire   |
+pub struct MessageCaptureWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> MessageCaptureWriter<W> {
+    pub fn new(mut writer: W) -> std::io::Result<Self> {
+        writer.write_all(&MESSAGE_CAPTURE_MAGIC)?;
+        Ok(Self { writer })
+    }
+
+    pub fn write(&mut self, message: &CapturedMessage) -> std::io::Result<()> {
+        let direction: u8 = match message.direction {
+            ChannelDirection::Inbound => 0,
+            ChannelDirection::Outbound => 1,
+        };
+        self.writer.write_all(&message.timestamp.to_le_bytes())?;
+        self.writer
+            .write_all(&(message.channel_id.as_usize() as u64).to_le_bytes())?;
+        self.writer.write_all(&[direction])?;
+        self.writer
+            .write_all(&(message.bytes.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&message.bytes)
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Reads the records of a capture file that was written by `MessageCaptureWriter`
+pub struct MessageCaptureReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> MessageCaptureReader<R> {
+    pub fn new(mut reader: R) -> anyhow::Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != MESSAGE_CAPTURE_MAGIC {
+            bail!("not a message capture file");
+        }
+        Ok(Self { reader })
+    }
+
+    /// Returns None at the end of the capture
+    pub fn read(&mut self) -> anyhow::Result<Option<CapturedMessage>> {
+        let mut timestamp = [0; 8];
+        match self.reader.read_exact(&mut timestamp) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut channel_id = [0; 8];
+        self.reader.read_exact(&mut channel_id)?;
+
+        let mut direction = [0; 1];
+        self.reader.read_exact(&mut direction)?;
+        let direction = match direction[0] {
+            0 => ChannelDirection::Inbound,
+            1 => ChannelDirection::Outbound,
+            _ => bail!("invalid channel direction: {}", direction[0]),
+        };
+
+        let mut len = [0; 4];
+        self.reader.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MessageHeader::SERIALIZED_SIZE + Message::MAX_MESSAGE_SIZE {
+            bail!("captured message too big: {} bytes", len);
+        }
+        let mut bytes = vec![0; len];
+        self.reader.read_exact(&mut bytes)?;
+
+        Ok(Some(CapturedMessage {
+            timestamp: i64::from_le_bytes(timestamp),
+            channel_id: ChannelId::from(u64::from_le_bytes(channel_id) as usize),
+            direction,
+            bytes,
+        }))
+    }
+
+    pub fn read_all(&mut self) -> anyhow::Result<Vec<CapturedMessage>> {
+        let mut messages = Vec::new();
+        while let Some(message) = self.read()? {
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_messages::{BulkPull, Publish};
+
+    #[test]
+    fn empty_capture() {
+        let mut buffer = Vec::new();
+        MessageCaptureWriter::new(&mut buffer).unwrap();
+
+        let mut reader = MessageCaptureReader::new(buffer.as_slice()).unwrap();
+        assert!(reader.read().unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_magic() {
+        let buffer = b"NOTACAPTURE".to_vec();
+        assert!(MessageCaptureReader::new(buffer.as_slice()).is_err());
+    }
+
+    #[test]
+    fn write_and_read_messages() {
+        let message1 = CapturedMessage::new_test_instance();
+        let message2 = CapturedMessage::new(
+            1_700_000_000_500,
+            ChannelId::from(7),
+            ChannelDirection::Outbound,
+            &Message::BulkPull(BulkPull::new_test_instance()),
+            ProtocolInfo::default(),
+        );
+
+        let mut buffer = Vec::new();
+        let mut writer = MessageCaptureWriter::new(&mut buffer).unwrap();
+        writer.write(&message1).unwrap();
+        writer.write(&message2).unwrap();
+
+        let mut reader = MessageCaptureReader::new(buffer.as_slice()).unwrap();
+        assert_eq!(reader.read_all().unwrap(), vec![message1, message2]);
+    }
+
+    #[test]
+    fn truncated_record() {
+        let mut buffer = Vec::new();
+        let mut writer = MessageCaptureWriter::new(&mut buffer).unwrap();
+        writer.write(&CapturedMessage::new_test_instance()).unwrap();
+        buffer.pop();
+
+        let mut reader = MessageCaptureReader::new(buffer.as_slice()).unwrap();
+        assert!(reader.read().is_err());
+    }
+
+    #[tokio::test]
+    async fn deserialize_captured_message() {
+        let message = Message::Publish(Publish::new_test_instance());
+        let captured = CapturedMessage::new(
+            1,
+            ChannelId::from(1),
+            ChannelDirection::Inbound,
+            &message,
+            ProtocolInfo::default(),
+        );
+
+        let deserialized = captured
+            .deserialize(
+                ProtocolInfo::default(),
+                WorkThresholds::new(0, 0, 0),
+                Arc::new(NetworkFilter::default()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(deserialized.message, message);
+    }
+}
@@ -3,7 +3,9 @@ use crate::{
     stats::{Direction, StatType, Stats},
 };
 use rsnano_messages::{Message, MessageSerializer, ProtocolInfo};
-use rsnano_network::{ChannelId, ChannelInfo, DropPolicy, Network, TrafficType};
+use rsnano_network::{
+    bandwidth_limiter::BandwidthLimitType, ChannelId, ChannelInfo, DropPolicy, Network, TrafficType,
+};
 use std::sync::{Arc, Mutex};
 use tracing::trace;
 
@@ -176,9 +178,32 @@ fn try_send_serialized_message(
     drop_policy: DropPolicy,
     traffic_type: TrafficType,
 ) -> bool {
+    let category = limit_category(message);
+    let limited = drop_policy == DropPolicy::CanDrop;
+    if limited {
+        if let Some(limit_type) = exceeded_bandwidth_limit(network, channel_id, buffer, category) {
+            stats.inc_dir(
+                StatType::BandwidthLimiter,
+                limit_type.into(),
+                Direction::Out,
+            );
+            stats.inc_dir_aggregate(StatType::Drop, message.into(), Direction::Out);
+            trace!(%channel_id, message = ?message, limit = limit_type.as_str(), "Message dropped by bandwidth limiter");
+            return false;
+        }
+    }
+
     let sent = network.try_send_buffer(channel_id, buffer, drop_policy, traffic_type);
 
     if sent {
+        if limited {
+            // The budgets are only used by messages that actually went out
+            let limiter = network.limiter();
+            if let Some(category) = category {
+                limiter.consume(buffer.len(), category);
+            }
+            limiter.consume_peer(buffer.len(), channel_id);
+        }
         stats.inc_dir_aggregate(StatType::Message, message.into(), Direction::Out);
         trace!(%channel_id, message = ?message, "Message sent");
     } else {
@@ -189,3 +214,59 @@ fn try_send_serialized_message(
 
     sent
 }
+
+fn limit_category(message: &Message) -> Option<BandwidthLimitType> {
+    match message {
+        Message::ConfirmAck(_) => Some(BandwidthLimitType::Vote),
+        Message::Publish(_) => Some(BandwidthLimitType::Block),
+        Message::TelemetryReq | Message::TelemetryAck(_) => Some(BandwidthLimitType::Telemetry),
+        _ => None,
+    }
+}
+
+/// Checks the per message category and per peer budgets without using them.
+/// The standard and bootstrap budgets are enforced by the channel itself
+fn exceeded_bandwidth_limit(
+    network: &Network,
+    channel_id: ChannelId,
+    buffer: &[u8],
+    category: Option<BandwidthLimitType>,
+) -> Option<BandwidthLimitType> {
+    let limiter = network.limiter();
+    if let Some(category) = category {
+        if !limiter.can_pass(buffer.len(), category) {
+            return Some(category);
+        }
+    }
+
+    if !limiter.can_pass_peer(buffer.len(), channel_id) {
+        return Some(BandwidthLimitType::Peer);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dont_use_bandwidth_budget_when_message_isnt_sent() {
+        let mut publisher = MessagePublisher::new_null(tokio::runtime::Handle::current());
+        let limiter = publisher.network.limiter().clone();
+        limiter.reset(8, 1.0, BandwidthLimitType::Telemetry);
+        limiter.reset(8, 1.0, BandwidthLimitType::Peer);
+        let unknown_channel = ChannelId::from(42);
+
+        let sent = publisher.try_send(
+            unknown_channel,
+            &Message::TelemetryReq,
+            DropPolicy::CanDrop,
+            TrafficType::Generic,
+        );
+
+        assert!(!sent);
+        assert!(limiter.can_pass(8, BandwidthLimitType::Telemetry));
+        assert!(limiter.can_pass_peer(8, unknown_channel));
+    }
+}
@@ -0,0 +1,252 @@
+use super::{CapturedMessage, InboundMessageQueue, NetworkFilter};
+use crate::NetworkParams;
+use rsnano_core::work::WorkThresholds;
+use rsnano_messages::ProtocolInfo;
+use rsnano_network::{ChannelDirection, ChannelId, ChannelInfo};
+use rsnano_nullable_clock::SteadyClock;
+use std::{
+    collections::HashMap,
+    net::{Ipv6Addr, SocketAddrV6},
+    sync::Arc,
+    time::Duration,
+};
+
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ReplayResult {
+    /// Messages that were put into the inbound queue
+    pub replayed: usize,
+    /// Messages that were rejected by the full inbound queue
+    pub dropped: usize,
+    /// Messages that could not be deserialized
+    pub invalid: usize,
+    /// Outbound messages are part of the capture, but they are not replayed
+    pub outbound: usize,
+}
+
+/// Feeds the inbound messages of a capture into an `InboundMessageQueue`.
+/// The messages are replayed in the order of their timestamps. Instead of waiting
+/// for the real time to pass, a nulled clock gets advanced to the capture time of
+/// each message, so that replaying a capture always produces the same result.
+pub struct MessageReplay {
+    queue: Arc<InboundMessageQueue>,
+    clock: Arc<SteadyClock>,
+    protocol: ProtocolInfo,
+    work_thresholds: WorkThresholds,
+    network_filter: Arc<NetworkFilter>,
+    channels: HashMap<ChannelId, Arc<ChannelInfo>>,
+}
+
+impl MessageReplay {
+    pub fn new(
+        queue: Arc<InboundMessageQueue>,
+        clock: Arc<SteadyClock>,
+        network_params: &NetworkParams,
+    ) -> Self {
+        Self {
+            queue,
+            clock,
+            protocol: network_params.network.protocol_info(),
+            work_thresholds: network_params.work.clone(),
+            network_filter: Arc::new(NetworkFilter::default()),
+            channels: HashMap::new(),
+        }
+    }
+
+    pub fn clock(&self) -> &Arc<SteadyClock> {
+        &self.clock
+    }
+
+    pub async fn replay(&mut self, capture: &[CapturedMessage]) -> ReplayResult {
+        let mut messages: Vec<_> = capture.iter().collect();
+        messages.sort_by_key(|m| m.timestamp);
+
+        let mut result = ReplayResult::default();
+        let mut last_timestamp = messages.first().map(|m| m.timestamp).unwrap_or_default();
+
+        for captured in messages {
+            let elapsed = captured.timestamp.saturating_sub(last_timestamp).max(0);
+            self.clock.advance(Duration::from_millis(elapsed as u64));
+            last_timestamp = captured.timestamp;
+
+            if captured.direction == ChannelDirection::Outbound {
+                result.outbound += 1;
+                continue;
+            }
+
+            let deserialized = captured
+                .deserialize(
+                    self.protocol,
+                    self.work_thresholds.clone(),
+                    self.network_filter.clone(),
+                )
+                .await;
+
+            let Ok(deserialized) = deserialized else {
+                result.invalid += 1;
+                continue;
+            };
+
+            let channel = self.get_channel(captured);
+            if self.queue.put(deserialized.message, channel) {
+                result.replayed += 1;
+            } else {
+                result.dropped += 1;
+            }
+        }
+
+        result
+    }
+
+    fn get_channel(&mut self, captured: &CapturedMessage) -> Arc<ChannelInfo> {
+        let now = self.clock.now();
+        let protocol_version = self.protocol.version_using;
+        self.channels
+            .entry(captured.channel_id)
+            .or_insert_with(|| {
+                // The real peer address is not part of the capture
+                let peer_addr = SocketAddrV6::new(
+                    Ipv6Addr::LOCALHOST,
+                    captured.channel_id.as_usize() as u16,
+                    0,
+                    0,
+                );
+                Arc::new(ChannelInfo::new(
+                    captured.channel_id,
+                    SocketAddrV6::new(Ipv6Addr::LOCALHOST, 0, 0, 0),
+                    peer_addr,
+                    ChannelDirection::Inbound,
+                    protocol_version,
+                    now,
+                ))
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stats::Stats, transport::TrustedPeers, DEV_NETWORK_PARAMS};
+    use rsnano_messages::{BulkPull, Message};
+    use rsnano_nullable_clock::Timestamp;
+
+    #[tokio::test]
+    async fn empty_capture() {
+        let mut fixture = Fixture::new(10);
+
+        let result = fixture.replay.replay(&[]).await;
+
+        assert_eq!(result, ReplayResult::default());
+        assert_eq!(fixture.queue.size(), 0);
+    }
+
+    #[tokio::test]
+    async fn replay_inbound_messages_in_timestamp_order() {
+        let mut fixture = Fixture::new(10);
+        let second = captured(2000, Message::BulkPull(BulkPull::new_test_instance()));
+        let first = captured(1000, Message::TelemetryReq);
+
+        let result = fixture.replay.replay(&[second, first]).await;
+
+        assert_eq!(result.replayed, 2);
+        let batch = fixture.queue.next_batch(10);
+        let messages: Vec<_> = batch.iter().map(|(_, (msg, _))| msg.clone()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                Message::TelemetryReq,
+                Message::BulkPull(BulkPull::new_test_instance())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn advance_clock_by_capture_time() {
+        let mut fixture = Fixture::new(10);
+        let start = fixture.replay.clock().now();
+
+        fixture
+            .replay
+            .replay(&[
+                captured(1000, Message::TelemetryReq),
+                captured(1500, Message::TelemetryReq),
+            ])
+            .await;
+
+        assert_eq!(
+            fixture.replay.clock().now(),
+            start + Duration::from_millis(500)
+        );
+    }
+
+    #[tokio::test]
+    async fn skip_outbound_messages() {
+        let mut fixture = Fixture::new(10);
+        let outbound = CapturedMessage {
+            direction: ChannelDirection::Outbound,
+            ..captured(1000, Message::TelemetryReq)
+        };
+
+        let result = fixture.replay.replay(&[outbound]).await;
+
+        assert_eq!(result.outbound, 1);
+        assert_eq!(fixture.queue.size(), 0);
+    }
+
+    #[tokio::test]
+    async fn count_invalid_messages() {
+        let mut fixture = Fixture::new(10);
+        let invalid = CapturedMessage {
+            bytes: vec![1, 2, 3],
+            ..captured(1000, Message::TelemetryReq)
+        };
+
+        let result = fixture.replay.replay(&[invalid]).await;
+
+        assert_eq!(result.invalid, 1);
+    }
+
+    #[tokio::test]
+    async fn count_dropped_messages() {
+        let mut fixture = Fixture::new(1);
+
+        let result = fixture
+            .replay
+            .replay(&[
+                captured(1000, Message::TelemetryReq),
+                captured(1001, Message::TelemetryReq),
+            ])
+            .await;
+
+        assert_eq!(result.replayed, 1);
+        assert_eq!(result.dropped, 1);
+    }
+
+    struct Fixture {
+        queue: Arc<InboundMessageQueue>,
+        replay: MessageReplay,
+    }
+
+    impl Fixture {
+        fn new(max_queue: usize) -> Self {
+            let queue = Arc::new(InboundMessageQueue::new(
+                max_queue,
+                Arc::new(Stats::default()),
+                Arc::new(TrustedPeers::default()),
+            ));
+            let clock = Arc::new(SteadyClock::new_null_with(Timestamp::from(0)));
+            let replay = MessageReplay::new(queue.clone(), clock, &DEV_NETWORK_PARAMS);
+            Self { queue, replay }
+        }
+    }
+
+    fn captured(timestamp: i64, message: Message) -> CapturedMessage {
+        CapturedMessage::new(
+            timestamp,
+            ChannelId::from(1),
+            ChannelDirection::Inbound,
+            &message,
+            DEV_NETWORK_PARAMS.network.protocol_info(),
+        )
+    }
+}
@@ -3,17 +3,22 @@ mod fair_queue;
 mod handshake_process;
 mod inbound_message_queue;
 mod latest_keepalives;
+mod message_capture;
 mod message_deserializer;
 mod message_processor;
 mod message_publisher;
+mod message_replay;
 mod network_filter;
 mod network_threads;
+mod peer_bans;
 mod peer_cache_connector;
 mod peer_cache_updater;
+mod port_mapping;
 mod realtime_message_handler;
 mod response_server;
 mod response_server_spawner;
 mod syn_cookies;
+mod trusted_peers;
 mod vec_buffer_reader;
 
 pub use block_deserializer::read_block;
@@ -21,15 +26,20 @@ pub(crate) use fair_queue::*;
 pub(crate) use handshake_process::*;
 pub use inbound_message_queue::*;
 pub use latest_keepalives::*;
+pub use message_capture::*;
 pub use message_deserializer::MessageDeserializer;
 pub use message_processor::*;
 pub use message_publisher::*;
+pub use message_replay::*;
 pub use network_filter::NetworkFilter;
 pub(crate) use network_threads::*;
+pub use peer_bans::*;
 pub use peer_cache_connector::*;
 pub use peer_cache_updater::*;
+pub use port_mapping::*;
 pub use realtime_message_handler::RealtimeMessageHandler;
 pub use response_server::*;
 pub use response_server_spawner::*;
 pub use syn_cookies::SynCookies;
+pub use trusted_peers::*;
 pub use vec_buffer_reader::VecBufferReader;
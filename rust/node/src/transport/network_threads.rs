@@ -192,27 +192,17 @@ impl KeepaliveFactory {
         result.peers[3] = result.peers[1];
         // Replace part of message with node external address or listening port
         result.peers[1] = SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0); // For node v19 (response channels)
-        if self.config.external_address != Ipv6Addr::UNSPECIFIED.to_string()
-            && self.config.external_port != 0
-        {
-            result.peers[0] = SocketAddrV6::new(
-                self.config.external_address.parse().unwrap(),
-                self.config.external_port,
-                0,
-                0,
-            );
-        } else {
-            // TODO Read external address from port_mapping!
-            //let external_address  node.port_mapping.external_address ());
-            let external_address = SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0);
-            if !external_address.ip().is_unspecified() {
-                result.peers[0] =
-                    SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, network.listening_port(), 0, 0);
-                result.peers[1] = external_address;
-            } else {
-                result.peers[0] =
-                    SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, network.listening_port(), 0, 0);
+        let listening = SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, network.listening_port(), 0, 0);
+        match network.external_address() {
+            Some(external) if Some(external) == self.config.configured_external_address() => {
+                result.peers[0] = external;
+            }
+            // Discovered by the port mapping
+            Some(external) => {
+                result.peers[0] = listening;
+                result.peers[1] = external;
             }
+            None => result.peers[0] = listening,
         }
         result
     }
@@ -0,0 +1,287 @@
+use rsnano_ledger::Ledger;
+use rsnano_network::{
+    peer_exclusion::{ExcludedPeer, IpSubnet},
+    NetworkInfo,
+};
+use rsnano_nullable_clock::{SteadyClock, SystemTimeFactory, Timestamp};
+use rsnano_store_lmdb::StoredPeerBan;
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+use tracing::info;
+
+/// A subnet that was banned by the node operator
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PeerBan {
+    pub subnet: IpSubnet,
+    /// None if the ban never expires
+    pub until: Option<SystemTime>,
+}
+
+/// A peer that is excluded because it misbehaved
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MisbehavingPeer {
+    pub peer: ExcludedPeer,
+    pub exclude_until: SystemTime,
+}
+
+/// Lets the node operator ban and unban peers. Bans are written to the database,
+/// so that they are applied again when the node is restarted
+pub struct PeerBans {
+    network_info: Arc<RwLock<NetworkInfo>>,
+    ledger: Arc<Ledger>,
+    steady_clock: Arc<SteadyClock>,
+    time_factory: SystemTimeFactory,
+}
+
+impl PeerBans {
+    /// Longer bans are cut to this duration. Use a ban without duration to ban forever
+    pub const MAX_DURATION: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+
+    pub fn new(
+        network_info: Arc<RwLock<NetworkInfo>>,
+        ledger: Arc<Ledger>,
+        steady_clock: Arc<SteadyClock>,
+        time_factory: SystemTimeFactory,
+    ) -> Self {
+        Self {
+            network_info,
+            ledger,
+            steady_clock,
+            time_factory,
+        }
+    }
+
+    /// Applies the bans of the last run and deletes the expired ones
+    pub fn load(&self) {
+        let now = self.time_factory.now();
+        let mut tx = self.ledger.rw_txn();
+        let stored: Vec<_> = self.ledger.store.peer_ban.iter(&tx).collect();
+        let mut network_info = self.network_info.write().unwrap();
+        let mut loaded = 0;
+        for ban in stored {
+            match ban.until {
+                Some(until) if until <= now => {
+                    self.ledger
+                        .store
+                        .peer_ban
+                        .del(&mut tx, &ban.address, ban.prefix_len);
+                }
+                _ => {
+                    let subnet = IpSubnet::new(ban.address, ban.prefix_len);
+                    network_info.ban(subnet, self.steady_until(ban.until, now));
+                    loaded += 1;
+                }
+            }
+        }
+        if loaded > 0 {
+            info!("Loaded {} banned subnets", loaded);
+        }
+    }
+
+    /// Bans the subnet and closes all channels to it. A ban without duration never expires.
+    /// Returns the number of closed channels
+    pub fn ban(&self, subnet: IpSubnet, duration: Option<Duration>) -> usize {
+        let duration = duration.map(|d| d.min(Self::MAX_DURATION));
+        let until = duration.and_then(|d| self.time_factory.now().checked_add(d));
+        let closed = self
+            .network_info
+            .write()
+            .unwrap()
+            .ban(subnet, self.steady_until(until, self.time_factory.now()));
+
+        let mut tx = self.ledger.rw_txn();
+        self.ledger.store.peer_ban.put(
+            &mut tx,
+            &StoredPeerBan {
+                address: subnet.address(),
+                prefix_len: subnet.prefix_len(),
+                until,
+            },
+        );
+        info!(%subnet, ?duration, "Subnet banned");
+        closed
+    }
+
+    /// Removes the ban of the subnet and forgets the misbehavior of its peers.
+    /// Returns false if nothing was banned or excluded
+    pub fn unban(&self, subnet: &IpSubnet) -> bool {
+        let removed = self.network_info.write().unwrap().unban(subnet);
+        let mut tx = self.ledger.rw_txn();
+        let stored =
+            self.ledger.store.peer_ban.iter(&tx).any(|ban| {
+                ban.address == subnet.address() && ban.prefix_len == subnet.prefix_len()
+            });
+        if stored {
+            self.ledger
+                .store
+                .peer_ban
+                .del(&mut tx, &subnet.address(), subnet.prefix_len());
+        }
+        if removed || stored {
+            info!(%subnet, "Subnet unbanned");
+        }
+        removed || stored
+    }
+
+    /// Bans that have not expired yet
+    pub fn bans(&self) -> Vec<PeerBan> {
+        let now = self.time_factory.now();
+        let tx = self.ledger.read_txn();
+        self.ledger
+            .store
+            .peer_ban
+            .iter(&tx)
+            .filter(|ban| ban.until.map(|until| until > now).unwrap_or(true))
+            .map(|ban| PeerBan {
+                subnet: IpSubnet::new(ban.address, ban.prefix_len),
+                until: ban.until,
+            })
+            .collect()
+    }
+
+    pub fn misbehaving_peers(&self) -> Vec<MisbehavingPeer> {
+        let now = self.time_factory.now();
+        let steady_now = self.steady_clock.now();
+        self.network_info
+            .read()
+            .unwrap()
+            .misbehaving_peers()
+            .into_iter()
+            .map(|peer| {
+                let exclude_until = if peer.exclude_until > steady_now {
+                    now + (peer.exclude_until - steady_now)
+                } else {
+                    now - peer.exclude_until.elapsed(steady_now)
+                };
+                MisbehavingPeer {
+                    peer,
+                    exclude_until,
+                }
+            })
+            .collect()
+    }
+
+    fn steady_until(&self, until: Option<SystemTime>, now: SystemTime) -> Timestamp {
+        match until {
+            Some(until) => {
+                let remaining = until.duration_since(now).unwrap_or_default();
+                self.steady_clock.now() + remaining.min(Self::MAX_DURATION)
+            }
+            None => Timestamp::MAX,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::utils::{new_test_timestamp, NULL_ENDPOINT, TEST_ENDPOINT_1, TEST_ENDPOINT_2};
+    use rsnano_network::{ChannelDirection, ChannelMode};
+
+    #[test]
+    fn ban_closes_channels_and_persists_ban() {
+        let now = new_test_timestamp();
+        let steady_clock = Arc::new(SteadyClock::new_null());
+        let mut network = NetworkInfo::new_test_instance();
+        let channel = network
+            .add(
+                NULL_ENDPOINT,
+                TEST_ENDPOINT_1,
+                ChannelDirection::Inbound,
+                ChannelMode::Realtime,
+                steady_clock.now(),
+            )
+            .unwrap();
+        let network = Arc::new(RwLock::new(network));
+        let ledger = Arc::new(Ledger::new_null_builder().finish());
+        let put_tracker = ledger.store.peer_ban.track_puts();
+        let bans = PeerBans::new(
+            network.clone(),
+            ledger,
+            steady_clock.clone(),
+            SystemTimeFactory::new_null_with(now),
+        );
+        let subnet = IpSubnet::single(*TEST_ENDPOINT_1.ip());
+
+        let closed = bans.ban(subnet, Some(Duration::from_secs(60)));
+
+        assert_eq!(closed, 1);
+        assert!(channel.is_closed());
+        assert!(network
+            .write()
+            .unwrap()
+            .is_excluded(&TEST_ENDPOINT_1, steady_clock.now()));
+        assert_eq!(
+            put_tracker.output(),
+            vec![StoredPeerBan {
+                address: *TEST_ENDPOINT_1.ip(),
+                prefix_len: 128,
+                until: Some(now + Duration::from_secs(60)),
+            }]
+        );
+    }
+
+    #[test]
+    fn cap_ban_duration() {
+        let now = new_test_timestamp();
+        let steady_clock = Arc::new(SteadyClock::new_null());
+        let network = Arc::new(RwLock::new(NetworkInfo::new_test_instance()));
+        let ledger = Arc::new(Ledger::new_null_builder().finish());
+        let put_tracker = ledger.store.peer_ban.track_puts();
+        let bans = PeerBans::new(
+            network.clone(),
+            ledger,
+            steady_clock.clone(),
+            SystemTimeFactory::new_null_with(now),
+        );
+
+        bans.ban(IpSubnet::single(*TEST_ENDPOINT_1.ip()), Some(Duration::MAX));
+
+        assert_eq!(
+            put_tracker.output()[0].until,
+            Some(now + PeerBans::MAX_DURATION)
+        );
+        assert_eq!(
+            network.read().unwrap().banned_subnets()[0].1,
+            steady_clock.now() + PeerBans::MAX_DURATION
+        );
+    }
+
+    #[test]
+    fn load_stored_bans() {
+        let now = new_test_timestamp();
+        let steady_clock = Arc::new(SteadyClock::new_null());
+        let active = StoredPeerBan {
+            address: *TEST_ENDPOINT_1.ip(),
+            prefix_len: 128,
+            until: None,
+        };
+        let expired = StoredPeerBan {
+            address: *TEST_ENDPOINT_2.ip(),
+            prefix_len: 128,
+            until: Some(now - Duration::from_secs(1)),
+        };
+        let ledger = Arc::new(
+            Ledger::new_null_builder()
+                .peer_bans([active, expired])
+                .finish(),
+        );
+        let delete_tracker = ledger.store.peer_ban.track_deletions();
+        let network = Arc::new(RwLock::new(NetworkInfo::new_test_instance()));
+        let bans = PeerBans::new(
+            network.clone(),
+            ledger,
+            steady_clock.clone(),
+            SystemTimeFactory::new_null_with(now),
+        );
+
+        bans.load();
+
+        let mut network = network.write().unwrap();
+        assert!(network.is_excluded(&TEST_ENDPOINT_1, steady_clock.now()));
+        assert!(!network.is_excluded(&TEST_ENDPOINT_2, steady_clock.now()));
+        assert_eq!(delete_tracker.output(), vec![(*TEST_ENDPOINT_2.ip(), 128)]);
+    }
+}
@@ -0,0 +1,550 @@
+mod natpmp;
+mod upnp;
+
+use crate::{
+    stats::{DetailType, StatType, Stats},
+    utils::{CancellationToken, Runnable},
+    PortmappingConstants,
+};
+use natpmp::{default_gateway, NatPmpClient, NATPMP_PORT};
+use rsnano_network::NetworkInfo;
+use rsnano_nullable_clock::{SteadyClock, Timestamp};
+use std::{
+    net::{SocketAddr, SocketAddrV4, SocketAddrV6},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::Duration,
+};
+use tracing::{info, warn};
+use upnp::{UpnpGateway, SSDP_ADDRESS};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortMappingConfig {
+    /// Maps the peering port on the gateway with UPnP or NAT-PMP
+    pub enabled: bool,
+    /// Address of the NAT-PMP gateway. The default gateway is used if none is set
+    pub natpmp_gateway: Option<SocketAddr>,
+    /// Where UPnP discovery requests are sent to
+    pub ssdp_address: SocketAddr,
+    /// How long to wait for the gateway to answer
+    pub timeout: Duration,
+}
+
+impl Default for PortMappingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            natpmp_gateway: None,
+            ssdp_address: SSDP_ADDRESS.parse().unwrap(),
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Maps the peering port on the internet gateway, so that peers can connect
+/// to a node behind a NAT. UPnP IGD is tried first, then NAT-PMP.
+/// The mapping gets renewed before its lease expires and it gets checked
+/// periodically, because gateways tend to forget mappings when they restart.
+/// The external address of the mapping is published in `NetworkInfo`, unless
+/// an external address is configured.
+pub struct PortMapping {
+    config: PortMappingConfig,
+    lease_duration: Duration,
+    network_info: Arc<RwLock<NetworkInfo>>,
+    stats: Arc<Stats>,
+    clock: Arc<SteadyClock>,
+    /// Taken out while the gateway is contacted, so that the lock isn't held during I/O
+    mapping: Mutex<Option<ActiveMapping>>,
+    external_address: RwLock<Option<SocketAddrV6>>,
+    configured_external_address: Option<SocketAddrV6>,
+    stopped: AtomicBool,
+}
+
+struct ActiveMapping {
+    gateway: Gateway,
+    internal_port: u16,
+    external: SocketAddrV4,
+    renew_at: Timestamp,
+}
+
+enum Gateway {
+    Upnp(UpnpGateway),
+    NatPmp(NatPmpClient),
+}
+
+impl Gateway {
+    fn name(&self) -> String {
+        match self {
+            Gateway::Upnp(gateway) => format!("UPnP gateway {}", gateway.control_address()),
+            Gateway::NatPmp(client) => format!("NAT-PMP gateway {}", client.gateway()),
+        }
+    }
+
+    /// Maps the port and returns the external address of the mapping
+    fn map(&self, internal_port: u16, lease: Duration) -> anyhow::Result<SocketAddrV4> {
+        match self {
+            Gateway::Upnp(gateway) => {
+                gateway.add_port_mapping(internal_port, internal_port, lease)?;
+                Ok(SocketAddrV4::new(
+                    gateway.external_address()?,
+                    internal_port,
+                ))
+            }
+            Gateway::NatPmp(client) => {
+                let mapping = client.map_tcp(internal_port, internal_port, lease)?;
+                Ok(SocketAddrV4::new(
+                    client.external_address()?,
+                    mapping.external_port,
+                ))
+            }
+        }
+    }
+
+    fn check(&self, external: &SocketAddrV4) -> anyhow::Result<()> {
+        let external_ip = match self {
+            Gateway::Upnp(gateway) => {
+                if !gateway.port_mapping_exists(external.port())? {
+                    bail!("port mapping disappeared");
+                }
+                gateway.external_address()?
+            }
+            Gateway::NatPmp(client) => client.external_address()?,
+        };
+        if external_ip != *external.ip() {
+            bail!("external address changed to {}", external_ip);
+        }
+        Ok(())
+    }
+
+    fn unmap(&self, internal_port: u16, external_port: u16) -> anyhow::Result<()> {
+        match self {
+            Gateway::Upnp(gateway) => gateway.delete_port_mapping(external_port),
+            Gateway::NatPmp(client) => client.unmap_tcp(internal_port),
+        }
+    }
+}
+
+impl PortMapping {
+    pub fn new(
+        config: PortMappingConfig,
+        constants: &PortmappingConstants,
+        network_info: Arc<RwLock<NetworkInfo>>,
+        stats: Arc<Stats>,
+        clock: Arc<SteadyClock>,
+        configured_external_address: Option<SocketAddrV6>,
+    ) -> Self {
+        network_info
+            .write()
+            .unwrap()
+            .set_external_address(configured_external_address);
+        Self {
+            config,
+            lease_duration: Duration::from_secs(constants.lease_duration_s as u64),
+            network_info,
+            stats,
+            clock,
+            mapping: Mutex::new(None),
+            external_address: RwLock::new(None),
+            configured_external_address,
+            stopped: AtomicBool::new(false),
+        }
+    }
+
+    /// The external address of the peering port, if the port is mapped
+    pub fn external_address(&self) -> Option<SocketAddrV6> {
+        *self.external_address.read().unwrap()
+    }
+
+    /// Creates the mapping, renews it or checks if it still works
+    pub fn refresh(&self) {
+        let internal_port = self.network_info.read().unwrap().listening_port();
+        if internal_port == 0 || self.stopped.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let previous = self.mapping.lock().unwrap().take();
+        let active = self.refresh_mapping(previous, internal_port);
+
+        let mut mapping = self.mapping.lock().unwrap();
+        if self.stopped.load(Ordering::SeqCst) {
+            // The node was stopped while the gateway was contacted
+            drop(mapping);
+            if let Some(active) = active {
+                Self::unmap(active);
+            }
+            return;
+        }
+        self.set_external_address(active.as_ref().map(|a| a.external));
+        *mapping = active;
+    }
+
+    fn refresh_mapping(
+        &self,
+        previous: Option<ActiveMapping>,
+        internal_port: u16,
+    ) -> Option<ActiveMapping> {
+        if let Some(mut active) = previous {
+            if active.internal_port == internal_port {
+                match self.keep_alive(&mut active) {
+                    Ok(()) => return Some(active),
+                    Err(e) => {
+                        self.stats
+                            .inc(StatType::PortMapping, DetailType::HealthCheckFailed);
+                        warn!(
+                            "Port mapping on {} is not working anymore: {:?}",
+                            active.gateway.name(),
+                            e
+                        );
+                    }
+                }
+            }
+            self.set_external_address(None);
+        }
+
+        match self.create_mapping(internal_port) {
+            Ok(active) => {
+                self.stats.inc(StatType::PortMapping, DetailType::Mapped);
+                info!(
+                    "Mapped port {} to external address {} on {}",
+                    internal_port,
+                    active.external,
+                    active.gateway.name()
+                );
+                Some(active)
+            }
+            Err(e) => {
+                self.stats
+                    .inc(StatType::PortMapping, DetailType::MappingFailed);
+                warn!("Could not map port {}: {:?}", internal_port, e);
+                None
+            }
+        }
+    }
+
+    /// Removes the mapping from the gateway
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        let mapping = self.mapping.lock().unwrap().take();
+        self.set_external_address(None);
+        if let Some(active) = mapping {
+            Self::unmap(active);
+        }
+    }
+
+    fn unmap(active: ActiveMapping) {
+        if let Err(e) = active
+            .gateway
+            .unmap(active.internal_port, active.external.port())
+        {
+            warn!(
+                "Could not remove port mapping on {}: {:?}",
+                active.gateway.name(),
+                e
+            );
+        }
+    }
+
+    fn keep_alive(&self, active: &mut ActiveMapping) -> anyhow::Result<()> {
+        let now = self.clock.now();
+        if now >= active.renew_at {
+            let external = active
+                .gateway
+                .map(active.internal_port, self.lease_duration)?;
+            active.external = external;
+            active.renew_at = now + self.lease_duration / 2;
+            self.stats.inc(StatType::PortMapping, DetailType::Renewed);
+            Ok(())
+        } else {
+            active.gateway.check(&active.external)
+        }
+    }
+
+    fn create_mapping(&self, internal_port: u16) -> anyhow::Result<ActiveMapping> {
+        let mut errors = Vec::new();
+        for gateway in self.discover_gateways(&mut errors) {
+            match gateway.map(internal_port, self.lease_duration) {
+                Ok(external) => {
+                    return Ok(ActiveMapping {
+                        gateway,
+                        internal_port,
+                        external,
+                        renew_at: self.clock.now() + self.lease_duration / 2,
+                    })
+                }
+                Err(e) => errors.push(format!("{}: {}", gateway.name(), e)),
+            }
+        }
+        Err(anyhow!("{}", errors.join(", ")))
+    }
+
+    fn discover_gateways(&self, errors: &mut Vec<String>) -> Vec<Gateway> {
+        let mut gateways = Vec::new();
+        match UpnpGateway::discover(self.config.ssdp_address, self.config.timeout) {
+            Ok(gateway) => gateways.push(Gateway::Upnp(gateway)),
+            Err(e) => errors.push(format!("UPnP: {}", e)),
+        }
+        let natpmp_gateway = self.config.natpmp_gateway.or_else(|| {
+            default_gateway().map(|ip| SocketAddr::V4(SocketAddrV4::new(ip, NATPMP_PORT)))
+        });
+        match natpmp_gateway {
+            Some(address) => gateways.push(Gateway::NatPmp(NatPmpClient::new(
+                address,
+                self.config.timeout,
+            ))),
+            None => errors.push("NAT-PMP: no default gateway found".to_owned()),
+        }
+        gateways
+    }
+
+    fn set_external_address(&self, external: Option<SocketAddrV4>) {
+        let external = external.map(|a| SocketAddrV6::new(a.ip().to_ipv6_mapped(), a.port(), 0, 0));
+        *self.external_address.write().unwrap() = external;
+        self.network_info
+            .write()
+            .unwrap()
+            .set_external_address(self.configured_external_address.or(external));
+    }
+}
+
+/// Refreshes the port mapping periodically
+pub struct PortMappingRefresh {
+    port_mapping: Arc<PortMapping>,
+}
+
+impl PortMappingRefresh {
+    pub fn new(port_mapping: Arc<PortMapping>) -> Self {
+        Self { port_mapping }
+    }
+}
+
+impl Runnable for PortMappingRefresh {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.port_mapping.refresh();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{natpmp::fake::FakeNatPmpGateway, upnp::fake::FakeUpnpGateway, *};
+    use crate::{config::NetworkConstants, stats::Direction};
+    use std::net::{Ipv4Addr, UdpSocket};
+
+    #[test]
+    fn map_port_with_upnp() {
+        let upnp = FakeUpnpGateway::start();
+        let fixture = Fixture::new(upnp.ssdp_address, None);
+
+        fixture.port_mapping.refresh();
+
+        assert_eq!(
+            fixture.port_mapping.external_address(),
+            Some(external(FakeUpnpGateway::EXTERNAL_IP, 7075))
+        );
+        assert!(upnp.mappings.lock().unwrap().contains_key(&7075));
+        assert_eq!(
+            fixture
+                .stats
+                .count(StatType::PortMapping, DetailType::Mapped, Direction::In),
+            1
+        );
+    }
+
+    #[test]
+    fn fall_back_to_natpmp() {
+        let natpmp = FakeNatPmpGateway::start();
+        let fixture = Fixture::new(silent_address(), Some(natpmp.address));
+
+        fixture.port_mapping.refresh();
+
+        assert_eq!(
+            fixture.port_mapping.external_address(),
+            Some(external(FakeNatPmpGateway::EXTERNAL_IP, 8075))
+        );
+    }
+
+    #[test]
+    fn no_external_address_without_gateway() {
+        let fixture = Fixture::new(silent_address(), Some(silent_address()));
+        fixture.port_mapping.refresh();
+        assert_eq!(fixture.port_mapping.external_address(), None);
+        assert_eq!(
+            fixture.stats.count(
+                StatType::PortMapping,
+                DetailType::MappingFailed,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn renew_lease() {
+        let natpmp = FakeNatPmpGateway::start();
+        let fixture = Fixture::new(silent_address(), Some(natpmp.address));
+        fixture.port_mapping.refresh();
+
+        fixture.port_mapping.refresh();
+        assert_eq!(natpmp.mappings.lock().unwrap().len(), 1);
+
+        fixture.clock.advance(fixture.lease_duration / 2);
+        fixture.port_mapping.refresh();
+
+        assert_eq!(natpmp.mappings.lock().unwrap().len(), 2);
+        assert_eq!(
+            fixture
+                .stats
+                .count(StatType::PortMapping, DetailType::Renewed, Direction::In),
+            1
+        );
+    }
+
+    #[test]
+    fn map_again_when_gateway_forgot_mapping() {
+        let upnp = FakeUpnpGateway::start();
+        let fixture = Fixture::new(upnp.ssdp_address, None);
+        fixture.port_mapping.refresh();
+
+        upnp.mappings.lock().unwrap().clear();
+        fixture.port_mapping.refresh();
+
+        assert!(upnp.mappings.lock().unwrap().contains_key(&7075));
+        assert_eq!(
+            fixture.stats.count(
+                StatType::PortMapping,
+                DetailType::HealthCheckFailed,
+                Direction::In
+            ),
+            1
+        );
+        assert_eq!(
+            fixture
+                .stats
+                .count(StatType::PortMapping, DetailType::Mapped, Direction::In),
+            2
+        );
+    }
+
+    #[test]
+    fn remove_mapping_on_stop() {
+        let upnp = FakeUpnpGateway::start();
+        let fixture = Fixture::new(upnp.ssdp_address, None);
+        fixture.port_mapping.refresh();
+
+        fixture.port_mapping.stop();
+
+        assert!(upnp.mappings.lock().unwrap().is_empty());
+        assert_eq!(fixture.port_mapping.external_address(), None);
+        assert_eq!(
+            fixture.network_info.read().unwrap().external_address(),
+            None
+        );
+    }
+
+    #[test]
+    fn publish_mapped_address() {
+        let upnp = FakeUpnpGateway::start();
+        let fixture = Fixture::new(upnp.ssdp_address, None);
+
+        fixture.port_mapping.refresh();
+
+        assert_eq!(
+            fixture.network_info.read().unwrap().external_address(),
+            Some(external(FakeUpnpGateway::EXTERNAL_IP, 7075))
+        );
+    }
+
+    #[test]
+    fn configured_address_takes_precedence() {
+        let upnp = FakeUpnpGateway::start();
+        let configured = external(Ipv4Addr::new(1, 2, 3, 4), 1234);
+        let fixture = Fixture::with_configured_address(upnp.ssdp_address, None, Some(configured));
+        assert_eq!(
+            fixture.network_info.read().unwrap().external_address(),
+            Some(configured)
+        );
+
+        fixture.port_mapping.refresh();
+
+        assert_eq!(
+            fixture.port_mapping.external_address(),
+            Some(external(FakeUpnpGateway::EXTERNAL_IP, 7075))
+        );
+        assert_eq!(
+            fixture.network_info.read().unwrap().external_address(),
+            Some(configured)
+        );
+    }
+
+    #[test]
+    fn dont_map_after_stop() {
+        let upnp = FakeUpnpGateway::start();
+        let fixture = Fixture::new(upnp.ssdp_address, None);
+        fixture.port_mapping.stop();
+
+        fixture.port_mapping.refresh();
+
+        assert!(upnp.mappings.lock().unwrap().is_empty());
+    }
+
+    struct Fixture {
+        port_mapping: PortMapping,
+        network_info: Arc<RwLock<NetworkInfo>>,
+        stats: Arc<Stats>,
+        clock: Arc<SteadyClock>,
+        lease_duration: Duration,
+    }
+
+    impl Fixture {
+        fn new(ssdp_address: SocketAddr, natpmp_gateway: Option<SocketAddr>) -> Self {
+            Self::with_configured_address(ssdp_address, natpmp_gateway, None)
+        }
+
+        fn with_configured_address(
+            ssdp_address: SocketAddr,
+            natpmp_gateway: Option<SocketAddr>,
+            configured_external_address: Option<SocketAddrV6>,
+        ) -> Self {
+            let mut network_info = NetworkInfo::new_test_instance();
+            network_info.set_listening_port(7075);
+            let stats = Arc::new(Stats::default());
+            let clock = Arc::new(SteadyClock::new_null());
+            let constants = PortmappingConstants::new(&NetworkConstants::empty());
+            let config = PortMappingConfig {
+                enabled: true,
+                natpmp_gateway: Some(natpmp_gateway.unwrap_or_else(silent_address)),
+                ssdp_address,
+                timeout: Duration::from_millis(200),
+            };
+            let network_info = Arc::new(RwLock::new(network_info));
+            Self {
+                port_mapping: PortMapping::new(
+                    config,
+                    &constants,
+                    network_info.clone(),
+                    stats.clone(),
+                    clock.clone(),
+                    configured_external_address,
+                ),
+                network_info,
+                stats,
+                clock,
+                lease_duration: Duration::from_secs(constants.lease_duration_s as u64),
+            }
+        }
+    }
+
+    fn external(ip: Ipv4Addr, port: u16) -> SocketAddrV6 {
+        SocketAddrV6::new(ip.to_ipv6_mapped(), port, 0, 0)
+    }
+
+    /// An address where nobody answers
+    fn silent_address() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let address = socket.local_addr().unwrap();
+        // Leak the socket so that no one else gets the port during the test
+        std::mem::forget(socket);
+        address
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+pub(crate) const NATPMP_PORT: u16 = 5351;
+
+const VERSION: u8 = 0;
+const OP_EXTERNAL_ADDRESS: u8 = 0;
+const OP_MAP_TCP: u8 = 2;
+const RESPONSE_FLAG: u8 = 128;
+
+/// Minimal NAT-PMP client (RFC 6886)
+pub(crate) struct NatPmpClient {
+    gateway: SocketAddr,
+    timeout: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct NatPmpMapping {
+    pub external_port: u16,
+    pub lifetime: Duration,
+}
+
+impl NatPmpClient {
+    pub fn new(gateway: SocketAddr, timeout: Duration) -> Self {
+        Self { gateway, timeout }
+    }
+
+    pub fn gateway(&self) -> SocketAddr {
+        self.gateway
+    }
+
+    pub fn external_address(&self) -> anyhow::Result<Ipv4Addr> {
+        let response = self.request(&[VERSION, OP_EXTERNAL_ADDRESS], 12)?;
+        Ok(Ipv4Addr::new(
+            response[8],
+            response[9],
+            response[10],
+            response[11],
+        ))
+    }
+
+    /// Maps a TCP port. The gateway may choose another external port than the suggested one
+    pub fn map_tcp(
+        &self,
+        internal_port: u16,
+        suggested_external_port: u16,
+        lifetime: Duration,
+    ) -> anyhow::Result<NatPmpMapping> {
+        let mut request = vec![VERSION, OP_MAP_TCP, 0, 0];
+        request.extend_from_slice(&internal_port.to_be_bytes());
+        request.extend_from_slice(&suggested_external_port.to_be_bytes());
+        request.extend_from_slice(&(lifetime.as_secs() as u32).to_be_bytes());
+
+        let response = self.request(&request, 16)?;
+        let mapped_internal_port = u16::from_be_bytes([response[8], response[9]]);
+        if mapped_internal_port != internal_port {
+            bail!(
+                "NAT-PMP gateway mapped the wrong internal port {}",
+                mapped_internal_port
+            );
+        }
+        Ok(NatPmpMapping {
+            external_port: u16::from_be_bytes([response[10], response[11]]),
+            lifetime: Duration::from_secs(u32::from_be_bytes([
+                response[12],
+                response[13],
+                response[14],
+                response[15],
+            ]) as u64),
+        })
+    }
+
+    pub fn unmap_tcp(&self, internal_port: u16) -> anyhow::Result<()> {
+        self.map_tcp(internal_port, 0, Duration::ZERO)?;
+        Ok(())
+    }
+
+    fn request(&self, request: &[u8], response_len: usize) -> anyhow::Result<Vec<u8>> {
+        let bind_address: SocketAddr = if self.gateway.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(bind_address)?;
+        socket.connect(self.gateway)?;
+        socket.set_read_timeout(Some(self.timeout))?;
+        socket.send(request)?;
+
+        let mut buffer = [0; 16];
+        let len = socket.recv(&mut buffer)?;
+        if len < response_len {
+            bail!("NAT-PMP response too short: {} bytes", len);
+        }
+        if buffer[0] != VERSION || buffer[1] != request[1] | RESPONSE_FLAG {
+            bail!("unexpected NAT-PMP response opcode {}", buffer[1]);
+        }
+        let result_code = u16::from_be_bytes([buffer[2], buffer[3]]);
+        if result_code != 0 {
+            bail!("NAT-PMP request failed: {}", result_code_name(result_code));
+        }
+        Ok(buffer[..len].to_vec())
+    }
+}
+
+fn result_code_name(code: u16) -> &'static str {
+    match code {
+        1 => "unsupported version",
+        2 => "not authorized",
+        3 => "network failure",
+        4 => "out of resources",
+        5 => "unsupported opcode",
+        _ => "unknown error",
+    }
+}
+
+/// Reads the IPv4 default gateway from the routing table
+#[cfg(target_os = "linux")]
+pub(crate) fn default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    parse_default_gateway(&routes)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn default_gateway() -> Option<Ipv4Addr> {
+    None
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let columns: Vec<_> = line.split_whitespace().collect();
+        if columns.len() < 3 || columns[1] != "00000000" {
+            return None;
+        }
+        let gateway = u32::from_str_radix(columns[2], 16).ok()?;
+        if gateway == 0 {
+            return None;
+        }
+        // The kernel writes the address in host byte order
+        Some(Ipv4Addr::from(gateway.to_le_bytes()))
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod fake {
+    use super::*;
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        thread::JoinHandle,
+    };
+
+    /// A NAT-PMP gateway on localhost that records the mapped ports
+    pub(crate) struct FakeNatPmpGateway {
+        pub address: SocketAddr,
+        pub mappings: Arc<Mutex<Vec<(u16, u16, u32)>>>,
+        pub offline: Arc<AtomicBool>,
+        stopped: Arc<AtomicBool>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    impl FakeNatPmpGateway {
+        pub const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(203, 0, 113, 7);
+
+        pub fn start() -> Self {
+            let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+            socket
+                .set_read_timeout(Some(Duration::from_millis(20)))
+                .unwrap();
+            let address = socket.local_addr().unwrap();
+            let mappings = Arc::new(Mutex::new(Vec::new()));
+            let offline = Arc::new(AtomicBool::new(false));
+            let stopped = Arc::new(AtomicBool::new(false));
+
+            let thread_mappings = mappings.clone();
+            let thread_offline = offline.clone();
+            let thread_stopped = stopped.clone();
+            let thread = std::thread::spawn(move || {
+                let mut buffer = [0; 16];
+                while !thread_stopped.load(Ordering::SeqCst) {
+                    let Ok((len, peer)) = socket.recv_from(&mut buffer) else {
+                        continue;
+                    };
+                    if thread_offline.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                    let response = Self::respond(&buffer[..len], &thread_mappings);
+                    socket.send_to(&response, peer).unwrap();
+                }
+            });
+
+            Self {
+                address,
+                mappings,
+                offline,
+                stopped,
+                thread: Some(thread),
+            }
+        }
+
+        fn respond(request: &[u8], mappings: &Mutex<Vec<(u16, u16, u32)>>) -> Vec<u8> {
+            let mut response = vec![VERSION, request[1] | RESPONSE_FLAG, 0, 0, 0, 0, 0, 1];
+            match request[1] {
+                OP_EXTERNAL_ADDRESS => response.extend_from_slice(&Self::EXTERNAL_IP.octets()),
+                OP_MAP_TCP => {
+                    let internal = u16::from_be_bytes([request[4], request[5]]);
+                    let lifetime =
+                        u32::from_be_bytes([request[8], request[9], request[10], request[11]]);
+                    // The gateway hands out another port than requested
+                    let external = if lifetime == 0 { 0 } else { internal + 1000 };
+                    mappings
+                        .lock()
+                        .unwrap()
+                        .push((internal, external, lifetime));
+                    response.extend_from_slice(&internal.to_be_bytes());
+                    response.extend_from_slice(&external.to_be_bytes());
+                    response.extend_from_slice(&lifetime.to_be_bytes());
+                }
+                _ => response[3] = 5,
+            }
+            response
+        }
+    }
+
+    impl Drop for FakeNatPmpGateway {
+        fn drop(&mut self) {
+            self.stopped.store(true, Ordering::SeqCst);
+            if let Some(thread) = self.thread.take() {
+                thread.join().unwrap();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fake::FakeNatPmpGateway, *};
+
+    #[test]
+    fn get_external_address() {
+        let gateway = FakeNatPmpGateway::start();
+        let client = NatPmpClient::new(gateway.address, Duration::from_secs(1));
+        assert_eq!(
+            client.external_address().unwrap(),
+            FakeNatPmpGateway::EXTERNAL_IP
+        );
+    }
+
+    #[test]
+    fn map_tcp_port() {
+        let gateway = FakeNatPmpGateway::start();
+        let client = NatPmpClient::new(gateway.address, Duration::from_secs(1));
+
+        let mapping = client
+            .map_tcp(7075, 7075, Duration::from_secs(1787))
+            .unwrap();
+
+        assert_eq!(
+            mapping,
+            NatPmpMapping {
+                external_port: 8075,
+                lifetime: Duration::from_secs(1787)
+            }
+        );
+        assert_eq!(*gateway.mappings.lock().unwrap(), vec![(7075, 8075, 1787)]);
+    }
+
+    #[test]
+    fn unmap_tcp_port() {
+        let gateway = FakeNatPmpGateway::start();
+        let client = NatPmpClient::new(gateway.address, Duration::from_secs(1));
+        client.unmap_tcp(7075).unwrap();
+        assert_eq!(*gateway.mappings.lock().unwrap(), vec![(7075, 0, 0)]);
+    }
+
+    #[test]
+    fn time_out_when_gateway_does_not_answer() {
+        let gateway = FakeNatPmpGateway::start();
+        gateway
+            .offline
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        let client = NatPmpClient::new(gateway.address, Duration::from_millis(50));
+        assert!(client.external_address().is_err());
+    }
+
+    #[test]
+    fn parse_gateway_from_routing_table() {
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                      eth0\t0010A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\n\
+                      eth0\t00000000\t0100A8C0\t0003\t0\t0\t0\t00000000\n";
+        assert_eq!(
+            parse_default_gateway(routes),
+            Some(Ipv4Addr::new(192, 168, 0, 1))
+        );
+    }
+}
@@ -0,0 +1,734 @@
+use std::{
+    io::{Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+pub(crate) const SSDP_ADDRESS: &str = "239.255.255.250:1900";
+
+const SEARCH_TARGET: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+
+/// Services that can map ports, in order of preference
+const WAN_SERVICES: [&str; 3] = [
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// SOAP error code of gateways that don't support leases
+const ONLY_PERMANENT_LEASES_SUPPORTED: u32 = 725;
+
+const PORT_MAPPING_DESCRIPTION: &str = "Nano Node";
+
+/// Gateway descriptions and SOAP responses are small. Bigger responses are rejected
+const MAX_RESPONSE_SIZE: u64 = 64 * 1024;
+
+/// An UPnP Internet Gateway Device that was found with SSDP
+pub(crate) struct UpnpGateway {
+    control_address: SocketAddr,
+    control_path: String,
+    service_type: String,
+    /// Address of this host on the network of the gateway
+    local_ip: Ipv4Addr,
+    timeout: Duration,
+}
+
+impl UpnpGateway {
+    /// Searches for a gateway and returns the first one that offers a WAN connection service
+    pub fn discover(ssdp_address: SocketAddr, timeout: Duration) -> anyhow::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        let request = format!(
+            "M-SEARCH * HTTP/1.1\r\n\
+             HOST: {}\r\n\
+             ST: {}\r\n\
+             MAN: \"ssdp:discover\"\r\n\
+             MX: 2\r\n\r\n",
+            SSDP_ADDRESS, SEARCH_TARGET
+        );
+        socket.send_to(request.as_bytes(), ssdp_address)?;
+
+        let deadline = Instant::now() + timeout;
+        let mut buffer = [0; 2048];
+        let mut last_error = anyhow!("no UPnP gateway found");
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(last_error);
+            }
+            socket.set_read_timeout(Some(remaining))?;
+            let Ok(len) = socket.recv(&mut buffer) else {
+                return Err(last_error);
+            };
+            let response = String::from_utf8_lossy(&buffer[..len]);
+            let Some(location) = header_value(&response, "location") else {
+                continue;
+            };
+            match Self::from_location(location, timeout) {
+                Ok(gateway) => return Ok(gateway),
+                Err(e) => last_error = e,
+            }
+        }
+    }
+
+    fn from_location(location: &str, timeout: Duration) -> anyhow::Result<Self> {
+        let (address, path) =
+            parse_http_url(location).ok_or_else(|| anyhow!("invalid location {}", location))?;
+        let (response, local_address) = http_request(
+            address,
+            &format!(
+                "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+                path, address
+            ),
+            timeout,
+        )?;
+        if response.status != 200 {
+            bail!(
+                "could not get gateway description: HTTP {}",
+                response.status
+            );
+        }
+
+        let (service_type, control_url) = find_wan_service(&response.body)
+            .ok_or_else(|| anyhow!("gateway has no WAN connection service"))?;
+        let (control_address, control_path) = if control_url.starts_with("http://") {
+            parse_http_url(&control_url)
+                .ok_or_else(|| anyhow!("invalid control URL {}", control_url))?
+        } else if control_url.starts_with('/') {
+            (address, control_url)
+        } else {
+            (address, format!("/{}", control_url))
+        };
+
+        let SocketAddr::V4(local_address) = local_address else {
+            bail!("UPnP is only supported over IPv4");
+        };
+        Ok(Self {
+            control_address,
+            control_path,
+            service_type,
+            local_ip: *local_address.ip(),
+            timeout,
+        })
+    }
+
+    pub fn control_address(&self) -> SocketAddr {
+        self.control_address
+    }
+
+    pub fn external_address(&self) -> anyhow::Result<Ipv4Addr> {
+        let response = self.soap_request("GetExternalIPAddress", &[])?;
+        let address = xml_value(&response, "NewExternalIPAddress")
+            .ok_or_else(|| anyhow!("gateway did not return an external address"))?;
+        Ok(address.trim().parse()?)
+    }
+
+    pub fn add_port_mapping(
+        &self,
+        internal_port: u16,
+        external_port: u16,
+        lease: Duration,
+    ) -> anyhow::Result<()> {
+        match self.try_add_port_mapping(internal_port, external_port, lease) {
+            Err(UpnpError::Soap(ONLY_PERMANENT_LEASES_SUPPORTED)) if !lease.is_zero() => self
+                .try_add_port_mapping(internal_port, external_port, Duration::ZERO)
+                .map_err(Into::into),
+            result => result.map_err(Into::into),
+        }
+    }
+
+    fn try_add_port_mapping(
+        &self,
+        internal_port: u16,
+        external_port: u16,
+        lease: Duration,
+    ) -> Result<(), UpnpError> {
+        self.soap_request(
+            "AddPortMapping",
+            &[
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", external_port.to_string()),
+                ("NewProtocol", "TCP".to_owned()),
+                ("NewInternalPort", internal_port.to_string()),
+                ("NewInternalClient", self.local_ip.to_string()),
+                ("NewEnabled", "1".to_owned()),
+                (
+                    "NewPortMappingDescription",
+                    PORT_MAPPING_DESCRIPTION.to_owned(),
+                ),
+                ("NewLeaseDuration", lease.as_secs().to_string()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Checks if the gateway still forwards the port to this host
+    pub fn port_mapping_exists(&self, external_port: u16) -> anyhow::Result<bool> {
+        let result = self.soap_request(
+            "GetSpecificPortMappingEntry",
+            &[
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", external_port.to_string()),
+                ("NewProtocol", "TCP".to_owned()),
+            ],
+        );
+        match result {
+            Ok(response) => Ok(xml_value(&response, "NewInternalClient")
+                .map(|client| client.trim() == self.local_ip.to_string())
+                .unwrap_or(false)),
+            Err(UpnpError::Soap(_)) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn delete_port_mapping(&self, external_port: u16) -> anyhow::Result<()> {
+        self.soap_request(
+            "DeletePortMapping",
+            &[
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", external_port.to_string()),
+                ("NewProtocol", "TCP".to_owned()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn soap_request(
+        &self,
+        action: &str,
+        arguments: &[(&str, String)],
+    ) -> Result<String, UpnpError> {
+        let mut args = String::new();
+        for (name, value) in arguments {
+            args.push_str(&format!("<{name}>{value}</{name}>"));
+        }
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{service}\">{args}</u:{action}></s:Body>\
+             </s:Envelope>",
+            service = self.service_type
+        );
+        let request = format!(
+            "POST {} HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Content-Type: text/xml; charset=\"utf-8\"\r\n\
+             SOAPAction: \"{}#{}\"\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            self.control_path,
+            self.control_address,
+            self.service_type,
+            action,
+            body.len(),
+            body
+        );
+
+        let (response, _) = http_request(self.control_address, &request, self.timeout)?;
+        if response.status == 200 {
+            return Ok(response.body);
+        }
+        match xml_value(&response.body, "errorCode").and_then(|c| c.trim().parse().ok()) {
+            Some(code) => Err(UpnpError::Soap(code)),
+            None => Err(UpnpError::Other(anyhow!(
+                "{} failed with HTTP {}",
+                action,
+                response.status
+            ))),
+        }
+    }
+}
+
+enum UpnpError {
+    /// The gateway answered with a UPnP error code
+    Soap(u32),
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for UpnpError {
+    fn from(value: anyhow::Error) -> Self {
+        Self::Other(value)
+    }
+}
+
+impl From<UpnpError> for anyhow::Error {
+    fn from(value: UpnpError) -> Self {
+        match value {
+            UpnpError::Soap(code) => anyhow!("UPnP error {}", code),
+            UpnpError::Other(e) => e,
+        }
+    }
+}
+
+struct HttpResponse {
+    status: u16,
+    body: String,
+}
+
+/// Sends a raw HTTP/1.1 request and reads the response until the connection is closed.
+/// Also returns the local address of the connection.
+fn http_request(
+    address: SocketAddr,
+    request: &str,
+    timeout: Duration,
+) -> anyhow::Result<(HttpResponse, SocketAddr)> {
+    let mut stream = TcpStream::connect_timeout(&address, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let local_address = stream.local_addr()?;
+    stream.write_all(request.as_bytes())?;
+
+    let mut raw = Vec::new();
+    stream.take(MAX_RESPONSE_SIZE + 1).read_to_end(&mut raw)?;
+    if raw.len() as u64 > MAX_RESPONSE_SIZE {
+        bail!("HTTP response is bigger than {} bytes", MAX_RESPONSE_SIZE);
+    }
+    let body_start = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| anyhow!("invalid HTTP response"))?;
+    let head = String::from_utf8_lossy(&raw[..body_start]);
+    let body = &raw[body_start + 4..];
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| anyhow!("invalid HTTP status line"))?;
+    let body = if header_value(&head, "transfer-encoding")
+        .map(|v| v.eq_ignore_ascii_case("chunked"))
+        .unwrap_or(false)
+    {
+        decode_chunked(body)?
+    } else {
+        body.to_vec()
+    };
+    let body = String::from_utf8_lossy(&body).into_owned();
+    Ok((HttpResponse { status, body }, local_address))
+}
+
+fn decode_chunked(mut input: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    loop {
+        let line_end = input
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(|| anyhow!("invalid chunked encoding"))?;
+        let size_line = std::str::from_utf8(&input[..line_end])?;
+        let rest = &input[line_end + 2..];
+        let size_hex = size_line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_hex, 16)?;
+        if size == 0 {
+            return Ok(output);
+        }
+        if rest.len() < size {
+            bail!("truncated chunk");
+        }
+        output.extend_from_slice(&rest[..size]);
+        input = rest[size..].strip_prefix(b"\r\n").unwrap_or(&rest[size..]);
+    }
+}
+
+fn header_value<'a>(message: &'a str, name: &str) -> Option<&'a str> {
+    message.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then_some(value.trim())
+    })
+}
+
+/// Splits `http://host:port/path` into a socket address and a path
+fn parse_http_url(url: &str) -> Option<(SocketAddr, String)> {
+    let rest = url.strip_prefix("http://")?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], rest[i..].to_owned()),
+        None => (rest, "/".to_owned()),
+    };
+    let address = if host.contains(':') {
+        host.to_socket_addrs().ok()?.next()?
+    } else {
+        (host, 80).to_socket_addrs().ok()?.next()?
+    };
+    Some((address, path))
+}
+
+/// Finds the service type and control URL of the preferred WAN connection service
+fn find_wan_service(description: &str) -> Option<(String, String)> {
+    let services: Vec<(&str, &str)> = description
+        .split("<service>")
+        .skip(1)
+        .filter_map(|service| {
+            Some((
+                xml_value(service, "serviceType")?.trim(),
+                xml_value(service, "controlURL")?.trim(),
+            ))
+        })
+        .collect();
+
+    WAN_SERVICES.iter().find_map(|wanted| {
+        services
+            .iter()
+            .find(|(service_type, _)| service_type == wanted)
+            .map(|(service_type, url)| (service_type.to_string(), url.to_string()))
+    })
+}
+
+fn xml_value<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start_tag = format!("<{}>", tag);
+    let end_tag = format!("</{}>", tag);
+    let start = xml.find(&start_tag)? + start_tag.len();
+    let end = start + xml[start..].find(&end_tag)?;
+    Some(&xml[start..end])
+}
+
+#[cfg(test)]
+pub(crate) mod fake {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        thread::JoinHandle,
+    };
+
+    /// An UPnP gateway on localhost. SSDP search requests must be sent to `ssdp_address`
+    pub(crate) struct FakeUpnpGateway {
+        pub ssdp_address: SocketAddr,
+        /// external port => (internal client, internal port, lease)
+        pub mappings: Arc<Mutex<HashMap<u16, (String, u16, u64)>>>,
+        pub only_permanent_leases: Arc<AtomicBool>,
+        stopped: Arc<AtomicBool>,
+        threads: Vec<JoinHandle<()>>,
+    }
+
+    impl FakeUpnpGateway {
+        pub const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(198, 51, 100, 3);
+
+        pub fn start() -> Self {
+            let stopped = Arc::new(AtomicBool::new(false));
+            let mappings = Arc::new(Mutex::new(HashMap::new()));
+            let only_permanent_leases = Arc::new(AtomicBool::new(false));
+
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.set_nonblocking(true).unwrap();
+            let http_address = listener.local_addr().unwrap();
+
+            let ssdp = UdpSocket::bind("127.0.0.1:0").unwrap();
+            ssdp.set_read_timeout(Some(Duration::from_millis(20)))
+                .unwrap();
+            let ssdp_address = ssdp.local_addr().unwrap();
+
+            let ssdp_stopped = stopped.clone();
+            let ssdp_thread = std::thread::spawn(move || {
+                let mut buffer = [0; 1024];
+                while !ssdp_stopped.load(Ordering::SeqCst) {
+                    let Ok((len, peer)) = ssdp.recv_from(&mut buffer) else {
+                        continue;
+                    };
+                    if !String::from_utf8_lossy(&buffer[..len]).starts_with("M-SEARCH") {
+                        continue;
+                    }
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nST: {}\r\nLOCATION: http://{}/rootDesc.xml\r\n\r\n",
+                        SEARCH_TARGET, http_address
+                    );
+                    ssdp.send_to(response.as_bytes(), peer).unwrap();
+                }
+            });
+
+            let http_stopped = stopped.clone();
+            let http_mappings = mappings.clone();
+            let http_permanent = only_permanent_leases.clone();
+            let http_thread = std::thread::spawn(move || {
+                while !http_stopped.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            Self::serve(stream, &http_mappings, &http_permanent);
+                        }
+                        Err(_) => std::thread::sleep(Duration::from_millis(5)),
+                    }
+                }
+            });
+
+            Self {
+                ssdp_address,
+                mappings,
+                only_permanent_leases,
+                stopped,
+                threads: vec![ssdp_thread, http_thread],
+            }
+        }
+
+        fn serve(
+            mut stream: TcpStream,
+            mappings: &Mutex<HashMap<u16, (String, u16, u64)>>,
+            only_permanent_leases: &AtomicBool,
+        ) {
+            stream.set_nonblocking(false).unwrap();
+            let request = read_request(&mut stream);
+            let (status, body) = if request.starts_with("GET /rootDesc.xml") {
+                (200, DESCRIPTION.to_owned())
+            } else if request.starts_with("POST /ctl/IPConn") {
+                Self::handle_soap(&request, mappings, only_permanent_leases)
+            } else {
+                (404, String::new())
+            };
+            let response = format!(
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+
+        fn handle_soap(
+            request: &str,
+            mappings: &Mutex<HashMap<u16, (String, u16, u64)>>,
+            only_permanent_leases: &AtomicBool,
+        ) -> (u16, String) {
+            let arg = |name| xml_value(request, name).unwrap_or_default().to_owned();
+            let external_port: u16 = arg("NewExternalPort").parse().unwrap_or_default();
+            let mut mappings = mappings.lock().unwrap();
+
+            if request.contains("#GetExternalIPAddress\"") {
+                ok(&format!(
+                    "<NewExternalIPAddress>{}</NewExternalIPAddress>",
+                    Self::EXTERNAL_IP
+                ))
+            } else if request.contains("#AddPortMapping\"") {
+                let lease: u64 = arg("NewLeaseDuration").parse().unwrap();
+                if lease != 0 && only_permanent_leases.load(Ordering::SeqCst) {
+                    return soap_error(ONLY_PERMANENT_LEASES_SUPPORTED);
+                }
+                mappings.insert(
+                    external_port,
+                    (
+                        arg("NewInternalClient"),
+                        arg("NewInternalPort").parse().unwrap(),
+                        lease,
+                    ),
+                );
+                ok("")
+            } else if request.contains("#GetSpecificPortMappingEntry\"") {
+                match mappings.get(&external_port) {
+                    Some((client, port, _)) => ok(&format!(
+                        "<NewInternalPort>{}</NewInternalPort><NewInternalClient>{}</NewInternalClient>",
+                        port, client
+                    )),
+                    None => soap_error(714),
+                }
+            } else if request.contains("#DeletePortMapping\"") {
+                match mappings.remove(&external_port) {
+                    Some(_) => ok(""),
+                    None => soap_error(714),
+                }
+            } else {
+                soap_error(401)
+            }
+        }
+    }
+
+    impl Drop for FakeUpnpGateway {
+        fn drop(&mut self) {
+            self.stopped.store(true, Ordering::SeqCst);
+            for thread in self.threads.drain(..) {
+                thread.join().unwrap();
+            }
+        }
+    }
+
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut data = Vec::new();
+        let mut buffer = [0; 1024];
+        loop {
+            let len = stream.read(&mut buffer).unwrap_or(0);
+            if len == 0 {
+                break;
+            }
+            data.extend_from_slice(&buffer[..len]);
+            let text = String::from_utf8_lossy(&data);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let content_length: usize = header_value(head, "content-length")
+                    .and_then(|l| l.parse().ok())
+                    .unwrap_or(0);
+                if body.len() >= content_length {
+                    break;
+                }
+            }
+        }
+        String::from_utf8_lossy(&data).into_owned()
+    }
+
+    fn ok(content: &str) -> (u16, String) {
+        (
+            200,
+            format!("<s:Envelope><s:Body>{}</s:Body></s:Envelope>", content),
+        )
+    }
+
+    fn soap_error(code: u32) -> (u16, String) {
+        (
+            500,
+            format!(
+                "<s:Envelope><s:Body><s:Fault><detail><UPnPError>\
+                 <errorCode>{}</errorCode></UPnPError></detail></s:Fault></s:Body></s:Envelope>",
+                code
+            ),
+        )
+    }
+
+    const DESCRIPTION: &str = "<?xml version=\"1.0\"?>\
+        <root xmlns=\"urn:schemas-upnp-org:device-1-0\"><device><serviceList>\
+        <service><serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>\
+        <controlURL>/ctl/L3F</controlURL></service>\
+        <service><serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+        <controlURL>/ctl/IPConn</controlURL></service>\
+        </serviceList></device></root>";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fake::FakeUpnpGateway, *};
+    use std::sync::atomic::Ordering;
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn discover_gateway() {
+        let fake = FakeUpnpGateway::start();
+        let gateway = UpnpGateway::discover(fake.ssdp_address, TIMEOUT).unwrap();
+        assert_eq!(gateway.control_path, "/ctl/IPConn");
+        assert_eq!(
+            gateway.service_type,
+            "urn:schemas-upnp-org:service:WANIPConnection:1"
+        );
+        assert_eq!(gateway.local_ip, Ipv4Addr::LOCALHOST);
+    }
+
+    #[test]
+    fn discovery_times_out_without_gateway() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let result = UpnpGateway::discover(socket.local_addr().unwrap(), Duration::from_millis(50));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn get_external_address() {
+        let fake = FakeUpnpGateway::start();
+        let gateway = UpnpGateway::discover(fake.ssdp_address, TIMEOUT).unwrap();
+        assert_eq!(
+            gateway.external_address().unwrap(),
+            FakeUpnpGateway::EXTERNAL_IP
+        );
+    }
+
+    #[test]
+    fn add_check_and_delete_port_mapping() {
+        let fake = FakeUpnpGateway::start();
+        let gateway = UpnpGateway::discover(fake.ssdp_address, TIMEOUT).unwrap();
+
+        gateway
+            .add_port_mapping(7075, 7075, Duration::from_secs(1787))
+            .unwrap();
+        assert_eq!(
+            fake.mappings.lock().unwrap().get(&7075),
+            Some(&("127.0.0.1".to_owned(), 7075, 1787))
+        );
+        assert!(gateway.port_mapping_exists(7075).unwrap());
+
+        gateway.delete_port_mapping(7075).unwrap();
+        assert!(!gateway.port_mapping_exists(7075).unwrap());
+    }
+
+    #[test]
+    fn fall_back_to_permanent_lease() {
+        let fake = FakeUpnpGateway::start();
+        fake.only_permanent_leases.store(true, Ordering::SeqCst);
+        let gateway = UpnpGateway::discover(fake.ssdp_address, TIMEOUT).unwrap();
+
+        gateway
+            .add_port_mapping(7075, 7075, Duration::from_secs(1787))
+            .unwrap();
+
+        assert_eq!(fake.mappings.lock().unwrap().get(&7075).unwrap().2, 0);
+    }
+
+    #[test]
+    fn parse_url() {
+        assert_eq!(
+            parse_http_url("http://192.168.0.1:5000/rootDesc.xml"),
+            Some((
+                "192.168.0.1:5000".parse().unwrap(),
+                "/rootDesc.xml".to_owned()
+            ))
+        );
+        assert_eq!(
+            parse_http_url("http://192.168.0.1"),
+            Some(("192.168.0.1:80".parse().unwrap(), "/".to_owned()))
+        );
+        assert_eq!(parse_http_url("https://192.168.0.1/"), None);
+    }
+
+    #[test]
+    fn decode_chunked_body() {
+        assert_eq!(
+            decode_chunked(b"5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n").unwrap(),
+            b"hello world"
+        );
+    }
+
+    #[test]
+    fn chunk_sizes_count_bytes() {
+        // "ä" is two bytes. A chunk boundary inside of it must not panic
+        let mut input = b"1\r\n".to_vec();
+        input.extend_from_slice(&"ä".as_bytes()[..1]);
+        input.extend_from_slice(b"\r\n1\r\n");
+        input.extend_from_slice(&"ä".as_bytes()[1..]);
+        input.extend_from_slice(b"\r\n0\r\n\r\n");
+        assert_eq!(decode_chunked(&input).unwrap(), "ä".as_bytes());
+    }
+
+    #[test]
+    fn malformed_chunks_dont_panic() {
+        assert!(decode_chunked(b"2\r\n\xff\xfe\r\n0\r\n\r\n").is_ok());
+        assert!(decode_chunked(b"\xff\r\nab\r\n").is_err());
+        assert!(decode_chunked(b"5\r\nab").is_err());
+    }
+
+    #[test]
+    fn reject_oversized_response() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let body = vec![b'a'; MAX_RESPONSE_SIZE as usize];
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n");
+            let _ = stream.write_all(&body);
+        });
+
+        let result = http_request(address, "GET / HTTP/1.1\r\n\r\n", TIMEOUT);
+
+        assert!(result.is_err());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn prefer_ip_connection() {
+        let description =
+            "<service><serviceType>urn:schemas-upnp-org:service:WANPPPConnection:1</serviceType>\
+            <controlURL>/ppp</controlURL></service>\
+            <service><serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+            <controlURL>/ip</controlURL></service>";
+        assert_eq!(
+            find_wan_service(description),
+            Some((
+                "urn:schemas-upnp-org:service:WANIPConnection:1".to_owned(),
+                "/ip".to_owned()
+            ))
+        );
+    }
+}
@@ -18,6 +18,7 @@ use rsnano_core::{KeyPair, PublicKey};
 use rsnano_ledger::Ledger;
 use rsnano_messages::*;
 use rsnano_network::{Channel, ChannelMode, ChannelReader, NetworkInfo};
+use rsnano_nullable_tcp::{Socks5Proxy, TcpStreamFactory};
 use rsnano_output_tracker::{OutputListenerMt, OutputTrackerMt};
 use std::{
     net::SocketAddrV6,
@@ -36,6 +37,9 @@ pub struct TcpConfig {
     pub max_attempts: usize,
     pub max_attempts_per_ip: usize,
     pub connect_timeout: Duration,
+    /// Outbound peer and bootstrap connections are established through this proxy if set.
+    /// HTTP callbacks don't use it, and work peers aren't contacted by this node yet
+    pub socks5_proxy: Option<Socks5Proxy>,
 }
 
 impl TcpConfig {
@@ -46,6 +50,16 @@ impl TcpConfig {
             max_attempts: 128,
             max_attempts_per_ip: 128,
             connect_timeout: Duration::from_secs(5),
+            socks5_proxy: None,
+        }
+    }
+
+    /// Creates the factory for outbound connections, which dials through the
+    /// SOCKS5 proxy if one is configured
+    pub fn outbound_stream_factory(&self) -> TcpStreamFactory {
+        match &self.socks5_proxy {
+            Some(proxy) => TcpStreamFactory::new_with_socks5_proxy(proxy.clone()),
+            None => TcpStreamFactory::new(),
         }
     }
 }
@@ -58,6 +72,7 @@ impl Default for TcpConfig {
             max_attempts: 60,
             max_attempts_per_ip: 1,
             connect_timeout: Duration::from_secs(60),
+            socks5_proxy: None,
         }
     }
 }
@@ -0,0 +1,227 @@
+use crate::{
+    stats::{DetailType, StatType, Stats},
+    utils::{CancellationToken, Runnable},
+};
+use rsnano_network::{
+    ChannelId, ChannelInfo, DeadChannelCleanupStep, NetworkInfo, PeerConnector, TrustedPeer,
+};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex, RwLock},
+};
+use tracing::debug;
+
+/// Keeps track of the channels to the configured trusted peers.
+/// A channel is trusted once its handshake proved the node ID of a trusted peer
+pub struct TrustedPeers {
+    peers: Vec<TrustedPeer>,
+    channels: Mutex<HashSet<ChannelId>>,
+}
+
+impl TrustedPeers {
+    /// Fair queue priority of trusted channels. All other channels have a priority of 1
+    pub const PRIORITY: usize = 4;
+
+    pub fn new(peers: Vec<TrustedPeer>) -> Self {
+        Self {
+            peers,
+            channels: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn peers(&self) -> &[TrustedPeer] {
+        &self.peers
+    }
+
+    /// Marks the channel as trusted if it belongs to a trusted peer
+    pub fn channel_added(&self, channel: &ChannelInfo) -> bool {
+        let Some(node_id) = channel.node_id() else {
+            return false;
+        };
+        if !self.peers.iter().any(|p| p.node_id == node_id) {
+            return false;
+        }
+        debug!(
+            "Trusted peer connected: {} ({})",
+            node_id.to_node_id(),
+            channel.peer_addr()
+        );
+        self.channels.lock().unwrap().insert(channel.channel_id());
+        true
+    }
+
+    pub fn is_trusted_channel(&self, channel_id: &ChannelId) -> bool {
+        self.channels.lock().unwrap().contains(channel_id)
+    }
+
+    pub fn priority(&self, channel_id: &ChannelId) -> usize {
+        if self.is_trusted_channel(channel_id) {
+            Self::PRIORITY
+        } else {
+            1
+        }
+    }
+}
+
+impl Default for TrustedPeers {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+pub struct TrustedPeersCleanup(Arc<TrustedPeers>);
+
+impl TrustedPeersCleanup {
+    pub fn new(trusted_peers: Arc<TrustedPeers>) -> Self {
+        Self(trusted_peers)
+    }
+}
+
+impl DeadChannelCleanupStep for TrustedPeersCleanup {
+    fn clean_up_dead_channels(&self, dead_channel_ids: &[ChannelId]) {
+        let mut channels = self.0.channels.lock().unwrap();
+        for channel_id in dead_channel_ids {
+            channels.remove(channel_id);
+        }
+    }
+}
+
+/// Reconnects to trusted peers that are not connected
+pub struct TrustedPeerConnector {
+    trusted_peers: Arc<TrustedPeers>,
+    network_info: Arc<RwLock<NetworkInfo>>,
+    peer_connector: Arc<PeerConnector>,
+    stats: Arc<Stats>,
+}
+
+impl TrustedPeerConnector {
+    pub fn new(
+        trusted_peers: Arc<TrustedPeers>,
+        network_info: Arc<RwLock<NetworkInfo>>,
+        peer_connector: Arc<PeerConnector>,
+        stats: Arc<Stats>,
+    ) -> Self {
+        Self {
+            trusted_peers,
+            network_info,
+            peer_connector,
+            stats,
+        }
+    }
+
+    fn disconnected_peers(&self) -> Vec<TrustedPeer> {
+        let network = self.network_info.read().unwrap();
+        self.trusted_peers
+            .peers()
+            .iter()
+            .filter(|p| network.find_node_id(&p.node_id).is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+impl Runnable for TrustedPeerConnector {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.stats
+            .inc(StatType::Network, DetailType::LoopReachoutTrusted);
+        for peer in self.disconnected_peers() {
+            self.stats
+                .inc(StatType::Network, DetailType::ReachoutTrusted);
+            self.peer_connector.connect_to(peer.endpoint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::Direction;
+    use rsnano_core::{
+        utils::{TEST_ENDPOINT_1, TEST_ENDPOINT_2},
+        PublicKey,
+    };
+    use rsnano_network::{ChannelDirection, ChannelMode};
+    use rsnano_nullable_clock::Timestamp;
+
+    #[test]
+    fn channel_with_trusted_node_id_gets_priority() {
+        let trusted = TrustedPeers::new(vec![trusted_peer(1, TEST_ENDPOINT_1)]);
+        let channel = ChannelInfo::new_test_instance();
+        channel.set_node_id(PublicKey::from(1));
+        let other = ChannelInfo::new(
+            2.into(),
+            TEST_ENDPOINT_1,
+            TEST_ENDPOINT_2,
+            ChannelDirection::Inbound,
+            1,
+            Timestamp::new_test_instance(),
+        );
+        other.set_node_id(PublicKey::from(2));
+
+        assert!(trusted.channel_added(&channel));
+        assert!(!trusted.channel_added(&other));
+
+        assert_eq!(
+            trusted.priority(&channel.channel_id()),
+            TrustedPeers::PRIORITY
+        );
+        assert_eq!(trusted.priority(&other.channel_id()), 1);
+    }
+
+    #[test]
+    fn clean_up_dead_channel() {
+        let trusted = Arc::new(TrustedPeers::new(vec![trusted_peer(1, TEST_ENDPOINT_1)]));
+        let channel = ChannelInfo::new_test_instance();
+        channel.set_node_id(PublicKey::from(1));
+        trusted.channel_added(&channel);
+
+        TrustedPeersCleanup::new(trusted.clone()).clean_up_dead_channels(&[channel.channel_id()]);
+
+        assert!(!trusted.is_trusted_channel(&channel.channel_id()));
+    }
+
+    #[tokio::test]
+    async fn reconnect_disconnected_trusted_peers() {
+        let trusted = Arc::new(TrustedPeers::new(vec![
+            trusted_peer(1, TEST_ENDPOINT_1),
+            trusted_peer(2, TEST_ENDPOINT_2),
+        ]));
+        let network_info = Arc::new(RwLock::new(NetworkInfo::new_test_instance()));
+        let channel = network_info
+            .write()
+            .unwrap()
+            .add(
+                TEST_ENDPOINT_2,
+                TEST_ENDPOINT_1,
+                ChannelDirection::Outbound,
+                ChannelMode::Realtime,
+                Timestamp::new_test_instance(),
+            )
+            .unwrap();
+        channel.set_node_id(PublicKey::from(1));
+        let peer_connector = Arc::new(PeerConnector::new_null(tokio::runtime::Handle::current()));
+        let connect_tracker = peer_connector.track_connections();
+        let stats = Arc::new(Stats::default());
+        let mut connector =
+            TrustedPeerConnector::new(trusted, network_info, peer_connector, stats.clone());
+
+        connector.run(&CancellationToken::new_null());
+
+        assert_eq!(connect_tracker.output(), [TEST_ENDPOINT_2]);
+        assert_eq!(
+            stats.count(
+                StatType::Network,
+                DetailType::ReachoutTrusted,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    fn trusted_peer(node_id: u64, endpoint: std::net::SocketAddrV6) -> TrustedPeer {
+        TrustedPeer {
+            node_id: PublicKey::from(node_id),
+            endpoint,
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use anyhow::bail;
+use rsnano_core::{
+    utils::{MemoryStream, Stream, StreamExt},
+    Account, Amount, BlockHash, WalletId,
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Controls which receivable blocks of an account get received automatically.
+/// Explicit receives via RPC are only affected by `minimum`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AutoReceivePolicy {
+    pub enabled: bool,
+    /// Overrides the node's `receive_minimum` for this account
+    pub minimum: Option<Amount>,
+    /// If not empty, only sends from these accounts are received automatically
+    pub allow: Vec<Account>,
+    /// Sends from these accounts are never received automatically
+    pub deny: Vec<Account>,
+    /// Receivable blocks are collected and received together once the oldest one
+    /// waited this long. Zero receives right away. The delay is a fixed time and
+    /// doesn't depend on the network load or the work difficulty
+    pub batch_delay: Duration,
+    /// A delayed batch is received early once it holds this many blocks. Zero means no limit
+    pub batch_size: usize,
+}
+
+impl Default for AutoReceivePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            minimum: None,
+            allow: Vec::new(),
+            deny: Vec::new(),
+            batch_delay: Duration::ZERO,
+            batch_size: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AutoReceiveDecision {
+    Receive,
+    Delay,
+    Skip,
+}
+
+impl AutoReceivePolicy {
+    const VERSION: u8 = 1;
+    pub const MAX_LIST_LEN: usize = 1024;
+
+    /// Used in place of a policy that can't be read, so that nothing gets received
+    /// against the owner's intent
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    pub fn minimum_or(&self, default_minimum: Amount) -> Amount {
+        self.minimum.unwrap_or(default_minimum)
+    }
+
+    pub fn decide(
+        &self,
+        source: &Account,
+        amount: Amount,
+        default_minimum: Amount,
+    ) -> AutoReceiveDecision {
+        if !self.enabled
+            || amount < self.minimum_or(default_minimum)
+            || self.deny.contains(source)
+            || (!self.allow.is_empty() && !self.allow.contains(source))
+        {
+            AutoReceiveDecision::Skip
+        } else if !self.batch_delay.is_zero() {
+            AutoReceiveDecision::Delay
+        } else {
+            AutoReceiveDecision::Receive
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.allow.len() > Self::MAX_LIST_LEN || self.deny.len() > Self::MAX_LIST_LEN {
+            bail!(
+                "allow and deny lists are limited to {} accounts",
+                Self::MAX_LIST_LEN
+            );
+        }
+        if u32::try_from(self.batch_size).is_err() {
+            bail!("batch size must not exceed {}", u32::MAX);
+        }
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut stream = MemoryStream::new();
+        stream.write_u8(Self::VERSION).unwrap();
+        stream.write_u8(self.enabled as u8).unwrap();
+        match self.minimum {
+            Some(minimum) => {
+                stream.write_u8(1).unwrap();
+                stream.write_bytes(&minimum.to_be_bytes()).unwrap();
+            }
+            None => stream.write_u8(0).unwrap(),
+        }
+        for list in [&self.allow, &self.deny] {
+            stream.write_u32_be(list.len() as u32).unwrap();
+            for account in list {
+                stream.write_bytes(account.as_bytes()).unwrap();
+            }
+        }
+        stream
+            .write_u64_be(self.batch_delay.as_millis() as u64)
+            .unwrap();
+        stream.write_u32_be(self.batch_size as u32).unwrap();
+        stream.to_vec()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut stream = MemoryStream::new();
+        stream.write_bytes(bytes)?;
+        let version = stream.read_u8()?;
+        if version != Self::VERSION {
+            bail!("unsupported auto receive policy version: {}", version);
+        }
+        let enabled = stream.read_u8()? != 0;
+        let minimum = match stream.read_u8()? {
+            0 => None,
+            _ => Some(Amount::from_be_bytes({
+                let mut buffer = [0; 16];
+                stream.read_bytes(&mut buffer, 16)?;
+                buffer
+            })),
+        };
+        let mut lists = [Vec::new(), Vec::new()];
+        for list in &mut lists {
+            let len = stream.read_u32_be()? as usize;
+            if len > Self::MAX_LIST_LEN {
+                bail!("auto receive policy list too long: {}", len);
+            }
+            for _ in 0..len {
+                let mut buffer = [0; 32];
+                stream.read_bytes(&mut buffer, 32)?;
+                list.push(Account::from_bytes(buffer));
+            }
+        }
+        let [allow, deny] = lists;
+        Ok(Self {
+            enabled,
+            minimum,
+            allow,
+            deny,
+            batch_delay: Duration::from_millis(stream.read_u64_be()?),
+            batch_size: stream.read_u32_be()? as usize,
+        })
+    }
+}
+
+struct DelayedBatch {
+    started: Instant,
+    hashes: Vec<BlockHash>,
+}
+
+/// Receivable blocks that wait for the `batch_delay` of their account's policy.
+/// Batches are kept per wallet, because each wallet has its own policies
+#[derive(Default)]
+pub struct DelayedReceives {
+    batches: HashMap<(WalletId, Account), DelayedBatch>,
+}
+
+impl DelayedReceives {
+    pub fn add(&mut self, wallet_id: WalletId, account: Account, hash: BlockHash, now: Instant) {
+        let batch = self
+            .batches
+            .entry((wallet_id, account))
+            .or_insert_with(|| DelayedBatch {
+                started: now,
+                hashes: Vec::new(),
+            });
+        if !batch.hashes.contains(&hash) {
+            batch.hashes.push(hash);
+        }
+    }
+
+    /// Removes and returns the batches that are due according to the policy of their account
+    pub fn pop_due(
+        &mut self,
+        now: Instant,
+        policy: impl Fn(&WalletId, &Account) -> AutoReceivePolicy,
+    ) -> Vec<(WalletId, Account, Vec<BlockHash>)> {
+        let due: Vec<(WalletId, Account)> = self
+            .batches
+            .iter()
+            .filter(|((wallet_id, account), batch)| {
+                let policy = policy(wallet_id, account);
+                now.duration_since(batch.started) >= policy.batch_delay
+                    || (policy.batch_size > 0 && batch.hashes.len() >= policy.batch_size)
+            })
+            .map(|(key, _)| *key)
+            .collect();
+
+        due.into_iter()
+            .filter_map(|key| {
+                self.batches
+                    .remove(&key)
+                    .map(|batch| (key.0, key.1, batch.hashes))
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.batches.values().map(|b| b.hashes.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_receives_above_minimum() {
+        let policy = AutoReceivePolicy::default();
+        let source = Account::from(1);
+        assert_eq!(
+            policy.decide(&source, Amount::raw(10), Amount::raw(10)),
+            AutoReceiveDecision::Receive
+        );
+        assert_eq!(
+            policy.decide(&source, Amount::raw(9), Amount::raw(10)),
+            AutoReceiveDecision::Skip
+        );
+    }
+
+    #[test]
+    fn policy_rules() {
+        let allowed = Account::from(1);
+        let denied = Account::from(2);
+        let policy = AutoReceivePolicy {
+            minimum: Some(Amount::raw(1)),
+            allow: vec![allowed, denied],
+            deny: vec![denied],
+            batch_delay: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let default_minimum = Amount::raw(100);
+
+        assert_eq!(
+            policy.decide(&allowed, Amount::raw(1), default_minimum),
+            AutoReceiveDecision::Delay
+        );
+        assert_eq!(
+            policy.decide(&denied, Amount::raw(1), default_minimum),
+            AutoReceiveDecision::Skip
+        );
+        assert_eq!(
+            policy.decide(&Account::from(3), Amount::raw(1), default_minimum),
+            AutoReceiveDecision::Skip
+        );
+        assert_eq!(
+            AutoReceivePolicy::disabled().decide(&allowed, Amount::MAX, default_minimum),
+            AutoReceiveDecision::Skip
+        );
+    }
+
+    #[test]
+    fn validate_limits() {
+        assert!(AutoReceivePolicy::default().validate().is_ok());
+        let too_long = AutoReceivePolicy {
+            deny: vec![Account::from(1); AutoReceivePolicy::MAX_LIST_LEN + 1],
+            ..Default::default()
+        };
+        assert!(too_long.validate().is_err());
+        let max_batch = AutoReceivePolicy {
+            batch_size: u32::MAX as usize,
+            ..Default::default()
+        };
+        assert!(max_batch.validate().is_ok());
+        let too_large_batch = AutoReceivePolicy {
+            batch_size: u32::MAX as usize + 1,
+            ..Default::default()
+        };
+        assert!(too_large_batch.validate().is_err());
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let policy = AutoReceivePolicy {
+            enabled: false,
+            minimum: Some(Amount::raw(42)),
+            allow: vec![Account::from(1)],
+            deny: vec![Account::from(2), Account::from(3)],
+            batch_delay: Duration::from_millis(1500),
+            batch_size: 7,
+        };
+        assert_eq!(
+            AutoReceivePolicy::from_bytes(&policy.to_bytes()).unwrap(),
+            policy
+        );
+        let default = AutoReceivePolicy::default();
+        assert_eq!(
+            AutoReceivePolicy::from_bytes(&default.to_bytes()).unwrap(),
+            default
+        );
+        assert!(AutoReceivePolicy::from_bytes(&[2]).is_err());
+    }
+
+    #[test]
+    fn delayed_batches() {
+        let policy = AutoReceivePolicy {
+            batch_delay: Duration::from_secs(10),
+            batch_size: 3,
+            ..Default::default()
+        };
+        let mut delayed = DelayedReceives::default();
+        let now = Instant::now();
+        let wallet = WalletId::from(1);
+        let account1 = Account::from(1);
+        let account2 = Account::from(2);
+        delayed.add(wallet, account1, BlockHash::from(1), now);
+        delayed.add(wallet, account1, BlockHash::from(1), now);
+        for i in 2..5 {
+            delayed.add(wallet, account2, BlockHash::from(i), now);
+        }
+        assert_eq!(delayed.len(), 4);
+
+        // account2 reached the batch size
+        assert_eq!(
+            delayed.pop_due(now, |_, _| policy.clone()),
+            vec![(
+                wallet,
+                account2,
+                vec![BlockHash::from(2), BlockHash::from(3), BlockHash::from(4)]
+            )]
+        );
+        assert!(delayed
+            .pop_due(now + Duration::from_secs(9), |_, _| policy.clone())
+            .is_empty());
+        assert_eq!(
+            delayed.pop_due(now + Duration::from_secs(10), |_, _| policy.clone()),
+            vec![(wallet, account1, vec![BlockHash::from(1)])]
+        );
+        assert_eq!(delayed.len(), 0);
+    }
+
+    #[test]
+    fn delayed_batches_are_kept_per_wallet() {
+        let delayed_policy = AutoReceivePolicy {
+            batch_delay: Duration::from_secs(10),
+            ..Default::default()
+        };
+        let mut delayed = DelayedReceives::default();
+        let now = Instant::now();
+        let wallet1 = WalletId::from(1);
+        let wallet2 = WalletId::from(2);
+        let account = Account::from(1);
+        delayed.add(wallet1, account, BlockHash::from(1), now);
+        delayed.add(wallet2, account, BlockHash::from(1), now);
+
+        let due = delayed.pop_due(now, |wallet_id, _| {
+            if *wallet_id == wallet1 {
+                AutoReceivePolicy::default()
+            } else {
+                delayed_policy.clone()
+            }
+        });
+
+        assert_eq!(due, vec![(wallet1, account, vec![BlockHash::from(1)])]);
+        assert_eq!(delayed.len(), 1);
+    }
+}
@@ -1,9 +1,13 @@
+mod auto_receive_policy;
 mod wallet;
 mod wallet_action_thread;
+mod wallet_backup;
 mod wallet_representatives;
 mod wallets;
 
+pub use auto_receive_policy::*;
 pub use wallet::*;
 pub use wallet_action_thread::*;
+pub use wallet_backup::*;
 pub use wallet_representatives::*;
 pub use wallets::*;
@@ -1,9 +1,10 @@
 use anyhow::Context;
 use rsnano_core::{
-    work::WorkThresholds, KeyDerivationFunction, KeyPair, PublicKey, Root, WorkVersion,
+    hd_wallet::Slip10Node, work::WorkThresholds, KeyDerivationFunction, KeyPair, PublicKey, RawKey,
+    Root, WalletId, WorkVersion,
 };
 use rsnano_ledger::Ledger;
-use rsnano_store_lmdb::{LmdbWalletStore, LmdbWriteTransaction, Transaction};
+use rsnano_store_lmdb::{LmdbWalletStore, LmdbWriteTransaction, Transaction, WalletValue};
 use std::{
     collections::HashSet,
     path::Path,
@@ -12,6 +13,7 @@ use std::{
 use tracing::warn;
 
 pub struct Wallet {
+    pub id: WalletId,
     pub representatives: Mutex<HashSet<PublicKey>>,
     pub store: Arc<LmdbWalletStore>,
     ledger: Arc<Ledger>,
@@ -20,6 +22,7 @@ pub struct Wallet {
 
 impl Wallet {
     pub fn new(
+        id: WalletId,
         ledger: Arc<Ledger>,
         work_thresholds: WorkThresholds,
         txn: &mut LmdbWriteTransaction,
@@ -32,6 +35,7 @@ impl Wallet {
             .context("could not create wallet store")?;
 
         Ok(Self {
+            id,
             representatives: Mutex::new(HashSet::new()),
             store: Arc::new(store),
             ledger,
@@ -40,6 +44,7 @@ impl Wallet {
     }
 
     pub fn new_from_json(
+        id: WalletId,
         ledger: Arc<Ledger>,
         work_thresholds: WorkThresholds,
         txn: &mut LmdbWriteTransaction,
@@ -52,6 +57,29 @@ impl Wallet {
             .context("could not create wallet store")?;
 
         Ok(Self {
+            id,
+            representatives: Mutex::new(HashSet::new()),
+            store: Arc::new(store),
+            ledger,
+            work_thresholds,
+        })
+    }
+
+    pub fn new_from_entries(
+        id: WalletId,
+        ledger: Arc<Ledger>,
+        work_thresholds: WorkThresholds,
+        txn: &mut LmdbWriteTransaction,
+        fanout: usize,
+        kdf: KeyDerivationFunction,
+        wallet_path: &Path,
+        entries: Vec<(PublicKey, WalletValue)>,
+    ) -> anyhow::Result<Self> {
+        let store = LmdbWalletStore::new_from_entries(fanout, kdf, txn, &wallet_path, entries)
+            .context("could not create wallet store")?;
+
+        Ok(Self {
+            id,
             representatives: Mutex::new(HashSet::new()),
             store: Arc::new(store),
             ledger,
@@ -80,12 +108,23 @@ impl Wallet {
     }
 
     pub fn deterministic_check(&self, txn: &dyn Transaction, index: u32) -> u32 {
+        self.last_used_index(index, u32::MAX, |i| self.store.deterministic_key(txn, i))
+    }
+
+    pub fn bip44_check(&self, txn: &dyn Transaction, index: u32) -> u32 {
+        self.last_used_index(index, Slip10Node::MAX_INDEX, |i| {
+            self.store.bip44_key(txn, i)
+        })
+    }
+
+    /// Finds the highest index after `index` and up to `max_index` whose account has blocks or receivables
+    fn last_used_index(&self, index: u32, max_index: u32, key: impl Fn(u32) -> RawKey) -> u32 {
         let mut result = index;
         let block_txn = self.ledger.read_txn();
         let mut i = index + 1;
         let mut n = index + 64;
-        while i < n {
-            let prv = self.store.deterministic_key(txn, i);
+        while i < n && i <= max_index {
+            let prv = key(i);
             let pair = KeyPair::from_priv_key_bytes(prv.as_bytes()).unwrap();
             // Check if account received at least 1 block
             let latest = self.ledger.any().account_head(&block_txn, &pair.account());
@@ -0,0 +1,265 @@
+use super::Wallets;
+use rsnano_core::{
+    utils::milliseconds_since_epoch, KdfParams, PassphraseCipher, PublicKey, RawKey, WalletId,
+};
+use rsnano_store_lmdb::WalletValue;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, Permissions},
+    io::Write,
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+pub const WALLET_BACKUP_FILE_PREFIX: &str = "wallets_";
+pub const WALLET_BACKUP_FILE_EXTENSION: &str = "backup";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WalletBackupConfig {
+    /// Number of automatic backups to keep. Older ones get deleted
+    pub max_backups: usize,
+    /// Passphrase of the automatic backups. Automatic backups are disabled while it is empty
+    pub passphrase: String,
+}
+
+impl WalletBackupConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.passphrase.is_empty()
+    }
+}
+
+impl Default for WalletBackupConfig {
+    fn default() -> Self {
+        Self {
+            max_backups: 24,
+            passphrase: String::new(),
+        }
+    }
+}
+
+/// Content of an encrypted wallet backup file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletBackup {
+    pub version: u32,
+    /// Milliseconds since epoch
+    pub created: u64,
+    pub wallets: Vec<WalletBackupWallet>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletBackupWallet {
+    pub id: WalletId,
+    /// All raw store entries: seed, ad-hoc keys, representative, deterministic index, ...
+    pub entries: Vec<WalletBackupEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletBackupEntry {
+    pub key: PublicKey,
+    /// Hex encoded value. Private keys are still encrypted with the wallet key
+    pub value: String,
+    /// Cached work
+    pub work: u64,
+}
+
+impl WalletBackupWallet {
+    pub fn new(id: WalletId, entries: Vec<(PublicKey, WalletValue)>) -> Self {
+        Self {
+            id,
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| WalletBackupEntry {
+                    key,
+                    value: value.key.encode_hex(),
+                    work: value.work,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn store_entries(&self) -> anyhow::Result<Vec<(PublicKey, WalletValue)>> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let value = RawKey::decode_hex(&entry.value)?;
+                Ok((entry.key, WalletValue::new(value, entry.work)))
+            })
+            .collect()
+    }
+}
+
+impl WalletBackup {
+    pub const VERSION: u32 = 1;
+    /// Backups leave the node, so they always use the recommended parameters
+    pub const KDF_PARAMS: KdfParams = KdfParams::RECOMMENDED;
+
+    pub fn new(wallets: Vec<WalletBackupWallet>) -> Self {
+        Self {
+            version: Self::VERSION,
+            created: milliseconds_since_epoch(),
+            wallets,
+        }
+    }
+
+    pub fn encrypt(&self, passphrase: &str) -> Vec<u8> {
+        let json = serde_json::to_vec(self).expect("wallet backup is always serializable");
+        PassphraseCipher::new(Self::KDF_PARAMS).encrypt(&json, passphrase)
+    }
+
+    pub fn decrypt(data: &[u8], passphrase: &str) -> anyhow::Result<Self> {
+        let json = PassphraseCipher::decrypt(data, passphrase)?;
+        let backup: Self = serde_json::from_slice(&json)?;
+        if backup.version > Self::VERSION {
+            bail!("unsupported wallet backup version: {}", backup.version);
+        }
+        Ok(backup)
+    }
+
+    /// Writes the encrypted backup to a new file that only the current user can read
+    pub fn write(&self, path: &Path, passphrase: &str) -> anyhow::Result<()> {
+        let data = self.encrypt(passphrase);
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    pub fn read(path: &Path, passphrase: &str) -> anyhow::Result<Self> {
+        let data = fs::read(path)?;
+        Self::decrypt(&data, passphrase)
+    }
+}
+
+/// Writes a new automatic backup into `backups_path` and deletes old backups
+/// as configured by `max_backups`. Fails if no passphrase is configured
+pub fn backup_wallets_with_retention(
+    wallets: &Wallets,
+    backups_path: &Path,
+    config: &WalletBackupConfig,
+) -> anyhow::Result<PathBuf> {
+    if !config.is_enabled() {
+        bail!("automatic wallet backups need a passphrase");
+    }
+    fs::create_dir_all(backups_path)?;
+    fs::set_permissions(backups_path, Permissions::from_mode(0o700))?;
+    let backup = wallets.create_backup();
+    let path = backups_path.join(format!(
+        "{}{}.{}",
+        WALLET_BACKUP_FILE_PREFIX, backup.created, WALLET_BACKUP_FILE_EXTENSION
+    ));
+    backup.write(&path, &config.passphrase)?;
+
+    let backups = list_wallet_backups(backups_path)?;
+    let to_delete = backups.len().saturating_sub(config.max_backups.max(1));
+    for old in &backups[..to_delete] {
+        info!("Deleting old wallet backup {:?}", old);
+        fs::remove_file(old)?;
+    }
+    Ok(path)
+}
+
+/// Returns the backup files created by `backup_wallets_with_retention`, oldest first
+pub fn list_wallet_backups(backups_path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    if !backups_path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<(u64, PathBuf)> = fs::read_dir(backups_path)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let timestamp = name
+                .to_str()?
+                .strip_prefix(WALLET_BACKUP_FILE_PREFIX)?
+                .strip_suffix(WALLET_BACKUP_FILE_EXTENSION)?
+                .strip_suffix('.')?
+                .parse()
+                .ok()?;
+            Some((timestamp, entry.path()))
+        })
+        .collect();
+    backups.sort();
+    Ok(backups.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let backup = test_backup();
+
+        let encrypted = backup.encrypt("passphrase");
+
+        assert_eq!(
+            WalletBackup::decrypt(&encrypted, "passphrase").unwrap(),
+            backup
+        );
+        assert!(WalletBackup::decrypt(&encrypted, "wrong").is_err());
+    }
+
+    #[test]
+    fn use_strong_kdf_params() {
+        let encrypted = test_backup().encrypt("passphrase");
+        let params = KdfParams::from_bytes(encrypted[7..39].try_into().unwrap()).unwrap();
+        assert_eq!(params, WalletBackup::KDF_PARAMS);
+    }
+
+    #[test]
+    fn disabled_without_passphrase() {
+        assert!(!WalletBackupConfig::default().is_enabled());
+    }
+
+    #[test]
+    fn entries_roundtrip() {
+        let wallet = WalletBackupWallet::new(
+            WalletId::from(1),
+            vec![(PublicKey::from(2), WalletValue::new(RawKey::from(3), 4))],
+        );
+
+        let entries = wallet.store_entries().unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, PublicKey::from(2));
+        assert_eq!(entries[0].1.key, RawKey::from(3));
+        assert_eq!(entries[0].1.work, 4);
+    }
+
+    #[test]
+    fn list_backups_oldest_first() {
+        let dir =
+            std::env::temp_dir().join(format!("rsnano-test-{}", uuid::Uuid::new_v4().simple()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "wallets_300.backup",
+            "wallets_100.backup",
+            "other.backup",
+            "wallets_200.json",
+        ] {
+            fs::write(dir.join(name), []).unwrap();
+        }
+
+        let backups = list_wallet_backups(&dir).unwrap();
+
+        assert_eq!(
+            backups,
+            vec![
+                dir.join("wallets_100.backup"),
+                dir.join("wallets_300.backup")
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn test_backup() -> WalletBackup {
+        WalletBackup::new(vec![WalletBackupWallet::new(
+            WalletId::from(1),
+            vec![(PublicKey::from(2), WalletValue::new(RawKey::from(3), 4))],
+        )])
+    }
+}
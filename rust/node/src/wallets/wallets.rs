@@ -1,4 +1,7 @@
-use super::{Wallet, WalletActionThread, WalletRepresentatives};
+use super::{
+    AutoReceiveDecision, AutoReceivePolicy, DelayedReceives, Wallet, WalletActionThread,
+    WalletBackup, WalletBackupWallet, WalletRepresentatives,
+};
 use crate::{
     block_processing::{BlockProcessor, BlockSource},
     cementation::{ConfirmingSet, ConfirmingSetConfig},
@@ -10,8 +13,10 @@ use crate::{
     work::DistributedWorkFactory,
     NetworkParams,
 };
+use anyhow::Context;
 use rand::{thread_rng, Rng};
 use rsnano_core::{
+    hd_wallet::Mnemonic,
     utils::{get_env_or_default_string, ContainerInfo, ContainerInfoComponent},
     work::{WorkPoolImpl, WorkThresholds},
     Account, Amount, BlockDetails, BlockEnum, BlockHash, Epoch, HackyUnsafeMutBlock,
@@ -30,11 +35,9 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     fmt,
-    fs::Permissions,
     mem::size_of,
     ops::Deref,
-    os::unix::fs::PermissionsExt,
-    path::{Path, PathBuf},
+    path::PathBuf,
     sync::{Arc, Condvar, Mutex},
     time::{Duration, Instant},
 };
@@ -49,6 +52,9 @@ pub enum WalletsError {
     AccountNotFound,
     InvalidPassword,
     BadPublicKey,
+    MnemonicNotFound,
+    WalletAlreadyExists,
+    InvalidIndex,
 }
 
 impl fmt::Display for WalletsError {
@@ -61,16 +67,22 @@ impl fmt::Display for WalletsError {
             WalletsError::AccountNotFound => "Account not found",
             WalletsError::InvalidPassword => "Invalid password",
             WalletsError::BadPublicKey => "Bad public key",
+            WalletsError::MnemonicNotFound => "Wallet has no mnemonic",
+            WalletsError::WalletAlreadyExists => "Wallet already exists",
+            WalletsError::InvalidIndex => "Invalid index",
         };
         write!(f, "{}", error_message)
     }
 }
 
+impl std::error::Error for WalletsError {}
+
 pub type WalletsIterator<'txn> = BinaryDbIterator<'txn, [u8; 64], NoValue>;
 
 pub struct Wallets {
     db: Option<LmdbDatabase>,
     send_action_ids_handle: Option<LmdbDatabase>,
+    auto_receive_policies_handle: Option<LmdbDatabase>,
     env: Arc<LmdbEnv>,
     pub mutex: Mutex<HashMap<WalletId, Arc<Wallet>>>,
     node_config: NodeConfig,
@@ -80,10 +92,13 @@ pub struct Wallets {
     work_thresholds: WorkThresholds,
     network_params: NetworkParams,
     pub delayed_work: Mutex<HashMap<Account, Root>>,
+    delayed_receives: Mutex<DelayedReceives>,
     workers: Arc<dyn ThreadPool>,
     wallet_actions: WalletActionThread,
     block_processor: Arc<BlockProcessor>,
     pub representative_wallets: Mutex<WalletRepresentatives>,
+    /// Representatives whose keys are held outside of the wallets, for example by a remote signer
+    external_representatives: Mutex<Vec<PublicKey>>,
     online_reps: Arc<Mutex<OnlineReps>>,
     pub kdf: KeyDerivationFunction,
     start_election: Mutex<Option<Box<dyn Fn(Arc<BlockEnum>) + Send + Sync>>>,
@@ -138,6 +153,7 @@ impl Wallets {
         Self {
             db: None,
             send_action_ids_handle: None,
+            auto_receive_policies_handle: None,
             mutex: Mutex::new(HashMap::new()),
             env,
             node_config: node_config.clone(),
@@ -147,6 +163,7 @@ impl Wallets {
             work_thresholds: work.clone(),
             network_params,
             delayed_work: Mutex::new(HashMap::new()),
+            delayed_receives: Mutex::new(DelayedReceives::default()),
             workers,
             wallet_actions: WalletActionThread::new(),
             block_processor,
@@ -154,6 +171,7 @@ impl Wallets {
                 node_config.vote_minimum,
                 Arc::clone(&ledger),
             )),
+            external_representatives: Mutex::new(Vec::new()),
             online_reps,
             kdf: kdf.clone(),
             start_election: Mutex::new(None),
@@ -181,6 +199,10 @@ impl Wallets {
             txn.rw_txn_mut()
                 .create_db(Some("send_action_ids"), DatabaseFlags::empty())?
         });
+        self.auto_receive_policies_handle = Some(unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("auto_receive_policies"), DatabaseFlags::empty())?
+        });
         {
             let mut guard = self.mutex.lock().unwrap();
             let wallet_ids = self.get_wallet_ids(&txn);
@@ -189,6 +211,7 @@ impl Wallets {
                 let representative = self.node_config.random_representative();
                 let text = PathBuf::from(id.encode_hex());
                 let wallet = Wallet::new(
+                    id,
                     self.ledger.clone(),
                     self.work_thresholds.clone(),
                     &mut txn,
@@ -288,6 +311,119 @@ impl Wallets {
         tx.clear_db(self.send_action_ids_handle.unwrap()).unwrap();
     }
 
+    /// Returns the auto receive policy of the account in the wallet, or the default policy if none is set
+    pub fn auto_receive_policy(
+        &self,
+        wallet_id: &WalletId,
+        account: &Account,
+    ) -> AutoReceivePolicy {
+        let tx = self.env.tx_begin_read();
+        self.auto_receive_policy_txn(&tx, wallet_id, account)
+    }
+
+    /// Like `auto_receive_policy`, but fails if the wallet doesn't contain the account
+    pub fn wallet_auto_receive_policy(
+        &self,
+        wallet_id: &WalletId,
+        account: &Account,
+    ) -> Result<AutoReceivePolicy, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        if !wallet.store.exists(&tx, &account.into()) {
+            return Err(WalletsError::AccountNotFound);
+        }
+        Ok(self.auto_receive_policy_txn(&tx, wallet_id, account))
+    }
+
+    fn auto_receive_policy_txn(
+        &self,
+        txn: &dyn Transaction,
+        wallet_id: &WalletId,
+        account: &Account,
+    ) -> AutoReceivePolicy {
+        match txn.get(
+            self.auto_receive_policies_handle.unwrap(),
+            &Self::auto_receive_policy_key(wallet_id, account),
+        ) {
+            Ok(bytes) => AutoReceivePolicy::from_bytes(bytes).unwrap_or_else(|e| {
+                warn!(
+                    "Invalid auto receive policy for {} in wallet {}, disabling auto receive: {:?}",
+                    account.encode_account(),
+                    wallet_id,
+                    e
+                );
+                AutoReceivePolicy::disabled()
+            }),
+            Err(_) => AutoReceivePolicy::default(),
+        }
+    }
+
+    /// Stores the auto receive policy of an account of the wallet. The default policy removes the entry
+    pub fn set_auto_receive_policy(
+        &self,
+        wallet_id: &WalletId,
+        account: &Account,
+        policy: &AutoReceivePolicy,
+    ) -> Result<(), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        if !wallet.store.exists(&tx, &account.into()) {
+            return Err(WalletsError::AccountNotFound);
+        }
+        if *policy == AutoReceivePolicy::default() {
+            self.erase_auto_receive_policy(&mut tx, wallet_id, account);
+        } else {
+            tx.put(
+                self.auto_receive_policies_handle.unwrap(),
+                &Self::auto_receive_policy_key(wallet_id, account),
+                &policy.to_bytes(),
+                WriteFlags::empty(),
+            )
+            .unwrap();
+        }
+        Ok(())
+    }
+
+    fn erase_auto_receive_policy(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet_id: &WalletId,
+        account: &Account,
+    ) {
+        let _ = txn.delete(
+            self.auto_receive_policies_handle.unwrap(),
+            &Self::auto_receive_policy_key(wallet_id, account),
+            None,
+        );
+    }
+
+    /// Policies are stored per wallet, so that an account that is in several wallets
+    /// can have a different policy in each of them
+    fn auto_receive_policy_key(wallet_id: &WalletId, account: &Account) -> [u8; 64] {
+        let mut key = [0; 64];
+        key[..32].copy_from_slice(wallet_id.as_bytes());
+        key[32..].copy_from_slice(account.as_bytes());
+        key
+    }
+
+    fn auto_receive_decision(
+        &self,
+        txn: &dyn Transaction,
+        wallet_id: &WalletId,
+        destination: &Account,
+        source: &Account,
+        amount: Amount,
+    ) -> AutoReceiveDecision {
+        self.auto_receive_policy_txn(txn, wallet_id, destination)
+            .decide(source, amount, self.node_config.receive_minimum)
+    }
+
+    pub fn delayed_receives_count(&self) -> usize {
+        self.delayed_receives.lock().unwrap().len()
+    }
+
     pub fn foreach_representative<F>(&self, mut action: F)
     where
         F: FnMut(&KeyPair),
@@ -394,6 +530,23 @@ impl Wallets {
         Ok(())
     }
 
+    /// Returns whether the account was added with `insert_watch`, which means
+    /// that the wallet doesn't hold its private key
+    pub fn is_watch_only(
+        &self,
+        wallet_id: &WalletId,
+        account: &Account,
+    ) -> Result<bool, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        let pub_key = PublicKey::from(*account);
+        if !wallet.store.exists(&tx, &pub_key) {
+            return Err(WalletsError::AccountNotFound);
+        }
+        Ok(wallet.store.get_key_type(&tx, &pub_key) == KeyType::Unknown)
+    }
+
     pub fn valid_password(&self, wallet_id: &WalletId) -> Result<bool, WalletsError> {
         let guard = self.mutex.lock().unwrap();
         let wallet = Self::get_wallet(&guard, wallet_id)?;
@@ -408,8 +561,8 @@ impl Wallets {
     ) -> Result<(), WalletsError> {
         let guard = self.mutex.lock().unwrap();
         let wallet = Self::get_wallet(&guard, wallet_id)?;
-        let tx = self.env.tx_begin_write();
-        if wallet.store.attempt_password(&tx, password.as_ref()) {
+        let mut tx = self.env.tx_begin_write();
+        if wallet.store.attempt_password(&mut tx, password.as_ref()) {
             Ok(())
         } else {
             Err(WalletsError::InvalidPassword)
@@ -462,6 +615,15 @@ impl Wallets {
             }
             *wallet.representatives.lock().unwrap() = representatives;
         }
+        for representative in self.external_representatives.lock().unwrap().iter() {
+            reps_guard.check_rep(*representative, half_principal_weight);
+        }
+    }
+
+    /// Lets the node vote for representatives whose keys are not stored in a wallet
+    pub fn set_external_representatives(&self, representatives: Vec<PublicKey>) {
+        *self.external_representatives.lock().unwrap() = representatives;
+        self.compute_reps();
     }
 
     pub fn exists(&self, pub_key: &PublicKey) -> bool {
@@ -483,6 +645,7 @@ impl Wallets {
                 let text = PathBuf::from(id.encode_hex());
                 let representative = self.node_config.random_representative();
                 if let Ok(wallet) = Wallet::new(
+                    id,
                     Arc::clone(&self.ledger),
                     self.work_thresholds.clone(),
                     &mut tx,
@@ -515,6 +678,9 @@ impl Wallets {
         // action_mutex should be locked after transactions to prevent deadlocks in deterministic_insert () & insert_adhoc ()
         let _action_guard = self.wallet_actions.lock_safe();
         let wallet = guard.remove(id).unwrap();
+        for account in wallet.store.accounts(&tx) {
+            self.erase_auto_receive_policy(&mut tx, id, &account);
+        }
         wallet.store.destroy(&mut tx);
     }
 
@@ -533,6 +699,7 @@ impl Wallets {
             return Err(WalletsError::AccountNotFound);
         }
         wallet.store.erase(&mut tx, pub_key);
+        self.erase_auto_receive_policy(&mut tx, wallet_id, &(*pub_key).into());
         Ok(())
     }
 
@@ -572,24 +739,65 @@ impl Wallets {
             .map_err(|_| WalletsError::AccountNotFound)
     }
 
-    pub fn backup(&self, path: &Path) -> anyhow::Result<()> {
+    /// Snapshot of all wallets that can be written as an encrypted backup file
+    pub fn create_backup(&self) -> WalletBackup {
         let guard = self.mutex.lock().unwrap();
         let tx = self.env.tx_begin_read();
-        for (id, wallet) in guard.iter() {
-            std::fs::create_dir_all(path)?;
-            std::fs::set_permissions(path, Permissions::from_mode(0o700))?;
-            let mut backup_path = PathBuf::from(path);
-            backup_path.push(format!("{}.json", id));
-            wallet.store.write_backup(&tx, &backup_path)?;
+        let wallets = guard
+            .iter()
+            .map(|(id, wallet)| WalletBackupWallet::new(*id, wallet.store.entries(&tx)))
+            .collect();
+        WalletBackup::new(wallets)
+    }
+
+    /// Restores the wallets of a backup. Wallets that already exist are skipped.
+    /// Returns the ids of the restored wallets
+    pub fn restore_backup(&self, backup: &WalletBackup) -> anyhow::Result<Vec<WalletId>> {
+        // Validate everything before writing, so that a corrupt backup doesn't restore only
+        // some wallets. The write transaction commits on drop, even when returning an error
+        let mut restore = Vec::new();
+        for wallet in &backup.wallets {
+            let entries = wallet.store_entries()?;
+            LmdbWalletStore::validate_entries(&entries)
+                .with_context(|| format!("invalid wallet {} in backup", wallet.id))?;
+            restore.push((wallet.id, entries));
         }
-        Ok(())
+
+        let mut guard = self.mutex.lock().unwrap();
+        let mut tx = self.env.tx_begin_write();
+        let mut restored = Vec::new();
+        for (wallet_id, entries) in restore {
+            if guard.contains_key(&wallet_id) {
+                warn!("Wallet {} already exists, skipping it", wallet_id);
+                continue;
+            }
+            let wallet = Wallet::new_from_entries(
+                wallet_id,
+                Arc::clone(&self.ledger),
+                self.work_thresholds.clone(),
+                &mut tx,
+                self.node_config.password_fanout as usize,
+                self.kdf.clone(),
+                &PathBuf::from(wallet_id.encode_hex()),
+                entries,
+            )?;
+            // Wallets without password are unlocked right away, like on node startup
+            wallet.store.attempt_password(&mut tx, "");
+            guard.insert(wallet_id, Arc::new(wallet));
+            restored.push(wallet_id);
+        }
+        Ok(restored)
     }
 
     pub fn deterministic_index_get(&self, wallet_id: &WalletId) -> Result<u32, WalletsError> {
         let guard = self.mutex.lock().unwrap();
         let wallet = Self::get_wallet(&guard, wallet_id)?;
         let tx = self.env.tx_begin_read();
-        Ok(wallet.store.deterministic_index_get(&tx))
+        if wallet.store.has_mnemonic(&tx) {
+            Ok(wallet.store.bip44_index_get(&tx))
+        } else {
+            Ok(wallet.store.deterministic_index_get(&tx))
+        }
     }
 
     fn prepare_send(
@@ -782,10 +990,16 @@ impl Wallets {
             .map_err(|_| WalletsError::Generic)
     }
 
+    /// Creates a new wallet with the given id from an exported wallet. The new wallet
+    /// has the password of the exported wallet and starts locked
     pub fn import(&self, wallet_id: WalletId, json: &str) -> anyhow::Result<()> {
-        let _guard = self.mutex.lock().unwrap();
+        let mut guard = self.mutex.lock().unwrap();
+        if guard.contains_key(&wallet_id) {
+            return Err(WalletsError::WalletAlreadyExists.into());
+        }
         let mut tx = self.env.tx_begin_write();
-        let _wallet = Wallet::new_from_json(
+        let wallet = Wallet::new_from_json(
+            wallet_id,
             Arc::clone(&self.ledger),
             self.work_thresholds.clone(),
             &mut tx,
@@ -794,6 +1008,7 @@ impl Wallets {
             &PathBuf::from(wallet_id.to_string()),
             json,
         )?;
+        guard.insert(wallet_id, Arc::new(wallet));
         Ok(())
     }
 
@@ -804,10 +1019,11 @@ impl Wallets {
         password: &str,
     ) -> anyhow::Result<()> {
         let guard = self.mutex.lock().unwrap();
-        let existing = guard
-            .get(&wallet_id)
-            .ok_or_else(|| anyhow!("wallet not found"))?;
+        let existing = Self::get_wallet(&guard, &wallet_id)?;
         let mut tx = self.env.tx_begin_write();
+        if !existing.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked.into());
+        }
         let id = WalletId::from_bytes(thread_rng().gen());
         let temp = LmdbWalletStore::new_from_json(
             1,
@@ -817,10 +1033,10 @@ impl Wallets {
             json,
         )?;
 
-        let result = if temp.attempt_password(&tx, password) {
+        let result = if temp.attempt_password(&mut tx, password) {
             existing.store.import(&mut tx, &temp)
         } else {
-            Err(anyhow!("bad password"))
+            Err(WalletsError::InvalidPassword.into())
         };
         temp.destroy(&mut tx);
         result
@@ -836,6 +1052,19 @@ impl Wallets {
         Ok(wallet.store.seed(&tx))
     }
 
+    pub fn get_mnemonic(&self, wallet_id: WalletId) -> Result<Mnemonic, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, &wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        if !wallet.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked);
+        }
+        wallet
+            .store
+            .mnemonic(&tx)
+            .ok_or(WalletsError::MnemonicNotFound)
+    }
+
     pub fn key_type(&self, wallet_id: WalletId, pub_key: &PublicKey) -> KeyType {
         let guard = self.mutex.lock().unwrap();
         match guard.get(&wallet_id) {
@@ -974,6 +1203,16 @@ pub trait WalletsExt {
         count: u32,
     ) -> PublicKey;
 
+    /// Replaces the deterministic keys of the wallet with BIP44 keys derived from the mnemonic
+    /// and restores the used accounts. Returns the number of restored accounts and the last one
+    fn restore_mnemonic(
+        &self,
+        wallet_id: WalletId,
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        count: u32,
+    ) -> Result<(u32, Account), WalletsError>;
+
     fn send_action(
         &self,
         wallet: &Arc<Wallet>,
@@ -1107,6 +1346,9 @@ pub trait WalletsExt {
     ) -> Result<(), ()>;
 
     fn receive_confirmed(&self, hash: BlockHash, destinaton: Account);
+    /// Receives the delayed batches whose `batch_delay` has passed
+    fn receive_delayed(&self);
+    fn ongoing_receive_delayed(&self);
     fn search_receivable_all(&self);
     fn search_receivable_wallet(&self, wallet_id: WalletId) -> Result<(), WalletsError>;
 
@@ -1115,7 +1357,7 @@ pub trait WalletsExt {
     fn enter_password_wallet(
         &self,
         wallet: &Arc<Wallet>,
-        wallet_tx: &dyn Transaction,
+        wallet_tx: &mut LmdbWriteTransaction,
         password: &str,
     ) -> Result<(), ()>;
 
@@ -1202,7 +1444,14 @@ impl WalletsExt for Arc<Wallets> {
         if !wallet.store.valid_password(tx) {
             return PublicKey::zero();
         }
-        let key = wallet.store.deterministic_insert(tx);
+        let key = if wallet.store.has_mnemonic(tx) {
+            match wallet.store.bip44_insert(tx) {
+                Some(key) => key,
+                None => return PublicKey::zero(),
+            }
+        } else {
+            wallet.store.deterministic_insert(tx)
+        };
         if generate_work {
             self.work_ensure(wallet, key.into(), key.into());
         }
@@ -1226,7 +1475,14 @@ impl WalletsExt for Arc<Wallets> {
         if !wallet.store.valid_password(&tx) {
             return Err(WalletsError::WalletLocked);
         }
-        let account = wallet.store.deterministic_insert_at(&mut tx, index);
+        let account = if wallet.store.has_mnemonic(&tx) {
+            wallet
+                .store
+                .bip44_insert_at(&mut tx, index)
+                .ok_or(WalletsError::InvalidIndex)?
+        } else {
+            wallet.store.deterministic_insert_at(&mut tx, index)
+        };
         if generate_work {
             self.work_ensure(wallet, account.into(), account.into());
         }
@@ -1417,6 +1673,32 @@ impl WalletsExt for Arc<Wallets> {
         Ok((restored_count, first_account.into()))
     }
 
+    fn restore_mnemonic(
+        &self,
+        wallet_id: WalletId,
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        mut count: u32,
+    ) -> Result<(u32, Account), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Wallets::get_wallet(&guard, &wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        if !wallet.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked);
+        }
+        wallet.store.set_mnemonic(&mut tx, mnemonic, passphrase);
+        let mut account = self.deterministic_insert(wallet, &mut tx, true);
+        if count == 0 {
+            count = wallet.bip44_check(&tx, 0);
+        }
+        for _ in 0..count {
+            // Disable work generation to prevent weak CPU nodes stuck
+            account = self.deterministic_insert(wallet, &mut tx, false);
+        }
+        let restored_count = wallet.store.bip44_index_get(&tx);
+        Ok((restored_count, account.into()))
+    }
+
     fn send_action2(
         &self,
         wallet_id: &WalletId,
@@ -1567,7 +1849,10 @@ impl WalletsExt for Arc<Wallets> {
         mut work: u64,
         generate_work: bool,
     ) -> Option<BlockEnum> {
-        if amount < self.node_config.receive_minimum {
+        let receive_minimum = self
+            .auto_receive_policy(&wallet.id, &account)
+            .minimum_or(self.node_config.receive_minimum);
+        if amount < receive_minimum {
             warn!(
                 "Not receiving block {} due to minimum receive threshold",
                 send_hash
@@ -1873,7 +2158,14 @@ impl WalletsExt for Arc<Wallets> {
                 ) {
                     let hash = key.send_block_hash;
                     let amount = info.amount;
-                    if self.node_config.receive_minimum <= amount {
+                    let decision = self.auto_receive_decision(
+                        wallet_tx,
+                        &wallet.id,
+                        &account.into(),
+                        &info.source,
+                        amount,
+                    );
+                    if decision != AutoReceiveDecision::Skip {
                         info!(
                             "Found a receivable block {} for account {}",
                             hash,
@@ -1884,18 +2176,27 @@ impl WalletsExt for Arc<Wallets> {
                             .confirmed()
                             .block_exists_or_pruned(&block_tx, &hash)
                         {
-                            let representative = wallet.store.representative(wallet_tx);
-                            // Receive confirmed block
-                            self.receive_async_wallet(
-                                Arc::clone(wallet),
-                                hash,
-                                representative,
-                                amount,
-                                account.into(),
-                                Box::new(|_| {}),
-                                0,
-                                true,
-                            );
+                            if decision == AutoReceiveDecision::Delay {
+                                self.delayed_receives.lock().unwrap().add(
+                                    wallet.id,
+                                    account.into(),
+                                    hash,
+                                    Instant::now(),
+                                );
+                            } else {
+                                let representative = wallet.store.representative(wallet_tx);
+                                // Receive confirmed block
+                                self.receive_async_wallet(
+                                    Arc::clone(wallet),
+                                    hash,
+                                    representative,
+                                    amount,
+                                    account.into(),
+                                    Box::new(|_| {}),
+                                    0,
+                                    true,
+                                );
+                            }
                         } else if !self.confirming_set.exists(&hash) {
                             let block = self.ledger.any().get_block(&block_tx, &hash);
                             if let Some(block) = block {
@@ -1933,16 +2234,35 @@ impl WalletsExt for Arc<Wallets> {
                     .get_pending(&self.ledger.read_txn(), &PendingKey::new(destination, hash));
                 if let Some(pending) = pending {
                     let amount = pending.amount;
-                    self.receive_async_wallet(
-                        wallet,
-                        hash,
-                        representative,
+                    match self.auto_receive_decision(
+                        &wallet_tx,
+                        &wallet.id,
+                        &destination,
+                        &pending.source,
                         amount,
-                        destination,
-                        Box::new(|_| {}),
-                        0,
-                        true,
-                    );
+                    ) {
+                        AutoReceiveDecision::Receive => self.receive_async_wallet(
+                            wallet,
+                            hash,
+                            representative,
+                            amount,
+                            destination,
+                            Box::new(|_| {}),
+                            0,
+                            true,
+                        ),
+                        AutoReceiveDecision::Delay => self.delayed_receives.lock().unwrap().add(
+                            wallet.id,
+                            destination,
+                            hash,
+                            Instant::now(),
+                        ),
+                        AutoReceiveDecision::Skip => info!(
+                            "Not receiving block {} due to the auto receive policy of {}",
+                            hash,
+                            destination.encode_account()
+                        ),
+                    }
                 } else {
                     if !self
                         .ledger
@@ -1959,6 +2279,95 @@ impl WalletsExt for Arc<Wallets> {
         }
     }
 
+    fn receive_delayed(&self) {
+        let due = {
+            let tx = self.env.tx_begin_read();
+            self.delayed_receives
+                .lock()
+                .unwrap()
+                .pop_due(Instant::now(), |wallet_id, account| {
+                    self.auto_receive_policy_txn(&tx, wallet_id, account)
+                })
+        };
+        if due.is_empty() {
+            return;
+        }
+
+        let (wallet_tx, wallets) = {
+            let guard = self.mutex.lock().unwrap();
+            (self.env.tx_begin_read(), guard.clone())
+        };
+        let block_tx = self.ledger.read_txn();
+        for (wallet_id, destination, hashes) in due {
+            let Some(wallet) = wallets.get(&wallet_id) else {
+                continue;
+            };
+            if !wallet.store.exists(&wallet_tx, &destination.into()) {
+                continue;
+            }
+            info!(
+                "Receiving delayed batch of {} blocks for {}",
+                hashes.len(),
+                destination.encode_account()
+            );
+            let representative = wallet.store.representative(&wallet_tx);
+            for hash in &hashes {
+                // Skip blocks that got received in the meantime
+                let Some(pending) = self
+                    .ledger
+                    .any()
+                    .get_pending(&block_tx, &PendingKey::new(destination, *hash))
+                else {
+                    continue;
+                };
+                // The policy may have changed while the batch was waiting
+                let decision = self.auto_receive_decision(
+                    &wallet_tx,
+                    &wallet_id,
+                    &destination,
+                    &pending.source,
+                    pending.amount,
+                );
+                if decision == AutoReceiveDecision::Skip {
+                    info!(
+                        "Not receiving delayed block {} due to the auto receive policy of {}",
+                        hash,
+                        destination.encode_account()
+                    );
+                    continue;
+                }
+                self.receive_async_wallet(
+                    Arc::clone(wallet),
+                    *hash,
+                    representative,
+                    pending.amount,
+                    destination,
+                    Box::new(|_| {}),
+                    0,
+                    true,
+                );
+            }
+        }
+    }
+
+    fn ongoing_receive_delayed(&self) {
+        self.receive_delayed();
+
+        let delay = if self.network_params.network.is_dev_network() {
+            Duration::from_millis(50)
+        } else {
+            Duration::from_secs(1)
+        };
+
+        let self_l = Arc::clone(self);
+        self.workers.add_delayed_task(
+            delay,
+            Box::new(move || {
+                self_l.ongoing_receive_delayed();
+            }),
+        );
+    }
+
     fn search_receivable_all(&self) {
         let wallets = self.mutex.lock().unwrap().clone();
         let wallet_tx = self.env.tx_begin_read();
@@ -1985,15 +2394,15 @@ impl WalletsExt for Arc<Wallets> {
     fn enter_password(&self, wallet_id: WalletId, password: &str) -> Result<(), WalletsError> {
         let guard = self.mutex.lock().unwrap();
         let wallet = Wallets::get_wallet(&guard, &wallet_id)?;
-        let tx = self.env.tx_begin_write();
-        self.enter_password_wallet(wallet, &tx, password)
+        let mut tx = self.env.tx_begin_write();
+        self.enter_password_wallet(wallet, &mut tx, password)
             .map_err(|_| WalletsError::InvalidPassword)
     }
 
     fn enter_password_wallet(
         &self,
         wallet: &Arc<Wallet>,
-        wallet_tx: &dyn Transaction,
+        wallet_tx: &mut LmdbWriteTransaction,
         password: &str,
     ) -> Result<(), ()> {
         if !wallet.store.attempt_password(wallet_tx, password) {
@@ -2023,7 +2432,7 @@ impl WalletsExt for Arc<Wallets> {
                 // Newly created wallets have a zero key
                 let _ = wallet.store.rekey(&mut tx, "");
             } else {
-                let _ = self.enter_password_wallet(wallet, &tx, "");
+                let _ = self.enter_password_wallet(wallet, &mut tx, "");
             }
         }
     }
@@ -2034,6 +2443,7 @@ impl WalletsExt for Arc<Wallets> {
         let wallet = {
             let mut tx = self.env.tx_begin_write();
             let Ok(wallet) = Wallet::new(
+                wallet_id,
                 Arc::clone(&self.ledger),
                 self.work_thresholds.clone(),
                 &mut tx,
@@ -2178,10 +2588,12 @@ impl WalletsExt for Arc<Wallets> {
         let Some(existing) = guard.get(&wallet_id) else {
             return false;
         };
-        let tx = self.env.tx_begin_write();
+        let mut tx = self.env.tx_begin_write();
         let mut valid = existing.store.valid_password(&tx);
         if !valid {
-            valid = self.enter_password_wallet(existing, &tx, password).is_ok();
+            valid = self
+                .enter_password_wallet(existing, &mut tx, password)
+                .is_ok();
         }
 
         valid
@@ -2197,6 +2609,7 @@ impl WalletsExt for Arc<Wallets> {
         if self.node_config.enable_voting {
             self.ongoing_compute_reps();
         }
+        self.ongoing_receive_delayed();
     }
 }
 
@@ -29,7 +29,7 @@ pub struct WebsocketListener {
     endpoint: Mutex<SocketAddr>,
     tx_stop: Mutex<Option<oneshot::Sender<()>>>,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; 12]>,
     sessions: Arc<Mutex<Vec<Weak<WebsocketSessionEntry>>>>,
     tokio: tokio::runtime::Handle,
 }
@@ -223,7 +223,7 @@ impl WebsocketListenerExt for Arc<WebsocketListener> {
 async fn accept_connection(
     stream: TcpStream,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; 12]>,
     remote_endpoint: SocketAddr,
     tx_send: mpsc::Sender<OutgoingMessageEnvelope>,
     mut rx_send: mpsc::Receiver<OutgoingMessageEnvelope>,
@@ -25,6 +25,8 @@ pub enum Topic {
     Telemetry,
     /// New block arrival message
     NewUnconfirmedBlock,
+    /// An election that had competing blocks ended
+    Fork,
     /// Auxiliary length, not a valid topic, must be the last enum
     Length,
 }
@@ -173,6 +175,7 @@ pub fn to_topic(topic: impl AsRef<str>) -> Topic {
         "bootstrap" => Topic::Bootstrap,
         "telemetry" => Topic::Telemetry,
         "new_unconfirmed_block" => Topic::NewUnconfirmedBlock,
+        "fork" => Topic::Fork,
         _ => Topic::Invalid,
     }
 }
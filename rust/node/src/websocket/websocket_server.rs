@@ -1,6 +1,8 @@
 use super::{OutgoingMessageEnvelope, WebsocketConfig, WebsocketListener};
 use crate::{
-    consensus::{ActiveElections, ElectionStatus, ElectionStatusType, VoteProcessor},
+    consensus::{
+        ActiveElections, ElectionStatus, ElectionStatusType, ForkJournal, ForkRecord, VoteProcessor,
+    },
     wallets::Wallets,
     websocket::Topic,
     Telemetry,
@@ -11,7 +13,7 @@ use serde::Serialize;
 use std::{
     net::{IpAddr, SocketAddr, SocketAddrV6},
     sync::Arc,
-    time::UNIX_EPOCH,
+    time::{SystemTime, UNIX_EPOCH},
 };
 use tracing::error;
 
@@ -22,6 +24,7 @@ pub fn create_websocket_server(
     active_elections: &ActiveElections,
     telemetry: &Telemetry,
     vote_processor: &VoteProcessor,
+    fork_journal: &ForkJournal,
 ) -> Option<Arc<WebsocketListener>> {
     if !config.enabled {
         return None;
@@ -106,9 +109,71 @@ pub fn create_websocket_server(
         },
     ));
 
+    let server_w = Arc::downgrade(&server);
+    fork_journal.on_fork(Box::new(move |record| {
+        if let Some(server) = server_w.upgrade() {
+            if server.any_subscriber(Topic::Fork) {
+                server.broadcast(&fork_resolved(record));
+            }
+        }
+    }));
+
     Some(server)
 }
 
+fn fork_resolved(record: &ForkRecord) -> OutgoingMessageEnvelope {
+    OutgoingMessageEnvelope::new(
+        Topic::Fork,
+        ForkResolved {
+            root: record.root.root.to_string(),
+            previous: record.root.previous.to_string(),
+            account: record.account.encode_account(),
+            winner: record.winner.to_string(),
+            confirmed: record.confirmed.to_string(),
+            detected: as_millis(record.detected).to_string(),
+            resolved: as_millis(record.resolved).to_string(),
+            blocks: record
+                .blocks
+                .iter()
+                .map(|b| ForkBlockJson {
+                    hash: b.hash.to_string(),
+                    tally: b.tally.to_string_dec(),
+                    channel_id: b.channel_id.map(|id| id.to_string()),
+                    address: b.peer_addr.map(|a| a.to_string()),
+                })
+                .collect(),
+        },
+    )
+}
+
+fn as_millis(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+#[derive(Serialize)]
+struct ForkResolved {
+    root: String,
+    previous: String,
+    account: String,
+    winner: String,
+    confirmed: String,
+    detected: String,
+    resolved: String,
+    blocks: Vec<ForkBlockJson>,
+}
+
+#[derive(Serialize)]
+struct ForkBlockJson {
+    hash: String,
+    tally: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+}
+
 fn telemetry_received(data: &TelemetryData, endpoint: SocketAddrV6) -> OutgoingMessageEnvelope {
     OutgoingMessageEnvelope::new(
         Topic::Telemetry,
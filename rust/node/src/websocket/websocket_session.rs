@@ -81,14 +81,14 @@ impl WebsocketSessionEntry {
 pub struct WebsocketSession {
     entry: Arc<WebsocketSessionEntry>,
     wallets: Arc<Wallets>,
-    topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+    topic_subscriber_count: Arc<[AtomicUsize; 12]>,
     remote_endpoint: SocketAddr,
 }
 
 impl WebsocketSession {
     pub fn new(
         wallets: Arc<Wallets>,
-        topic_subscriber_count: Arc<[AtomicUsize; 11]>,
+        topic_subscriber_count: Arc<[AtomicUsize; 12]>,
         remote_endpoint: SocketAddr,
         entry: Arc<WebsocketSessionEntry>,
     ) -> Self {
@@ -0,0 +1,279 @@
+use rsnano_core::{Amount, KeyPair, WalletId, DEV_GENESIS_KEY};
+use rsnano_ledger::DEV_GENESIS_ACCOUNT;
+use rsnano_node::wallets::{AutoReceivePolicy, WalletsExt};
+use std::time::Duration;
+use test_helpers::{assert_always_eq, assert_timely_eq, System};
+
+#[test]
+fn denied_source_is_not_received() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), true)
+        .unwrap();
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.private_key(), true)
+        .unwrap();
+    let policy = AutoReceivePolicy {
+        deny: vec![*DEV_GENESIS_ACCOUNT],
+        ..Default::default()
+    };
+    node.wallets
+        .set_auto_receive_policy(&wallet_id, &key.account(), &policy)
+        .unwrap();
+
+    let send = node
+        .wallets
+        .send_action2(
+            &wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            key.account(),
+            Amount::nano(1000),
+            0,
+            true,
+            None,
+        )
+        .unwrap();
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send.hash()),
+        true,
+    );
+    node.wallets.search_receivable_wallet(wallet_id).unwrap();
+    assert_always_eq(
+        Duration::from_secs(1),
+        || node.balance(&key.account()),
+        Amount::zero(),
+    );
+
+    // Resetting the policy allows receiving again
+    node.wallets
+        .set_auto_receive_policy(&wallet_id, &key.account(), &AutoReceivePolicy::default())
+        .unwrap();
+    node.wallets.search_receivable_wallet(wallet_id).unwrap();
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.balance(&key.account()),
+        Amount::nano(1000),
+    );
+}
+
+#[test]
+fn delayed_receives_are_received_in_batch() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), true)
+        .unwrap();
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.private_key(), true)
+        .unwrap();
+    let policy = AutoReceivePolicy {
+        batch_delay: Duration::from_secs(60),
+        batch_size: 2,
+        ..Default::default()
+    };
+    node.wallets
+        .set_auto_receive_policy(&wallet_id, &key.account(), &policy)
+        .unwrap();
+
+    let send = |amount| {
+        node.wallets
+            .send_action2(
+                &wallet_id,
+                *DEV_GENESIS_ACCOUNT,
+                key.account(),
+                amount,
+                0,
+                true,
+                None,
+            )
+            .unwrap()
+    };
+
+    send(Amount::nano(1));
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.wallets.delayed_receives_count(),
+        1,
+    );
+    assert_eq!(node.balance(&key.account()), Amount::zero());
+
+    // The second send fills the batch, so both get received long before the delay ends
+    send(Amount::nano(2));
+    assert_timely_eq(
+        Duration::from_secs(10),
+        || node.balance(&key.account()),
+        Amount::nano(3),
+    );
+    assert_eq!(node.wallets.delayed_receives_count(), 0);
+    assert_eq!(
+        node.wallets.auto_receive_policy(&wallet_id, &key.account()),
+        policy
+    );
+}
+
+#[test]
+fn delayed_receives_are_dropped_when_policy_gets_disabled() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), true)
+        .unwrap();
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.private_key(), true)
+        .unwrap();
+    let policy = AutoReceivePolicy {
+        batch_delay: Duration::from_secs(60),
+        ..Default::default()
+    };
+    node.wallets
+        .set_auto_receive_policy(&wallet_id, &key.account(), &policy)
+        .unwrap();
+
+    node.wallets
+        .send_action2(
+            &wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            key.account(),
+            Amount::nano(1),
+            0,
+            true,
+            None,
+        )
+        .unwrap();
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.wallets.delayed_receives_count(),
+        1,
+    );
+
+    node.wallets
+        .set_auto_receive_policy(&wallet_id, &key.account(), &AutoReceivePolicy::disabled())
+        .unwrap();
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.wallets.delayed_receives_count(),
+        0,
+    );
+    assert_always_eq(
+        Duration::from_secs(1),
+        || node.balance(&key.account()),
+        Amount::zero(),
+    );
+}
+
+#[test]
+fn delayed_receives_are_checked_against_the_current_policy() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), true)
+        .unwrap();
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.private_key(), true)
+        .unwrap();
+    let policy = AutoReceivePolicy {
+        batch_delay: Duration::from_secs(60),
+        ..Default::default()
+    };
+    node.wallets
+        .set_auto_receive_policy(&wallet_id, &key.account(), &policy)
+        .unwrap();
+
+    node.wallets
+        .send_action2(
+            &wallet_id,
+            *DEV_GENESIS_ACCOUNT,
+            key.account(),
+            Amount::nano(1),
+            0,
+            true,
+            None,
+        )
+        .unwrap();
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.wallets.delayed_receives_count(),
+        1,
+    );
+
+    // The batch becomes due right away, but the source is denied now
+    let policy = AutoReceivePolicy {
+        batch_delay: Duration::from_millis(1),
+        deny: vec![*DEV_GENESIS_ACCOUNT],
+        ..Default::default()
+    };
+    node.wallets
+        .set_auto_receive_policy(&wallet_id, &key.account(), &policy)
+        .unwrap();
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.wallets.delayed_receives_count(),
+        0,
+    );
+    assert_always_eq(
+        Duration::from_secs(1),
+        || node.balance(&key.account()),
+        Amount::zero(),
+    );
+}
+
+#[test]
+fn policies_are_kept_per_wallet() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet1 = node.wallets.wallet_ids()[0];
+    let wallet2 = WalletId::random();
+    node.wallets.create(wallet2);
+    let key = KeyPair::new();
+    for wallet_id in [&wallet1, &wallet2] {
+        node.wallets
+            .insert_adhoc2(wallet_id, &key.private_key(), false)
+            .unwrap();
+    }
+    let policy1 = AutoReceivePolicy::disabled();
+    let policy2 = AutoReceivePolicy {
+        minimum: Some(Amount::nano(1)),
+        ..Default::default()
+    };
+    node.wallets
+        .set_auto_receive_policy(&wallet1, &key.account(), &policy1)
+        .unwrap();
+    node.wallets
+        .set_auto_receive_policy(&wallet2, &key.account(), &policy2)
+        .unwrap();
+
+    assert_eq!(
+        node.wallets.auto_receive_policy(&wallet1, &key.account()),
+        policy1
+    );
+    assert_eq!(
+        node.wallets.auto_receive_policy(&wallet2, &key.account()),
+        policy2
+    );
+
+    // Removing the account from one wallet keeps the policy of the other wallet
+    node.wallets
+        .remove_key(&wallet1, &key.public_key())
+        .unwrap();
+    assert_eq!(
+        node.wallets.auto_receive_policy(&wallet1, &key.account()),
+        AutoReceivePolicy::default()
+    );
+    assert_eq!(
+        node.wallets.auto_receive_policy(&wallet2, &key.account()),
+        policy2
+    );
+}
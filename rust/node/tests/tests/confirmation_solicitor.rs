@@ -54,6 +54,7 @@ fn batches() {
                 ElectionBehavior::Priority,
                 Box::new(|_| {}),
                 Box::new(|_| {}),
+                node1.steady_clock.clone(),
             );
 
             let data = election.mutex.lock().unwrap();
@@ -66,6 +67,7 @@ fn batches() {
             ElectionBehavior::Priority,
             Box::new(|_| {}),
             Box::new(|_| {}),
+            node1.steady_clock.clone(),
         );
         // Broadcasting should be immediate
         assert_eq!(
@@ -133,6 +135,7 @@ fn different_hashes() {
         ElectionBehavior::Priority,
         Box::new(|_| {}),
         Box::new(|_| {}),
+        node1.steady_clock.clone(),
     );
     let mut data = election.mutex.lock().unwrap();
     // Add a vote for something else, not the winner
@@ -203,6 +206,7 @@ fn bypass_max_requests_cap() {
         ElectionBehavior::Priority,
         Box::new(|_| {}),
         Box::new(|_| {}),
+        node1.steady_clock.clone(),
     );
     let mut data = election.mutex.lock().unwrap();
     // Add a vote for something else, not the winner
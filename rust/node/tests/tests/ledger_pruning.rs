@@ -0,0 +1,157 @@
+use rsnano_core::{
+    Account, Amount, BlockEnum, BlockHash, KeyPair, PublicKey, StateBlock, DEV_GENESIS_KEY,
+};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
+use rsnano_node::{
+    config::{FrontiersConfirmationMode, NodeConfig, NodeFlags},
+    stats::{DetailType, Direction, StatType},
+    Node,
+};
+use std::sync::Arc;
+use test_helpers::{setup_chain, System};
+
+#[test]
+fn prune_without_keep_list() {
+    let mut system = System::new();
+    let node = create_pruning_node(&mut system, pruning_config());
+    setup_chain(&node, 3, &DEV_GENESIS_KEY, true);
+
+    node.ledger_pruning(2, false);
+
+    assert!(node.ledger.pruned_count() > 0);
+    let status = node.pruning_status();
+    assert_eq!(status.last_run_pruned, node.ledger.pruned_count());
+    assert_eq!(status.last_run_kept_accounts, 0);
+    assert_eq!(
+        node.stats
+            .count(StatType::Pruning, DetailType::Pruned, Direction::In),
+        node.ledger.pruned_count()
+    );
+}
+
+#[test]
+fn keep_configured_accounts() {
+    let mut system = System::new();
+    let mut config = pruning_config();
+    config.pruning_keep_accounts = vec![*DEV_GENESIS_ACCOUNT];
+    let node = create_pruning_node(&mut system, config);
+    setup_chain(&node, 3, &DEV_GENESIS_KEY, true);
+
+    node.ledger_pruning(2, false);
+
+    assert_eq!(node.ledger.pruned_count(), 0);
+    assert_eq!(node.pruning_status().last_run_kept_accounts, 1);
+    assert!(
+        node.stats
+            .count(StatType::Pruning, DetailType::KeptAccount, Direction::In)
+            > 0
+    );
+}
+
+#[test]
+fn keep_wallet_accounts() {
+    let mut system = System::new();
+    let mut config = pruning_config();
+    config.pruning_keep_wallet_accounts = true;
+    let node = create_pruning_node(&mut system, config);
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+    setup_chain(&node, 3, &DEV_GENESIS_KEY, true);
+
+    node.ledger_pruning(2, false);
+
+    assert_eq!(node.ledger.pruned_count(), 0);
+}
+
+#[test]
+fn keep_representatives() {
+    let mut system = System::new();
+    let mut config = pruning_config();
+    config.pruning_keep_representatives = true;
+    let node = create_pruning_node(&mut system, config);
+    let rep = KeyPair::new();
+    let delegator = KeyPair::new();
+    // The voting weight is split between genesis and `rep`. `delegator` has no weight of its own
+    let rep_chain = create_account_chain(&node, &rep, rep.public_key(), Amount::nano(1000));
+    let delegator_chain =
+        create_account_chain(&node, &delegator, rep.public_key(), Amount::nano(1000));
+    assert!(node.ledger.weight(&rep.public_key()) > Amount::zero());
+    assert!(node.ledger.weight(&DEV_GENESIS_PUB_KEY) > Amount::zero());
+    assert_eq!(node.ledger.weight(&delegator.public_key()), Amount::zero());
+
+    node.ledger_pruning(2, false);
+
+    let txn = node.ledger.read_txn();
+    let is_pruned = |block: &BlockEnum| node.ledger.store.pruned.exists(&txn, &block.hash());
+    assert!(!rep_chain.iter().any(is_pruned));
+    assert!(delegator_chain.iter().any(is_pruned));
+    assert_eq!(
+        node.ledger
+            .store
+            .pruned
+            .exists(&txn, &node.latest(&DEV_GENESIS_ACCOUNT)),
+        false
+    );
+    assert_eq!(node.pruning_status().last_run_kept_accounts, 2);
+}
+
+/// Funds `key` from genesis and creates a confirmed chain of an open and three send blocks
+fn create_account_chain(
+    node: &Node,
+    key: &KeyPair,
+    representative: PublicKey,
+    amount: Amount,
+) -> Vec<BlockEnum> {
+    let genesis_latest = node.latest(&DEV_GENESIS_ACCOUNT);
+    let send = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        genesis_latest,
+        *DEV_GENESIS_PUB_KEY,
+        node.balance(&DEV_GENESIS_ACCOUNT) - amount,
+        key.account().into(),
+        &DEV_GENESIS_KEY,
+        node.work_generate_dev(genesis_latest.into()),
+    ));
+    node.process(send.clone()).unwrap();
+    node.confirm(send.hash());
+
+    let mut blocks = Vec::new();
+    let mut previous = BlockHash::zero();
+    let mut balance = amount;
+    let mut link = send.hash().into();
+    for _ in 0..4 {
+        let root = if previous.is_zero() {
+            key.public_key().into()
+        } else {
+            previous.into()
+        };
+        let block = BlockEnum::State(StateBlock::new(
+            key.account(),
+            previous,
+            representative,
+            balance,
+            link,
+            key,
+            node.work_generate_dev(root),
+        ));
+        node.process(block.clone()).unwrap();
+        node.confirm(block.hash());
+        previous = block.hash();
+        balance = balance - Amount::raw(1);
+        link = Account::from(KeyPair::new().public_key()).into();
+        blocks.push(block);
+    }
+    blocks
+}
+
+fn pruning_config() -> NodeConfig {
+    let mut config = System::default_config();
+    config.frontiers_confirmation = FrontiersConfirmationMode::Disabled;
+    config.enable_voting = false;
+    config
+}
+
+fn create_pruning_node(system: &mut System, config: NodeConfig) -> Arc<Node> {
+    let mut flags = NodeFlags::new();
+    flags.enable_pruning = true;
+    system.build_node().config(config).flags(flags).finish()
+}
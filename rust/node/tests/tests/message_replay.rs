@@ -0,0 +1,67 @@
+use rsnano_core::{Amount, BlockEnum, KeyPair, StateBlock, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_messages::{Message, Publish};
+use rsnano_network::{ChannelDirection, ChannelId};
+use rsnano_node::{
+    transport::{CapturedMessage, MessageCaptureReader, MessageCaptureWriter, MessageReplay},
+    unique_path,
+};
+use rsnano_nullable_clock::SteadyClock;
+use std::{fs::File, sync::Arc, time::Duration};
+use test_helpers::{assert_timely, System};
+
+#[test]
+fn replay_capture_into_fresh_node() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let key = KeyPair::new();
+    let send = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::raw(1),
+        key.account().into(),
+        &DEV_GENESIS_KEY,
+        node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+    ));
+    let protocol = node.network_params.network.protocol_info();
+
+    let file = unique_path().unwrap().join("messages.capture");
+    let mut writer = MessageCaptureWriter::new(File::create(&file).unwrap()).unwrap();
+    writer
+        .write(&CapturedMessage::new(
+            1000,
+            ChannelId::from(1),
+            ChannelDirection::Inbound,
+            &Message::Publish(Publish::new_forward(send.clone())),
+            protocol,
+        ))
+        .unwrap();
+    writer
+        .write(&CapturedMessage::new(
+            1500,
+            ChannelId::from(1),
+            ChannelDirection::Outbound,
+            &Message::TelemetryReq,
+            protocol,
+        ))
+        .unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+
+    let capture = MessageCaptureReader::new(File::open(&file).unwrap())
+        .unwrap()
+        .read_all()
+        .unwrap();
+    let clock = Arc::new(SteadyClock::new_null_with(node.steady_clock.now()));
+    let mut replay = MessageReplay::new(
+        node.inbound_message_queue.clone(),
+        clock,
+        &node.network_params,
+    );
+    let result = node.runtime.block_on(replay.replay(&capture));
+
+    assert_eq!(result.replayed, 1);
+    assert_eq!(result.outbound, 1);
+    assert_timely(Duration::from_secs(5), || node.block_exists(&send.hash()));
+}
@@ -1,9 +1,12 @@
 mod active_elections;
+mod auto_receive_policy;
 mod bootstrap;
 mod bootstrap_server;
 mod confirmation_solicitor;
 mod election;
 mod election_scheduler;
+mod ledger_pruning;
+mod message_replay;
 mod network;
 mod node;
 mod peer_container;
@@ -12,4 +15,5 @@ mod request_aggregator;
 mod telemetry;
 mod vote_processor;
 mod votes;
+mod wallet_backup;
 mod websocket;
@@ -203,3 +203,29 @@ fn mismatched_node_id() {
         0,
     );
 }
+
+#[test]
+fn history() {
+    let mut system = System::new();
+    let node_client = system.make_node();
+    let node_server = system.make_node();
+
+    let channel = node_client
+        .network_info
+        .read()
+        .unwrap()
+        .find_node_id(&node_server.get_node_id())
+        .unwrap()
+        .clone();
+
+    assert_timely(Duration::from_secs(5), || {
+        node_client.telemetry.history(&channel.peer_addr()).len() >= 2
+    });
+
+    let history = node_client.telemetry.history(&channel.peer_addr());
+    assert!(history[0].received <= history[1].received);
+    assert_eq!(
+        history[1].cemented_count,
+        node_server.ledger.cemented_count()
+    );
+}
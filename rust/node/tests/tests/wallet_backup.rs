@@ -0,0 +1,133 @@
+use rsnano_core::{KeyPair, PublicKey, RawKey, WalletId};
+use rsnano_node::{
+    unique_path,
+    wallets::{
+        backup_wallets_with_retention, list_wallet_backups, WalletBackup, WalletBackupConfig,
+        WalletBackupWallet, WalletsExt,
+    },
+};
+use test_helpers::System;
+
+#[test]
+fn backup_and_restore() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let node2 = system.make_node();
+    let wallet_id = WalletId::random();
+    node1.wallets.create(wallet_id);
+    node1
+        .wallets
+        .change_seed(wallet_id, &RawKey::from(42), 0)
+        .unwrap();
+    let deterministic = node1
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    let adhoc = KeyPair::new();
+    node1
+        .wallets
+        .insert_adhoc2(&wallet_id, &adhoc.private_key(), false)
+        .unwrap();
+    node1
+        .wallets
+        .work_set(&wallet_id, &adhoc.public_key(), 12345)
+        .unwrap();
+    node1
+        .wallets
+        .set_representative(wallet_id, PublicKey::from(7), false)
+        .unwrap();
+    let file = unique_path().unwrap().join("wallets.backup");
+
+    node1
+        .wallets
+        .create_backup()
+        .write(&file, "passphrase")
+        .unwrap();
+    assert!(WalletBackup::read(&file, "wrong").is_err());
+    let backup = WalletBackup::read(&file, "passphrase").unwrap();
+    let restored = node2.wallets.restore_backup(&backup).unwrap();
+
+    // node1's default wallet gets restored too
+    assert_eq!(restored.len(), 2);
+    assert!(restored.contains(&wallet_id));
+    assert_eq!(node2.wallets.get_seed(wallet_id).unwrap(), RawKey::from(42));
+    assert_eq!(
+        node2.wallets.deterministic_index_get(&wallet_id).unwrap(),
+        node1.wallets.deterministic_index_get(&wallet_id).unwrap()
+    );
+    assert_eq!(
+        node2.wallets.get_representative(wallet_id).unwrap(),
+        PublicKey::from(7)
+    );
+    assert_eq!(
+        node2
+            .wallets
+            .fetch(&wallet_id, &adhoc.public_key())
+            .unwrap(),
+        adhoc.private_key()
+    );
+    assert!(node2.wallets.exists(&deterministic));
+    assert_eq!(
+        node2.wallets.work_get(&wallet_id, &adhoc.public_key()),
+        12345
+    );
+
+    // restoring again must not overwrite the existing wallet
+    assert!(node2.wallets.restore_backup(&backup).unwrap().is_empty());
+}
+
+#[test]
+fn dont_restore_any_wallet_of_a_corrupt_backup() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let node2 = system.make_node();
+    let mut backup = node1.wallets.create_backup();
+    let valid_wallet = backup.wallets[0].id;
+    let corrupt_wallet = WalletId::random();
+    backup
+        .wallets
+        .push(WalletBackupWallet::new(corrupt_wallet, Vec::new()));
+
+    assert!(node2.wallets.restore_backup(&backup).is_err());
+
+    let wallet_ids = node2.wallets.wallet_ids();
+    assert!(!wallet_ids.contains(&corrupt_wallet));
+    // The default wallet of node1 has a random id, so it must not exist in node2
+    assert!(!wallet_ids.contains(&valid_wallet));
+}
+
+#[test]
+fn backup_rotation() {
+    let mut system = System::new();
+    let node = system.make_node();
+    node.wallets.create(WalletId::random());
+    let backups_path = unique_path().unwrap().join("backups");
+    let config = WalletBackupConfig {
+        max_backups: 2,
+        passphrase: "passphrase".to_string(),
+    };
+
+    let mut written = Vec::new();
+    for _ in 0..3 {
+        written.push(backup_wallets_with_retention(&node.wallets, &backups_path, &config).unwrap());
+        // backup file names have millisecond resolution
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+
+    assert_eq!(list_wallet_backups(&backups_path).unwrap(), written[1..]);
+    let backup = WalletBackup::read(&written[2], "passphrase").unwrap();
+    assert_eq!(backup.wallets.len(), node.wallets.wallet_ids().len());
+}
+
+#[test]
+fn refuse_automatic_backup_without_passphrase() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let backups_path = unique_path().unwrap().join("backups");
+
+    let result =
+        backup_wallets_with_retention(&node.wallets, &backups_path, &WalletBackupConfig::default());
+
+    assert!(result.is_err());
+    assert!(list_wallet_backups(&backups_path).unwrap().is_empty());
+}
@@ -1,5 +1,6 @@
 use std::{
     ops::{Add, Sub},
+    sync::atomic::{AtomicI64, Ordering},
     time::{Duration, Instant},
 };
 
@@ -9,14 +10,28 @@ pub struct SteadyClock {
 
 impl SteadyClock {
     pub fn new_null() -> Self {
+        Self::new_null_with(Timestamp(DEFAULT_STUB_DURATION))
+    }
+
+    pub fn new_null_with(now: Timestamp) -> Self {
         Self {
-            time_source: TimeSource::Stub(DEFAULT_STUB_DURATION),
+            time_source: TimeSource::Stub(AtomicI64::new(now.0)),
         }
     }
 
     pub fn now(&self) -> Timestamp {
         Timestamp(self.time_source.now())
     }
+
+    /// Moves the time of a nulled clock forward. Panics if the clock isn't nulled
+    pub fn advance(&self, duration: Duration) {
+        match &self.time_source {
+            TimeSource::System(_) => panic!("cannot advance a real clock"),
+            TimeSource::Stub(value) => {
+                value.fetch_add(duration.as_millis() as i64, Ordering::SeqCst);
+            }
+        }
+    }
 }
 
 impl Default for SteadyClock {
@@ -29,14 +44,14 @@ impl Default for SteadyClock {
 
 enum TimeSource {
     System(Instant),
-    Stub(i64),
+    Stub(AtomicI64),
 }
 
 impl TimeSource {
     fn now(&self) -> i64 {
         match self {
             TimeSource::System(instant) => instant.elapsed().as_millis() as i64,
-            TimeSource::Stub(value) => *value,
+            TimeSource::Stub(value) => value.load(Ordering::SeqCst),
         }
     }
 }
@@ -141,5 +156,18 @@ mod tests {
             let now2 = clock.now();
             assert_eq!(now1, now2);
         }
+
+        #[test]
+        fn configure_now() {
+            let clock = SteadyClock::new_null_with(Timestamp::from(1234));
+            assert_eq!(clock.now(), Timestamp::from(1234));
+        }
+
+        #[test]
+        fn advance() {
+            let clock = SteadyClock::new_null_with(Timestamp::from(1000));
+            clock.advance(Duration::from_millis(500));
+            assert_eq!(clock.now(), Timestamp::from(1500));
+        }
     }
 }
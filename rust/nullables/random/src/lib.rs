@@ -1,4 +1,4 @@
-use rand::{rngs::ThreadRng, CryptoRng, RngCore};
+use rand::{rngs::StdRng, CryptoRng, RngCore, SeedableRng};
 
 pub struct NullableRng {
     strategy: RngStrategy,
@@ -23,38 +23,48 @@ impl NullableRng {
 
     pub fn thread_rng() -> Self {
         Self {
-            strategy: RngStrategy::Thread(rand::thread_rng()),
+            strategy: RngStrategy::Thread,
         }
     }
 
-    fn as_rng_core(&mut self) -> &mut dyn RngCore {
+    /// A pseudo random generator that always produces the same sequence for the same seed
+    pub fn new_seeded(seed: u64) -> Self {
+        Self {
+            strategy: RngStrategy::Seeded(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// The thread rng is fetched on every call, so that a `NullableRng` can be sent between threads
+    fn with_rng_core<T>(&mut self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
         match &mut self.strategy {
-            RngStrategy::Thread(i) => i,
-            RngStrategy::Nulled(i) => i,
+            RngStrategy::Thread => f(&mut rand::thread_rng()),
+            RngStrategy::Seeded(i) => f(i),
+            RngStrategy::Nulled(i) => f(i),
         }
     }
 }
 
 impl RngCore for NullableRng {
     fn next_u32(&mut self) -> u32 {
-        self.as_rng_core().next_u32()
+        self.with_rng_core(|rng| rng.next_u32())
     }
 
     fn next_u64(&mut self) -> u64 {
-        self.as_rng_core().next_u64()
+        self.with_rng_core(|rng| rng.next_u64())
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        self.as_rng_core().fill_bytes(dest)
+        self.with_rng_core(|rng| rng.fill_bytes(dest))
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
-        self.as_rng_core().try_fill_bytes(dest)
+        self.with_rng_core(|rng| rng.try_fill_bytes(dest))
     }
 }
 
 enum RngStrategy {
-    Thread(ThreadRng),
+    Thread,
+    Seeded(StdRng),
     Nulled(RngStub),
 }
 
@@ -127,6 +137,18 @@ mod tests {
         assert_eq!(buffer.iter().all(|&b| b == 0), false);
     }
 
+    #[test]
+    fn seeded_rng() {
+        let mut a = NullableRng::new_seeded(7);
+        let mut b = NullableRng::new_seeded(7);
+        let mut c = NullableRng::new_seeded(8);
+        let sequence_a: Vec<u64> = (0..3).map(|_| a.next_u64()).collect();
+        let sequence_b: Vec<u64> = (0..3).map(|_| b.next_u64()).collect();
+        let sequence_c: Vec<u64> = (0..3).map(|_| c.next_u64()).collect();
+        assert_eq!(sequence_a, sequence_b);
+        assert_ne!(sequence_a, sequence_c);
+    }
+
     #[test]
     fn nullable_with_u64() {
         let mut rng = NullableRng::new_null();
@@ -0,0 +1,412 @@
+use std::{
+    io::{Error, ErrorKind},
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+const VERSION: u8 = 5;
+const AUTH_NONE: u8 = 0;
+const AUTH_USERNAME_PASSWORD: u8 = 2;
+const AUTH_NO_ACCEPTABLE_METHOD: u8 = 0xff;
+const AUTH_USERNAME_PASSWORD_VERSION: u8 = 1;
+const COMMAND_CONNECT: u8 = 1;
+const ADDRESS_IPV4: u8 = 1;
+const ADDRESS_DOMAIN: u8 = 3;
+const ADDRESS_IPV6: u8 = 4;
+const REPLY_SUCCEEDED: u8 = 0;
+const REPLY_HOST_UNREACHABLE: u8 = 4;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Socks5Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// A SOCKS5 proxy through which outbound TCP connections are established (RFC 1928/1929)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Socks5Proxy {
+    pub address: SocketAddr,
+    pub credentials: Option<Socks5Credentials>,
+}
+
+impl Socks5Proxy {
+    pub fn new(address: SocketAddr) -> Self {
+        Self {
+            address,
+            credentials: None,
+        }
+    }
+
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some(Socks5Credentials {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+
+    /// Connects to the proxy and asks it to open a connection to `target`
+    pub async fn connect(&self, target: SocketAddr) -> tokio::io::Result<TcpStream> {
+        let mut stream = TcpStream::connect(self.address).await?;
+        self.authenticate(&mut stream).await?;
+        send_connect_request(&mut stream, target).await?;
+        Ok(stream)
+    }
+
+    async fn authenticate(&self, stream: &mut TcpStream) -> tokio::io::Result<()> {
+        let greeting: &[u8] = match self.credentials {
+            Some(_) => &[VERSION, 2, AUTH_NONE, AUTH_USERNAME_PASSWORD],
+            None => &[VERSION, 1, AUTH_NONE],
+        };
+        stream.write_all(greeting).await?;
+
+        let mut reply = [0; 2];
+        stream.read_exact(&mut reply).await?;
+        if reply[0] != VERSION {
+            return Err(protocol_error("invalid SOCKS version in proxy reply"));
+        }
+
+        match (reply[1], &self.credentials) {
+            (AUTH_NONE, _) => Ok(()),
+            (AUTH_USERNAME_PASSWORD, Some(credentials)) => {
+                send_credentials(stream, credentials).await
+            }
+            (AUTH_NO_ACCEPTABLE_METHOD, _) => Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "SOCKS5 proxy accepted none of the offered authentication methods",
+            )),
+            _ => Err(protocol_error(
+                "SOCKS5 proxy selected an unsupported authentication method",
+            )),
+        }
+    }
+}
+
+async fn send_credentials(
+    stream: &mut TcpStream,
+    credentials: &Socks5Credentials,
+) -> tokio::io::Result<()> {
+    let username = credentials.username.as_bytes();
+    let password = credentials.password.as_bytes();
+    if username.len() > u8::MAX as usize || password.len() > u8::MAX as usize {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "SOCKS5 username and password must not exceed 255 bytes",
+        ));
+    }
+
+    let mut request = Vec::with_capacity(3 + username.len() + password.len());
+    request.push(AUTH_USERNAME_PASSWORD_VERSION);
+    request.push(username.len() as u8);
+    request.extend_from_slice(username);
+    request.push(password.len() as u8);
+    request.extend_from_slice(password);
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0 {
+        return Err(Error::new(
+            ErrorKind::PermissionDenied,
+            "SOCKS5 proxy rejected the credentials",
+        ));
+    }
+    Ok(())
+}
+
+async fn send_connect_request(stream: &mut TcpStream, target: SocketAddr) -> tokio::io::Result<()> {
+    let mut request = vec![VERSION, COMMAND_CONNECT, 0];
+    // IPv4 mapped addresses are sent as plain IPv4, because not every proxy supports IPv6
+    match target.ip().to_canonical() {
+        IpAddr::V4(ip) => {
+            request.push(ADDRESS_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            request.push(ADDRESS_IPV6);
+            request.extend_from_slice(&ip.octets());
+        }
+    }
+    request.extend_from_slice(&target.port().to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != VERSION {
+        return Err(protocol_error("invalid SOCKS version in proxy reply"));
+    }
+    if reply[1] != REPLY_SUCCEEDED {
+        return Err(Error::new(
+            ErrorKind::ConnectionRefused,
+            format!(
+                "SOCKS5 proxy could not connect to {} (reply {})",
+                target, reply[1]
+            ),
+        ));
+    }
+
+    // Skip the bound address, which we don't need
+    let address_len = match reply[3] {
+        ADDRESS_IPV4 => 4,
+        ADDRESS_IPV6 => 16,
+        ADDRESS_DOMAIN => stream.read_u8().await? as usize,
+        _ => return Err(protocol_error("invalid address type in proxy reply")),
+    };
+    let mut bound_address = vec![0; address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+    Ok(())
+}
+
+fn protocol_error(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+/// A minimal SOCKS5 proxy that listens on localhost and relays CONNECT requests.
+/// It is used for testing outbound connections through a proxy
+pub struct Socks5StubProxy {
+    address: SocketAddr,
+    connections: Arc<Mutex<Vec<SocketAddr>>>,
+}
+
+impl Socks5StubProxy {
+    pub async fn start(credentials: Option<Socks5Credentials>) -> tokio::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let connections = Arc::new(Mutex::new(Vec::new()));
+        let connections_l = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((client, _)) = listener.accept().await {
+                let credentials = credentials.clone();
+                let connections = connections_l.clone();
+                tokio::spawn(async move {
+                    let _ = relay(client, credentials, connections).await;
+                });
+            }
+        });
+        Ok(Self {
+            address,
+            connections,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn proxy(&self) -> Socks5Proxy {
+        Socks5Proxy::new(self.address)
+    }
+
+    /// The targets of all successfully relayed connections
+    pub fn connections(&self) -> Vec<SocketAddr> {
+        self.connections.lock().unwrap().clone()
+    }
+}
+
+async fn relay(
+    mut client: TcpStream,
+    credentials: Option<Socks5Credentials>,
+    connections: Arc<Mutex<Vec<SocketAddr>>>,
+) -> tokio::io::Result<()> {
+    let mut header = [0; 2];
+    client.read_exact(&mut header).await?;
+    let mut methods = vec![0; header[1] as usize];
+    client.read_exact(&mut methods).await?;
+
+    let method = if credentials.is_some() {
+        AUTH_USERNAME_PASSWORD
+    } else {
+        AUTH_NONE
+    };
+    if !methods.contains(&method) {
+        client
+            .write_all(&[VERSION, AUTH_NO_ACCEPTABLE_METHOD])
+            .await?;
+        return Ok(());
+    }
+    client.write_all(&[VERSION, method]).await?;
+
+    if let Some(expected) = credentials {
+        let _version = client.read_u8().await?;
+        let mut username = vec![0; client.read_u8().await? as usize];
+        client.read_exact(&mut username).await?;
+        let mut password = vec![0; client.read_u8().await? as usize];
+        client.read_exact(&mut password).await?;
+        let valid =
+            username == expected.username.as_bytes() && password == expected.password.as_bytes();
+        client
+            .write_all(&[AUTH_USERNAME_PASSWORD_VERSION, if valid { 0 } else { 1 }])
+            .await?;
+        if !valid {
+            return Ok(());
+        }
+    }
+
+    let mut request = [0; 4];
+    client.read_exact(&mut request).await?;
+    let ip: IpAddr = match request[3] {
+        ADDRESS_IPV4 => {
+            let mut octets = [0; 4];
+            client.read_exact(&mut octets).await?;
+            octets.into()
+        }
+        ADDRESS_IPV6 => {
+            let mut octets = [0; 16];
+            client.read_exact(&mut octets).await?;
+            octets.into()
+        }
+        _ => return Err(protocol_error("unsupported address type")),
+    };
+    let target = SocketAddr::new(ip, client.read_u16().await?);
+
+    let mut upstream = match TcpStream::connect(target).await {
+        Ok(upstream) => upstream,
+        Err(_) => {
+            client
+                .write_all(&[
+                    VERSION,
+                    REPLY_HOST_UNREACHABLE,
+                    0,
+                    ADDRESS_IPV4,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                ])
+                .await?;
+            return Ok(());
+        }
+    };
+    connections.lock().unwrap().push(target);
+    client
+        .write_all(&[VERSION, REPLY_SUCCEEDED, 0, ADDRESS_IPV4, 0, 0, 0, 0, 0, 0])
+        .await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, SocketAddrV6};
+
+    #[tokio::test]
+    async fn connect_through_proxy() {
+        let (target, _server) = start_echo_server().await;
+        let proxy = Socks5StubProxy::start(None).await.unwrap();
+
+        let mut stream = proxy.proxy().connect(target).await.unwrap();
+
+        assert_echo(&mut stream).await;
+        assert_eq!(proxy.connections(), [target]);
+    }
+
+    #[tokio::test]
+    async fn connect_with_credentials() {
+        let (target, _server) = start_echo_server().await;
+        let credentials = Socks5Credentials {
+            username: "user".to_string(),
+            password: "secret".to_string(),
+        };
+        let proxy = Socks5StubProxy::start(Some(credentials)).await.unwrap();
+
+        let mut stream = proxy
+            .proxy()
+            .with_credentials("user", "secret")
+            .connect(target)
+            .await
+            .unwrap();
+
+        assert_echo(&mut stream).await;
+    }
+
+    #[tokio::test]
+    async fn wrong_credentials() {
+        let credentials = Socks5Credentials {
+            username: "user".to_string(),
+            password: "secret".to_string(),
+        };
+        let proxy = Socks5StubProxy::start(Some(credentials)).await.unwrap();
+
+        let error = proxy
+            .proxy()
+            .with_credentials("user", "wrong")
+            .connect(proxy.address())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn missing_credentials() {
+        let credentials = Socks5Credentials {
+            username: "user".to_string(),
+            password: "secret".to_string(),
+        };
+        let proxy = Socks5StubProxy::start(Some(credentials)).await.unwrap();
+
+        let error = proxy.proxy().connect(proxy.address()).await.unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn ipv4_mapped_target_is_sent_as_ipv4() {
+        let (target, _server) = start_echo_server().await;
+        let proxy = Socks5StubProxy::start(None).await.unwrap();
+        let mapped = SocketAddr::V6(SocketAddrV6::new(
+            Ipv4Addr::LOCALHOST.to_ipv6_mapped(),
+            target.port(),
+            0,
+            0,
+        ));
+
+        proxy.proxy().connect(mapped).await.unwrap();
+
+        assert_eq!(proxy.connections(), [target]);
+    }
+
+    #[tokio::test]
+    async fn target_unreachable() {
+        let proxy = Socks5StubProxy::start(None).await.unwrap();
+        let unused = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target = unused.local_addr().unwrap();
+        drop(unused);
+
+        let error = proxy.proxy().connect(target).await.unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::ConnectionRefused);
+    }
+
+    async fn start_echo_server() -> (SocketAddr, tokio::task::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let (mut reader, mut writer) = socket.split();
+                    let _ = tokio::io::copy(&mut reader, &mut writer).await;
+                });
+            }
+        });
+        (address, handle)
+    }
+
+    async fn assert_echo(stream: &mut TcpStream) {
+        stream.write_all(&[1, 2, 3]).await.unwrap();
+        let mut buf = [0; 3];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+    }
+}
@@ -39,6 +39,22 @@ impl TcpStream {
         }
     }
 
+    /// A stream that never receives data and hands every written buffer to `on_write`.
+    /// It is used to connect nodes that run in the same process
+    pub fn new_in_memory(
+        local_addr: SocketAddrV6,
+        peer_addr: SocketAddrV6,
+        on_write: impl Fn(&[u8]) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            stream: Box::new(InMemoryTcpStream {
+                local_addr,
+                peer_addr,
+                on_write: Box::new(on_write),
+            }),
+        }
+    }
+
     pub async fn shutdown(&mut self) -> tokio::io::Result<()> {
         self.stream.shutdown().await
     }
@@ -180,6 +196,46 @@ impl InternalTcpStream for TcpStreamStub {
     }
 }
 
+type WriteCallback = Box<dyn Fn(&[u8]) + Send + Sync>;
+
+struct InMemoryTcpStream {
+    local_addr: SocketAddrV6,
+    peer_addr: SocketAddrV6,
+    on_write: WriteCallback,
+}
+
+#[async_trait]
+impl InternalTcpStream for InMemoryTcpStream {
+    async fn readable(&self) -> tokio::io::Result<()> {
+        std::future::pending().await
+    }
+
+    fn try_read(&self, _buf: &mut [u8]) -> tokio::io::Result<usize> {
+        Err(ErrorKind::WouldBlock.into())
+    }
+
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        Ok(SocketAddr::V6(self.local_addr))
+    }
+
+    fn peer_addr(&self) -> std::io::Result<SocketAddr> {
+        Ok(SocketAddr::V6(self.peer_addr))
+    }
+
+    async fn writable(&self) -> tokio::io::Result<()> {
+        Ok(())
+    }
+
+    fn try_write(&self, buf: &[u8]) -> tokio::io::Result<usize> {
+        (self.on_write)(buf);
+        Ok(buf.len())
+    }
+
+    async fn shutdown(&mut self) -> tokio::io::Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +243,7 @@ mod tests {
     use std::{
         io::ErrorKind,
         net::{IpAddr, Ipv4Addr, SocketAddr},
+        sync::{Arc, Mutex},
     };
     use tokio::{net::TcpListener, spawn};
 
@@ -289,6 +346,31 @@ mod tests {
             .expect_err("try_read should fail on second call");
     }
 
+    #[tokio::test]
+    async fn in_memory_stream_forwards_written_data() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let written_l = written.clone();
+        let peer_addr = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 2222, 0, 0);
+        let stream = TcpStream::new_in_memory(TEST_ENDPOINT_1, peer_addr, move |buf| {
+            written_l.lock().unwrap().extend_from_slice(buf)
+        });
+
+        stream.writable().await.unwrap();
+        assert_eq!(stream.try_write(&[1, 2, 3]).unwrap(), 3);
+        assert_eq!(stream.try_write(&[4]).unwrap(), 1);
+
+        assert_eq!(*written.lock().unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            stream.local_addr().unwrap(),
+            SocketAddr::V6(TEST_ENDPOINT_1)
+        );
+        assert_eq!(stream.peer_addr().unwrap(), SocketAddr::V6(peer_addr));
+        assert_eq!(
+            stream.try_read(&mut [0]).unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+    }
+
     async fn start_test_tcp_server(endpoint: SocketAddr) {
         let listener = TcpListener::bind(endpoint).await.unwrap();
 
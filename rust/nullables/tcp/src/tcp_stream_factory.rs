@@ -1,4 +1,4 @@
-use super::tcp_stream::TcpStream;
+use super::{tcp_stream::TcpStream, Socks5Proxy};
 use async_trait::async_trait;
 use std::net::{SocketAddr, ToSocketAddrs};
 
@@ -13,6 +13,13 @@ impl TcpStreamFactory {
         }
     }
 
+    /// Establishes all connections through the given SOCKS5 proxy
+    pub fn new_with_socks5_proxy(proxy: Socks5Proxy) -> Self {
+        Self {
+            inner: Box::new(Socks5TcpStreamFactory { proxy }),
+        }
+    }
+
     pub fn new_null() -> Self {
         Self {
             inner: Box::new(NullTcpStreamFactory {}),
@@ -53,9 +60,22 @@ impl InternalTcpStreamFactory for TcpStreamFactoryWrapper {
     }
 }
 
+struct Socks5TcpStreamFactory {
+    proxy: Socks5Proxy,
+}
+
+#[async_trait]
+impl InternalTcpStreamFactory for Socks5TcpStreamFactory {
+    async fn connect(&self, addr: SocketAddr) -> tokio::io::Result<TcpStream> {
+        let tokio_stream = self.proxy.connect(addr).await?;
+        Ok(TcpStream::new(tokio_stream))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Socks5StubProxy;
     use std::io::ErrorKind;
 
     #[tokio::test]
@@ -72,4 +92,17 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn connect_through_socks5_proxy() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target = listener.local_addr().unwrap();
+        let proxy = Socks5StubProxy::start(None).await.unwrap();
+        let factory = TcpStreamFactory::new_with_socks5_proxy(proxy.proxy());
+
+        let stream = factory.connect(target).await.unwrap();
+
+        assert_eq!(stream.peer_addr().unwrap(), proxy.address());
+        assert_eq!(proxy.connections(), [target]);
+    }
 }
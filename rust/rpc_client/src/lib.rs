@@ -26,6 +26,99 @@ impl NanoRpcClient {
         }
     }
 
+    pub async fn stats(&self, stats_type: StatsType) -> Result<Value> {
+        let cmd = RpcCommand::stats(stats_type);
+        self.rpc_request(&cmd).await
+    }
+
+    pub async fn pruned_exists(&self, hash: BlockHash) -> Result<ExistsDto> {
+        let cmd = RpcCommand::pruned_exists(hash);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn pruning_status(&self) -> Result<PruningStatusDto> {
+        let cmd = RpcCommand::pruning_status();
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn forks(&self, args: ForksArgs) -> Result<ForksDto> {
+        let cmd = RpcCommand::forks(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn online_weight(&self) -> Result<OnlineWeightDto> {
+        let cmd = RpcCommand::online_weight();
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn peer_exclusions(&self) -> Result<PeerExclusionsDto> {
+        let cmd = RpcCommand::peer_exclusions();
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn bandwidth_limits(&self) -> Result<BandwidthLimitsDto> {
+        let cmd = RpcCommand::bandwidth_limits();
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn bandwidth_limit_set(
+        &self,
+        args: BandwidthLimitSetArgs,
+    ) -> Result<BandwidthLimitsDto> {
+        let cmd = RpcCommand::bandwidth_limit_set(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn telemetry_history(
+        &self,
+        address: Ipv6Addr,
+        port: u16,
+    ) -> Result<TelemetryHistoryDto> {
+        let cmd = RpcCommand::telemetry_history(address, port);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn telemetry_summary(
+        &self,
+        args: TelemetrySummaryArgs,
+    ) -> Result<TelemetrySummaryDto> {
+        let cmd = RpcCommand::telemetry_summary(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn peer_ban(&self, args: PeerBanArgs) -> Result<PeerBanDto> {
+        let cmd = RpcCommand::peer_ban(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn peer_unban(&self, subnet: impl Into<String>) -> Result<RemovedDto> {
+        let cmd = RpcCommand::peer_unban(subnet);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn peer_drop(&self, args: PeerDropArgs) -> Result<PeerDropDto> {
+        let cmd = RpcCommand::peer_drop(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn ledger_backup(&self, args: LedgerBackupArgs) -> Result<LedgerBackupDto> {
+        let cmd = RpcCommand::ledger_backup(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn account_get(&self, key: PublicKey) -> Result<AccountRpcMessage> {
         let cmd = RpcCommand::account_get(key);
         let result = self.rpc_request(&cmd).await?;
@@ -38,6 +131,21 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn block_create_unsigned(
+        &self,
+        args: BlockCreateUnsignedArgs,
+    ) -> Result<BlockCreateUnsignedDto> {
+        let cmd = RpcCommand::block_create_unsigned(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn block_assemble(&self, args: BlockAssembleArgs) -> Result<BlockAssembleDto> {
+        let cmd = RpcCommand::block_assemble(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn republish(&self, args: impl Into<RepublishArgs>) -> Result<BlockHashesDto> {
         let cmd = RpcCommand::republish(args.into());
         let result = self.rpc_request(&cmd).await?;
@@ -74,6 +182,49 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn threshold_sign_start(
+        &self,
+        block: JsonBlock,
+        group: String,
+    ) -> Result<HashRpcMessage> {
+        let cmd = RpcCommand::threshold_sign_start(block, group);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn threshold_sign_commit(
+        &self,
+        hash: BlockHash,
+        commitment: String,
+    ) -> Result<ThresholdSignStatusDto> {
+        let cmd = RpcCommand::threshold_sign_commit(hash, commitment);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn threshold_sign_submit(
+        &self,
+        hash: BlockHash,
+        participant: u16,
+        partial_signature: String,
+    ) -> Result<ThresholdSignStatusDto> {
+        let cmd = RpcCommand::threshold_sign_submit(hash, participant, partial_signature);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn threshold_sign_status(&self, hash: BlockHash) -> Result<ThresholdSignStatusDto> {
+        let cmd = RpcCommand::threshold_sign_status(hash);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn threshold_sign_abort(&self, hash: BlockHash) -> Result<SuccessDto> {
+        let cmd = RpcCommand::threshold_sign_abort(hash);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn unchecked_get(&self, hash: BlockHash) -> Result<UncheckedGetDto> {
         let cmd = RpcCommand::unchecked_get(hash);
         let result = self.rpc_request(&cmd).await?;
@@ -445,6 +596,36 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn wallet_import(&self, args: WalletImportArgs) -> Result<WalletRpcMessage> {
+        let cmd = RpcCommand::wallet_import(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn wallet_mnemonic_create(
+        &self,
+        args: WalletMnemonicCreateArgs,
+    ) -> Result<WalletMnemonicCreateDto> {
+        let cmd = RpcCommand::wallet_mnemonic_create(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn wallet_mnemonic_restore(
+        &self,
+        args: WalletMnemonicRestoreArgs,
+    ) -> Result<WalletChangeSeedDto> {
+        let cmd = RpcCommand::wallet_mnemonic_restore(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn wallet_mnemonic_export(&self, wallet: WalletId) -> Result<MnemonicDto> {
+        let cmd = RpcCommand::wallet_mnemonic_export(wallet);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn password_change(&self, wallet: WalletId, password: String) -> Result<SuccessDto> {
         let cmd = RpcCommand::password_change(wallet, password);
         let result = self.rpc_request(&cmd).await?;
@@ -542,6 +723,22 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn receive_policy(
+        &self,
+        wallet: WalletId,
+        account: Account,
+    ) -> Result<ReceivePolicyDto> {
+        let cmd = RpcCommand::receive_policy(wallet, account);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn receive_policy_set(&self, args: ReceivePolicySetArgs) -> Result<SuccessDto> {
+        let cmd = RpcCommand::receive_policy_set(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn wallet_change_seed(
         &self,
         args: impl Into<WalletChangeSeedArgs>,
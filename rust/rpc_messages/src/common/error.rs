@@ -1,9 +1,10 @@
-use rsnano_node::wallets::WalletsError;
+use rsnano_node::{signing::ThresholdSigningError, wallets::WalletsError};
 use serde::{ser::SerializeMap, Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 pub enum ErrorDto {
     WalletsError(WalletsError),
+    ThresholdSigningError(ThresholdSigningError),
     RPCControlDisabled,
     AccountNotFound,
     BlockNotFound,
@@ -35,6 +36,22 @@ pub enum ErrorDto {
     BlockWorkVersioMismatch,
     AccountHeadNotFound,
     InsufficientBalance,
+    LedgerBackupFailed,
+    PruningDisabled,
+    InvalidSubnet,
+    InvalidBanDuration,
+    InvalidBandwidthLimitType,
+    InvalidBandwidthLimit,
+    InvalidBurstRatio,
+    InvalidWalletJson,
+    InvalidMnemonic,
+    InvalidMnemonicWordCount,
+    NotWatchOnlyAccount,
+    InvalidSigningPayload,
+    InvalidReceivePolicy,
+    InvalidThresholdGroup,
+    InvalidSigningCommitment,
+    InvalidPartialSignature,
 }
 
 impl Serialize for ErrorDto {
@@ -44,6 +61,7 @@ impl Serialize for ErrorDto {
     {
         let error_message = match self {
             ErrorDto::WalletsError(e) => e.to_string(),
+            ErrorDto::ThresholdSigningError(e) => e.to_string(),
             ErrorDto::RPCControlDisabled => "RPC control is disabled".to_string(),
             ErrorDto::AccountNotFound => "Account not found".to_string(),
             ErrorDto::BlockNotFound => "Block not found".to_string(),
@@ -75,6 +93,22 @@ impl Serialize for ErrorDto {
             ErrorDto::BlockWorkVersioMismatch => "Block work version mismatch".to_string(),
             ErrorDto::AccountHeadNotFound => "Account head not found".to_string(),
             ErrorDto::InsufficientBalance => "Insufficient balance".to_string(),
+            ErrorDto::LedgerBackupFailed => "Ledger backup failed".to_string(),
+            ErrorDto::PruningDisabled => "Pruning is disabled".to_string(),
+            ErrorDto::InvalidSubnet => "Invalid IP address or subnet".to_string(),
+            ErrorDto::InvalidBanDuration => "Ban duration is too long".to_string(),
+            ErrorDto::InvalidBandwidthLimitType => "Invalid bandwidth limit type".to_string(),
+            ErrorDto::InvalidBandwidthLimit => "Bandwidth limit is too high".to_string(),
+            ErrorDto::InvalidBurstRatio => "Invalid burst ratio".to_string(),
+            ErrorDto::InvalidWalletJson => "Invalid wallet json".to_string(),
+            ErrorDto::InvalidMnemonic => "Invalid mnemonic".to_string(),
+            ErrorDto::InvalidMnemonicWordCount => "Invalid mnemonic word count".to_string(),
+            ErrorDto::NotWatchOnlyAccount => "Account is not a watch-only account".to_string(),
+            ErrorDto::InvalidSigningPayload => "Invalid signing payload".to_string(),
+            ErrorDto::InvalidReceivePolicy => "Invalid receive policy".to_string(),
+            ErrorDto::InvalidThresholdGroup => "Invalid threshold group".to_string(),
+            ErrorDto::InvalidSigningCommitment => "Invalid signing commitment".to_string(),
+            ErrorDto::InvalidPartialSignature => "Invalid partial signature".to_string(),
         };
 
         let mut map = serializer.serialize_map(Some(1))?;
@@ -12,7 +12,7 @@ impl RpcCommand {
 
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct BlocksInfoDto {
-    blocks: HashMap<BlockHash, BlockInfoDto>,
+    pub blocks: HashMap<BlockHash, BlockInfoDto>,
 }
 
 impl BlocksInfoDto {
@@ -21,6 +21,8 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod pruned_exists;
+mod pruning_status;
 mod representatives;
 mod successors;
 mod unopened;
@@ -43,5 +45,6 @@ pub use chain::*;
 pub use delegators::*;
 pub use frontiers::*;
 pub use ledger::*;
+pub use pruning_status::*;
 pub use representatives::*;
 pub use unopened::*;
@@ -0,0 +1,34 @@
+use crate::{common::HashRpcMessage, RpcCommand};
+use rsnano_core::BlockHash;
+
+impl RpcCommand {
+    pub fn pruned_exists(hash: BlockHash) -> Self {
+        Self::PrunedExists(HashRpcMessage::new(hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RpcCommand;
+    use rsnano_core::BlockHash;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_pruned_exists_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::pruned_exists(BlockHash::zero())).unwrap(),
+            r#"{
+  "action": "pruned_exists",
+  "hash": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_pruned_exists_command() {
+        let cmd = RpcCommand::pruned_exists(BlockHash::zero());
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
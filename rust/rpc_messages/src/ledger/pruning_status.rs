@@ -0,0 +1,66 @@
+use crate::RpcCommand;
+use rsnano_core::Account;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn pruning_status() -> Self {
+        Self::PruningStatus
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PruningStatusDto {
+    pub enabled: bool,
+    pub pruned_count: u64,
+    pub block_count: u64,
+    pub runs: u64,
+    pub last_run: u64,
+    pub last_run_pruned: u64,
+    pub last_run_kept_accounts: u64,
+    pub keep_wallet_accounts: bool,
+    pub keep_representatives: bool,
+    pub keep_accounts: Vec<Account>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_pruning_status_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::pruning_status()).unwrap(),
+            r#"{
+  "action": "pruning_status"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_pruning_status_command() {
+        let cmd = RpcCommand::pruning_status();
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_pruning_status_dto() {
+        let dto = PruningStatusDto {
+            enabled: true,
+            pruned_count: 1,
+            block_count: 2,
+            runs: 3,
+            last_run: 4,
+            last_run_pruned: 5,
+            last_run_kept_accounts: 6,
+            keep_wallet_accounts: true,
+            keep_representatives: false,
+            keep_accounts: vec![Account::from(7)],
+        };
+        let serialized = to_string_pretty(&dto).unwrap();
+        let deserialized: PruningStatusDto = from_str(&serialized).unwrap();
+        assert_eq!(dto, deserialized)
+    }
+}
@@ -57,6 +57,10 @@ pub enum RpcCommand {
     Frontiers(FrontiersArgs),
     WalletInfo(WalletRpcMessage),
     WalletExport(WalletRpcMessage),
+    WalletImport(WalletImportArgs),
+    WalletMnemonicCreate(WalletMnemonicCreateArgs),
+    WalletMnemonicRestore(WalletMnemonicRestoreArgs),
+    WalletMnemonicExport(WalletRpcMessage),
     PasswordChange(WalletWithPasswordArgs),
     PasswordEnter(WalletWithPasswordArgs),
     PasswordValid(WalletRpcMessage),
@@ -72,6 +76,8 @@ pub enum RpcCommand {
     NodeId,
     SearchReceivableAll,
     ReceiveMinimum,
+    ReceivePolicy(WalletWithAccountArgs),
+    ReceivePolicySet(ReceivePolicySetArgs),
     WalletChangeSeed(WalletChangeSeedArgs),
     Delegators(DelegatorsArgs),
     DelegatorsCount(AccountRpcMessage),
@@ -111,6 +117,27 @@ pub enum RpcCommand {
     WorkGenerate(WorkGenerateArgs),
     Republish(RepublishArgs),
     BlockCreate(BlockCreateArgs),
+    BlockCreateUnsigned(BlockCreateUnsignedArgs),
+    BlockAssemble(BlockAssembleArgs),
+    LedgerBackup(LedgerBackupArgs),
+    PrunedExists(HashRpcMessage),
+    PruningStatus,
+    Stats(StatsArgs),
+    Forks(ForksArgs),
+    OnlineWeight,
+    PeerExclusions,
+    PeerBan(PeerBanArgs),
+    PeerUnban(PeerUnbanArgs),
+    PeerDrop(PeerDropArgs),
+    BandwidthLimits,
+    BandwidthLimitSet(BandwidthLimitSetArgs),
+    TelemetryHistory(AddressWithPortArgs),
+    TelemetrySummary(TelemetrySummaryArgs),
+    ThresholdSignStart(ThresholdSignStartArgs),
+    ThresholdSignCommit(ThresholdSignCommitArgs),
+    ThresholdSignSubmit(ThresholdSignSubmitArgs),
+    ThresholdSignStatus(HashRpcMessage),
+    ThresholdSignAbort(HashRpcMessage),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -154,6 +181,10 @@ pub enum RpcDto {
     Frontiers(FrontiersDto),
     WalletInfo(WalletInfoDto),
     WalletExport(JsonDto),
+    WalletImport(WalletRpcMessage),
+    WalletMnemonicCreate(WalletMnemonicCreateDto),
+    WalletMnemonicRestore(WalletChangeSeedDto),
+    WalletMnemonicExport(MnemonicDto),
     PasswordChange(SuccessDto),
     PasswordEnter(ValidDto),
     PasswordValid(ValidDto),
@@ -170,6 +201,8 @@ pub enum RpcDto {
     Send(BlockDto),
     SearchReceivableAll(SuccessDto),
     ReceiveMinimum(AmountRpcMessage),
+    ReceivePolicy(ReceivePolicyDto),
+    ReceivePolicySet(SuccessDto),
     WalletChangeSeed(WalletChangeSeedDto),
     Delegators(DelegatorsDto),
     DelegatorsCount(CountRpcMessage),
@@ -209,4 +242,23 @@ pub enum RpcDto {
     WorkGenerate(WorkGenerateDto),
     Republish(BlockHashesDto),
     BlockCreate(BlockCreateDto),
+    BlockCreateUnsigned(BlockCreateUnsignedDto),
+    BlockAssemble(BlockAssembleDto),
+    LedgerBackup(LedgerBackupDto),
+    PrunedExists(ExistsDto),
+    PruningStatus(PruningStatusDto),
+    Forks(ForksDto),
+    OnlineWeight(OnlineWeightDto),
+    PeerExclusions(PeerExclusionsDto),
+    PeerBan(PeerBanDto),
+    PeerDrop(PeerDropDto),
+    BandwidthLimits(BandwidthLimitsDto),
+    TelemetryHistory(TelemetryHistoryDto),
+    TelemetrySummary(TelemetrySummaryDto),
+    ThresholdSignStart(HashRpcMessage),
+    ThresholdSignCommit(ThresholdSignStatusDto),
+    ThresholdSignSubmit(ThresholdSignStatusDto),
+    ThresholdSignStatus(ThresholdSignStatusDto),
+    ThresholdSignAbort(SuccessDto),
+    Stats(serde_json::Value),
 }
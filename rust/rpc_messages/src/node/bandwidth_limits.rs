@@ -0,0 +1,97 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn bandwidth_limits() -> Self {
+        Self::BandwidthLimits
+    }
+
+    pub fn bandwidth_limit_set(args: BandwidthLimitSetArgs) -> Self {
+        Self::BandwidthLimitSet(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BandwidthLimitSetArgs {
+    /// One of "standard", "bootstrap", "vote", "block", "telemetry" or "peer"
+    #[serde(rename = "type")]
+    pub limit_type: String,
+    /// Bytes per second. 0 means unlimited
+    pub limit: u64,
+    /// Decimal number, e.g. "2.5". The current burst ratio is kept if missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burst_ratio: Option<String>,
+}
+
+impl BandwidthLimitSetArgs {
+    pub fn new(limit_type: impl Into<String>, limit: u64) -> Self {
+        Self {
+            limit_type: limit_type.into(),
+            limit,
+            burst_ratio: None,
+        }
+    }
+
+    pub fn with_burst_ratio(limit_type: impl Into<String>, limit: u64, burst_ratio: f64) -> Self {
+        Self {
+            limit_type: limit_type.into(),
+            limit,
+            burst_ratio: Some(burst_ratio.to_string()),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct BandwidthLimitsDto {
+    pub limits: Vec<BandwidthLimitDto>,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct BandwidthLimitDto {
+    #[serde(rename = "type")]
+    pub limit_type: String,
+    pub limit: u64,
+    pub burst_ratio: f64,
+    /// Outbound messages dropped because this budget was exhausted
+    pub dropped: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_bandwidth_limits_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::bandwidth_limits()).unwrap(),
+            r#"{
+  "action": "bandwidth_limits"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_bandwidth_limit_set_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::bandwidth_limit_set(
+                BandwidthLimitSetArgs::with_burst_ratio("vote", 1024, 2.5)
+            ))
+            .unwrap(),
+            r#"{
+  "action": "bandwidth_limit_set",
+  "type": "vote",
+  "limit": 1024,
+  "burst_ratio": "2.5"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_bandwidth_limit_set_command() {
+        let cmd = RpcCommand::bandwidth_limit_set(BandwidthLimitSetArgs::new("peer", 4096));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
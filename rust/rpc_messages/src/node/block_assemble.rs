@@ -0,0 +1,81 @@
+use crate::RpcCommand;
+use rsnano_core::{BlockHash, JsonBlock, Signature};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn block_assemble(args: BlockAssembleArgs) -> Self {
+        Self::BlockAssemble(args)
+    }
+}
+
+/// Combines a signing payload of `block_create_unsigned` with the detached
+/// signature of the offline signer
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockAssembleArgs {
+    pub payload: String,
+    pub signature: Signature,
+    /// Processes the block after the signature was verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process: Option<bool>,
+}
+
+impl BlockAssembleArgs {
+    pub fn new(payload: String, signature: Signature) -> Self {
+        Self {
+            payload,
+            signature,
+            process: None,
+        }
+    }
+
+    pub fn with_process(payload: String, signature: Signature) -> Self {
+        Self {
+            payload,
+            signature,
+            process: Some(true),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockAssembleDto {
+    pub hash: BlockHash,
+    pub block: JsonBlock,
+}
+
+impl BlockAssembleDto {
+    pub fn new(hash: BlockHash, block: JsonBlock) -> Self {
+        Self { hash, block }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_block_assemble_command() {
+        let args = BlockAssembleArgs::with_process("01AB".to_owned(), Signature::new());
+
+        assert_eq!(
+            serde_json::to_value(RpcCommand::block_assemble(args)).unwrap(),
+            json!({
+                "action": "block_assemble",
+                "payload": "01AB",
+                "signature": Signature::new().encode_hex(),
+                "process": true
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_block_assemble_command() {
+        let command =
+            RpcCommand::block_assemble(BlockAssembleArgs::new("01AB".to_owned(), Signature::new()));
+
+        let json = serde_json::to_string(&command).unwrap();
+
+        assert_eq!(serde_json::from_str::<RpcCommand>(&json).unwrap(), command);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, Amount, BlockHash, JsonBlock, Link, WalletId, WorkNonce};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn block_create_unsigned(args: BlockCreateUnsignedArgs) -> Self {
+        Self::BlockCreateUnsigned(args)
+    }
+}
+
+/// Creates an unsigned state block for a watch-only account of a wallet.
+/// Previous is taken from the ledger and work is generated unless it is supplied
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockCreateUnsignedArgs {
+    pub wallet: WalletId,
+    pub account: Account,
+    /// The new balance of the account
+    pub balance: Amount,
+    pub link: Link,
+    /// Defaults to the current representative of the account, or to the
+    /// wallet representative if the account is not opened yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub representative: Option<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work: Option<WorkNonce>,
+}
+
+impl BlockCreateUnsignedArgs {
+    pub fn builder(
+        wallet: WalletId,
+        account: Account,
+        balance: Amount,
+        link: Link,
+    ) -> BlockCreateUnsignedArgsBuilder {
+        BlockCreateUnsignedArgsBuilder {
+            args: BlockCreateUnsignedArgs {
+                wallet,
+                account,
+                balance,
+                link,
+                representative: None,
+                work: None,
+            },
+        }
+    }
+}
+
+pub struct BlockCreateUnsignedArgsBuilder {
+    args: BlockCreateUnsignedArgs,
+}
+
+impl BlockCreateUnsignedArgsBuilder {
+    pub fn representative(mut self, representative: Account) -> Self {
+        self.args.representative = Some(representative);
+        self
+    }
+
+    pub fn work(mut self, work: WorkNonce) -> Self {
+        self.args.work = Some(work);
+        self
+    }
+
+    pub fn build(self) -> BlockCreateUnsignedArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BlockCreateUnsignedDto {
+    pub hash: BlockHash,
+    pub difficulty: WorkNonce,
+    /// The block with a zero signature, for display purposes
+    pub block: JsonBlock,
+    /// Hex encoded canonical signing payload that gets passed to the offline signer
+    pub payload: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_block_create_unsigned_command() {
+        let args = BlockCreateUnsignedArgs::builder(
+            WalletId::from(1),
+            Account::from(2),
+            Amount::raw(3),
+            Link::from(4),
+        )
+        .work(5.into())
+        .build();
+
+        assert_eq!(
+            serde_json::to_value(RpcCommand::block_create_unsigned(args)).unwrap(),
+            json!({
+                "action": "block_create_unsigned",
+                "wallet": "0000000000000000000000000000000000000000000000000000000000000001",
+                "account": Account::from(2).encode_account(),
+                "balance": "3",
+                "link": "0000000000000000000000000000000000000000000000000000000000000004",
+                "work": "0000000000000005"
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_block_create_unsigned_command() {
+        let args = BlockCreateUnsignedArgs::builder(
+            WalletId::from(1),
+            Account::from(2),
+            Amount::raw(3),
+            Link::from(4),
+        )
+        .representative(Account::from(6))
+        .build();
+        let command = RpcCommand::block_create_unsigned(args);
+
+        let json = serde_json::to_string(&command).unwrap();
+
+        assert_eq!(serde_json::from_str::<RpcCommand>(&json).unwrap(), command);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, Amount, BlockHash, Root};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddrV6;
+
+impl RpcCommand {
+    pub fn forks(args: ForksArgs) -> Self {
+        Self::Forks(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub struct ForksArgs {
+    /// Only return forks of this account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+    /// Only return forks that were resolved at or after this unix timestamp (seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<u64>,
+    /// Only return forks that were resolved at or before this unix timestamp (seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ForksDto {
+    pub forks: Vec<ForkDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ForkDto {
+    pub root: Root,
+    pub previous: BlockHash,
+    pub account: Account,
+    pub winner: BlockHash,
+    pub confirmed: bool,
+    pub detected: u64,
+    pub resolved: u64,
+    pub blocks: Vec<ForkBlockDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ForkBlockDto {
+    pub hash: BlockHash,
+    pub tally: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<SocketAddrV6>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_forks_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::forks(ForksArgs::default())).unwrap(),
+            r#"{
+  "action": "forks"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_forks_command_with_filters() {
+        let cmd = RpcCommand::forks(ForksArgs {
+            account: Some(Account::from(1)),
+            from: Some(100),
+            to: Some(200),
+            count: Some(3),
+        });
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_forks_dto() {
+        let dto = ForksDto {
+            forks: vec![ForkDto {
+                root: Root::from(1),
+                previous: BlockHash::from(2),
+                account: Account::from(3),
+                winner: BlockHash::from(4),
+                confirmed: true,
+                detected: 5,
+                resolved: 6,
+                blocks: vec![
+                    ForkBlockDto {
+                        hash: BlockHash::from(4),
+                        tally: Amount::raw(7),
+                        channel_id: Some(8),
+                        address: Some("[::1]:7075".parse().unwrap()),
+                    },
+                    ForkBlockDto {
+                        hash: BlockHash::from(9),
+                        tally: Amount::zero(),
+                        channel_id: None,
+                        address: None,
+                    },
+                ],
+            }],
+        };
+        let serialized = to_string_pretty(&dto).unwrap();
+        let deserialized: ForksDto = from_str(&serialized).unwrap();
+        assert_eq!(dto, deserialized)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+impl RpcCommand {
+    pub fn ledger_backup(args: LedgerBackupArgs) -> Self {
+        Self::LedgerBackup(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub struct LedgerBackupArgs {
+    /// Directory for the backup. Defaults to a new directory in the node's backup folder
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+impl LedgerBackupArgs {
+    pub fn new() -> Self {
+        Self { path: None }
+    }
+
+    pub fn with_path(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct LedgerBackupDto {
+    pub path: PathBuf,
+    pub created: u64,
+    pub block_count: u64,
+    pub cemented_count: u64,
+    pub account_count: u64,
+    pub store_version: i32,
+    pub sha256: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_ledger_backup_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::ledger_backup(LedgerBackupArgs::new())).unwrap(),
+            r#"{
+  "action": "ledger_backup"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_ledger_backup_command_with_path() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::ledger_backup(LedgerBackupArgs::with_path(
+                "/tmp/backup"
+            )))
+            .unwrap(),
+            r#"{
+  "action": "ledger_backup",
+  "path": "/tmp/backup"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_ledger_backup_command() {
+        let cmd = RpcCommand::ledger_backup(LedgerBackupArgs::with_path("/tmp/backup"));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_ledger_backup_dto() {
+        let dto = LedgerBackupDto {
+            path: "/tmp/backup".into(),
+            created: 1,
+            block_count: 2,
+            cemented_count: 3,
+            account_count: 4,
+            store_version: 24,
+            sha256: "ABCD".to_string(),
+        };
+        let serialized = to_string_pretty(&dto).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{
+  "path": "/tmp/backup",
+  "created": 1,
+  "block_count": 2,
+  "cemented_count": 3,
+  "account_count": 4,
+  "store_version": 24,
+  "sha256": "ABCD"
+}"#
+        );
+        let deserialized: LedgerBackupDto = from_str(&serialized).unwrap();
+        assert_eq!(dto, deserialized);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::RpcCommand;
+use rsnano_core::Amount;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn online_weight() -> Self {
+        Self::OnlineWeight
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct OnlineWeightDto {
+    pub online_stake_total: Amount,
+    pub trended_stake_total: Amount,
+    pub online_weight_minimum: Amount,
+    pub quorum_delta: Amount,
+    /// Stored online weight samples, oldest first
+    pub samples: Vec<OnlineWeightSampleDto>,
+    pub tiers: RepTiersDto,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct OnlineWeightSampleDto {
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    pub weight: Amount,
+}
+
+/// Representatives grouped by their share of the trended online weight
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RepTiersDto {
+    /// Below 0.1%
+    pub none: RepTierDto,
+    /// 0.1% - 1%
+    pub tier_1: RepTierDto,
+    /// 1% - 5%
+    pub tier_2: RepTierDto,
+    /// Above 5%
+    pub tier_3: RepTierDto,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub struct RepTierDto {
+    pub representatives: u64,
+    pub weight: Amount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_online_weight_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::online_weight()).unwrap(),
+            r#"{
+  "action": "online_weight"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_online_weight_command() {
+        let cmd = RpcCommand::online_weight();
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_online_weight_dto() {
+        let dto = OnlineWeightDto {
+            online_stake_total: Amount::raw(1),
+            trended_stake_total: Amount::raw(2),
+            online_weight_minimum: Amount::raw(3),
+            quorum_delta: Amount::raw(4),
+            samples: vec![OnlineWeightSampleDto {
+                timestamp: 5,
+                weight: Amount::raw(6),
+            }],
+            tiers: RepTiersDto {
+                none: RepTierDto::default(),
+                tier_1: RepTierDto {
+                    representatives: 7,
+                    weight: Amount::raw(8),
+                },
+                tier_2: RepTierDto::default(),
+                tier_3: RepTierDto::default(),
+            },
+        };
+        let serialized = to_string_pretty(&dto).unwrap();
+        let deserialized: OnlineWeightDto = from_str(&serialized).unwrap();
+        assert_eq!(dto, deserialized)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn peer_ban(args: PeerBanArgs) -> Self {
+        Self::PeerBan(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerBanArgs {
+    /// IP address or subnet in CIDR notation, e.g. "10.0.0.0/24"
+    pub subnet: String,
+    /// Ban duration in seconds. The ban never expires if missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+}
+
+impl PeerBanArgs {
+    pub fn new(subnet: impl Into<String>) -> Self {
+        Self {
+            subnet: subnet.into(),
+            duration: None,
+        }
+    }
+
+    pub fn with_duration(subnet: impl Into<String>, duration: u64) -> Self {
+        Self {
+            subnet: subnet.into(),
+            duration: Some(duration),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerBanDto {
+    pub subnet: String,
+    /// Unix timestamp in seconds. Missing if the ban never expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<u64>,
+    /// Number of channels that were closed because of the ban
+    pub dropped: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_ban_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_ban(PeerBanArgs::with_duration(
+                "10.0.0.0/24",
+                3600
+            )))
+            .unwrap(),
+            r#"{
+  "action": "peer_ban",
+  "subnet": "10.0.0.0/24",
+  "duration": 3600
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_peer_ban_command() {
+        let cmd = RpcCommand::peer_ban(PeerBanArgs::new("10.0.0.1"));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddrV6;
+
+impl RpcCommand {
+    pub fn peer_drop(args: PeerDropArgs) -> Self {
+        Self::PeerDrop(args)
+    }
+}
+
+/// Selects the channels to drop, either by channel id or by the remote address
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub struct PeerDropArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<SocketAddrV6>,
+}
+
+impl PeerDropArgs {
+    pub fn channel(channel_id: u64) -> Self {
+        Self {
+            channel_id: Some(channel_id),
+            address: None,
+        }
+    }
+
+    pub fn address(address: SocketAddrV6) -> Self {
+        Self {
+            channel_id: None,
+            address: Some(address),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerDropDto {
+    pub dropped: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_drop_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_drop(PeerDropArgs::channel(42))).unwrap(),
+            r#"{
+  "action": "peer_drop",
+  "channel_id": 42
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_peer_drop_command() {
+        let cmd = RpcCommand::peer_drop(PeerDropArgs::address("[::1]:7075".parse().unwrap()));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
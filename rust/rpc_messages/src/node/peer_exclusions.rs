@@ -0,0 +1,73 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddrV6;
+
+impl RpcCommand {
+    pub fn peer_exclusions() -> Self {
+        Self::PeerExclusions
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerExclusionsDto {
+    /// Peers that are excluded because they misbehaved
+    pub excluded: Vec<ExcludedPeerDto>,
+    /// Subnets that were banned by the node operator
+    pub banned: Vec<BannedSubnetDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ExcludedPeerDto {
+    pub address: SocketAddrV6,
+    pub score: u64,
+    /// Unix timestamp in seconds
+    pub excluded_until: u64,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BannedSubnetDto {
+    pub subnet: String,
+    /// Unix timestamp in seconds. Missing if the ban never expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_exclusions_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_exclusions()).unwrap(),
+            r#"{
+  "action": "peer_exclusions"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_peer_exclusions_dto() {
+        let dto = PeerExclusionsDto {
+            excluded: vec![ExcludedPeerDto {
+                address: "[::ffff:10.0.0.1]:7075".parse().unwrap(),
+                score: 2,
+                excluded_until: 3,
+            }],
+            banned: vec![
+                BannedSubnetDto {
+                    subnet: "10.0.0.0/24".to_owned(),
+                    until: Some(4),
+                },
+                BannedSubnetDto {
+                    subnet: "2001:db8::/32".to_owned(),
+                    until: None,
+                },
+            ],
+        };
+        let serialized = to_string_pretty(&dto).unwrap();
+        let deserialized: PeerExclusionsDto = from_str(&serialized).unwrap();
+        assert_eq!(dto, deserialized)
+    }
+}
@@ -0,0 +1,41 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn peer_unban(subnet: impl Into<String>) -> Self {
+        Self::PeerUnban(PeerUnbanArgs {
+            subnet: subnet.into(),
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PeerUnbanArgs {
+    /// IP address or subnet in CIDR notation, e.g. "10.0.0.0/24"
+    pub subnet: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_peer_unban_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::peer_unban("10.0.0.0/24")).unwrap(),
+            r#"{
+  "action": "peer_unban",
+  "subnet": "10.0.0.0/24"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_peer_unban_command() {
+        let cmd = RpcCommand::peer_unban("2001:db8::/32");
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::RpcCommand;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn stats(stats_type: StatsType) -> Self {
+        Self::Stats(StatsArgs { stats_type })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsType {
+    Counters,
+    Samples,
+    /// Sizes of the node's internal containers and queues
+    Objects,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct StatsArgs {
+    #[serde(rename = "type")]
+    pub stats_type: StatsType,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_stats() {
+        let command = RpcCommand::stats(StatsType::Counters);
+        let serialized = serde_json::to_value(command).unwrap();
+        assert_eq!(serialized, json!({"action": "stats", "type": "counters"}));
+    }
+
+    #[test]
+    fn deserialize_stats() {
+        let json = json!({"action": "stats", "type": "objects"});
+        let deserialized: RpcCommand = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, RpcCommand::stats(StatsType::Objects));
+    }
+}
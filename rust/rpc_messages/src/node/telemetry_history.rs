@@ -0,0 +1,53 @@
+use crate::{AddressWithPortArgs, RpcCommand};
+use serde::{Deserialize, Serialize};
+use std::net::Ipv6Addr;
+
+impl RpcCommand {
+    pub fn telemetry_history(address: Ipv6Addr, port: u16) -> Self {
+        Self::TelemetryHistory(AddressWithPortArgs::new(address, port))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TelemetryHistoryDto {
+    /// Oldest sample first
+    pub samples: Vec<TelemetrySampleDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TelemetrySampleDto {
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    pub block_count: u64,
+    pub cemented_count: u64,
+    pub bandwidth_cap: u64,
+    pub active_difficulty: u64,
+    pub version: String,
+    pub maker: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_telemetry_history_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::telemetry_history(Ipv6Addr::LOCALHOST, 7075)).unwrap(),
+            r#"{
+  "action": "telemetry_history",
+  "address": "::1",
+  "port": 7075
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_telemetry_history_command() {
+        let cmd = RpcCommand::telemetry_history(Ipv6Addr::LOCALHOST, 7075);
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
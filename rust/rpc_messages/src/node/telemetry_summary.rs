@@ -0,0 +1,83 @@
+use crate::RpcCommand;
+use rsnano_core::Amount;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddrV6;
+
+impl RpcCommand {
+    pub fn telemetry_summary(args: TelemetrySummaryArgs) -> Self {
+        Self::TelemetrySummary(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub struct TelemetrySummaryArgs {
+    /// Peers whose cemented count is more than this many blocks behind the
+    /// median are reported as lagging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lag_threshold: Option<u64>,
+}
+
+impl TelemetrySummaryArgs {
+    pub fn new(lag_threshold: u64) -> Self {
+        Self {
+            lag_threshold: Some(lag_threshold),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TelemetrySummaryDto {
+    pub peers: u64,
+    /// Heaviest first
+    pub versions: Vec<CensusBucketDto>,
+    /// Heaviest first
+    pub makers: Vec<CensusBucketDto>,
+    pub median_cemented_count: u64,
+    pub lagging_peers: Vec<LaggingPeerDto>,
+    /// Where peers on the internet can reach this node, if known
+    pub external_address: Option<SocketAddrV6>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct CensusBucketDto {
+    pub name: String,
+    pub peers: u64,
+    /// Weight of the representatives reachable through these peers
+    pub weight: Amount,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct LaggingPeerDto {
+    pub endpoint: SocketAddrV6,
+    pub cemented_count: u64,
+    pub behind: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_telemetry_summary_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::telemetry_summary(TelemetrySummaryArgs::new(
+                1000
+            )))
+            .unwrap(),
+            r#"{
+  "action": "telemetry_summary",
+  "lag_threshold": 1000
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_telemetry_summary_command_without_threshold() {
+        let deserialized: RpcCommand = from_str(r#"{"action": "telemetry_summary"}"#).unwrap();
+        assert_eq!(
+            deserialized,
+            RpcCommand::telemetry_summary(TelemetrySummaryArgs::default())
+        )
+    }
+}
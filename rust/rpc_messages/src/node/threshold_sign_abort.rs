@@ -0,0 +1,25 @@
+use crate::{common::HashRpcMessage, RpcCommand};
+use rsnano_core::BlockHash;
+
+impl RpcCommand {
+    pub fn threshold_sign_abort(hash: BlockHash) -> Self {
+        Self::ThresholdSignAbort(HashRpcMessage::new(hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_threshold_sign_abort_command() {
+        assert_eq!(
+            serde_json::to_value(RpcCommand::threshold_sign_abort(BlockHash::from(1))).unwrap(),
+            json!({
+                "action": "threshold_sign_abort",
+                "hash": BlockHash::from(1)
+            })
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::RpcCommand;
+use rsnano_core::BlockHash;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn threshold_sign_commit(hash: BlockHash, commitment: String) -> Self {
+        Self::ThresholdSignCommit(ThresholdSignCommitArgs::new(hash, commitment))
+    }
+}
+
+/// First signing round: a participant publishes its nonce commitment
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ThresholdSignCommitArgs {
+    pub hash: BlockHash,
+    /// Hex encoded `SigningCommitment`
+    pub commitment: String,
+}
+
+impl ThresholdSignCommitArgs {
+    pub fn new(hash: BlockHash, commitment: String) -> Self {
+        Self { hash, commitment }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_threshold_sign_commit_command() {
+        let command = RpcCommand::threshold_sign_commit(BlockHash::from(1), "01AB".to_owned());
+
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            json!({
+                "action": "threshold_sign_commit",
+                "hash": BlockHash::from(1),
+                "commitment": "01AB"
+            })
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use crate::RpcCommand;
+use rsnano_core::JsonBlock;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn threshold_sign_start(block: JsonBlock, group: String) -> Self {
+        Self::ThresholdSignStart(ThresholdSignStartArgs::new(block, group))
+    }
+}
+
+/// Starts collecting the signatures of a threshold group for an unsigned block
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ThresholdSignStartArgs {
+    pub block: JsonBlock,
+    /// Hex encoded `ThresholdGroup`
+    pub group: String,
+}
+
+impl ThresholdSignStartArgs {
+    pub fn new(block: JsonBlock, group: String) -> Self {
+        Self { block, group }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::BlockEnum;
+
+    #[test]
+    fn serialize_threshold_sign_start_command() {
+        let block = BlockEnum::new_test_instance().json_representation();
+        let command = RpcCommand::threshold_sign_start(block, "01AB".to_owned());
+
+        let json = serde_json::to_value(&command).unwrap();
+
+        assert_eq!(json["action"], "threshold_sign_start");
+        assert_eq!(json["group"], "01AB");
+        assert_eq!(json["block"]["type"], "state");
+    }
+
+    #[test]
+    fn deserialize_threshold_sign_start_command() {
+        let block = BlockEnum::new_test_instance().json_representation();
+        let command = RpcCommand::threshold_sign_start(block, "01AB".to_owned());
+
+        let json = serde_json::to_string(&command).unwrap();
+
+        assert_eq!(serde_json::from_str::<RpcCommand>(&json).unwrap(), command);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{common::HashRpcMessage, RpcCommand};
+use rsnano_core::{BlockHash, JsonBlock, Signature};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn threshold_sign_status(hash: BlockHash) -> Self {
+        Self::ThresholdSignStatus(HashRpcMessage::new(hash))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ThresholdSignStatusDto {
+    pub hash: BlockHash,
+    pub threshold: u16,
+    /// Hex encoded commitments of the signers, which every signer needs for its partial signature
+    pub commitments: Vec<String>,
+    pub partial_signatures: Vec<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+    /// The signed block, once all signers submitted their partial signature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<JsonBlock>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_threshold_sign_status_command() {
+        assert_eq!(
+            serde_json::to_value(RpcCommand::threshold_sign_status(BlockHash::from(1))).unwrap(),
+            json!({
+                "action": "threshold_sign_status",
+                "hash": BlockHash::from(1)
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_threshold_sign_status_dto() {
+        let dto = ThresholdSignStatusDto {
+            hash: BlockHash::from(1),
+            threshold: 2,
+            commitments: vec!["01AB".to_owned()],
+            partial_signatures: vec![1],
+            signature: None,
+            block: None,
+        };
+
+        let json = serde_json::to_string(&dto).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<ThresholdSignStatusDto>(&json).unwrap(),
+            dto
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::RpcCommand;
+use rsnano_core::BlockHash;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn threshold_sign_submit(
+        hash: BlockHash,
+        participant: u16,
+        partial_signature: String,
+    ) -> Self {
+        Self::ThresholdSignSubmit(ThresholdSignSubmitArgs::new(
+            hash,
+            participant,
+            partial_signature,
+        ))
+    }
+}
+
+/// Second signing round: a signer publishes its partial signature
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ThresholdSignSubmitArgs {
+    pub hash: BlockHash,
+    pub participant: u16,
+    /// Hex encoded `PartialSignature`
+    pub partial_signature: String,
+}
+
+impl ThresholdSignSubmitArgs {
+    pub fn new(hash: BlockHash, participant: u16, partial_signature: String) -> Self {
+        Self {
+            hash,
+            participant,
+            partial_signature,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_threshold_sign_submit_command() {
+        let command = RpcCommand::threshold_sign_submit(BlockHash::from(1), 2, "01AB".to_owned());
+
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            json!({
+                "action": "threshold_sign_submit",
+                "hash": BlockHash::from(1),
+                "participant": 2,
+                "partial_signature": "01AB"
+            })
+        );
+    }
+}
@@ -8,6 +8,8 @@ mod password_enter;
 mod password_valid;
 mod receive;
 mod receive_minimum;
+mod receive_policy;
+mod receive_policy_set;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
@@ -21,10 +23,14 @@ mod wallet_destroy;
 mod wallet_export;
 mod wallet_frontiers;
 mod wallet_history;
+mod wallet_import;
 mod wallet_info;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_mnemonic_create;
+mod wallet_mnemonic_export;
+mod wallet_mnemonic_restore;
 mod wallet_receivable;
 mod wallet_representative;
 mod wallet_representative_set;
@@ -37,6 +43,8 @@ pub use account_create::*;
 pub use account_move::*;
 pub use accounts_create::*;
 pub use receive::*;
+pub use receive_policy::*;
+pub use receive_policy_set::*;
 pub use send::*;
 pub use wallet_add::*;
 pub use wallet_add_watch::*;
@@ -45,8 +53,12 @@ pub use wallet_change_seed::*;
 pub use wallet_create::*;
 pub use wallet_export::*;
 pub use wallet_history::*;
+pub use wallet_import::*;
 pub use wallet_info::*;
 pub use wallet_ledger::*;
+pub use wallet_mnemonic_create::*;
+pub use wallet_mnemonic_export::*;
+pub use wallet_mnemonic_restore::*;
 pub use wallet_receivable::*;
 pub use wallet_representative::*;
 pub use wallet_representative_set::*;
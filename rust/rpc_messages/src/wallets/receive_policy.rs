@@ -0,0 +1,99 @@
+use crate::{common::WalletWithAccountArgs, RpcCommand};
+use rsnano_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn receive_policy(wallet: WalletId, account: Account) -> Self {
+        Self::ReceivePolicy(WalletWithAccountArgs::new(wallet, account))
+    }
+}
+
+/// Auto receive policy of a wallet account
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct ReceivePolicyDto {
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// Overrides the node's receive_minimum for this account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Amount>,
+    /// If not empty, only sends from these accounts are received automatically
+    #[serde(default)]
+    pub allow: Vec<Account>,
+    /// Sends from these accounts are never received automatically
+    #[serde(default)]
+    pub deny: Vec<Account>,
+    /// Milliseconds to collect receivable blocks before they are received in one batch.
+    /// This is a fixed delay that doesn't depend on the network load
+    #[serde(default)]
+    pub batch_delay: u64,
+    /// Receives a delayed batch early once it holds this many blocks. 0 means no limit
+    #[serde(default)]
+    pub batch_size: u32,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+impl Default for ReceivePolicyDto {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            minimum: None,
+            allow: Vec::new(),
+            deny: Vec::new(),
+            batch_delay: 0,
+            batch_size: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_receive_policy_command() {
+        assert_eq!(
+            serde_json::to_value(RpcCommand::receive_policy(
+                WalletId::zero(),
+                Account::zero()
+            ))
+            .unwrap(),
+            json!({
+                "action": "receive_policy",
+                "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+                "account": "nano_1111111111111111111111111111111111111111111111111111hifc8npp"
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_receive_policy_dto_with_defaults() {
+        let dto: ReceivePolicyDto = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(dto, ReceivePolicyDto::default());
+    }
+
+    #[test]
+    fn serialize_receive_policy_dto() {
+        let dto = ReceivePolicyDto {
+            enabled: false,
+            minimum: Some(Amount::raw(5)),
+            deny: vec![Account::zero()],
+            batch_delay: 1000,
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(dto).unwrap(),
+            json!({
+                "enabled": false,
+                "minimum": "5",
+                "allow": [],
+                "deny": ["nano_1111111111111111111111111111111111111111111111111111hifc8npp"],
+                "batch_delay": 1000,
+                "batch_size": 0
+            })
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{ReceivePolicyDto, RpcCommand};
+use rsnano_core::{Account, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn receive_policy_set(args: ReceivePolicySetArgs) -> Self {
+        Self::ReceivePolicySet(args)
+    }
+}
+
+/// Replaces the auto receive policy of a wallet account. Omitted fields get their default value
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ReceivePolicySetArgs {
+    pub wallet: WalletId,
+    pub account: Account,
+    #[serde(flatten)]
+    pub policy: ReceivePolicyDto,
+}
+
+impl ReceivePolicySetArgs {
+    pub fn new(wallet: WalletId, account: Account, policy: ReceivePolicyDto) -> Self {
+        Self {
+            wallet,
+            account,
+            policy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_receive_policy_set_command() {
+        let command: RpcCommand = serde_json::from_value(json!({
+            "action": "receive_policy_set",
+            "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+            "account": "nano_1111111111111111111111111111111111111111111111111111hifc8npp",
+            "enabled": false
+        }))
+        .unwrap();
+
+        assert_eq!(
+            command,
+            RpcCommand::receive_policy_set(ReceivePolicySetArgs::new(
+                WalletId::zero(),
+                Account::zero(),
+                ReceivePolicyDto {
+                    enabled: false,
+                    ..Default::default()
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn serialize_receive_policy_set_command() {
+        let command = RpcCommand::receive_policy_set(ReceivePolicySetArgs::new(
+            WalletId::zero(),
+            Account::zero(),
+            ReceivePolicyDto {
+                batch_delay: 500,
+                batch_size: 10,
+                ..Default::default()
+            },
+        ));
+
+        let json = serde_json::to_string(&command).unwrap();
+
+        assert_eq!(serde_json::from_str::<RpcCommand>(&json).unwrap(), command);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::RpcCommand;
+use rsnano_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_import(args: WalletImportArgs) -> Self {
+        Self::WalletImport(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletImportArgs {
+    /// The keys are added to this wallet if it exists. Otherwise a new wallet
+    /// with this id is created. A random id is used if none is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<WalletId>,
+    /// The wallet as returned by `wallet_export`
+    pub json: String,
+    /// Password of the exported wallet. Only needed when adding to an existing wallet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl WalletImportArgs {
+    pub fn new(json: String) -> Self {
+        Self {
+            wallet: None,
+            json,
+            password: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_wallet_import_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_import(WalletImportArgs {
+                wallet: Some(WalletId::zero()),
+                json: "{}".to_owned(),
+                password: Some("secret".to_owned()),
+            }))
+            .unwrap(),
+            r#"{
+  "action": "wallet_import",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "json": "{}",
+  "password": "secret"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_import_command() {
+        let cmd: RpcCommand = from_str(r#"{"action": "wallet_import", "json": "{}"}"#).unwrap();
+        assert_eq!(
+            cmd,
+            RpcCommand::wallet_import(WalletImportArgs::new("{}".to_owned()))
+        )
+    }
+}
@@ -0,0 +1,70 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_mnemonic_create(args: WalletMnemonicCreateArgs) -> Self {
+        Self::WalletMnemonicCreate(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletMnemonicCreateArgs {
+    pub wallet: WalletId,
+    /// 12, 15, 18, 21 or 24. Defaults to 24
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<u32>,
+    /// Optional BIP39 passphrase. It is not stored and must be supplied again on restore
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+}
+
+impl WalletMnemonicCreateArgs {
+    pub fn new(wallet: WalletId) -> Self {
+        Self {
+            wallet,
+            words: None,
+            passphrase: None,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletMnemonicCreateDto {
+    pub mnemonic: String,
+    /// The first account `m/44'/165'/0'`
+    pub account: Account,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_wallet_mnemonic_create_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_mnemonic_create(
+                WalletMnemonicCreateArgs {
+                    wallet: WalletId::zero(),
+                    words: Some(12),
+                    passphrase: None,
+                }
+            ))
+            .unwrap(),
+            r#"{
+  "action": "wallet_mnemonic_create",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "words": 12
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_mnemonic_create_command() {
+        let cmd = RpcCommand::wallet_mnemonic_create(WalletMnemonicCreateArgs::new(1.into()));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{RpcCommand, WalletRpcMessage};
+use rsnano_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_mnemonic_export(wallet: WalletId) -> Self {
+        Self::WalletMnemonicExport(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MnemonicDto {
+    pub mnemonic: String,
+}
+
+impl MnemonicDto {
+    pub fn new(mnemonic: String) -> Self {
+        Self { mnemonic }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_wallet_mnemonic_export_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_mnemonic_export(WalletId::zero())).unwrap(),
+            r#"{
+  "action": "wallet_mnemonic_export",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_mnemonic_dto() {
+        let dto: MnemonicDto = from_str(r#"{"mnemonic": "zoo vote"}"#).unwrap();
+        assert_eq!(dto, MnemonicDto::new("zoo vote".to_owned()));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::RpcCommand;
+use rsnano_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_mnemonic_restore(args: WalletMnemonicRestoreArgs) -> Self {
+        Self::WalletMnemonicRestore(args)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WalletMnemonicRestoreArgs {
+    pub wallet: WalletId,
+    pub mnemonic: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    /// Number of accounts to restore. By default all accounts with blocks or receivables are restored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+impl WalletMnemonicRestoreArgs {
+    pub fn new(wallet: WalletId, mnemonic: impl Into<String>) -> Self {
+        Self {
+            wallet,
+            mnemonic: mnemonic.into(),
+            passphrase: None,
+            count: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn serialize_wallet_mnemonic_restore_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_mnemonic_restore(
+                WalletMnemonicRestoreArgs {
+                    wallet: WalletId::zero(),
+                    mnemonic: "abandon about".to_owned(),
+                    passphrase: Some("secret".to_owned()),
+                    count: Some(3),
+                }
+            ))
+            .unwrap(),
+            r#"{
+  "action": "wallet_mnemonic_restore",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "mnemonic": "abandon about",
+  "passphrase": "secret",
+  "count": 3
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_mnemonic_restore_command() {
+        let cmd =
+            RpcCommand::wallet_mnemonic_restore(WalletMnemonicRestoreArgs::new(1.into(), "zoo"));
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+}
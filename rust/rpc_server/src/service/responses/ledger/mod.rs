@@ -21,6 +21,8 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod pruned_exists;
+mod pruning_status;
 mod representatives;
 mod successors;
 mod unopened;
@@ -48,6 +50,8 @@ pub use delegators_count::*;
 pub use frontier_count::*;
 pub use frontiers::*;
 pub use ledger::*;
+pub use pruned_exists::*;
+pub use pruning_status::*;
 pub use representatives::*;
 pub use successors::*;
 pub use unopened::*;
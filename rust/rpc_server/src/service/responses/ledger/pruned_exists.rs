@@ -0,0 +1,12 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, ExistsDto, HashRpcMessage, RpcDto};
+use std::sync::Arc;
+
+pub async fn pruned_exists(node: Arc<Node>, args: HashRpcMessage) -> RpcDto {
+    if !node.ledger.pruning_enabled() {
+        return RpcDto::Error(ErrorDto::PruningDisabled);
+    }
+    let tx = node.ledger.read_txn();
+    let exists = node.ledger.store.pruned.exists(&tx, &args.hash);
+    RpcDto::PrunedExists(ExistsDto::new(exists))
+}
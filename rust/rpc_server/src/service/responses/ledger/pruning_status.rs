@@ -0,0 +1,19 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{PruningStatusDto, RpcDto};
+use std::sync::Arc;
+
+pub async fn pruning_status(node: Arc<Node>) -> RpcDto {
+    let status = node.pruning_status();
+    RpcDto::PruningStatus(PruningStatusDto {
+        enabled: node.ledger.pruning_enabled(),
+        pruned_count: node.ledger.pruned_count(),
+        block_count: node.ledger.block_count(),
+        runs: status.runs,
+        last_run: status.last_run,
+        last_run_pruned: status.last_run_pruned,
+        last_run_kept_accounts: status.last_run_kept_accounts,
+        keep_wallet_accounts: node.config.pruning_keep_wallet_accounts,
+        keep_representatives: node.config.pruning_keep_representatives,
+        keep_accounts: node.config.pruning_keep_accounts.clone(),
+    })
+}
@@ -0,0 +1,61 @@
+use rsnano_network::bandwidth_limiter::{BandwidthLimitType, OutboundBandwidthLimiter};
+use rsnano_node::{
+    stats::{Direction, StatType},
+    Node,
+};
+use rsnano_rpc_messages::{
+    BandwidthLimitDto, BandwidthLimitSetArgs, BandwidthLimitsDto, ErrorDto, RpcDto,
+};
+use std::sync::Arc;
+
+pub async fn bandwidth_limits(node: Arc<Node>) -> RpcDto {
+    let limiter = node.network.limiter();
+    let limits = BandwidthLimitType::ALL
+        .iter()
+        .map(|&limit_type| {
+            let (limit, burst_ratio) = limiter.limit(limit_type);
+            BandwidthLimitDto {
+                limit_type: limit_type.as_str().to_owned(),
+                limit: limit as u64,
+                burst_ratio,
+                dropped: node.stats.count(
+                    StatType::BandwidthLimiter,
+                    limit_type.into(),
+                    Direction::Out,
+                ),
+            }
+        })
+        .collect();
+
+    RpcDto::BandwidthLimits(BandwidthLimitsDto { limits })
+}
+
+pub async fn bandwidth_limit_set(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: BandwidthLimitSetArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Ok(limit_type) = args.limit_type.parse::<BandwidthLimitType>() else {
+        return RpcDto::Error(ErrorDto::InvalidBandwidthLimitType);
+    };
+
+    if args.limit > OutboundBandwidthLimiter::MAX_LIMIT as u64 {
+        return RpcDto::Error(ErrorDto::InvalidBandwidthLimit);
+    }
+
+    let limiter = node.network.limiter();
+    let burst_ratio = match args.burst_ratio {
+        Some(ratio) => match ratio.parse::<f64>() {
+            Ok(ratio) if ratio.is_finite() && ratio >= 1.0 => ratio,
+            _ => return RpcDto::Error(ErrorDto::InvalidBurstRatio),
+        },
+        None => limiter.limit(limit_type).1,
+    };
+
+    limiter.reset(args.limit as usize, burst_ratio, limit_type);
+    bandwidth_limits(node).await
+}
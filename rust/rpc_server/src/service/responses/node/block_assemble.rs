@@ -0,0 +1,26 @@
+use super::process;
+use rsnano_core::SigningPayload;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{BlockAssembleArgs, BlockAssembleDto, ErrorDto, ProcessArgs, RpcDto};
+use std::sync::Arc;
+
+pub async fn block_assemble(node: Arc<Node>, args: BlockAssembleArgs) -> RpcDto {
+    let payload = match SigningPayload::decode_hex(&args.payload) {
+        Ok(payload) => payload,
+        Err(_) => return RpcDto::Error(ErrorDto::InvalidSigningPayload),
+    };
+
+    let block = match payload.assemble(args.signature) {
+        Ok(block) => block,
+        Err(_) => return RpcDto::Error(ErrorDto::BadSignature),
+    };
+    let json_block = block.json_representation();
+
+    if args.process.unwrap_or(false) {
+        if let RpcDto::Error(e) = process(node, ProcessArgs::from(json_block.clone())).await {
+            return RpcDto::Error(e);
+        }
+    }
+
+    RpcDto::BlockAssemble(BlockAssembleDto::new(block.hash(), json_block))
+}
@@ -0,0 +1,83 @@
+use super::difficulty_ledger;
+use rsnano_core::{
+    Amount, BlockHash, PublicKey, Root, SigningPayload, StateHashables, WorkVersion,
+};
+use rsnano_node::Node;
+use rsnano_rpc_messages::{BlockCreateUnsignedArgs, BlockCreateUnsignedDto, ErrorDto, RpcDto};
+use std::sync::Arc;
+
+pub async fn block_create_unsigned(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: BlockCreateUnsignedArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    match node.wallets.is_watch_only(&args.wallet, &args.account) {
+        Ok(true) => {}
+        Ok(false) => return RpcDto::Error(ErrorDto::NotWatchOnlyAccount),
+        Err(e) => return RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+
+    let account_info = {
+        let tx = node.ledger.read_txn();
+        node.ledger.any().get_account(&tx, &args.account)
+    };
+    let (previous, current_representative, previous_balance) = match account_info {
+        Some(info) => (info.head, info.representative, info.balance),
+        None => match node.wallets.get_representative(args.wallet) {
+            Ok(representative) => (BlockHash::zero(), representative, Amount::zero()),
+            Err(e) => return RpcDto::Error(ErrorDto::WalletsError(e)),
+        },
+    };
+
+    let mut payload = SigningPayload::new(
+        StateHashables {
+            account: args.account,
+            previous,
+            representative: args
+                .representative
+                .map(PublicKey::from)
+                .unwrap_or(current_representative),
+            balance: args.balance,
+            link: args.link,
+        },
+        previous_balance,
+        0,
+    );
+
+    let root: Root = if previous.is_zero() {
+        args.account.into()
+    } else {
+        previous.into()
+    };
+
+    payload.work = match args.work {
+        Some(work) => work.into(),
+        None => {
+            let difficulty = difficulty_ledger(node.clone(), &payload.unsigned_block());
+            match node
+                .distributed_work
+                .make(root, difficulty, Some(args.account))
+                .await
+            {
+                Some(work) => work,
+                None => return RpcDto::Error(ErrorDto::InsufficientWork),
+            }
+        }
+    };
+
+    let difficulty = node
+        .network_params
+        .work
+        .difficulty(WorkVersion::Work1, &root, payload.work);
+
+    RpcDto::BlockCreateUnsigned(BlockCreateUnsignedDto {
+        hash: payload.hash(),
+        difficulty: difficulty.into(),
+        block: payload.unsigned_block().json_representation(),
+        payload: payload.encode_hex(),
+    })
+}
@@ -0,0 +1,50 @@
+use super::unix_seconds;
+use rsnano_node::{
+    consensus::{ForkQuery, ForkRecord},
+    Node,
+};
+use rsnano_rpc_messages::{ForkBlockDto, ForkDto, ForksArgs, ForksDto, RpcDto};
+use std::{
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+pub async fn forks(node: Arc<Node>, args: ForksArgs) -> RpcDto {
+    let query = ForkQuery {
+        account: args.account,
+        from: args.from.map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+        to: args.to.map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+        count: args.count.map(|c| c as usize),
+    };
+
+    let forks = node
+        .fork_journal
+        .forks(&query)
+        .into_iter()
+        .map(fork_dto)
+        .collect();
+
+    RpcDto::Forks(ForksDto { forks })
+}
+
+fn fork_dto(record: ForkRecord) -> ForkDto {
+    ForkDto {
+        root: record.root.root,
+        previous: record.root.previous,
+        account: record.account,
+        winner: record.winner,
+        confirmed: record.confirmed,
+        detected: unix_seconds(record.detected),
+        resolved: unix_seconds(record.resolved),
+        blocks: record
+            .blocks
+            .into_iter()
+            .map(|block| ForkBlockDto {
+                hash: block.hash,
+                tally: block.tally,
+                channel_id: block.channel_id.map(|id| id.as_usize() as u64),
+                address: block.peer_addr,
+            })
+            .collect(),
+    }
+}
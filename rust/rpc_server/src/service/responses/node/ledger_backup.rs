@@ -0,0 +1,41 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, LedgerBackupArgs, LedgerBackupDto, RpcDto};
+use std::sync::Arc;
+use tokio::task::spawn_blocking;
+use tracing::error;
+
+pub async fn ledger_backup(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: LedgerBackupArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let result = spawn_blocking(move || match args.path {
+        Some(path) => node
+            .ledger_backup
+            .backup(&path)
+            .map(|manifest| (path, manifest)),
+        None => node.ledger_backup.backup_with_retention(),
+    })
+    .await
+    .unwrap();
+
+    match result {
+        Ok((path, manifest)) => RpcDto::LedgerBackup(LedgerBackupDto {
+            path,
+            created: manifest.created,
+            block_count: manifest.block_count,
+            cemented_count: manifest.cemented_count,
+            account_count: manifest.account_count,
+            store_version: manifest.store_version,
+            sha256: manifest.sha256,
+        }),
+        Err(e) => {
+            error!(error = ?e, "Ledger backup failed");
+            RpcDto::Error(ErrorDto::LedgerBackupFailed)
+        }
+    }
+}
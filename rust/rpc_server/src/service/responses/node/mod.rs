@@ -1,12 +1,22 @@
+mod bandwidth_limits;
+mod block_assemble;
 mod block_create;
+mod block_create_unsigned;
 mod bootstrap;
 mod bootstrap_any;
 mod bootstrap_lazy;
 mod confirmation_active;
 mod confirmation_info;
 mod confirmation_quorum;
+mod forks;
 mod keepalive;
+mod ledger_backup;
 mod node_id;
+mod online_weight;
+mod peer_ban;
+mod peer_drop;
+mod peer_exclusions;
+mod peer_unban;
 mod peers;
 mod populate_backlog;
 mod process;
@@ -15,8 +25,16 @@ mod receivable_exists;
 mod representatives_online;
 mod republish;
 mod sign;
+mod stats;
 mod stats_clear;
 mod stop;
+mod telemetry_history;
+mod telemetry_summary;
+mod threshold_sign_abort;
+mod threshold_sign_commit;
+mod threshold_sign_start;
+mod threshold_sign_status;
+mod threshold_sign_submit;
 mod unchecked;
 mod unchecked_clear;
 mod unchecked_get;
@@ -26,15 +44,25 @@ mod work_cancel;
 mod work_generate;
 mod work_validate;
 
+pub use bandwidth_limits::*;
+pub use block_assemble::*;
 pub use block_create::*;
+pub use block_create_unsigned::*;
 pub use bootstrap::*;
 pub use bootstrap_any::*;
 pub use bootstrap_lazy::*;
 pub use confirmation_active::*;
 pub use confirmation_info::*;
 pub use confirmation_quorum::*;
+pub use forks::*;
 pub use keepalive::*;
+pub use ledger_backup::*;
 pub use node_id::*;
+pub use online_weight::*;
+pub use peer_ban::*;
+pub use peer_drop::*;
+pub use peer_exclusions::*;
+pub use peer_unban::*;
 pub use peers::*;
 pub use populate_backlog::*;
 pub use process::*;
@@ -43,8 +71,16 @@ pub use receivable_exists::*;
 pub use representatives_online::*;
 pub use republish::*;
 pub use sign::*;
+pub use stats::*;
 pub use stats_clear::*;
 pub use stop::*;
+pub use telemetry_history::*;
+pub use telemetry_summary::*;
+pub use threshold_sign_abort::*;
+pub use threshold_sign_commit::*;
+pub use threshold_sign_start::*;
+pub use threshold_sign_status::*;
+pub use threshold_sign_submit::*;
 pub use unchecked::*;
 pub use unchecked_clear::*;
 pub use unchecked_get::*;
@@ -0,0 +1,51 @@
+use rsnano_node::{
+    consensus::{RepTier, RepTierWeight},
+    Node,
+};
+use rsnano_rpc_messages::{
+    OnlineWeightDto, OnlineWeightSampleDto, RepTierDto, RepTiersDto, RpcDto,
+};
+use std::sync::Arc;
+
+pub async fn online_weight(node: Arc<Node>) -> RpcDto {
+    let samples = node
+        .online_weight_sampler
+        .samples()
+        .into_iter()
+        .map(|(timestamp, weight)| OnlineWeightSampleDto {
+            timestamp: timestamp / 1_000_000_000,
+            weight,
+        })
+        .collect();
+
+    let tier_weights = node.rep_tiers.tier_weights();
+    let tier = |tier: RepTier| {
+        tier_weights
+            .iter()
+            .find(|t| t.tier == tier)
+            .map(tier_dto)
+            .unwrap_or_default()
+    };
+
+    let online_reps = node.online_reps.lock().unwrap();
+    RpcDto::OnlineWeight(OnlineWeightDto {
+        online_stake_total: online_reps.online_weight(),
+        trended_stake_total: online_reps.trended_weight(),
+        online_weight_minimum: online_reps.online_weight_minimum(),
+        quorum_delta: online_reps.quorum_delta(),
+        samples,
+        tiers: RepTiersDto {
+            none: tier(RepTier::None),
+            tier_1: tier(RepTier::Tier1),
+            tier_2: tier(RepTier::Tier2),
+            tier_3: tier(RepTier::Tier3),
+        },
+    })
+}
+
+fn tier_dto(tier: &RepTierWeight) -> RepTierDto {
+    RepTierDto {
+        representatives: tier.representatives as u64,
+        weight: tier.weight,
+    }
+}
@@ -0,0 +1,31 @@
+use super::unix_seconds;
+use rsnano_network::peer_exclusion::IpSubnet;
+use rsnano_node::{transport::PeerBans, Node};
+use rsnano_rpc_messages::{ErrorDto, PeerBanArgs, PeerBanDto, RpcDto};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+pub async fn peer_ban(node: Arc<Node>, enable_control: bool, args: PeerBanArgs) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Ok(subnet) = args.subnet.parse::<IpSubnet>() else {
+        return RpcDto::Error(ErrorDto::InvalidSubnet);
+    };
+
+    let duration = args.duration.map(Duration::from_secs);
+    if duration.is_some_and(|d| d > PeerBans::MAX_DURATION) {
+        return RpcDto::Error(ErrorDto::InvalidBanDuration);
+    }
+    let until = duration.map(|d| unix_seconds(SystemTime::now() + d));
+    let dropped = node.peer_bans.ban(subnet, duration);
+
+    RpcDto::PeerBan(PeerBanDto {
+        subnet: subnet.to_string(),
+        until,
+        dropped: dropped as u64,
+    })
+}
@@ -0,0 +1,36 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, PeerDropArgs, PeerDropDto, RpcDto};
+use std::sync::Arc;
+
+pub async fn peer_drop(node: Arc<Node>, enable_control: bool, args: PeerDropArgs) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let network_info = node.network_info.read().unwrap();
+    let mut channel_ids = Vec::new();
+    if let Some(channel_id) = args.channel_id {
+        channel_ids.push((channel_id as usize).into());
+    }
+    if let Some(address) = args.address {
+        channel_ids.extend(
+            network_info
+                .find_channels_by_remote_addr(&address)
+                .iter()
+                .map(|c| c.channel_id()),
+        );
+    }
+
+    let dropped = channel_ids
+        .into_iter()
+        .filter(|id| network_info.close_channel(*id))
+        .count();
+
+    if dropped == 0 {
+        RpcDto::Error(ErrorDto::PeerNotFound)
+    } else {
+        RpcDto::PeerDrop(PeerDropDto {
+            dropped: dropped as u64,
+        })
+    }
+}
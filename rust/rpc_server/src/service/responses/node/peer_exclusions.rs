@@ -0,0 +1,37 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{BannedSubnetDto, ExcludedPeerDto, PeerExclusionsDto, RpcDto};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub async fn peer_exclusions(node: Arc<Node>) -> RpcDto {
+    let excluded = node
+        .peer_bans
+        .misbehaving_peers()
+        .into_iter()
+        .map(|excluded| ExcludedPeerDto {
+            address: excluded.peer.address,
+            score: excluded.peer.score,
+            excluded_until: unix_seconds(excluded.exclude_until),
+        })
+        .collect();
+
+    let banned = node
+        .peer_bans
+        .bans()
+        .into_iter()
+        .map(|ban| BannedSubnetDto {
+            subnet: ban.subnet.to_string(),
+            until: ban.until.map(unix_seconds),
+        })
+        .collect();
+
+    RpcDto::PeerExclusions(PeerExclusionsDto { excluded, banned })
+}
+
+pub(crate) fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
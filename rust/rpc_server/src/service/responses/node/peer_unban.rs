@@ -0,0 +1,16 @@
+use rsnano_network::peer_exclusion::IpSubnet;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, PeerUnbanArgs, RemovedDto, RpcDto};
+use std::sync::Arc;
+
+pub async fn peer_unban(node: Arc<Node>, enable_control: bool, args: PeerUnbanArgs) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Ok(subnet) = args.subnet.parse::<IpSubnet>() else {
+        return RpcDto::Error(ErrorDto::InvalidSubnet);
+    };
+
+    RpcDto::Removed(RemovedDto::new(node.peer_bans.unban(&subnet)))
+}
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Local};
+use rsnano_core::utils::ContainerInfoComponent;
+use rsnano_node::{stats::StatsLogSink, Node};
+use rsnano_rpc_messages::{RpcDto, StatsArgs, StatsType};
+use serde_json::{json, Map, Value};
+use std::{any::Any, sync::Arc, time::SystemTime};
+
+pub async fn stats(node: Arc<Node>, args: StatsArgs) -> RpcDto {
+    let json = match args.stats_type {
+        StatsType::Counters => {
+            let mut sink = JsonSink::default();
+            let _ = node.stats.log_counters(&mut sink);
+            sink.finish()
+        }
+        StatsType::Samples => {
+            let mut sink = JsonSink::default();
+            let _ = node.stats.log_samples(&mut sink);
+            sink.finish()
+        }
+        StatsType::Objects => {
+            let mut objects = Map::new();
+            add_container_info(&mut objects, node.collect_container_info("node"));
+            Value::Object(objects)
+        }
+    };
+    RpcDto::Stats(json)
+}
+
+/// Collects stats in the same layout as the C++ node's `stats` RPC
+#[derive(Default)]
+struct JsonSink {
+    header: Map<String, Value>,
+    entries: Vec<Value>,
+    log_entries: usize,
+}
+
+impl JsonSink {
+    fn finish(mut self) -> Value {
+        self.header
+            .insert("entries".to_owned(), Value::Array(self.entries));
+        Value::Object(self.header)
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time).format("%H:%M:%S").to_string()
+}
+
+impl StatsLogSink for JsonSink {
+    fn begin(&mut self) -> anyhow::Result<()> {
+        self.header.clear();
+        self.entries.clear();
+        Ok(())
+    }
+
+    fn finalize(&mut self) {}
+
+    fn write_header(&mut self, header: &str, walltime: SystemTime) -> anyhow::Result<()> {
+        let created = DateTime::<Local>::from(walltime).format("%Y.%m.%d %H:%M:%S");
+        self.header.insert("type".to_owned(), header.into());
+        self.header
+            .insert("created".to_owned(), created.to_string().into());
+        Ok(())
+    }
+
+    fn write_counter_entry(
+        &mut self,
+        time: SystemTime,
+        entry_type: &str,
+        detail: &str,
+        dir: &str,
+        value: u64,
+    ) -> anyhow::Result<()> {
+        self.entries.push(json!({
+            "time": format_time(time),
+            "type": entry_type,
+            "detail": detail,
+            "dir": dir,
+            "value": value.to_string(),
+        }));
+        Ok(())
+    }
+
+    fn write_sampler_entry(
+        &mut self,
+        time: SystemTime,
+        sample: &str,
+        values: Vec<i64>,
+        expected_min_max: (i64, i64),
+    ) -> anyhow::Result<()> {
+        self.entries.push(json!({
+            "time": format_time(time),
+            "sample": sample,
+            "min": expected_min_max.0.to_string(),
+            "max": expected_min_max.1.to_string(),
+            "values": values.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+        }));
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn entries(&self) -> usize {
+        self.log_entries
+    }
+
+    fn inc_entries(&mut self) {
+        self.log_entries += 1;
+    }
+
+    fn to_string(&self) -> String {
+        String::new()
+    }
+
+    fn to_object(&self) -> Option<&dyn Any> {
+        None
+    }
+}
+
+fn add_container_info(parent: &mut Map<String, Value>, info: ContainerInfoComponent) {
+    match info {
+        ContainerInfoComponent::Leaf(leaf) => {
+            parent.insert(
+                leaf.name,
+                json!({
+                    "count": leaf.count.to_string(),
+                    "size": leaf.sizeof_element.to_string()
+                }),
+            );
+        }
+        ContainerInfoComponent::Composite(name, children) => {
+            let mut child_map = Map::new();
+            for child in children {
+                add_container_info(&mut child_map, child);
+            }
+            parent.insert(name, Value::Object(child_map));
+        }
+    }
+}
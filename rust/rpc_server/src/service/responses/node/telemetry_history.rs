@@ -0,0 +1,40 @@
+use super::unix_seconds;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{
+    AddressWithPortArgs, ErrorDto, RpcDto, TelemetryHistoryDto, TelemetrySampleDto,
+};
+use std::{net::SocketAddrV6, sync::Arc};
+
+pub async fn telemetry_history(node: Arc<Node>, args: AddressWithPortArgs) -> RpcDto {
+    let endpoint = SocketAddrV6::new(args.address, args.port, 0, 0);
+
+    // Telemetry is stored by remote address. Accept the peering address too
+    let endpoint = {
+        let network = node.network_info.read().unwrap();
+        network
+            .find_realtime_channel_by_peering_addr(&endpoint)
+            .and_then(|id| network.get(id))
+            .map(|channel| channel.peer_addr())
+            .unwrap_or(endpoint)
+    };
+
+    let samples = node.telemetry.history(&endpoint);
+    if samples.is_empty() {
+        return RpcDto::Error(ErrorDto::PeerNotFound);
+    }
+
+    RpcDto::TelemetryHistory(TelemetryHistoryDto {
+        samples: samples
+            .into_iter()
+            .map(|sample| TelemetrySampleDto {
+                timestamp: unix_seconds(sample.received),
+                block_count: sample.block_count,
+                cemented_count: sample.cemented_count,
+                bandwidth_cap: sample.bandwidth_cap,
+                active_difficulty: sample.active_difficulty,
+                version: sample.version(),
+                maker: sample.maker,
+            })
+            .collect(),
+    })
+}
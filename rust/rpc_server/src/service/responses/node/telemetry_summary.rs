@@ -0,0 +1,56 @@
+use rsnano_core::Amount;
+use rsnano_node::{CensusBucket, Node, TelemetryCensus};
+use rsnano_rpc_messages::{
+    CensusBucketDto, LaggingPeerDto, RpcDto, TelemetrySummaryArgs, TelemetrySummaryDto,
+};
+use std::{collections::HashMap, sync::Arc};
+
+pub async fn telemetry_summary(node: Arc<Node>, args: TelemetrySummaryArgs) -> RpcDto {
+    let telemetries = node.telemetry.get_all_telemetries();
+
+    let mut weights: HashMap<_, Amount> = HashMap::new();
+    {
+        let peered_reps = node.online_reps.lock().unwrap().peered_reps();
+        let network = node.network_info.read().unwrap();
+        for rep in peered_reps {
+            if let Some(channel) = network.get(rep.channel_id) {
+                *weights.entry(channel.peer_addr()).or_default() +=
+                    node.ledger.weight(&rep.account);
+            }
+        }
+    }
+
+    let census = TelemetryCensus::new(
+        &telemetries,
+        &weights,
+        args.lag_threshold
+            .unwrap_or(TelemetryCensus::DEFAULT_LAG_THRESHOLD),
+    );
+
+    let external_address = node.network_info.read().unwrap().external_address();
+
+    RpcDto::TelemetrySummary(TelemetrySummaryDto {
+        peers: census.peers as u64,
+        versions: census.versions.iter().map(bucket_dto).collect(),
+        makers: census.makers.iter().map(bucket_dto).collect(),
+        median_cemented_count: census.median_cemented_count,
+        lagging_peers: census
+            .lagging_peers
+            .into_iter()
+            .map(|peer| LaggingPeerDto {
+                endpoint: peer.endpoint,
+                cemented_count: peer.cemented_count,
+                behind: peer.behind,
+            })
+            .collect(),
+        external_address,
+    })
+}
+
+fn bucket_dto(bucket: &CensusBucket) -> CensusBucketDto {
+    CensusBucketDto {
+        name: bucket.name.clone(),
+        peers: bucket.peers as u64,
+        weight: bucket.weight,
+    }
+}
@@ -0,0 +1,18 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, HashRpcMessage, RpcDto, SuccessDto};
+use std::sync::Arc;
+
+pub async fn threshold_sign_abort(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: HashRpcMessage,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    match node.threshold_signing.abort(&args.hash) {
+        Ok(()) => RpcDto::ThresholdSignAbort(SuccessDto::new()),
+        Err(e) => RpcDto::Error(ErrorDto::ThresholdSigningError(e)),
+    }
+}
@@ -0,0 +1,24 @@
+use super::threshold_session_dto;
+use rsnano_core::threshold::SigningCommitment;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, RpcDto, ThresholdSignCommitArgs};
+use std::sync::Arc;
+
+pub async fn threshold_sign_commit(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: ThresholdSignCommitArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Ok(commitment) = SigningCommitment::decode_hex(&args.commitment) else {
+        return RpcDto::Error(ErrorDto::InvalidSigningCommitment);
+    };
+
+    match node.threshold_signing.commit(&args.hash, commitment) {
+        Ok(session) => RpcDto::ThresholdSignCommit(threshold_session_dto(&session)),
+        Err(e) => RpcDto::Error(ErrorDto::ThresholdSigningError(e)),
+    }
+}
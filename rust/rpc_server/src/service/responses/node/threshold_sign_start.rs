@@ -0,0 +1,24 @@
+use rsnano_core::{threshold::ThresholdGroup, BlockEnum};
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, HashRpcMessage, RpcDto, ThresholdSignStartArgs};
+use std::sync::Arc;
+
+pub async fn threshold_sign_start(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: ThresholdSignStartArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Ok(group) = ThresholdGroup::decode_hex(&args.group) else {
+        return RpcDto::Error(ErrorDto::InvalidThresholdGroup);
+    };
+
+    let block: BlockEnum = args.block.into();
+    match node.threshold_signing.start(block, group) {
+        Ok(hash) => RpcDto::ThresholdSignStart(HashRpcMessage::new(hash)),
+        Err(e) => RpcDto::Error(ErrorDto::ThresholdSigningError(e)),
+    }
+}
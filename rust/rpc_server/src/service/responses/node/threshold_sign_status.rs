@@ -0,0 +1,30 @@
+use rsnano_node::{
+    signing::{ThresholdSigningError, ThresholdSigningSession},
+    Node,
+};
+use rsnano_rpc_messages::{ErrorDto, HashRpcMessage, RpcDto, ThresholdSignStatusDto};
+use std::sync::Arc;
+
+pub async fn threshold_sign_status(node: Arc<Node>, args: HashRpcMessage) -> RpcDto {
+    match node.threshold_signing.get(&args.hash) {
+        Some(session) => RpcDto::ThresholdSignStatus(threshold_session_dto(&session)),
+        None => RpcDto::Error(ErrorDto::ThresholdSigningError(
+            ThresholdSigningError::SessionNotFound,
+        )),
+    }
+}
+
+pub(crate) fn threshold_session_dto(session: &ThresholdSigningSession) -> ThresholdSignStatusDto {
+    ThresholdSignStatusDto {
+        hash: session.hash(),
+        threshold: session.group.threshold(),
+        commitments: session.commitments.iter().map(|c| c.encode_hex()).collect(),
+        partial_signatures: session
+            .partial_signatures
+            .iter()
+            .map(|(participant, _)| *participant)
+            .collect(),
+        signature: session.signature.clone(),
+        block: session.signed_block().map(|b| b.json_representation()),
+    }
+}
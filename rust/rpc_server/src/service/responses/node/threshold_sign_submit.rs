@@ -0,0 +1,27 @@
+use super::threshold_session_dto;
+use rsnano_core::threshold::PartialSignature;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, RpcDto, ThresholdSignSubmitArgs};
+use std::sync::Arc;
+
+pub async fn threshold_sign_submit(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: ThresholdSignSubmitArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Ok(partial) = PartialSignature::decode_hex(&args.partial_signature) else {
+        return RpcDto::Error(ErrorDto::InvalidPartialSignature);
+    };
+
+    match node
+        .threshold_signing
+        .submit(&args.hash, args.participant, partial)
+    {
+        Ok(session) => RpcDto::ThresholdSignSubmit(threshold_session_dto(&session)),
+        Err(e) => RpcDto::Error(ErrorDto::ThresholdSigningError(e)),
+    }
+}
@@ -7,6 +7,8 @@ mod password_change;
 mod password_enter;
 mod password_valid;
 mod receive_minimum;
+mod receive_policy;
+mod receive_policy_set;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
@@ -20,10 +22,14 @@ mod wallet_destroy;
 mod wallet_export;
 mod wallet_frontiers;
 mod wallet_history;
+mod wallet_import;
 mod wallet_info;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_mnemonic_create;
+mod wallet_mnemonic_export;
+mod wallet_mnemonic_restore;
 mod wallet_receivable;
 mod wallet_representative;
 mod wallet_representative_set;
@@ -41,6 +47,8 @@ pub use password_change::*;
 pub use password_enter::*;
 pub use password_valid::*;
 pub use receive_minimum::*;
+pub use receive_policy::*;
+pub use receive_policy_set::*;
 pub use search_receivable::*;
 pub use search_receivable_all::*;
 pub use send::*;
@@ -54,10 +62,14 @@ pub use wallet_destroy::*;
 pub use wallet_export::*;
 pub use wallet_frontiers::*;
 pub use wallet_history::*;
+pub use wallet_import::*;
 pub use wallet_info::*;
 pub use wallet_ledger::*;
 pub use wallet_lock::*;
 pub use wallet_locked::*;
+pub use wallet_mnemonic_create::*;
+pub use wallet_mnemonic_export::*;
+pub use wallet_mnemonic_restore::*;
 pub use wallet_receivable::*;
 pub use wallet_representative::*;
 pub use wallet_representative_set::*;
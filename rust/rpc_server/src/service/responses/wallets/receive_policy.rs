@@ -0,0 +1,32 @@
+use rsnano_node::{wallets::AutoReceivePolicy, Node};
+use rsnano_rpc_messages::{ErrorDto, ReceivePolicyDto, RpcDto, WalletWithAccountArgs};
+use std::sync::Arc;
+
+pub async fn receive_policy(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: WalletWithAccountArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    match node
+        .wallets
+        .wallet_auto_receive_policy(&args.wallet, &args.account)
+    {
+        Ok(policy) => RpcDto::ReceivePolicy(policy_to_dto(policy)),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
+
+fn policy_to_dto(policy: AutoReceivePolicy) -> ReceivePolicyDto {
+    ReceivePolicyDto {
+        enabled: policy.enabled,
+        minimum: policy.minimum,
+        allow: policy.allow,
+        deny: policy.deny,
+        batch_delay: policy.batch_delay.as_millis() as u64,
+        batch_size: policy.batch_size as u32,
+    }
+}
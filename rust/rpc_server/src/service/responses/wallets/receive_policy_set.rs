@@ -0,0 +1,37 @@
+use rsnano_node::{wallets::AutoReceivePolicy, Node};
+use rsnano_rpc_messages::{ErrorDto, ReceivePolicyDto, ReceivePolicySetArgs, RpcDto, SuccessDto};
+use std::{sync::Arc, time::Duration};
+
+pub async fn receive_policy_set(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: ReceivePolicySetArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let policy = dto_to_policy(args.policy);
+    if policy.validate().is_err() {
+        return RpcDto::Error(ErrorDto::InvalidReceivePolicy);
+    }
+
+    match node
+        .wallets
+        .set_auto_receive_policy(&args.wallet, &args.account, &policy)
+    {
+        Ok(()) => RpcDto::ReceivePolicySet(SuccessDto::new()),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
+
+fn dto_to_policy(dto: ReceivePolicyDto) -> AutoReceivePolicy {
+    AutoReceivePolicy {
+        enabled: dto.enabled,
+        minimum: dto.minimum,
+        allow: dto.allow,
+        deny: dto.deny,
+        batch_delay: Duration::from_millis(dto.batch_delay),
+        batch_size: dto.batch_size as usize,
+    }
+}
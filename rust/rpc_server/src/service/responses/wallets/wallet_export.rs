@@ -1,8 +1,25 @@
-use rsnano_rpc_messages::{JsonDto, RpcDto, WalletRpcMessage};
-use serde_json::{to_string, Value};
+use rsnano_node::{wallets::WalletsError, Node};
+use rsnano_rpc_messages::{ErrorDto, JsonDto, RpcDto, WalletRpcMessage};
+use serde_json::Value;
+use std::sync::Arc;
 
-pub async fn wallet_export(args: WalletRpcMessage) -> RpcDto {
-    RpcDto::WalletExport(JsonDto::new(Value::String(
-        to_string(&args.wallet).unwrap(),
-    )))
+pub async fn wallet_export(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: WalletRpcMessage,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    match node.wallets.valid_password(&args.wallet) {
+        Ok(true) => {}
+        Ok(false) => return RpcDto::Error(ErrorDto::WalletsError(WalletsError::WalletLocked)),
+        Err(e) => return RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+
+    match node.wallets.serialize(args.wallet) {
+        Ok(json) => RpcDto::WalletExport(JsonDto::new(Value::String(json))),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
 }
@@ -0,0 +1,31 @@
+use rsnano_core::WalletId;
+use rsnano_node::{wallets::WalletsError, Node};
+use rsnano_rpc_messages::{ErrorDto, RpcDto, WalletImportArgs, WalletRpcMessage};
+use std::sync::Arc;
+
+pub async fn wallet_import(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: WalletImportArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let wallet = args.wallet.unwrap_or_else(WalletId::random);
+    let exists = node.wallets.mutex.lock().unwrap().contains_key(&wallet);
+    let result = if exists {
+        let password = args.password.unwrap_or_default();
+        node.wallets.import_replace(wallet, &args.json, &password)
+    } else {
+        node.wallets.import(wallet, &args.json)
+    };
+
+    match result {
+        Ok(()) => RpcDto::WalletImport(WalletRpcMessage::new(wallet)),
+        Err(e) => match e.downcast::<WalletsError>() {
+            Ok(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+            Err(_) => RpcDto::Error(ErrorDto::InvalidWalletJson),
+        },
+    }
+}
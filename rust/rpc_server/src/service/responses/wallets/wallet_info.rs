@@ -38,7 +38,7 @@ pub async fn wallet_info(node: Arc<Node>, args: WalletRpcMessage) -> RpcDto {
             .account_receivable(&block_transaction, &account, false);
 
         match node.wallets.key_type(args.wallet, &account.into()) {
-            KeyType::Deterministic => deterministic_count += 1,
+            KeyType::Deterministic | KeyType::Bip44 => deterministic_count += 1,
             KeyType::Adhoc => adhoc_count += 1,
             _ => (),
         }
@@ -0,0 +1,31 @@
+use rsnano_core::hd_wallet::Mnemonic;
+use rsnano_node::{wallets::WalletsExt, Node};
+use rsnano_rpc_messages::{ErrorDto, RpcDto, WalletMnemonicCreateArgs, WalletMnemonicCreateDto};
+use std::sync::Arc;
+
+pub async fn wallet_mnemonic_create(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: WalletMnemonicCreateArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let words = args.words.unwrap_or(Mnemonic::DEFAULT_WORD_COUNT as u32);
+    let Ok(mnemonic) = Mnemonic::random(words as usize) else {
+        return RpcDto::Error(ErrorDto::InvalidMnemonicWordCount);
+    };
+
+    let passphrase = args.passphrase.unwrap_or_default();
+    match node
+        .wallets
+        .restore_mnemonic(args.wallet, &mnemonic, &passphrase, 0)
+    {
+        Ok((_, account)) => RpcDto::WalletMnemonicCreate(WalletMnemonicCreateDto {
+            mnemonic: mnemonic.phrase(),
+            account,
+        }),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
@@ -0,0 +1,18 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, MnemonicDto, RpcDto, WalletRpcMessage};
+use std::sync::Arc;
+
+pub async fn wallet_mnemonic_export(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: WalletRpcMessage,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    match node.wallets.get_mnemonic(args.wallet) {
+        Ok(mnemonic) => RpcDto::WalletMnemonicExport(MnemonicDto::new(mnemonic.phrase())),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
@@ -0,0 +1,31 @@
+use rsnano_core::hd_wallet::Mnemonic;
+use rsnano_node::{wallets::WalletsExt, Node};
+use rsnano_rpc_messages::{ErrorDto, RpcDto, WalletChangeSeedDto, WalletMnemonicRestoreArgs};
+use std::sync::Arc;
+
+pub async fn wallet_mnemonic_restore(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: WalletMnemonicRestoreArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Ok(mnemonic) = Mnemonic::from_phrase(&args.mnemonic) else {
+        return RpcDto::Error(ErrorDto::InvalidMnemonic);
+    };
+
+    let passphrase = args.passphrase.unwrap_or_default();
+    match node.wallets.restore_mnemonic(
+        args.wallet,
+        &mnemonic,
+        &passphrase,
+        args.count.unwrap_or(0),
+    ) {
+        Ok((restored_count, last_restored_account)) => RpcDto::WalletMnemonicRestore(
+            WalletChangeSeedDto::new(last_restored_account, restored_count),
+        ),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
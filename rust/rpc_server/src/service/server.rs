@@ -2,19 +2,25 @@ use super::{
     account_balance, account_block_count, account_create, account_get, account_history,
     account_info, account_key, account_list, account_move, account_remove, account_representative,
     account_weight, accounts_balances, accounts_create, accounts_frontiers, accounts_receivable,
-    accounts_representatives, available_supply, block_account, block_confirm, block_count,
-    block_create, block_hash, block_info, blocks, blocks_info, bootstrap, bootstrap_any,
-    bootstrap_lazy, chain, confirmation_active, confirmation_info, confirmation_quorum, delegators,
-    delegators_count, deterministic_key, frontier_count, frontiers, keepalive, key_create,
-    key_expand, ledger, nano_to_raw, node_id, password_change, password_enter, password_valid,
-    peers, populate_backlog, process, raw_to_nano, receivable, receivable_exists, receive_minimum,
-    representatives, representatives_online, republish, search_receivable, search_receivable_all,
-    send, sign, stats_clear, stop, unchecked, unchecked_clear, unchecked_get, unchecked_keys,
-    unopened, uptime, validate_account_number, wallet_add, wallet_add_watch, wallet_balances,
-    wallet_change_seed, wallet_contains, wallet_create, wallet_destroy, wallet_export,
-    wallet_frontiers, wallet_history, wallet_info, wallet_ledger, wallet_lock, wallet_locked,
-    wallet_receivable, wallet_representative, wallet_representative_set, wallet_republish,
-    wallet_work_get, work_cancel, work_generate, work_get, work_set, work_validate,
+    accounts_representatives, available_supply, bandwidth_limit_set, bandwidth_limits,
+    block_account, block_assemble, block_confirm, block_count, block_create, block_create_unsigned,
+    block_hash, block_info, blocks, blocks_info, bootstrap, bootstrap_any, bootstrap_lazy, chain,
+    confirmation_active, confirmation_info, confirmation_quorum, delegators, delegators_count,
+    deterministic_key, forks, frontier_count, frontiers, keepalive, key_create, key_expand, ledger,
+    ledger_backup, nano_to_raw, node_id, online_weight, password_change, password_enter,
+    password_valid, peer_ban, peer_drop, peer_exclusions, peer_unban, peers, populate_backlog,
+    process, pruned_exists, pruning_status, raw_to_nano, receivable, receivable_exists,
+    receive_minimum, receive_policy, receive_policy_set, representatives, representatives_online,
+    republish, search_receivable, search_receivable_all, send, sign, stats, stats_clear, stop,
+    telemetry_history, telemetry_summary, threshold_sign_abort, threshold_sign_commit,
+    threshold_sign_start, threshold_sign_status, threshold_sign_submit, unchecked, unchecked_clear,
+    unchecked_get, unchecked_keys, unopened, uptime, validate_account_number, wallet_add,
+    wallet_add_watch, wallet_balances, wallet_change_seed, wallet_contains, wallet_create,
+    wallet_destroy, wallet_export, wallet_frontiers, wallet_history, wallet_import, wallet_info,
+    wallet_ledger, wallet_lock, wallet_locked, wallet_mnemonic_create, wallet_mnemonic_export,
+    wallet_mnemonic_restore, wallet_receivable, wallet_representative, wallet_representative_set,
+    wallet_republish, wallet_work_get, work_cancel, work_generate, work_get, work_set,
+    work_validate,
 };
 use anyhow::{Context, Result};
 use axum::{
@@ -135,7 +141,21 @@ async fn handle_rpc(
         RpcCommand::WalletFrontiers(args) => wallet_frontiers(rpc_service.node, args).await,
         RpcCommand::Frontiers(args) => frontiers(rpc_service.node, args).await,
         RpcCommand::WalletInfo(args) => wallet_info(rpc_service.node, args).await,
-        RpcCommand::WalletExport(args) => wallet_export(args).await,
+        RpcCommand::WalletExport(args) => {
+            wallet_export(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::WalletImport(args) => {
+            wallet_import(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::WalletMnemonicCreate(args) => {
+            wallet_mnemonic_create(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::WalletMnemonicRestore(args) => {
+            wallet_mnemonic_restore(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::WalletMnemonicExport(args) => {
+            wallet_mnemonic_export(rpc_service.node, rpc_service.enable_control, args).await
+        }
         RpcCommand::PasswordChange(args) => {
             password_change(rpc_service.node, rpc_service.enable_control, args).await
         }
@@ -162,6 +182,12 @@ async fn handle_rpc(
         RpcCommand::ReceiveMinimum => {
             receive_minimum(rpc_service.node, rpc_service.enable_control).await
         }
+        RpcCommand::ReceivePolicy(args) => {
+            receive_policy(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ReceivePolicySet(args) => {
+            receive_policy_set(rpc_service.node, rpc_service.enable_control, args).await
+        }
         RpcCommand::WalletChangeSeed(args) => {
             wallet_change_seed(rpc_service.node, rpc_service.enable_control, args).await
         }
@@ -224,6 +250,49 @@ async fn handle_rpc(
         RpcCommand::BlockCreate(args) => {
             block_create(rpc_service.node, rpc_service.enable_control, args).await
         }
+        RpcCommand::BlockCreateUnsigned(args) => {
+            block_create_unsigned(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::BlockAssemble(args) => block_assemble(rpc_service.node, args).await,
+        RpcCommand::LedgerBackup(args) => {
+            ledger_backup(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::PrunedExists(args) => pruned_exists(rpc_service.node, args).await,
+        RpcCommand::PruningStatus => pruning_status(rpc_service.node).await,
+        RpcCommand::Stats(args) => stats(rpc_service.node, args).await,
+        RpcCommand::Forks(args) => forks(rpc_service.node, args).await,
+        RpcCommand::OnlineWeight => online_weight(rpc_service.node).await,
+        RpcCommand::PeerExclusions => peer_exclusions(rpc_service.node).await,
+        RpcCommand::PeerBan(args) => {
+            peer_ban(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::PeerUnban(args) => {
+            peer_unban(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::PeerDrop(args) => {
+            peer_drop(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::BandwidthLimits => bandwidth_limits(rpc_service.node).await,
+        RpcCommand::BandwidthLimitSet(args) => {
+            bandwidth_limit_set(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::TelemetryHistory(args) => telemetry_history(rpc_service.node, args).await,
+        RpcCommand::TelemetrySummary(args) => telemetry_summary(rpc_service.node, args).await,
+        RpcCommand::ThresholdSignStart(args) => {
+            threshold_sign_start(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ThresholdSignCommit(args) => {
+            threshold_sign_commit(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ThresholdSignSubmit(args) => {
+            threshold_sign_submit(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ThresholdSignStatus(args) => {
+            threshold_sign_status(rpc_service.node, args).await
+        }
+        RpcCommand::ThresholdSignAbort(args) => {
+            threshold_sign_abort(rpc_service.node, rpc_service.enable_control, args).await
+        }
         _ => todo!(),
     };
 
@@ -21,6 +21,8 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod pruned_exists;
+mod pruning_status;
 mod representatives;
 mod successors;
 mod unopened;
@@ -0,0 +1,49 @@
+use rsnano_core::DEV_GENESIS_KEY;
+use rsnano_ledger::DEV_GENESIS_HASH;
+use rsnano_node::config::NodeFlags;
+use test_helpers::{setup_chain, setup_rpc_client_and_server, System};
+
+#[test]
+fn pruned_exists() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.enable_voting = false;
+    let mut flags = NodeFlags::new();
+    flags.enable_pruning = true;
+    let node = system.build_node().config(config).flags(flags).finish();
+    let blocks = setup_chain(&node, 3, &DEV_GENESIS_KEY, true);
+    node.ledger_pruning(2, false);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let pruned = node
+        .runtime
+        .block_on(async { rpc_client.pruned_exists(blocks[0].hash()).await.unwrap() });
+    let not_pruned = node
+        .runtime
+        .block_on(async { rpc_client.pruned_exists(blocks[2].hash()).await.unwrap() });
+
+    assert!(pruned.exists);
+    assert!(!not_pruned.exists);
+
+    server.abort();
+}
+
+#[test]
+fn pruned_exists_fails_when_pruning_is_disabled() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.pruned_exists(*DEV_GENESIS_HASH).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Pruning is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,32 @@
+use rsnano_core::DEV_GENESIS_KEY;
+use rsnano_ledger::DEV_GENESIS_ACCOUNT;
+use rsnano_node::config::NodeFlags;
+use test_helpers::{setup_chain, setup_rpc_client_and_server, System};
+
+#[test]
+fn pruning_status() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.enable_voting = false;
+    config.pruning_keep_accounts = vec![*DEV_GENESIS_ACCOUNT];
+    let mut flags = NodeFlags::new();
+    flags.enable_pruning = true;
+    let node = system.build_node().config(config).flags(flags).finish();
+    setup_chain(&node, 3, &DEV_GENESIS_KEY, true);
+    node.ledger_pruning(2, false);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.pruning_status().await.unwrap() });
+
+    assert!(result.enabled);
+    assert_eq!(result.pruned_count, 0);
+    assert_eq!(result.block_count, 4);
+    assert!(result.runs > 0);
+    assert_eq!(result.last_run_kept_accounts, 1);
+    assert_eq!(result.keep_accounts, vec![*DEV_GENESIS_ACCOUNT]);
+
+    server.abort();
+}
@@ -0,0 +1,122 @@
+use rsnano_network::bandwidth_limiter::BandwidthLimitType;
+use rsnano_rpc_messages::BandwidthLimitSetArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn list_bandwidth_limits() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.bandwidth_limits().await.unwrap() });
+
+    let types: Vec<_> = result
+        .limits
+        .iter()
+        .map(|l| l.limit_type.as_str())
+        .collect();
+    assert_eq!(
+        types,
+        [
+            "standard",
+            "bootstrap",
+            "vote",
+            "block",
+            "telemetry",
+            "peer"
+        ]
+    );
+    assert!(result.limits.iter().all(|l| l.dropped == 0));
+
+    server.abort();
+}
+
+#[test]
+fn set_bandwidth_limit() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .bandwidth_limit_set(BandwidthLimitSetArgs::with_burst_ratio("vote", 1024, 2.5))
+            .await
+            .unwrap()
+    });
+
+    let vote = result
+        .limits
+        .iter()
+        .find(|l| l.limit_type == "vote")
+        .unwrap();
+    assert_eq!(vote.limit, 1024);
+    assert_eq!(vote.burst_ratio, 2.5);
+    assert_eq!(
+        node.network.limiter().limit(BandwidthLimitType::Vote),
+        (1024, 2.5)
+    );
+
+    server.abort();
+}
+
+#[test]
+fn set_bandwidth_limit_with_invalid_type() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .bandwidth_limit_set(BandwidthLimitSetArgs::new("gossip", 1024))
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid bandwidth limit type\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn set_bandwidth_limit_too_high() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .bandwidth_limit_set(BandwidthLimitSetArgs::new("vote", 1_000_000_001))
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Bandwidth limit is too high\"".to_string())
+    );
+    assert_eq!(node.network.limiter().limit(BandwidthLimitType::Vote).0, 0);
+
+    server.abort();
+}
+
+#[test]
+fn set_bandwidth_limit_requires_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .bandwidth_limit_set(BandwidthLimitSetArgs::new("peer", 1024))
+            .await
+    });
+    assert!(result.is_err());
+
+    server.abort();
+}
@@ -0,0 +1,123 @@
+use rsnano_core::{
+    sign_message, Amount, BlockEnum, KeyPair, Signature, SigningPayload, StateBlock,
+    StateHashables, DEV_GENESIS_KEY,
+};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_node::Node;
+use rsnano_rpc_messages::BlockAssembleArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+fn genesis_send_payload(node: &Node) -> SigningPayload {
+    SigningPayload::new(
+        StateHashables {
+            account: *DEV_GENESIS_ACCOUNT,
+            previous: *DEV_GENESIS_HASH,
+            representative: *DEV_GENESIS_PUB_KEY,
+            balance: Amount::MAX - Amount::raw(100),
+            link: DEV_GENESIS_KEY.account().into(),
+        },
+        Amount::MAX,
+        node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+    )
+}
+
+#[test]
+fn block_assemble_and_process() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+    let payload = genesis_send_payload(&node);
+    let signature = payload.sign(&DEV_GENESIS_KEY.private_key()).unwrap();
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .block_assemble(BlockAssembleArgs::with_process(
+                payload.encode_hex(),
+                signature,
+            ))
+            .await
+            .unwrap()
+    });
+
+    let expected = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::raw(100),
+        DEV_GENESIS_KEY.account().into(),
+        &DEV_GENESIS_KEY,
+        payload.work,
+    ));
+    assert_eq!(result.hash, payload.hash());
+    assert_eq!(result.block, expected.json_representation());
+    assert_eq!(node.latest(&DEV_GENESIS_ACCOUNT), payload.hash());
+
+    server.abort();
+}
+
+#[test]
+fn block_assemble_without_process() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+    let payload = genesis_send_payload(&node);
+    let signature = payload.sign(&DEV_GENESIS_KEY.private_key()).unwrap();
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .block_assemble(BlockAssembleArgs::new(payload.encode_hex(), signature))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.hash, payload.hash());
+    assert_eq!(node.latest(&DEV_GENESIS_ACCOUNT), *DEV_GENESIS_HASH);
+
+    server.abort();
+}
+
+#[test]
+fn block_assemble_fails_with_bad_signature() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+    let payload = genesis_send_payload(&node);
+    let signature = sign_message(&KeyPair::new().private_key(), payload.hash().as_bytes());
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .block_assemble(BlockAssembleArgs::with_process(
+                payload.encode_hex(),
+                signature,
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Bad signature\"".to_string())
+    );
+    assert_eq!(node.latest(&DEV_GENESIS_ACCOUNT), *DEV_GENESIS_HASH);
+
+    server.abort();
+}
+
+#[test]
+fn block_assemble_fails_with_invalid_payload() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .block_assemble(BlockAssembleArgs::new("01AB".to_owned(), Signature::new()))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid signing payload\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,103 @@
+use rsnano_core::{Amount, BlockEnum, SigningPayload, WalletId, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_node::wallets::WalletsExt;
+use rsnano_rpc_messages::BlockCreateUnsignedArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn block_create_unsigned() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_watch(&wallet_id, &[*DEV_GENESIS_ACCOUNT])
+        .unwrap();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = BlockCreateUnsignedArgs::builder(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        Amount::MAX - Amount::raw(100),
+        DEV_GENESIS_KEY.account().into(),
+    )
+    .build();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.block_create_unsigned(args).await.unwrap() });
+
+    let payload = SigningPayload::decode_hex(&result.payload).unwrap();
+    assert_eq!(payload.hash(), result.hash);
+    assert_eq!(payload.hashables.previous, *DEV_GENESIS_HASH);
+    assert_eq!(payload.hashables.representative, *DEV_GENESIS_PUB_KEY);
+    assert_eq!(payload.hashables.balance, Amount::MAX - Amount::raw(100));
+    assert_eq!(payload.previous_balance, Amount::MAX);
+    assert_eq!(BlockEnum::from(result.block), payload.unsigned_block());
+    // validate_entry returns true if the work is insufficient
+    assert!(!node.network_params.work.validate_entry(
+        payload.unsigned_block().work_version(),
+        &(*DEV_GENESIS_HASH).into(),
+        payload.work
+    ));
+
+    server.abort();
+}
+
+#[test]
+fn block_create_unsigned_fails_for_account_with_private_key() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), false)
+        .unwrap();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let args = BlockCreateUnsignedArgs::builder(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        Amount::MAX - Amount::raw(100),
+        DEV_GENESIS_KEY.account().into(),
+    )
+    .work(1.into())
+    .build();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.block_create_unsigned(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account is not a watch-only account\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn block_create_unsigned_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let args = BlockCreateUnsignedArgs::builder(
+        WalletId::random(),
+        *DEV_GENESIS_ACCOUNT,
+        Amount::MAX - Amount::raw(100),
+        DEV_GENESIS_KEY.account().into(),
+    )
+    .build();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.block_create_unsigned(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,69 @@
+use rsnano_core::{Account, Amount, BlockEnum, KeyPair, StateBlock, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_rpc_messages::ForksArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely, assert_timely_eq, setup_rpc_client_and_server, System};
+
+#[test]
+fn forks() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let work = node.work_generate_dev((*DEV_GENESIS_HASH).into());
+    let send1 = fork_send(work, Account::from(1));
+    let send2 = fork_send(work, Account::from(2));
+
+    node.process_active(send1.clone());
+    assert_timely(Duration::from_secs(5), || {
+        node.active.election(&send1.qualified_root()).is_some()
+    });
+    node.process_active(send2.clone());
+    assert_timely(Duration::from_secs(5), || {
+        node.active
+            .election(&send1.qualified_root())
+            .map(|election| election.mutex.lock().unwrap().last_blocks.len() == 2)
+            .unwrap_or(false)
+    });
+
+    // Let the genesis representative decide the fork
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+    assert_timely_eq(Duration::from_secs(5), || node.fork_journal.len(), 1);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.forks(ForksArgs::default()).await.unwrap() });
+
+    assert_eq!(result.forks.len(), 1);
+    let fork = &result.forks[0];
+    assert_eq!(fork.account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(fork.previous, *DEV_GENESIS_HASH);
+    assert!(fork.confirmed);
+    assert_eq!(fork.blocks.len(), 2);
+    assert!(fork.winner == send1.hash() || fork.winner == send2.hash());
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .forks(ForksArgs {
+                account: Some(KeyPair::new().account()),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    });
+    assert!(result.forks.is_empty());
+
+    server.abort();
+}
+
+fn fork_send(work: u64, destination: Account) -> BlockEnum {
+    BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::raw(1),
+        destination.into(),
+        &DEV_GENESIS_KEY,
+        work,
+    ))
+}
@@ -0,0 +1,47 @@
+use rsnano_node::ledger_backup::LedgerBackupManifest;
+use rsnano_rpc_messages::LedgerBackupArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn ledger_backup() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .ledger_backup(LedgerBackupArgs::new())
+            .await
+            .unwrap()
+    });
+
+    assert!(result.path.starts_with(node.ledger_backup.backups_path()));
+    assert_eq!(result.block_count, node.ledger.block_count());
+    assert_eq!(result.cemented_count, node.ledger.cemented_count());
+
+    let manifest = LedgerBackupManifest::load(&result.path).unwrap();
+    assert_eq!(manifest.sha256, result.sha256);
+    assert_eq!(manifest.block_count, result.block_count);
+
+    server.abort();
+}
+
+#[test]
+fn ledger_backup_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.ledger_backup(LedgerBackupArgs::new()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -1,12 +1,20 @@
+mod bandwidth_limits;
+mod block_assemble;
 mod block_create;
+mod block_create_unsigned;
 mod bootstrap;
 mod bootstrap_any;
 mod bootstrap_lazy;
 mod confirmation_active;
 mod confirmation_info;
 mod confirmation_quorum;
+mod forks;
 mod keepalive;
+mod ledger_backup;
 mod node_id;
+mod online_weight;
+mod peer_ban;
+mod peer_drop;
 mod peers;
 mod populate_backlog;
 mod process;
@@ -15,8 +23,11 @@ mod receivable_exists;
 mod representatives_online;
 mod republish;
 mod sign;
+mod stats;
 mod stats_clear;
 mod stop;
+mod telemetry;
+mod threshold_sign;
 mod unchecked;
 mod unchecked_clear;
 mod unchecked_get;
@@ -0,0 +1,34 @@
+use rsnano_core::Amount;
+use rsnano_node::consensus::RepTier;
+use std::time::Duration;
+use test_helpers::{assert_timely, setup_rpc_client_and_server, System};
+
+#[test]
+fn online_weight() {
+    let mut system = System::new();
+    let node = system.make_node();
+    node.online_weight_sampler.sample(Amount::nano(1000));
+    node.online_weight_sampler.sample(Amount::nano(2000));
+    assert_timely(Duration::from_secs(5), || {
+        node.rep_tiers.tier_weights()[RepTier::Tier3 as usize].representatives == 1
+    });
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.online_weight().await.unwrap() });
+
+    let samples: Vec<_> = result.samples.iter().map(|s| s.weight).collect();
+    assert_eq!(samples, vec![Amount::nano(1000), Amount::nano(2000)]);
+    assert!(result.samples[0].timestamp > 0);
+    assert_eq!(
+        result.online_weight_minimum,
+        node.config.online_weight_minimum
+    );
+    assert_eq!(result.tiers.tier_3.representatives, 1);
+    assert_eq!(result.tiers.tier_3.weight, Amount::MAX);
+    assert_eq!(result.tiers.tier_1.representatives, 0);
+
+    server.abort();
+}
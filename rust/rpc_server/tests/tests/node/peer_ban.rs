@@ -0,0 +1,106 @@
+use rsnano_rpc_messages::PeerBanArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely, establish_tcp, setup_rpc_client_and_server, System};
+
+#[test]
+fn ban_list_and_unban_peer() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let node2 = system.make_node();
+    let channel = establish_tcp(&node1, &node2);
+    let subnet = channel.peer_addr().ip().to_string();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node1.clone(), true);
+
+    let result = node1.runtime.block_on(async {
+        rpc_client
+            .peer_ban(PeerBanArgs::with_duration(subnet.clone(), 3600))
+            .await
+            .unwrap()
+    });
+    assert!(result.dropped >= 1);
+    assert!(result.until.is_some());
+    assert_timely(Duration::from_secs(5), || channel.is_closed());
+    assert_eq!(
+        node1.ledger.store.peer_ban.count(&node1.ledger.read_txn()),
+        1
+    );
+
+    let exclusions = node1
+        .runtime
+        .block_on(async { rpc_client.peer_exclusions().await.unwrap() });
+    assert_eq!(exclusions.banned.len(), 1);
+    assert_eq!(exclusions.banned[0].until, result.until);
+
+    let unbanned = node1
+        .runtime
+        .block_on(async { rpc_client.peer_unban(subnet.clone()).await.unwrap() });
+    assert!(unbanned.removed);
+    assert_eq!(
+        node1.ledger.store.peer_ban.count(&node1.ledger.read_txn()),
+        0
+    );
+
+    let exclusions = node1
+        .runtime
+        .block_on(async { rpc_client.peer_exclusions().await.unwrap() });
+    assert!(exclusions.banned.is_empty());
+
+    server.abort();
+}
+
+#[test]
+fn ban_invalid_subnet() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .peer_ban(PeerBanArgs::new("not an address"))
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid IP address or subnet\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn ban_too_long() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .peer_ban(PeerBanArgs::with_duration("10.0.0.1", u64::MAX))
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Ban duration is too long\"".to_string())
+    );
+    assert_eq!(node.ledger.store.peer_ban.count(&node.ledger.read_txn()), 0);
+
+    server.abort();
+}
+
+#[test]
+fn ban_requires_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.peer_ban(PeerBanArgs::new("10.0.0.1")).await });
+    assert!(result.is_err());
+
+    server.abort();
+}
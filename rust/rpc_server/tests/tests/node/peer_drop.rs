@@ -0,0 +1,31 @@
+use rsnano_rpc_messages::PeerDropArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely, establish_tcp, setup_rpc_client_and_server, System};
+
+#[test]
+fn drop_channel() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let node2 = system.make_node();
+    let channel = establish_tcp(&node1, &node2);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node1.clone(), true);
+
+    let result = node1.runtime.block_on(async {
+        rpc_client
+            .peer_drop(PeerDropArgs::channel(channel.channel_id().as_usize() as u64))
+            .await
+            .unwrap()
+    });
+    assert_eq!(result.dropped, 1);
+    assert_timely(Duration::from_secs(5), || channel.is_closed());
+
+    let result = node1.runtime.block_on(async {
+        rpc_client
+            .peer_drop(PeerDropArgs::channel(channel.channel_id().as_usize() as u64))
+            .await
+    });
+    assert!(result.is_err());
+
+    server.abort();
+}
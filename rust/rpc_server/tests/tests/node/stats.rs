@@ -0,0 +1,40 @@
+use rsnano_node::stats::{DetailType, StatType};
+use rsnano_rpc_messages::StatsType;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn stats_counters() {
+    let mut system = System::new();
+    let node = system.make_node();
+    node.stats.inc(StatType::Ledger, DetailType::Fork);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.stats(StatsType::Counters).await.unwrap() });
+
+    assert_eq!(result["type"], "counters");
+    let entries = result["entries"].as_array().unwrap();
+    assert!(entries
+        .iter()
+        .any(|e| e["type"] == "ledger" && e["detail"] == "fork" && e["value"] == "1"));
+
+    server.abort();
+}
+
+#[test]
+fn stats_objects() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.stats(StatsType::Objects).await.unwrap() });
+
+    assert_eq!(result["node"]["block_processor"]["blocks"]["count"], "0");
+
+    server.abort();
+}
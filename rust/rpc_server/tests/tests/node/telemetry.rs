@@ -0,0 +1,82 @@
+use rsnano_rpc_messages::TelemetrySummaryArgs;
+use std::{net::Ipv6Addr, time::Duration};
+use test_helpers::{assert_timely, setup_rpc_client_and_server, System};
+
+#[test]
+fn telemetry_history() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let node2 = system.make_node();
+
+    let peer_addr = node1
+        .network_info
+        .read()
+        .unwrap()
+        .find_node_id(&node2.get_node_id())
+        .unwrap()
+        .peer_addr();
+    assert_timely(Duration::from_secs(5), || {
+        !node1.telemetry.history(&peer_addr).is_empty()
+    });
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node1.clone(), false);
+
+    let result = node1.runtime.block_on(async {
+        rpc_client
+            .telemetry_history(*peer_addr.ip(), peer_addr.port())
+            .await
+            .unwrap()
+    });
+
+    let sample = result.samples.last().unwrap();
+    assert_eq!(sample.block_count, node2.ledger.block_count());
+    assert_eq!(sample.version, "2.0.0.99");
+
+    server.abort();
+}
+
+#[test]
+fn telemetry_history_of_unknown_peer() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.telemetry_history(Ipv6Addr::LOCALHOST, 1).await });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Peer not found\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn telemetry_summary() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let _node2 = system.make_node();
+
+    assert_timely(Duration::from_secs(5), || node1.telemetry.len() > 0);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node1.clone(), false);
+
+    let result = node1.runtime.block_on(async {
+        rpc_client
+            .telemetry_summary(TelemetrySummaryArgs::default())
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.peers, 1);
+    assert_eq!(result.versions.len(), 1);
+    assert_eq!(result.versions[0].name, "2.0.0.99");
+    assert_eq!(result.makers[0].name, "rsnano");
+    assert_eq!(result.median_cemented_count, 1);
+    assert!(result.lagging_peers.is_empty());
+    assert_eq!(result.external_address, None);
+
+    server.abort();
+}
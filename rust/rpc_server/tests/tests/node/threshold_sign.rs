@@ -0,0 +1,250 @@
+use rsnano_core::{
+    threshold::{create_key_shares, SigningCommitment, SigningNonces, ThresholdGroup},
+    validate_block_signature, Amount, BlockEnum, BlockHash, Signature, StateBlock, DEV_GENESIS_KEY,
+};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_node::Node;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+/// Sends to the group account and returns the unsigned open block of the group account
+fn unsigned_group_open(node: &Node, group: &ThresholdGroup) -> BlockEnum {
+    let send = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::raw(1000),
+        group.account().into(),
+        &DEV_GENESIS_KEY,
+        node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+    ));
+    node.process(send.clone()).unwrap();
+
+    BlockEnum::State(StateBlock::with_signature(
+        group.account(),
+        BlockHash::zero(),
+        group.public_key(),
+        Amount::raw(1000),
+        send.hash().into(),
+        Signature::new(),
+        node.work_generate_dev(group.account().into()),
+    ))
+}
+
+#[test]
+fn threshold_sign_two_of_three() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let shares = create_key_shares(2, 3);
+    let group = shares[0].group().clone();
+    let open = unsigned_group_open(&node, &group);
+    let signers = [&shares[0], &shares[2]];
+    let nonces: Vec<_> = signers.iter().map(|s| SigningNonces::new(s)).collect();
+
+    let hash = node.runtime.block_on(async {
+        rpc_client
+            .threshold_sign_start(open.json_representation(), group.encode_hex())
+            .await
+            .unwrap()
+            .hash
+    });
+    assert_eq!(hash, open.hash());
+
+    for nonce in &nonces {
+        node.runtime.block_on(async {
+            rpc_client
+                .threshold_sign_commit(hash, nonce.commitment().encode_hex())
+                .await
+                .unwrap()
+        });
+    }
+
+    let status = node
+        .runtime
+        .block_on(async { rpc_client.threshold_sign_status(hash).await.unwrap() });
+    assert_eq!(status.threshold, 2);
+    assert_eq!(status.block, None);
+    let commitments: Vec<_> = status
+        .commitments
+        .iter()
+        .map(|c| SigningCommitment::decode_hex(c).unwrap())
+        .collect();
+
+    let mut status = None;
+    for (signer, nonce) in signers.iter().zip(nonces) {
+        let partial = signer.sign(nonce, hash.as_bytes(), &commitments).unwrap();
+        status = Some(node.runtime.block_on(async {
+            rpc_client
+                .threshold_sign_submit(hash, signer.participant(), partial.encode_hex())
+                .await
+                .unwrap()
+        }));
+    }
+
+    let status = status.unwrap();
+    assert_eq!(status.partial_signatures, vec![1, 3]);
+    let signed: BlockEnum = status.block.unwrap().into();
+    let BlockEnum::State(state_block) = &signed else {
+        panic!("not a state block")
+    };
+    assert!(validate_block_signature(state_block).is_ok());
+    node.process(signed).unwrap();
+    assert_eq!(node.latest(&group.account()), hash);
+
+    server.abort();
+}
+
+#[test]
+fn threshold_sign_rejects_invalid_partial_signature() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let shares = create_key_shares(2, 2);
+    let group = shares[0].group().clone();
+    let open = unsigned_group_open(&node, &group);
+    let nonces: Vec<_> = shares.iter().map(SigningNonces::new).collect();
+    let commitments: Vec<_> = nonces.iter().map(|n| n.commitment()).collect();
+    let hash = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .threshold_sign_start(open.json_representation(), group.encode_hex())
+                .await
+        })
+        .unwrap()
+        .hash;
+    for commitment in &commitments {
+        node.runtime
+            .block_on(async {
+                rpc_client
+                    .threshold_sign_commit(hash, commitment.encode_hex())
+                    .await
+            })
+            .unwrap();
+    }
+    let partial = shares[0]
+        .sign(
+            nonces.into_iter().next().unwrap(),
+            b"other message",
+            &commitments,
+        )
+        .unwrap();
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .threshold_sign_submit(hash, 1, partial.encode_hex())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid partial signature\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn threshold_sign_commit_fails_with_invalid_commitment() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .threshold_sign_commit(BlockHash::from(1), "01AB".to_owned())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid signing commitment\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn threshold_sign_status_fails_without_session() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.threshold_sign_status(BlockHash::from(1)).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Signing session not found\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn threshold_sign_start_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+    let group = create_key_shares(1, 1)[0].group().clone();
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .threshold_sign_start(
+                BlockEnum::new_test_instance().json_representation(),
+                group.encode_hex(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn threshold_sign_abort_allows_restarting_the_session() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let shares = create_key_shares(2, 3);
+    let group = shares[0].group().clone();
+    let open = unsigned_group_open(&node, &group);
+    let start = || {
+        node.runtime.block_on(async {
+            rpc_client
+                .threshold_sign_start(open.json_representation(), group.encode_hex())
+                .await
+        })
+    };
+    let hash = start().unwrap().hash;
+    node.runtime
+        .block_on(async {
+            rpc_client
+                .threshold_sign_commit(
+                    hash,
+                    SigningNonces::new(&shares[0]).commitment().encode_hex(),
+                )
+                .await
+        })
+        .unwrap();
+
+    node.runtime
+        .block_on(async { rpc_client.threshold_sign_abort(hash).await })
+        .unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.threshold_sign_status(hash).await });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Signing session not found\"".to_string())
+    );
+    assert_eq!(start().unwrap().hash, hash);
+
+    server.abort();
+}
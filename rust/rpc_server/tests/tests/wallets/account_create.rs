@@ -1,4 +1,4 @@
-use rsnano_core::WalletId;
+use rsnano_core::{hd_wallet::Mnemonic, WalletId};
 use rsnano_node::wallets::WalletsExt;
 use rsnano_rpc_messages::AccountCreateArgs;
 use std::{time::Duration, u32};
@@ -48,6 +48,35 @@ fn account_create_index_max() {
     server.abort();
 }
 
+#[test]
+fn account_create_fails_with_bip44_index_out_of_range() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .restore_mnemonic(wallet_id, &Mnemonic::random(12).unwrap(), "", 1)
+        .unwrap();
+
+    let args = AccountCreateArgs::builder(wallet_id)
+        .with_index(0x8000_0000)
+        .build();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.account_create(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid index\"".to_string())
+    );
+
+    server.abort();
+}
+
 #[test]
 fn account_create_work_without_precomputed_work() {
     let mut system = System::new();
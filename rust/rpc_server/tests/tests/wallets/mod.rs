@@ -7,6 +7,8 @@ mod password_change;
 mod password_enter;
 mod password_valid;
 mod receive_minimum;
+mod receive_policy;
+mod receive_policy_set;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
@@ -20,10 +22,14 @@ mod wallet_destroy;
 mod wallet_export;
 mod wallet_frontiers;
 mod wallet_history;
+mod wallet_import;
 mod wallet_info;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_mnemonic_create;
+mod wallet_mnemonic_export;
+mod wallet_mnemonic_restore;
 mod wallet_receivable;
 mod wallet_representative;
 mod wallet_representative_set;
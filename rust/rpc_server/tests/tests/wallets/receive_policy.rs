@@ -0,0 +1,98 @@
+use rsnano_core::{Account, Amount, RawKey, WalletId};
+use rsnano_node::wallets::{AutoReceivePolicy, WalletsExt};
+use rsnano_rpc_messages::ReceivePolicyDto;
+use std::time::Duration;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn receive_policy() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    let account: Account = node
+        .wallets
+        .insert_adhoc2(&wallet, &RawKey::from(42), false)
+        .unwrap()
+        .into();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.receive_policy(wallet, account).await.unwrap() });
+    assert_eq!(result, ReceivePolicyDto::default());
+
+    let policy = AutoReceivePolicy {
+        minimum: Some(Amount::raw(1000)),
+        deny: vec![Account::from(1)],
+        batch_delay: Duration::from_millis(2500),
+        batch_size: 10,
+        ..Default::default()
+    };
+    node.wallets
+        .set_auto_receive_policy(&wallet, &account, &policy)
+        .unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.receive_policy(wallet, account).await.unwrap() });
+
+    assert_eq!(
+        result,
+        ReceivePolicyDto {
+            enabled: true,
+            minimum: Some(Amount::raw(1000)),
+            allow: Vec::new(),
+            deny: vec![Account::from(1)],
+            batch_delay: 2500,
+            batch_size: 10,
+        }
+    );
+
+    server.abort();
+}
+
+#[test]
+fn receive_policy_fails_with_account_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.receive_policy(wallet, Account::from(1)).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account not found\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn receive_policy_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .receive_policy(WalletId::zero(), Account::zero())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,129 @@
+use rsnano_core::{Account, Amount, RawKey, WalletId};
+use rsnano_node::wallets::{AutoReceivePolicy, WalletsExt};
+use rsnano_rpc_messages::{ReceivePolicyDto, ReceivePolicySetArgs};
+use std::time::Duration;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn receive_policy_set() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    let account: Account = node
+        .wallets
+        .insert_adhoc2(&wallet, &RawKey::from(42), false)
+        .unwrap()
+        .into();
+
+    let policy = ReceivePolicyDto {
+        enabled: false,
+        minimum: Some(Amount::raw(5)),
+        allow: vec![Account::from(2)],
+        deny: Vec::new(),
+        batch_delay: 100,
+        batch_size: 3,
+    };
+
+    node.runtime.block_on(async {
+        rpc_client
+            .receive_policy_set(ReceivePolicySetArgs::new(wallet, account, policy))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(
+        node.wallets.auto_receive_policy(&wallet, &account),
+        AutoReceivePolicy {
+            enabled: false,
+            minimum: Some(Amount::raw(5)),
+            allow: vec![Account::from(2)],
+            deny: Vec::new(),
+            batch_delay: Duration::from_millis(100),
+            batch_size: 3,
+        }
+    );
+
+    // Setting the default policy resets the account
+    node.runtime.block_on(async {
+        rpc_client
+            .receive_policy_set(ReceivePolicySetArgs::new(
+                wallet,
+                account,
+                ReceivePolicyDto::default(),
+            ))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(
+        node.wallets.auto_receive_policy(&wallet, &account),
+        AutoReceivePolicy::default()
+    );
+
+    server.abort();
+}
+
+#[test]
+fn receive_policy_set_fails_with_too_many_accounts() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    let account: Account = node
+        .wallets
+        .insert_adhoc2(&wallet, &RawKey::from(42), false)
+        .unwrap()
+        .into();
+
+    let policy = ReceivePolicyDto {
+        deny: (0..=AutoReceivePolicy::MAX_LIST_LEN as u64)
+            .map(Account::from)
+            .collect(),
+        ..Default::default()
+    };
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .receive_policy_set(ReceivePolicySetArgs::new(wallet, account, policy))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid receive policy\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn receive_policy_set_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .receive_policy_set(ReceivePolicySetArgs::new(
+                WalletId::zero(),
+                Account::zero(),
+                ReceivePolicyDto::default(),
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
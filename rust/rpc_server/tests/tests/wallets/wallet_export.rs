@@ -1,4 +1,4 @@
-use rsnano_core::WalletId;
+use rsnano_core::{RawKey, WalletId};
 use rsnano_node::wallets::WalletsExt;
 use test_helpers::{setup_rpc_client_and_server, System};
 
@@ -7,17 +7,67 @@ fn wallet_export() {
     let mut system = System::new();
     let node = system.make_node();
 
-    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
 
     let wallet = WalletId::zero();
-
     node.wallets.create(wallet);
+    node.wallets
+        .insert_adhoc2(&wallet, &RawKey::from(42), false)
+        .unwrap();
 
     let result = node
         .runtime
         .block_on(async { rpc_client.wallet_export(wallet).await.unwrap() });
 
-    assert_eq!(result.json, serde_json::to_string(&wallet).unwrap());
+    assert_eq!(
+        result.json,
+        serde_json::Value::String(node.wallets.serialize(wallet).unwrap())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_export_fails_when_locked() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets.lock(&wallet).unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_export(wallet).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet is locked\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_export_fails_without_rpc_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_export(wallet).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
 
     server.abort();
 }
@@ -0,0 +1,194 @@
+use rsnano_core::{Account, KeyPair, WalletId};
+use rsnano_node::wallets::{WalletsError, WalletsExt};
+use rsnano_rpc_messages::WalletImportArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn import_exported_wallet_into_other_node() {
+    let mut system = System::new();
+    let source = system.make_node();
+    let target = system.make_node();
+
+    let wallet = WalletId::from(1);
+    let key = KeyPair::new();
+    source.wallets.create(wallet);
+    source
+        .wallets
+        .insert_adhoc2(&wallet, &key.private_key(), false)
+        .unwrap();
+
+    let (source_client, source_server) = setup_rpc_client_and_server(source.clone(), true);
+    let (target_client, target_server) = setup_rpc_client_and_server(target.clone(), true);
+
+    let exported = source
+        .runtime
+        .block_on(async { source_client.wallet_export(wallet).await.unwrap() });
+    let json = exported.json.as_str().unwrap().to_owned();
+
+    let imported = target.runtime.block_on(async {
+        target_client
+            .wallet_import(WalletImportArgs::new(json))
+            .await
+            .unwrap()
+    });
+
+    // The imported wallet keeps the password of the exported wallet and starts locked
+    target.wallets.enter_password(imported.wallet, "").unwrap();
+    assert_eq!(
+        target
+            .wallets
+            .get_accounts_of_wallet(&imported.wallet)
+            .unwrap(),
+        vec![Account::from(key.public_key())]
+    );
+
+    source_server.abort();
+    target_server.abort();
+}
+
+#[test]
+fn import_reports_wallet_that_was_created_concurrently() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let wallet = WalletId::from(1);
+    node.wallets.create(wallet);
+    let json = node.wallets.serialize(wallet).unwrap();
+
+    // The RPC only calls import when the wallet didn't exist a moment ago
+    let error = node.wallets.import(wallet, &json).unwrap_err();
+    assert!(matches!(
+        error.downcast::<WalletsError>(),
+        Ok(WalletsError::WalletAlreadyExists)
+    ));
+}
+
+#[test]
+fn import_into_existing_wallet() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let source = WalletId::from(1);
+    let key = KeyPair::new();
+    node.wallets.create(source);
+    node.wallets
+        .insert_adhoc2(&source, &key.private_key(), false)
+        .unwrap();
+    node.wallets.rekey(&source, "secret").unwrap();
+    let json = node.wallets.serialize(source).unwrap();
+
+    let target = WalletId::from(2);
+    node.wallets.create(target);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_import(WalletImportArgs {
+                wallet: Some(target),
+                json,
+                password: Some("secret".to_owned()),
+            })
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.wallet, target);
+    assert_eq!(
+        node.wallets.get_accounts_of_wallet(&target).unwrap(),
+        vec![Account::from(key.public_key())]
+    );
+
+    server.abort();
+}
+
+#[test]
+fn import_into_existing_wallet_fails_with_wrong_password() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let source = WalletId::from(1);
+    node.wallets.create(source);
+    node.wallets.rekey(&source, "secret").unwrap();
+    let json = node.wallets.serialize(source).unwrap();
+
+    let target = WalletId::from(2);
+    node.wallets.create(target);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_import(WalletImportArgs {
+                wallet: Some(target),
+                json,
+                password: Some("wrong".to_owned()),
+            })
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid password\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn import_fails_with_invalid_json() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::from(1);
+    let import = || async {
+        rpc_client
+            .wallet_import(WalletImportArgs {
+                wallet: Some(wallet),
+                json:
+                    r#"{"0000000000000000000000000000000000000000000000000000000000000000": "01"}"#
+                        .to_owned(),
+                password: None,
+            })
+            .await
+    };
+
+    let result = node.runtime.block_on(import());
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid wallet json\"".to_string())
+    );
+    assert!(!node.wallets.mutex.lock().unwrap().contains_key(&wallet));
+
+    // A failed import must not leave a half written wallet behind
+    let result = node.runtime.block_on(import());
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid wallet json\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_import_fails_without_rpc_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_import(WalletImportArgs::new("{}".to_owned()))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
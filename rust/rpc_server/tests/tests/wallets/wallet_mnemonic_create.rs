@@ -0,0 +1,102 @@
+use rsnano_core::{hd_wallet::Mnemonic, WalletId};
+use rsnano_node::wallets::WalletsExt;
+use rsnano_rpc_messages::WalletMnemonicCreateArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_mnemonic_create() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_create(WalletMnemonicCreateArgs::new(wallet))
+            .await
+            .unwrap()
+    });
+
+    let mnemonic: Mnemonic = result.mnemonic.parse().unwrap();
+    assert_eq!(mnemonic.word_count(), 24);
+    assert_eq!(node.wallets.get_mnemonic(wallet).unwrap(), mnemonic);
+    assert!(node.wallets.exists(&result.account.into()));
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_create_with_word_count() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_create(WalletMnemonicCreateArgs {
+                words: Some(12),
+                ..WalletMnemonicCreateArgs::new(wallet)
+            })
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.mnemonic.split(' ').count(), 12);
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_create_fails_with_invalid_word_count() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_create(WalletMnemonicCreateArgs {
+                words: Some(13),
+                ..WalletMnemonicCreateArgs::new(wallet)
+            })
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid mnemonic word count\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_create_fails_without_rpc_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_create(WalletMnemonicCreateArgs::new(WalletId::zero()))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,96 @@
+use rsnano_core::{hd_wallet::Mnemonic, RawKey, WalletId};
+use rsnano_node::wallets::WalletsExt;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn wallet_mnemonic_export() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    let mnemonic = Mnemonic::random(12).unwrap();
+    node.wallets
+        .restore_mnemonic(wallet, &mnemonic, "", 1)
+        .unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_mnemonic_export(wallet).await.unwrap() });
+
+    assert_eq!(result.mnemonic, mnemonic.phrase());
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_export_fails_for_seed_wallet() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets
+        .change_seed(wallet, &RawKey::from(1), 0)
+        .unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_mnemonic_export(wallet).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet has no mnemonic\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_export_fails_when_locked() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets
+        .restore_mnemonic(wallet, &Mnemonic::random(12).unwrap(), "", 1)
+        .unwrap();
+    node.wallets.lock(&wallet).unwrap();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_mnemonic_export(wallet).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet is locked\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_export_fails_without_rpc_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_mnemonic_export(WalletId::zero()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,171 @@
+use rsnano_core::{
+    hd_wallet::{Mnemonic, Slip10Node},
+    Account, PublicKey, WalletId,
+};
+use rsnano_node::wallets::WalletsExt;
+use rsnano_rpc_messages::WalletMnemonicRestoreArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+const PHRASE: &str = "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur";
+
+fn restore_args(wallet: WalletId) -> WalletMnemonicRestoreArgs {
+    WalletMnemonicRestoreArgs {
+        passphrase: Some("some password".to_string()),
+        ..WalletMnemonicRestoreArgs::new(wallet, PHRASE)
+    }
+}
+
+fn expected_account(index: u32) -> Account {
+    let mnemonic: Mnemonic = PHRASE.parse().unwrap();
+    let key = Slip10Node::nano_accounts(&mnemonic.to_seed("some password")).account_key(index);
+    PublicKey::try_from(&key).unwrap().into()
+}
+
+#[test]
+fn wallet_mnemonic_restore() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_restore(restore_args(wallet))
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(
+        result.last_restored_account.encode_account(),
+        "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d"
+    );
+    assert_eq!(result.restored_count, 1);
+    assert_eq!(node.wallets.get_mnemonic(wallet).unwrap().phrase(), PHRASE);
+
+    server.abort();
+}
+
+#[test]
+fn accounts_created_after_restore_follow_bip44_path() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_restore(restore_args(wallet))
+            .await
+            .unwrap()
+    });
+
+    let account = node.wallets.deterministic_insert2(&wallet, false).unwrap();
+    assert_eq!(Account::from(account), expected_account(1));
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_restore_with_count() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_restore(WalletMnemonicRestoreArgs {
+                count: Some(3),
+                ..restore_args(wallet)
+            })
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(result.last_restored_account, expected_account(3));
+    assert_eq!(result.restored_count, 4);
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_restore_fails_with_invalid_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_restore(WalletMnemonicRestoreArgs::new(
+                wallet,
+                PHRASE.replace("occur", "edge"),
+            ))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid mnemonic\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_restore_fails_when_locked() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let wallet = WalletId::zero();
+    node.wallets.create(wallet);
+    node.wallets.lock(&wallet).unwrap();
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_restore(restore_args(wallet))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet is locked\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_restore_fails_without_rpc_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_mnemonic_restore(restore_args(WalletId::zero()))
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -13,10 +13,12 @@ mod iterator;
 mod lmdb_config;
 mod lmdb_env;
 mod online_weight_store;
+mod peer_ban_store;
 mod peer_store;
 mod pending_store;
 mod pruned_store;
 mod rep_weight_store;
+mod representative_store;
 mod store;
 mod version_store;
 mod wallet_store;
@@ -30,10 +32,12 @@ pub use iterator::{BinaryDbIterator, LmdbIterator, LmdbIteratorImpl};
 pub use lmdb_config::{LmdbConfig, SyncStrategy};
 pub use lmdb_env::*;
 pub use online_weight_store::LmdbOnlineWeightStore;
+pub use peer_ban_store::*;
 pub use peer_store::*;
 pub use pending_store::{ConfiguredPendingDatabaseBuilder, LmdbPendingStore};
 pub use pruned_store::{ConfiguredPrunedDatabaseBuilder, LmdbPrunedStore};
 pub use rep_weight_store::*;
+pub use representative_store::*;
 use rsnano_nullable_lmdb::{
     InactiveTransaction, LmdbDatabase, LmdbEnvironment, RoCursor, RoTransaction, RwTransaction,
 };
@@ -459,6 +463,8 @@ pub const PRUNED_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(5);
 pub const REP_WEIGHT_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(6);
 pub const CONFIRMATION_HEIGHT_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(7);
 pub const PEERS_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(8);
+pub const REPRESENTATIVES_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(9);
+pub const PEER_BANS_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(10);
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,224 @@
+use crate::{
+    iterator::LmdbIterator, LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction,
+    PEER_BANS_TEST_DATABASE,
+};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsnano_core::utils::{BufferWriter, Serialize};
+use rsnano_nullable_lmdb::ConfiguredDatabase;
+use rsnano_output_tracker::{OutputListenerMt, OutputTrackerMt};
+use std::{
+    net::Ipv6Addr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A subnet that was banned by the node operator
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StoredPeerBan {
+    pub address: Ipv6Addr,
+    pub prefix_len: u8,
+    /// None if the ban never expires
+    pub until: Option<SystemTime>,
+}
+
+impl StoredPeerBan {
+    fn key(address: &Ipv6Addr, prefix_len: u8) -> [u8; 17] {
+        let mut key = [0; 17];
+        key[..16].copy_from_slice(&address.octets());
+        key[16] = prefix_len;
+        key
+    }
+
+    fn value(&self) -> [u8; 8] {
+        // 0 is used for bans that never expire
+        let millis = match self.until {
+            Some(until) => (until
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64)
+                .max(1),
+            None => 0,
+        };
+        millis.to_be_bytes()
+    }
+
+    fn from_bytes(key: &[u8], value: &[u8]) -> Option<Self> {
+        let key: [u8; 17] = key.try_into().ok()?;
+        let millis = u64::from_be_bytes(value.try_into().ok()?);
+        let address: [u8; 16] = key[..16].try_into().ok()?;
+        Some(Self {
+            address: address.into(),
+            prefix_len: key[16],
+            until: (millis != 0).then(|| UNIX_EPOCH + Duration::from_millis(millis)),
+        })
+    }
+}
+
+/// Subnets banned by the node operator, so that bans survive a restart
+/// IPv6 address + prefix length -> ban end in milliseconds since epoch (0 = never)
+pub struct LmdbPeerBanStore {
+    database: LmdbDatabase,
+    put_listener: OutputListenerMt<StoredPeerBan>,
+    delete_listener: OutputListenerMt<(Ipv6Addr, u8)>,
+}
+
+impl LmdbPeerBanStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("peer_bans"), DatabaseFlags::empty())?;
+        Ok(Self {
+            database,
+            put_listener: OutputListenerMt::new(),
+            delete_listener: OutputListenerMt::new(),
+        })
+    }
+
+    pub fn track_puts(&self) -> Arc<OutputTrackerMt<StoredPeerBan>> {
+        self.put_listener.track()
+    }
+
+    pub fn track_deletions(&self) -> Arc<OutputTrackerMt<(Ipv6Addr, u8)>> {
+        self.delete_listener.track()
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn put(&self, txn: &mut LmdbWriteTransaction, ban: &StoredPeerBan) {
+        self.put_listener.emit(*ban);
+        txn.put(
+            self.database,
+            &StoredPeerBan::key(&ban.address, ban.prefix_len),
+            &ban.value(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, address: &Ipv6Addr, prefix_len: u8) {
+        self.delete_listener.emit((*address, prefix_len));
+        txn.delete(
+            self.database,
+            &StoredPeerBan::key(address, prefix_len),
+            None,
+        )
+        .unwrap();
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    pub fn iter<'a>(&self, txn: &'a dyn Transaction) -> impl Iterator<Item = StoredPeerBan> + 'a {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read peer ban database");
+        LmdbIterator::new(cursor, |k, v| {
+            (
+                SubnetBytes(k.try_into().unwrap()),
+                StoredPeerBan::from_bytes(k, v).unwrap(),
+            )
+        })
+        .map(|(_, ban)| ban)
+    }
+}
+
+struct SubnetBytes([u8; 17]);
+
+impl Serialize for SubnetBytes {
+    fn serialize(&self, stream: &mut dyn BufferWriter) {
+        stream.write_bytes_safe(&self.0)
+    }
+}
+
+pub struct ConfiguredPeerBansDatabaseBuilder {
+    database: ConfiguredDatabase,
+}
+
+impl ConfiguredPeerBansDatabaseBuilder {
+    pub fn new() -> Self {
+        Self {
+            database: ConfiguredDatabase::new(PEER_BANS_TEST_DATABASE, "peer_bans"),
+        }
+    }
+
+    pub fn ban(mut self, ban: &StoredPeerBan) -> Self {
+        self.database.entries.insert(
+            StoredPeerBan::key(&ban.address, ban.prefix_len).to_vec(),
+            ban.value().to_vec(),
+        );
+        self
+    }
+
+    pub fn build(self) -> ConfiguredDatabase {
+        self.database
+    }
+}
+
+impl Default for ConfiguredPeerBansDatabaseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_store() {
+        let fixture = Fixture::new(Vec::new());
+        let txn = fixture.env.tx_begin_read();
+        assert_eq!(fixture.store.count(&txn), 0);
+        assert_eq!(fixture.store.iter(&txn).next(), None);
+    }
+
+    #[test]
+    fn load_stored_bans() {
+        let temporary = StoredPeerBan {
+            address: Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x0a00, 0),
+            prefix_len: 120,
+            until: Some(UNIX_EPOCH + Duration::from_secs(1261440000)),
+        };
+        let permanent = StoredPeerBan {
+            address: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0),
+            prefix_len: 32,
+            until: None,
+        };
+        let fixture = Fixture::new(vec![temporary, permanent]);
+        let txn = fixture.env.tx_begin_read();
+
+        let mut bans: Vec<_> = fixture.store.iter(&txn).collect();
+        bans.sort_by_key(|b| b.prefix_len);
+        assert_eq!(bans, vec![permanent, temporary]);
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbPeerBanStore,
+    }
+
+    impl Fixture {
+        fn new(bans: Vec<StoredPeerBan>) -> Self {
+            let mut env =
+                LmdbEnv::new_null_with().database("peer_bans", LmdbDatabase::new_null(42));
+            for ban in bans {
+                env = env.entry(
+                    &StoredPeerBan::key(&ban.address, ban.prefix_len),
+                    &ban.value(),
+                );
+            }
+            let env = Arc::new(env.build().build());
+            Self {
+                env: env.clone(),
+                store: LmdbPeerBanStore::new(env).unwrap(),
+            }
+        }
+    }
+}
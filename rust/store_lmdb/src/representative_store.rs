@@ -0,0 +1,272 @@
+use crate::{
+    iterator::LmdbIterator, EndpointBytes, LmdbDatabase, LmdbEnv, LmdbWriteTransaction, TimeBytes,
+    Transaction, REPRESENTATIVES_TEST_DATABASE,
+};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsnano_core::{Amount, PublicKey};
+use rsnano_nullable_lmdb::ConfiguredDatabase;
+use rsnano_output_tracker::{OutputListenerMt, OutputTrackerMt};
+use std::{net::SocketAddrV6, sync::Arc, time::SystemTime};
+
+/// A representative that was found by the rep crawler
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StoredRepresentative {
+    /// Node ID of the node that voted for this representative
+    pub node_id: PublicKey,
+    /// Peering address of that node
+    pub endpoint: SocketAddrV6,
+    pub last_vote: SystemTime,
+    pub weight: Amount,
+}
+
+impl StoredRepresentative {
+    pub const SERIALIZED_SIZE: usize = 32 + 18 + 8 + 16;
+
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut bytes = [0; Self::SERIALIZED_SIZE];
+        bytes[..32].copy_from_slice(self.node_id.as_bytes());
+        bytes[32..50].copy_from_slice(&EndpointBytes::from(self.endpoint));
+        bytes[50..58].copy_from_slice(&TimeBytes::from(self.last_vote));
+        bytes[58..].copy_from_slice(&self.weight.to_be_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::SERIALIZED_SIZE {
+            return None;
+        }
+        Some(Self {
+            node_id: PublicKey::from_slice(&bytes[..32])?,
+            endpoint: EndpointBytes::try_from(&bytes[32..50]).ok()?.into(),
+            last_vote: TimeBytes::try_from(&bytes[50..58]).ok()?.into(),
+            weight: Amount::from_be_bytes(bytes[58..].try_into().ok()?),
+        })
+    }
+}
+
+/// Representatives that were found by the rep crawler, so that the node
+/// knows where to find them after a restart
+/// nano::account -> node_id, endpoint, last vote time, weight
+pub struct LmdbRepresentativeStore {
+    database: LmdbDatabase,
+    put_listener: OutputListenerMt<(PublicKey, StoredRepresentative)>,
+    delete_listener: OutputListenerMt<PublicKey>,
+}
+
+impl LmdbRepresentativeStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("representatives"), DatabaseFlags::empty())?;
+        Ok(Self {
+            database,
+            put_listener: OutputListenerMt::new(),
+            delete_listener: OutputListenerMt::new(),
+        })
+    }
+
+    pub fn track_puts(&self) -> Arc<OutputTrackerMt<(PublicKey, StoredRepresentative)>> {
+        self.put_listener.track()
+    }
+
+    pub fn track_deletions(&self) -> Arc<OutputTrackerMt<PublicKey>> {
+        self.delete_listener.track()
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        account: &PublicKey,
+        representative: &StoredRepresentative,
+    ) {
+        self.put_listener.emit((*account, representative.clone()));
+        txn.put(
+            self.database,
+            account.as_bytes(),
+            &representative.to_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, account: &PublicKey) -> Option<StoredRepresentative> {
+        match txn.get(self.database, account.as_bytes()) {
+            Ok(bytes) => StoredRepresentative::from_bytes(bytes),
+            Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not load representative: {:?}", e),
+        }
+    }
+
+    pub fn exists(&self, txn: &dyn Transaction, account: &PublicKey) -> bool {
+        txn.exists(self.database, account.as_bytes())
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, account: &PublicKey) {
+        self.delete_listener.emit(*account);
+        txn.delete(self.database, account.as_bytes(), None).unwrap();
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+
+    pub fn iter<'a>(
+        &self,
+        txn: &'a dyn Transaction,
+    ) -> impl Iterator<Item = (PublicKey, StoredRepresentative)> + 'a {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read representative store database");
+        LmdbIterator::new(cursor, |k, v| {
+            (
+                PublicKey::from_slice(k).unwrap(),
+                StoredRepresentative::from_bytes(v).unwrap(),
+            )
+        })
+    }
+}
+
+pub struct ConfiguredRepresentativesDatabaseBuilder {
+    database: ConfiguredDatabase,
+}
+
+impl ConfiguredRepresentativesDatabaseBuilder {
+    pub fn new() -> Self {
+        Self {
+            database: ConfiguredDatabase::new(REPRESENTATIVES_TEST_DATABASE, "representatives"),
+        }
+    }
+
+    pub fn representative(
+        mut self,
+        account: &PublicKey,
+        representative: &StoredRepresentative,
+    ) -> Self {
+        self.database.entries.insert(
+            account.as_bytes().to_vec(),
+            representative.to_bytes().to_vec(),
+        );
+        self
+    }
+
+    pub fn build(self) -> ConfiguredDatabase {
+        self.database
+    }
+}
+
+impl Default for ConfiguredRepresentativesDatabaseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PutEvent;
+    use std::{
+        net::Ipv6Addr,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    #[test]
+    fn empty_store() {
+        let fixture = Fixture::new();
+        let txn = fixture.env.tx_begin_read();
+        assert_eq!(fixture.store.count(&txn), 0);
+        assert_eq!(fixture.store.get(&txn, &PublicKey::from(1)), None);
+        assert_eq!(fixture.store.iter(&txn).next(), None);
+    }
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let account = PublicKey::from(1);
+        let representative = test_representative();
+
+        fixture.store.put(&mut txn, &account, &representative);
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: LmdbDatabase::new_null(42),
+                key: account.as_bytes().to_vec(),
+                value: representative.to_bytes().to_vec(),
+                flags: WriteFlags::empty()
+            }]
+        );
+    }
+
+    #[test]
+    fn get_stored_representative() {
+        let representative = test_representative();
+        let fixture = Fixture::with_stored_data(vec![(PublicKey::from(1), representative.clone())]);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.get(&txn, &PublicKey::from(1)),
+            Some(representative.clone())
+        );
+        assert_eq!(
+            fixture.store.iter(&txn).collect::<Vec<_>>(),
+            vec![(PublicKey::from(1), representative)]
+        );
+        assert!(fixture.store.exists(&txn, &PublicKey::from(1)));
+        assert!(!fixture.store.exists(&txn, &PublicKey::from(2)));
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let representative = test_representative();
+        assert_eq!(
+            StoredRepresentative::from_bytes(&representative.to_bytes()),
+            Some(representative)
+        );
+        assert_eq!(StoredRepresentative::from_bytes(&[1, 2, 3]), None);
+    }
+
+    fn test_representative() -> StoredRepresentative {
+        StoredRepresentative {
+            node_id: PublicKey::from(2),
+            endpoint: SocketAddrV6::new(Ipv6Addr::new(1, 2, 3, 4, 5, 6, 7, 8), 7075, 0, 0),
+            last_vote: UNIX_EPOCH + Duration::from_secs(1261440000),
+            weight: Amount::nano(1000),
+        }
+    }
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbRepresentativeStore,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_stored_data(Vec::new())
+        }
+
+        fn with_stored_data(entries: Vec<(PublicKey, StoredRepresentative)>) -> Self {
+            let mut env =
+                LmdbEnv::new_null_with().database("representatives", LmdbDatabase::new_null(42));
+
+            for (account, representative) in entries {
+                env = env.entry(account.as_bytes(), &representative.to_bytes());
+            }
+
+            let env = Arc::new(env.build().build());
+            Self {
+                env: env.clone(),
+                store: LmdbRepresentativeStore::new(env).unwrap(),
+            }
+        }
+    }
+}
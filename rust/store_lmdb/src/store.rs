@@ -1,9 +1,9 @@
 use crate::{
     EnvOptions, LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbDatabase,
-    LmdbEnv, LmdbFinalVoteStore, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore,
-    LmdbPrunedStore, LmdbReadTransaction, LmdbRepWeightStore, LmdbVersionStore,
-    LmdbWriteTransaction, NullTransactionTracker, TransactionTracker, STORE_VERSION_CURRENT,
-    STORE_VERSION_MINIMUM,
+    LmdbEnv, LmdbFinalVoteStore, LmdbOnlineWeightStore, LmdbPeerBanStore, LmdbPeerStore,
+    LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction, LmdbRepWeightStore,
+    LmdbRepresentativeStore, LmdbVersionStore, LmdbWriteTransaction, NullTransactionTracker,
+    TransactionTracker, STORE_VERSION_CURRENT, STORE_VERSION_MINIMUM,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
 use lmdb_sys::{MDB_CP_COMPACT, MDB_SUCCESS};
@@ -60,6 +60,8 @@ pub struct LmdbStore {
     pub pruned: Arc<LmdbPrunedStore>,
     pub rep_weight: Arc<LmdbRepWeightStore>,
     pub peer: Arc<LmdbPeerStore>,
+    pub peer_ban: Arc<LmdbPeerBanStore>,
+    pub representative: Arc<LmdbRepresentativeStore>,
     pub confirmation_height: Arc<LmdbConfirmationHeightStore>,
     pub final_vote: Arc<LmdbFinalVoteStore>,
     pub version: Arc<LmdbVersionStore>,
@@ -142,6 +144,8 @@ impl LmdbStore {
             pruned: Arc::new(LmdbPrunedStore::new(env.clone())?),
             rep_weight: Arc::new(LmdbRepWeightStore::new(env.clone())?),
             peer: Arc::new(LmdbPeerStore::new(env.clone())?),
+            peer_ban: Arc::new(LmdbPeerBanStore::new(env.clone())?),
+            representative: Arc::new(LmdbRepresentativeStore::new(env.clone())?),
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone())?),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone())?),
             version: Arc::new(LmdbVersionStore::new(env.clone())?),
@@ -153,6 +157,57 @@ impl LmdbStore {
         copy_db(&self.env, destination)
     }
 
+    /// Copies all tables into a new database at `destination`. Everything is read
+    /// through `txn`, so the copy is consistent even while other threads keep writing
+    /// to the ledger. `between_batches` gets called after every `batch_size` copied
+    /// entries and aborts the copy when it returns false.
+    pub fn copy_db_consistent(
+        &self,
+        txn: &LmdbReadTransaction,
+        destination: &Path,
+        batch_size: usize,
+        mut between_batches: impl FnMut() -> bool,
+    ) -> anyhow::Result<()> {
+        let target_env = LmdbEnv::new(destination)?;
+        let mut rw_txn = target_env.tx_begin_write();
+        let mut copied = 0;
+        for (name, source) in self.tables() {
+            let target = unsafe { rw_txn.create_db(Some(name), DatabaseFlags::empty()) }?;
+            let mut cursor = txn.txn().open_ro_cursor(source)?;
+            for x in cursor.iter_start() {
+                let (k, v) = x?;
+                rw_txn.put(target, k, v, WriteFlags::APPEND)?;
+                copied += 1;
+                if copied % batch_size.max(1) == 0 {
+                    crate::Transaction::refresh(&mut rw_txn);
+                    if !between_batches() {
+                        bail!("copy cancelled");
+                    }
+                }
+            }
+        }
+        rw_txn.commit();
+        target_env.environment.sync(true)?;
+        Ok(())
+    }
+
+    fn tables(&self) -> [(&'static str, LmdbDatabase); 12] {
+        [
+            ("accounts", self.account.database()),
+            ("blocks", self.block.database()),
+            ("confirmation_height", self.confirmation_height.database()),
+            ("final_votes", self.final_vote.database()),
+            ("meta", self.version.db_handle()),
+            ("online_weight", self.online_weight.database()),
+            ("peer_bans", self.peer_ban.database()),
+            ("peers", self.peer.database()),
+            ("pending", self.pending.database()),
+            ("pruned", self.pruned.database()),
+            ("rep_weights", self.rep_weight.database()),
+            ("representatives", self.representative.database()),
+        ]
+    }
+
     pub fn rebuild_db(&self, txn: &mut LmdbWriteTransaction) -> anyhow::Result<()> {
         let tables = [
             self.account.database(),
@@ -395,6 +450,7 @@ fn backup_file_path(source_path: &Path) -> anyhow::Result<PathBuf> {
 mod tests {
     use super::*;
     use crate::TestDbFile;
+    use rsnano_core::{Amount, PublicKey};
 
     #[test]
     fn create_store() -> anyhow::Result<()> {
@@ -427,6 +483,52 @@ mod tests {
         assert_eq!(store.version.get(&txn), Some(STORE_VERSION_MINIMUM));
     }
 
+    #[test]
+    fn consistent_copy_ignores_writes_after_txn_start() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        let copy_file = TestDbFile::random();
+        let store = LmdbStore::open(&file.path).build()?;
+        let weight_a = (PublicKey::from(1), Amount::raw(100));
+        let weight_b = (PublicKey::from(2), Amount::raw(200));
+        store
+            .rep_weight
+            .put(&mut store.tx_begin_write(), weight_a.0, weight_a.1);
+
+        let txn = store.tx_begin_read();
+        store
+            .rep_weight
+            .put(&mut store.tx_begin_write(), weight_b.0, weight_b.1);
+        store.copy_db_consistent(&txn, &copy_file.path, 1, || true)?;
+        drop(txn);
+
+        let copy = LmdbStore::open(&copy_file.path).build()?;
+        let copy_txn = copy.tx_begin_read();
+        assert_eq!(
+            copy.rep_weight.get(&copy_txn, &weight_a.0),
+            Some(weight_a.1)
+        );
+        assert_eq!(copy.rep_weight.get(&copy_txn, &weight_b.0), None);
+        assert_eq!(copy.version.get(&copy_txn), Some(STORE_VERSION_CURRENT));
+        Ok(())
+    }
+
+    #[test]
+    fn consistent_copy_can_be_cancelled() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        let copy_file = TestDbFile::random();
+        let store = LmdbStore::open(&file.path).build()?;
+        let mut batches = 0;
+
+        let result = store.copy_db_consistent(&store.tx_begin_read(), &copy_file.path, 1, || {
+            batches += 1;
+            false
+        });
+
+        assert_eq!(result.unwrap_err().to_string(), "copy cancelled");
+        assert_eq!(batches, 1);
+        Ok(())
+    }
+
     fn assert_upgrade_fails(path: &Path, error_msg: &str) {
         match LmdbStore::open(path).build() {
             Ok(_) => panic!("store should not be created!"),
@@ -5,19 +5,18 @@ use anyhow::bail;
 use lmdb::{DatabaseFlags, WriteFlags};
 use rsnano_core::{
     deterministic_key,
+    hd_wallet::{Mnemonic, Slip10Node},
     utils::{
         BufferReader, BufferWriter, Deserialize, FixedSizeSerialize, MutStreamAdapter, Serialize,
         Stream, StreamExt,
     },
-    Account, KeyDerivationFunction, PublicKey, RawKey,
+    Account, KdfParams, KeyDerivationFunction, PublicKey, RawKey,
 };
-use std::io::Write;
 use std::{
-    fs::{set_permissions, File, Permissions},
-    os::unix::prelude::PermissionsExt,
     path::Path,
     sync::{Mutex, MutexGuard},
 };
+use tracing::warn;
 
 pub struct Fans {
     pub password: Fan,
@@ -74,12 +73,14 @@ impl Deserialize for WalletValue {
     }
 }
 
-#[derive(FromPrimitive)]
+#[derive(FromPrimitive, PartialEq, Eq, Debug)]
 pub enum KeyType {
     NotAType,
     Unknown,
     Adhoc,
     Deterministic,
+    /// Derived from the wallet mnemonic along `m/44'/165'/i'`
+    Bip44,
 }
 
 pub type WalletIterator<'txn> = BinaryDbIterator<'txn, PublicKey, WalletValue>;
@@ -91,7 +92,7 @@ pub struct LmdbWalletStore {
 }
 
 impl LmdbWalletStore {
-    pub const VERSION_CURRENT: u32 = 4;
+    pub const VERSION_CURRENT: u32 = 6;
     pub fn new(
         fanout: usize,
         kdf: KeyDerivationFunction,
@@ -110,6 +111,7 @@ impl LmdbWalletStore {
             store.version_put(txn, Self::VERSION_CURRENT);
             let salt = RawKey::random();
             store.entry_put_raw(txn, &Self::salt_special(), &WalletValue::new(salt, 0));
+            store.kdf_params_put(txn, &store.kdf.default_params());
             // Wallet key is a fixed random key that encrypts all entries
             let wallet_key = RawKey::random();
             let password = RawKey::zero();
@@ -141,6 +143,7 @@ impl LmdbWalletStore {
                 &Self::deterministic_index_special(),
                 &WalletValue::new(RawKey::zero(), 0),
             );
+            store.mnemonic_specials_init(txn);
         }
         {
             let key = store.entry_get_raw(txn, &Self::wallet_key_special()).key;
@@ -157,6 +160,21 @@ impl LmdbWalletStore {
         wallet: &Path,
         json: &str,
     ) -> anyhow::Result<Self> {
+        // Parse everything before writing, so that invalid json doesn't leave a half written store behind
+        let entries = Self::parse_json(json)?;
+        Self::new_from_entries(fanout, kdf, txn, wallet, entries)
+    }
+
+    /// Creates a wallet store from raw entries as returned by [`LmdbWalletStore::entries`]
+    pub fn new_from_entries(
+        fanout: usize,
+        kdf: KeyDerivationFunction,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &Path,
+        entries: Vec<(PublicKey, WalletValue)>,
+    ) -> anyhow::Result<Self> {
+        Self::validate_entries(&entries)?;
+
         let store = Self {
             db_handle: Mutex::new(None),
             fans: Mutex::new(Fans::new(fanout)),
@@ -170,26 +188,10 @@ impl LmdbWalletStore {
             Err(e) => panic!("unexpected wallet store error: {:?}", e),
         }
 
-        let json: serde_json::Value = serde_json::from_str(json)?;
-        if let serde_json::Value::Object(map) = json {
-            for (k, v) in map.iter() {
-                if let serde_json::Value::String(v_str) = v {
-                    let key = PublicKey::decode_hex(k)?;
-                    let value = RawKey::decode_hex(v_str)?;
-                    store.entry_put_raw(txn, &key, &WalletValue::new(value, 0));
-                } else {
-                    bail!("expected string value");
-                }
-            }
-        } else {
-            bail!("invalid json")
+        for (key, value) in entries {
+            store.entry_put_raw(txn, &key, &value);
         }
 
-        store.ensure_key_exists(txn, &Self::version_special())?;
-        store.ensure_key_exists(txn, &Self::wallet_key_special())?;
-        store.ensure_key_exists(txn, &Self::salt_special())?;
-        store.ensure_key_exists(txn, &Self::check_special())?;
-        store.ensure_key_exists(txn, &Self::representative_special())?;
         let mut guard = store.fans.lock().unwrap();
         guard.password.value_set(RawKey::zero());
         let key = store.entry_get_raw(txn, &Self::wallet_key_special()).key;
@@ -202,8 +204,43 @@ impl LmdbWalletStore {
         self.fans.lock().unwrap().password.value()
     }
 
-    fn ensure_key_exists(&self, txn: &dyn Transaction, key: &PublicKey) -> anyhow::Result<()> {
-        txn.get(self.db_handle(), key.as_bytes())?;
+    fn parse_json(json: &str) -> anyhow::Result<Vec<(PublicKey, WalletValue)>> {
+        let json: serde_json::Value = serde_json::from_str(json)?;
+        let serde_json::Value::Object(map) = json else {
+            bail!("invalid json")
+        };
+
+        let mut entries = Vec::with_capacity(map.len());
+        for (k, v) in map.iter() {
+            if let serde_json::Value::String(v_str) = v {
+                let key = PublicKey::decode_hex(k)?;
+                let value = RawKey::decode_hex(v_str)?;
+                entries.push((key, WalletValue::new(value, 0)));
+            } else {
+                bail!("expected string value");
+            }
+        }
+
+        Self::validate_entries(&entries)?;
+        Ok(entries)
+    }
+
+    /// Checks that the entries form a complete wallet before anything gets written
+    pub fn validate_entries(entries: &[(PublicKey, WalletValue)]) -> anyhow::Result<()> {
+        for special in [
+            Self::version_special(),
+            Self::wallet_key_special(),
+            Self::salt_special(),
+            Self::check_special(),
+            Self::representative_special(),
+        ] {
+            if !entries.iter().any(|(key, _)| *key == special) {
+                bail!("missing wallet entry {}", special);
+            }
+        }
+        if let Some((_, kdf)) = entries.iter().find(|(key, _)| *key == Self::kdf_special()) {
+            KdfParams::from_bytes(kdf.key.as_bytes())?;
+        }
         Ok(())
     }
 
@@ -242,10 +279,40 @@ impl LmdbWalletStore {
         PublicKey::from(6)
     }
 
-    pub fn special_count() -> PublicKey {
+    /// BIP39 entropy of the wallet mnemonic, padded to 32 bytes
+    pub fn mnemonic_special() -> PublicKey {
         PublicKey::from(7)
     }
 
+    /// Length of the BIP39 entropy in bytes, zero if the wallet has no mnemonic
+    pub fn mnemonic_length_special() -> PublicKey {
+        PublicKey::from(8)
+    }
+
+    /// Private key of the SLIP-0010 node `m/44'/165'`
+    pub fn bip44_key_special() -> PublicKey {
+        PublicKey::from(9)
+    }
+
+    /// Chain code of the SLIP-0010 node `m/44'/165'`
+    pub fn bip44_chain_code_special() -> PublicKey {
+        PublicKey::from(10)
+    }
+
+    /// Current key index for BIP44 keys
+    pub fn bip44_index_special() -> PublicKey {
+        PublicKey::from(11)
+    }
+
+    /// Algorithm and parameters of the password key derivation, missing in version 4 wallets
+    pub fn kdf_special() -> PublicKey {
+        PublicKey::from(12)
+    }
+
+    pub fn special_count() -> PublicKey {
+        PublicKey::from(13)
+    }
+
     pub fn initialize(&self, txn: &mut LmdbWriteTransaction, path: &Path) -> anyhow::Result<()> {
         let path_str = path
             .as_os_str()
@@ -321,6 +388,121 @@ impl LmdbWalletStore {
         let ciphertext = prv.encrypt(&password_l, &iv);
         self.entry_put_raw(txn, &Self::seed_special(), &WalletValue::new(ciphertext, 0));
         self.deterministic_clear(txn);
+        self.mnemonic_clear(txn);
+    }
+
+    pub fn has_mnemonic(&self, txn: &dyn Transaction) -> bool {
+        self.mnemonic_length(txn) != 0
+    }
+
+    pub fn mnemonic(&self, txn: &dyn Transaction) -> Option<Mnemonic> {
+        let length = self.mnemonic_length(txn);
+        if length == 0 {
+            return None;
+        }
+        let entropy = self.secret_special_get(txn, &Self::mnemonic_special());
+        Mnemonic::from_entropy(&entropy.as_bytes()[..length]).ok()
+    }
+
+    /// Replaces the deterministic keys of the wallet with keys derived from the mnemonic.
+    /// Only the mnemonic and the derived `m/44'/165'` node are stored, not the passphrase
+    pub fn set_mnemonic(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+    ) {
+        let mut entropy = [0; 32];
+        entropy[..mnemonic.entropy().len()].copy_from_slice(mnemonic.entropy());
+        self.secret_special_put(txn, &Self::mnemonic_special(), &RawKey::from_bytes(entropy));
+        self.entry_put_raw(
+            txn,
+            &Self::mnemonic_length_special(),
+            &WalletValue::new(RawKey::from(mnemonic.entropy().len() as u64), 0),
+        );
+
+        let accounts = Slip10Node::nano_accounts(&mnemonic.to_seed(passphrase));
+        self.secret_special_put(txn, &Self::bip44_key_special(), &accounts.key);
+        self.secret_special_put(txn, &Self::bip44_chain_code_special(), &accounts.chain_code);
+        self.deterministic_clear(txn);
+        self.bip44_clear(txn);
+    }
+
+    pub fn bip44_key(&self, txn: &dyn Transaction, index: u32) -> RawKey {
+        debug_assert!(self.valid_password(txn));
+        let accounts = Slip10Node {
+            key: self.secret_special_get(txn, &Self::bip44_key_special()),
+            chain_code: self.secret_special_get(txn, &Self::bip44_chain_code_special()),
+        };
+        accounts.account_key(index)
+    }
+
+    pub fn bip44_index_get(&self, txn: &dyn Transaction) -> u32 {
+        let value = self.entry_get_raw(txn, &Self::bip44_index_special());
+        value.key.number().low_u32()
+    }
+
+    pub fn bip44_index_set(&self, txn: &mut LmdbWriteTransaction, index: u32) {
+        let index = RawKey::from(index as u64);
+        let value = WalletValue::new(index, 0);
+        self.entry_put_raw(txn, &Self::bip44_index_special(), &value);
+    }
+
+    fn mnemonic_length(&self, txn: &dyn Transaction) -> usize {
+        let value = self.entry_get_raw(txn, &Self::mnemonic_length_special());
+        value.key.number().low_u32() as usize
+    }
+
+    /// Writes empty mnemonic and BIP44 specials where they are missing, so that an older
+    /// node doesn't mistake them for accounts
+    fn mnemonic_specials_init(&self, txn: &mut LmdbWriteTransaction) {
+        for special in [
+            Self::mnemonic_special(),
+            Self::mnemonic_length_special(),
+            Self::bip44_key_special(),
+            Self::bip44_chain_code_special(),
+            Self::bip44_index_special(),
+        ] {
+            if let Err(lmdb::Error::NotFound) = txn.get(self.db_handle(), special.as_bytes()) {
+                self.entry_put_raw(txn, &special, &WalletValue::new(RawKey::zero(), 0));
+            }
+        }
+    }
+
+    fn mnemonic_clear(&self, txn: &mut LmdbWriteTransaction) {
+        for special in [
+            Self::mnemonic_special(),
+            Self::mnemonic_length_special(),
+            Self::bip44_key_special(),
+            Self::bip44_chain_code_special(),
+        ] {
+            self.entry_put_raw(txn, &special, &WalletValue::new(RawKey::zero(), 0));
+        }
+        self.bip44_clear(txn);
+    }
+
+    /// Every secret special gets its own IV, because they are all encrypted with the wallet key
+    fn special_iv(&self, txn: &dyn Transaction, special: &PublicKey) -> [u8; 16] {
+        let mut iv = self.salt(txn).initialization_vector_high();
+        iv[0] ^= special.as_bytes()[31];
+        iv
+    }
+
+    fn secret_special_get(&self, txn: &dyn Transaction, special: &PublicKey) -> RawKey {
+        let value = self.entry_get_raw(txn, special);
+        let password = self.wallet_key(txn);
+        value.key.decrypt(&password, &self.special_iv(txn, special))
+    }
+
+    fn secret_special_put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        special: &PublicKey,
+        secret: &RawKey,
+    ) {
+        let password = self.wallet_key(txn);
+        let ciphertext = secret.encrypt(&password, &self.special_iv(txn, special));
+        self.entry_put_raw(txn, special, &WalletValue::new(ciphertext, 0));
     }
 
     pub fn deterministic_key(&self, txn: &dyn Transaction, index: u32) -> RawKey {
@@ -357,15 +539,33 @@ impl LmdbWalletStore {
         self.check(txn) == check
     }
 
-    pub fn derive_key(&self, txn: &dyn Transaction, password: &str) -> RawKey {
+    pub fn derive_key(&self, txn: &dyn Transaction, password: &str) -> anyhow::Result<RawKey> {
         let salt = self.salt(txn);
-        self.kdf.hash_password(password, salt.as_bytes())
+        Ok(self
+            .kdf_params(txn)?
+            .hash_password(password, salt.as_bytes()))
+    }
+
+    /// Fails if the stored parameters are corrupt. Falling back to the legacy parameters
+    /// would silently hash the password with a weaker KDF
+    pub fn kdf_params(&self, txn: &dyn Transaction) -> anyhow::Result<KdfParams> {
+        let value = self.entry_get_raw(txn, &Self::kdf_special());
+        if value.key.is_zero() {
+            Ok(self.kdf.legacy_params())
+        } else {
+            KdfParams::from_bytes(value.key.as_bytes())
+        }
+    }
+
+    fn kdf_params_put(&self, txn: &mut LmdbWriteTransaction, params: &KdfParams) {
+        let value = RawKey::from_bytes(params.to_bytes());
+        self.entry_put_raw(txn, &Self::kdf_special(), &WalletValue::new(value, 0));
     }
 
     pub fn rekey(&self, txn: &mut LmdbWriteTransaction, password: &str) -> anyhow::Result<()> {
         let mut guard = self.fans.lock().unwrap();
         if self.valid_password_locked(&guard, txn) {
-            let password_new = self.derive_key(txn, password);
+            let password_new = self.derive_key(txn, password)?;
             let wallet_key = self.wallet_key_locked(&guard, txn);
             guard.password.value_set(password_new);
             let iv = self.salt(txn).initialization_vector_low();
@@ -434,27 +634,34 @@ impl LmdbWalletStore {
             KeyType::Adhoc
         } else if (number >> 32).low_u32() == 1 {
             KeyType::Deterministic
+        } else if (number >> 32).low_u32() == 2 {
+            KeyType::Bip44
         } else {
             KeyType::Unknown
         }
     }
 
     pub fn deterministic_clear(&self, txn: &mut LmdbWriteTransaction) {
-        {
-            let mut it = self.begin(txn);
-            while let Some((&account, value)) = it.current() {
-                match Self::key_type(value) {
-                    KeyType::Deterministic => {
-                        drop(it);
-                        self.erase(txn, &account);
-                        it = self.begin_at_key(txn, &account);
-                    }
-                    _ => it.next(),
-                }
+        self.erase_key_type(txn, KeyType::Deterministic);
+        self.deterministic_index_set(txn, 0);
+    }
+
+    pub fn bip44_clear(&self, txn: &mut LmdbWriteTransaction) {
+        self.erase_key_type(txn, KeyType::Bip44);
+        self.bip44_index_set(txn, 0);
+    }
+
+    fn erase_key_type(&self, txn: &mut LmdbWriteTransaction, key_type: KeyType) {
+        let mut it = self.begin(txn);
+        while let Some((&account, value)) = it.current() {
+            if Self::key_type(value) == key_type {
+                drop(it);
+                self.erase(txn, &account);
+                it = self.begin_at_key(txn, &account);
+            } else {
+                it.next();
             }
         }
-
-        self.deterministic_index_set(txn, 0);
     }
 
     pub fn valid_public_key(&self, key: &PublicKey) -> bool {
@@ -494,26 +701,93 @@ impl LmdbWalletStore {
         result
     }
 
+    /// Inserts the next unused BIP44 account. Returns `None` when all indexes are used up
+    pub fn bip44_insert(&self, txn: &mut LmdbWriteTransaction) -> Option<PublicKey> {
+        let mut index = self.bip44_index_get(txn);
+        loop {
+            if index > Slip10Node::MAX_INDEX {
+                return None;
+            }
+            let result = PublicKey::try_from(&self.bip44_key(txn, index)).unwrap();
+            if !self.exists(txn, &result) {
+                self.entry_put_raw(
+                    txn,
+                    &result,
+                    &WalletValue::new(Self::bip44_marker(index), 0),
+                );
+                self.bip44_index_set(txn, index + 1);
+                return Some(result);
+            }
+            index += 1;
+        }
+    }
+
+    /// Inserts the BIP44 account with the given index. Returns `None` if the index is out of range
+    pub fn bip44_insert_at(&self, txn: &mut LmdbWriteTransaction, index: u32) -> Option<PublicKey> {
+        if index > Slip10Node::MAX_INDEX {
+            return None;
+        }
+        let result = PublicKey::try_from(&self.bip44_key(txn, index)).unwrap();
+        self.entry_put_raw(
+            txn,
+            &result,
+            &WalletValue::new(Self::bip44_marker(index), 0),
+        );
+        Some(result)
+    }
+
+    fn bip44_marker(index: u32) -> RawKey {
+        ((2u64 << 32) | index as u64).into()
+    }
+
     pub fn version(&self, txn: &dyn Transaction) -> u32 {
         let value = self.entry_get_raw(txn, &Self::version_special());
         value.key.as_bytes()[31] as u32
     }
 
-    pub fn attempt_password(&self, txn: &dyn Transaction, password: &str) -> bool {
+    /// Unlocks the wallet and upgrades it to the current version. Version 4 wallets are
+    /// re-keyed with the default KDF parameters, version 5 wallets get the mnemonic specials
+    pub fn attempt_password(&self, txn: &mut LmdbWriteTransaction, password: &str) -> bool {
         let is_valid = {
             let mut guard = self.fans.lock().unwrap();
-            let password_key = self.derive_key(txn, password);
+            let password_key = match self.derive_key(txn, password) {
+                Ok(key) => key,
+                Err(e) => {
+                    warn!("Cannot unlock wallet: {:?}", e);
+                    return false;
+                }
+            };
             guard.password.value_set(password_key);
             self.valid_password_locked(&guard, txn)
         };
 
-        if is_valid && self.version(txn) != 4 {
-            panic!("invalid wallet store version!");
+        if is_valid {
+            match self.version(txn) {
+                4 => {
+                    self.upgrade_v4_v5(txn, password);
+                    self.upgrade_v5_v6(txn);
+                }
+                5 => self.upgrade_v5_v6(txn),
+                Self::VERSION_CURRENT => {}
+                _ => panic!("invalid wallet store version!"),
+            }
         }
 
         is_valid
     }
 
+    fn upgrade_v4_v5(&self, txn: &mut LmdbWriteTransaction, password: &str) {
+        self.kdf_params_put(txn, &self.kdf.default_params());
+        self.rekey(txn, password)
+            .expect("password was validated before upgrade");
+        self.version_put(txn, 5);
+    }
+
+    fn upgrade_v5_v6(&self, txn: &mut LmdbWriteTransaction) {
+        self.mnemonic_specials_init(txn);
+        self.version_put(txn, 6);
+    }
+
     pub fn lock(&self) {
         self.fans.lock().unwrap().password.value_set(RawKey::zero());
     }
@@ -580,6 +854,10 @@ impl LmdbWalletStore {
                 let index = value.key.number().low_u32();
                 self.deterministic_key(txn, index)
             }
+            KeyType::Bip44 => {
+                let index = value.key.number().low_u32();
+                self.bip44_key(txn, index)
+            }
             KeyType::Adhoc => {
                 // Ad-hoc keys
                 let password = self.wallet_key(txn);
@@ -597,6 +875,23 @@ impl LmdbWalletStore {
         Ok(prv)
     }
 
+    /// All raw entries of the wallet including the special entries and the work cache.
+    /// Keys stay encrypted with the wallet key
+    pub fn entries(&self, txn: &dyn Transaction) -> Vec<(PublicKey, WalletValue)> {
+        let mut result = Vec::new();
+        let mut it = LmdbIteratorImpl::new_iterator::<PublicKey, WalletValue>(
+            txn,
+            self.db_handle(),
+            None,
+            true,
+        );
+        while let Some((k, v)) = it.current() {
+            result.push((*k, WalletValue::new(v.key, v.work)));
+            it.next();
+        }
+        result
+    }
+
     pub fn serialize_json(&self, txn: &dyn Transaction) -> String {
         let mut map = serde_json::Map::new();
         let mut it = LmdbIteratorImpl::new_iterator::<Account, WalletValue>(
@@ -617,13 +912,6 @@ impl LmdbWalletStore {
         serde_json::Value::Object(map).to_string()
     }
 
-    pub fn write_backup(&self, txn: &dyn Transaction, path: &Path) -> anyhow::Result<()> {
-        let mut file = File::create(path)?;
-        set_permissions(path, Permissions::from_mode(0o600))?;
-        write!(file, "{}", self.serialize_json(txn))?;
-        Ok(())
-    }
-
     pub fn move_keys(
         &self,
         txn: &mut LmdbWriteTransaction,
@@ -717,3 +1005,141 @@ impl LmdbWalletStore {
         self.db_handle.lock().unwrap().is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestLmdbEnv;
+
+    #[test]
+    fn new_wallet_uses_default_kdf() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let kdf = KeyDerivationFunction::new(8);
+        let store = create_store(&mut txn, &kdf);
+
+        assert_eq!(store.version(&txn), LmdbWalletStore::VERSION_CURRENT);
+        assert_eq!(store.kdf_params(&txn).unwrap(), KdfParams::RECOMMENDED);
+    }
+
+    #[test]
+    fn upgrade_v4_wallet_on_unlock() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let kdf = KeyDerivationFunction::new(8);
+        let store = create_store(&mut txn, &kdf);
+        store.erase(&mut txn, &LmdbWalletStore::kdf_special());
+        store.version_put(&mut txn, 4);
+        store.rekey(&mut txn, "password").unwrap();
+        let seed = store.seed(&txn);
+        store.lock();
+
+        assert!(!store.attempt_password(&mut txn, "wrong"));
+        assert_eq!(store.version(&txn), 4);
+        assert!(store.attempt_password(&mut txn, "password"));
+
+        assert_eq!(store.version(&txn), LmdbWalletStore::VERSION_CURRENT);
+        assert_eq!(store.kdf_params(&txn).unwrap(), KdfParams::RECOMMENDED);
+        assert_eq!(store.seed(&txn), seed);
+        store.lock();
+        assert!(store.attempt_password(&mut txn, "password"));
+        assert_eq!(store.seed(&txn), seed);
+    }
+
+    #[test]
+    fn dont_unlock_wallet_with_corrupt_kdf() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = create_store(&mut txn, &KeyDerivationFunction::new(8));
+        store.rekey(&mut txn, "password").unwrap();
+        store.lock();
+        store.entry_put_raw(
+            &mut txn,
+            &LmdbWalletStore::kdf_special(),
+            &WalletValue::new(RawKey::from(1), 0),
+        );
+
+        assert!(store.kdf_params(&txn).is_err());
+        assert!(store.derive_key(&txn, "password").is_err());
+        assert!(!store.attempt_password(&mut txn, "password"));
+        assert!(store.rekey(&mut txn, "other").is_err());
+    }
+
+    #[test]
+    fn upgrade_v5_wallet_on_unlock() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = create_store(&mut txn, &KeyDerivationFunction::new(8));
+        let mnemonic_specials = [
+            LmdbWalletStore::mnemonic_special(),
+            LmdbWalletStore::mnemonic_length_special(),
+            LmdbWalletStore::bip44_key_special(),
+            LmdbWalletStore::bip44_chain_code_special(),
+            LmdbWalletStore::bip44_index_special(),
+        ];
+        for special in &mnemonic_specials {
+            store.erase(&mut txn, special);
+        }
+        store.version_put(&mut txn, 5);
+        store.rekey(&mut txn, "password").unwrap();
+        store.lock();
+
+        assert!(store.attempt_password(&mut txn, "password"));
+
+        assert_eq!(store.version(&txn), LmdbWalletStore::VERSION_CURRENT);
+        for special in &mnemonic_specials {
+            assert!(txn.get(store.db_handle(), special.as_bytes()).is_ok());
+        }
+        assert_eq!(store.mnemonic(&txn), None);
+        assert_eq!(store.bip44_index_get(&txn), 0);
+    }
+
+    #[test]
+    fn reject_json_with_invalid_kdf() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = create_store(&mut txn, &KeyDerivationFunction::new(8));
+        let json = store.serialize_json(&txn);
+        let kdf_key = LmdbWalletStore::kdf_special().encode_hex();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value[kdf_key.as_str()] = serde_json::Value::String(RawKey::from(1).encode_hex());
+
+        assert!(LmdbWalletStore::parse_json(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn reject_bip44_index_out_of_range() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = create_store(&mut txn, &KeyDerivationFunction::new(8));
+        store.set_mnemonic(&mut txn, &Mnemonic::random(24).unwrap(), "");
+
+        assert!(store
+            .bip44_insert_at(&mut txn, Slip10Node::MAX_INDEX)
+            .is_some());
+        assert_eq!(
+            store.bip44_insert_at(&mut txn, Slip10Node::MAX_INDEX + 1),
+            None
+        );
+    }
+
+    #[test]
+    fn bip44_insert_stops_at_last_index() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = create_store(&mut txn, &KeyDerivationFunction::new(8));
+        store.set_mnemonic(&mut txn, &Mnemonic::random(24).unwrap(), "");
+        store.bip44_index_set(&mut txn, Slip10Node::MAX_INDEX);
+
+        assert!(store.bip44_insert(&mut txn).is_some());
+        assert_eq!(store.bip44_index_get(&txn), Slip10Node::MAX_INDEX + 1);
+        assert_eq!(store.bip44_insert(&mut txn), None);
+    }
+
+    fn create_store(
+        txn: &mut LmdbWriteTransaction,
+        kdf: &KeyDerivationFunction,
+    ) -> LmdbWalletStore {
+        LmdbWalletStore::new(1, kdf.clone(), txn, &PublicKey::from(1), Path::new("0")).unwrap()
+    }
+}
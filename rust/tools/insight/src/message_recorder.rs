@@ -3,12 +3,21 @@ use crate::{
     message_rate_calculator::{MessageRates, MessageRatesCalculator},
 };
 use chrono::Utc;
+use rsnano_messages::ProtocolInfo;
 use rsnano_network::ChannelDirection;
-use rsnano_node::NodeCallbacks;
+use rsnano_node::{
+    transport::{CapturedMessage, MessageCaptureWriter},
+    NodeCallbacks,
+};
 use rsnano_nullable_clock::{SteadyClock, Timestamp};
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, RwLock,
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
 };
 
 pub(crate) struct MessageRecorder {
@@ -16,6 +25,11 @@ pub(crate) struct MessageRecorder {
     rate_calc: RwLock<MessageRatesCalculator>,
     messages: Arc<RwLock<MessageCollection>>,
     is_recording: AtomicBool,
+    capture_file: Mutex<Option<MessageCaptureWriter<BufWriter<File>>>>,
+    capture_error: Mutex<Option<String>>,
+    /// Protocol of the running node. It gets written with every captured message,
+    /// so that the capture can be replayed by a node of the same network
+    protocol_info: RwLock<ProtocolInfo>,
 }
 
 impl MessageRecorder {
@@ -25,6 +39,9 @@ impl MessageRecorder {
             rate_calc: RwLock::new(Default::default()),
             messages,
             is_recording: AtomicBool::new(false),
+            capture_file: Mutex::new(None),
+            capture_error: Mutex::new(None),
+            protocol_info: RwLock::new(ProtocolInfo::default()),
         }
     }
 
@@ -40,6 +57,45 @@ impl MessageRecorder {
         self.is_recording.store(false, Ordering::SeqCst);
     }
 
+    pub fn is_capturing_to_file(&self) -> bool {
+        self.capture_file.lock().unwrap().is_some()
+    }
+
+    pub fn set_protocol_info(&self, protocol_info: ProtocolInfo) {
+        *self.protocol_info.write().unwrap() = protocol_info;
+    }
+
+    /// The reason why the last file capture could not be started or was stopped
+    pub fn capture_error(&self) -> Option<String> {
+        self.capture_error.lock().unwrap().clone()
+    }
+
+    /// Writes all following messages to a capture file, which can be replayed with `rsnano_node node replay-capture`
+    pub fn start_file_capture(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let result =
+            File::create(path).and_then(|file| MessageCaptureWriter::new(BufWriter::new(file)));
+        match result {
+            Ok(writer) => {
+                *self.capture_file.lock().unwrap() = Some(writer);
+                *self.capture_error.lock().unwrap() = None;
+                Ok(())
+            }
+            Err(e) => {
+                *self.capture_error.lock().unwrap() =
+                    Some(format!("Could not write to {:?}: {}", path, e));
+                Err(e)
+            }
+        }
+    }
+
+    pub fn stop_file_capture(&self) -> std::io::Result<()> {
+        match self.capture_file.lock().unwrap().take() {
+            Some(mut writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
     pub fn clear(&self) {
         self.messages.write().unwrap().clear();
     }
@@ -50,11 +106,31 @@ impl MessageRecorder {
             rates.add(&message, now, &self.rates);
         }
 
+        self.write_to_capture_file(&message);
+
         if self.is_recording() {
             let mut messages = self.messages.write().unwrap();
             messages.add(message);
         }
     }
+
+    fn write_to_capture_file(&self, message: &RecordedMessage) {
+        let mut capture_file = self.capture_file.lock().unwrap();
+        if let Some(writer) = capture_file.as_mut() {
+            let captured = CapturedMessage::new(
+                message.date.timestamp_millis(),
+                message.channel_id,
+                message.direction,
+                &message.message,
+                *self.protocol_info.read().unwrap(),
+            );
+            if let Err(e) = writer.write(&captured) {
+                // Stop capturing, for example when the disk is full
+                *capture_file = None;
+                *self.capture_error.lock().unwrap() = Some(format!("Capture file stopped: {}", e));
+            }
+        }
+    }
 }
 
 pub(crate) fn make_node_callbacks(
@@ -2,7 +2,8 @@ use crate::{
     message_recorder::{make_node_callbacks, MessageRecorder},
     node_runner::{NodeRunner, NodeState},
 };
-use rsnano_node::Node;
+use rsnano_core::Networks;
+use rsnano_node::{config::NetworkConstants, Node};
 use rsnano_nullable_clock::SteadyClock;
 use std::sync::Arc;
 
@@ -33,15 +34,22 @@ impl NodeRunnerViewModel {
     }
 
     pub(crate) fn start_live_node(&mut self) {
+        self.set_protocol_info(Networks::NanoLiveNetwork);
         let callbacks = make_node_callbacks(self.msg_recorder.clone(), self.clock.clone());
         self.node_runner.start_live_node(callbacks);
     }
 
     pub(crate) fn start_beta_node(&mut self) {
+        self.set_protocol_info(Networks::NanoBetaNetwork);
         let callbacks = make_node_callbacks(self.msg_recorder.clone(), self.clock.clone());
         self.node_runner.start_beta_node(callbacks);
     }
 
+    fn set_protocol_info(&self, network: Networks) {
+        self.msg_recorder
+            .set_protocol_info(NetworkConstants::for_network(network).protocol_info());
+    }
+
     pub(crate) fn stop_node(&mut self) {
         self.node_runner.stop();
     }
@@ -1,5 +1,6 @@
 use crate::message_recorder::MessageRecorder;
-use eframe::egui::Ui;
+use chrono::Utc;
+use eframe::egui::{Color32, Ui};
 
 pub(crate) struct MessageRecorderControlsView<'a> {
     recorder: &'a MessageRecorder,
@@ -12,6 +13,7 @@ impl<'a> MessageRecorderControlsView<'a> {
 
     pub fn show(&self, ui: &mut Ui) {
         self.capture_check_box(ui);
+        self.capture_file_check_box(ui);
         self.clear_button(ui);
     }
 
@@ -25,6 +27,22 @@ impl<'a> MessageRecorderControlsView<'a> {
         }
     }
 
+    fn capture_file_check_box(&self, ui: &mut Ui) {
+        let was_checked = self.recorder.is_capturing_to_file();
+        let mut checked = was_checked;
+        ui.checkbox(&mut checked, "write to file");
+        if checked && !was_checked {
+            let file_name = format!("messages_{}.rsncap", Utc::now().format("%Y%m%d_%H%M%S"));
+            // The error is shown below via `capture_error`
+            let _ = self.recorder.start_file_capture(file_name);
+        } else if !checked && was_checked {
+            let _ = self.recorder.stop_file_capture();
+        }
+        if let Some(error) = self.recorder.capture_error() {
+            ui.colored_label(Color32::RED, error);
+        }
+    }
+
     fn clear_button(&self, ui: &mut Ui) {
         if ui.button("clear").clicked() {
             self.recorder.clear();
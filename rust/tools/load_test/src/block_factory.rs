@@ -1,6 +1,7 @@
+use crate::{ConfirmationTracker, Scenario, SendRate};
 use anyhow::Result;
 use rand::Rng;
-use rsnano_core::{Account, WalletId};
+use rsnano_core::{Account, BlockEnum, WalletId};
 use rsnano_ledger::DEV_GENESIS_ACCOUNT;
 use rsnano_rpc_client::NanoRpcClient;
 use rsnano_rpc_messages::{AccountInfoDto, KeyPairDto};
@@ -13,22 +14,29 @@ use std::{
     },
     time::Duration,
 };
-use tokio::{spawn, sync::Semaphore, time::sleep};
+use tokio::{
+    spawn,
+    sync::Semaphore,
+    time::{sleep, sleep_until, Instant},
+};
 
 pub async fn create_send_and_receive_blocks(
-    send_count: usize,
-    simultaneous_process_calls: usize,
+    scenario: &Scenario,
     destination_accounts: Vec<KeyPairDto>,
     wallet: WalletId,
     node_client: Arc<NanoRpcClient>,
+    tracker: Arc<ConfirmationTracker>,
 ) -> Result<HashMap<Account, AccountInfoDto>> {
     let factory = Arc::new(BlockFactory {
-        send_count,
-        simultaneous_calls_semaphore: Arc::new(Semaphore::new(simultaneous_process_calls)),
-        send_calls_remaining: AtomicUsize::new(send_count),
+        send_count: scenario.send_count,
+        simultaneous_calls_semaphore: Arc::new(Semaphore::new(scenario.simultaneous_process_calls)),
+        send_calls_remaining: AtomicUsize::new(scenario.send_count),
         destination_accounts,
         wallet,
         node_client,
+        rate: scenario.rate.clone(),
+        start: Instant::now(),
+        tracker,
     });
 
     let f1 = factory.clone();
@@ -64,6 +72,9 @@ struct BlockFactory {
     destination_accounts: Vec<KeyPairDto>,
     wallet: WalletId,
     node_client: Arc<NanoRpcClient>,
+    rate: SendRate,
+    start: Instant,
+    tracker: Arc<ConfirmationTracker>,
 }
 
 impl BlockFactory {
@@ -90,22 +101,28 @@ impl BlockFactory {
     }
 
     async fn send_and_receive(&self, send_no: usize) -> Result<()> {
+        sleep_until(self.start + self.rate.send_offset(send_no)).await;
         let _permit = Arc::clone(&self.simultaneous_calls_semaphore)
             .acquire_owned()
             .await?;
-        let destination_account = self.get_destination_account(send_no);
+        let destination_account = self.get_destination_account(send_no).account;
 
-        let res = self
-            .node_client
-            .send_receive(
-                self.wallet,
-                *DEV_GENESIS_ACCOUNT,
-                destination_account.account,
-            )
-            .await;
+        let res = self.publish_send_and_receive(destination_account).await;
         self.send_calls_remaining.fetch_sub(1, Ordering::SeqCst);
         res
     }
+
+    async fn publish_send_and_receive(&self, destination: Account) -> Result<()> {
+        let block = self
+            .node_client
+            .send_block(self.wallet, *DEV_GENESIS_ACCOUNT, destination)
+            .await?;
+        self.tracker
+            .published(BlockEnum::from(block.clone()).hash());
+        self.node_client
+            .receive_block(self.wallet, destination, block)
+            .await
+    }
 }
 
 async fn get_account_info(
@@ -0,0 +1,89 @@
+use crate::{ForkReport, LatencyPercentiles};
+use anyhow::Result;
+use rsnano_core::{Account, Amount, BlockHash, DEV_GENESIS_KEY};
+use rsnano_ledger::DEV_GENESIS_ACCOUNT;
+use rsnano_rpc_client::NanoRpcClient;
+use rsnano_rpc_messages::{BlockCreateArgs, BlockCreateDto, BlockTypeDto};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Publishes two conflicting sends of the genesis account at the same time,
+/// one on each node, and waits until the network confirmed one of them
+pub async fn inject_forks(
+    first_node: &NanoRpcClient,
+    second_node: &NanoRpcClient,
+    fork_count: usize,
+    timeout: Duration,
+) -> Result<ForkReport> {
+    let mut resolution_ms = Vec::new();
+    for _ in 0..fork_count {
+        let (fork_a, fork_b) = create_fork(first_node).await?;
+        let published = Instant::now();
+        // One of the two calls fails, because the node already knows the other block
+        let _ = tokio::join!(
+            first_node.process(fork_a.block),
+            second_node.process(fork_b.block)
+        );
+
+        if wait_for_resolution(first_node, &[fork_a.hash, fork_b.hash], timeout).await {
+            resolution_ms.push(published.elapsed().as_millis() as u64);
+        } else {
+            // The next fork would be built on an unconfirmed frontier
+            break;
+        }
+    }
+
+    Ok(ForkReport {
+        injected: fork_count,
+        resolved: resolution_ms.len(),
+        resolution_ms: LatencyPercentiles::from_samples(resolution_ms),
+    })
+}
+
+async fn create_fork(node: &NanoRpcClient) -> Result<(BlockCreateDto, BlockCreateDto)> {
+    let info = node.account_info(*DEV_GENESIS_ACCOUNT).await?;
+    let fork_a = create_send(node, info.frontier, info.balance).await?;
+    let fork_b = create_send(node, info.frontier, info.balance).await?;
+    Ok((fork_a, fork_b))
+}
+
+async fn create_send(
+    node: &NanoRpcClient,
+    previous: BlockHash,
+    balance: Amount,
+) -> Result<BlockCreateDto> {
+    let destination: Account = node.key_create().await?.account;
+    node.block_create(BlockCreateArgs::new(
+        BlockTypeDto::State,
+        Some(balance - Amount::raw(1)),
+        Some(DEV_GENESIS_KEY.private_key()),
+        None,
+        Some(*DEV_GENESIS_ACCOUNT),
+        None,
+        None,
+        Some(*DEV_GENESIS_ACCOUNT),
+        Some(destination.into()),
+        Some(previous),
+        None,
+        None,
+        None,
+    ))
+    .await
+}
+
+async fn wait_for_resolution(node: &NanoRpcClient, forks: &[BlockHash], timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        for hash in forks {
+            if let Ok(info) = node.block_info(*hash).await {
+                if info.confirmed {
+                    return true;
+                }
+            }
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+    false
+}
@@ -1,26 +1,87 @@
-use crate::{ProgramArgs, TestNode};
+use crate::{
+    inject_forks, ConfirmationTracker, ForkReport, LoadTestReport, MetricsSampler, ProgramArgs,
+    Scenario, TestNode,
+};
 use anyhow::{bail, Result};
-use rsnano_core::Account;
+use rsnano_core::{Account, WalletId};
 use rsnano_rpc_messages::AccountInfoDto;
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::time::{sleep, Instant};
 
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct LoadTest {
     args: ProgramArgs,
+    scenario: Scenario,
     nodes: Vec<TestNode>,
 }
 
 impl LoadTest {
-    pub fn new(args: ProgramArgs) -> Self {
-        let nodes = Vec::with_capacity(args.node_count);
-        Self { args, nodes }
+    pub fn new(args: ProgramArgs, scenario: Scenario) -> Self {
+        let nodes = Vec::with_capacity(scenario.node_count);
+        Self {
+            args,
+            scenario,
+            nodes,
+        }
     }
 
     pub async fn run(mut self) -> Result<()> {
         self.start_nodes().await?;
         self.wait_for_nodes_to_spin_up().await;
         self.connect_nodes().await?;
-        let expected_account_info = self.create_send_and_receive_blocks().await?;
+        let genesis_wallet = self.primary_node().create_genesis_wallet().await?;
+        self.distribute_rep_weights(genesis_wallet).await?;
+
+        let start = Instant::now();
+        let tracker = Arc::new(ConfirmationTracker::new(self.primary_node().client()));
+        let mut sampler =
+            MetricsSampler::new(self.nodes.iter().map(|n| n.client()).collect(), start);
+        let sends_done = AtomicBool::new(false);
+        let done = AtomicBool::new(false);
+
+        let (expected_account_info, _, _) = tokio::join!(
+            async {
+                let result = self
+                    .create_send_and_receive_blocks(genesis_wallet, tracker.clone())
+                    .await;
+                sends_done.store(true, Ordering::SeqCst);
+                result
+            },
+            async {
+                self.track_confirmations(&tracker, &sends_done).await;
+                done.store(true, Ordering::SeqCst);
+            },
+            self.collect_metrics(&mut sampler, &done)
+        );
+        let expected_account_info = expected_account_info?;
+        let forks = self.inject_forks().await?;
+
+        let report = LoadTestReport {
+            scenario: self.scenario.name.clone(),
+            node_count: self.scenario.node_count,
+            send_count: self.scenario.send_count,
+            duration_ms: start.elapsed().as_millis() as u64,
+            confirmed: tracker.confirmed(),
+            unconfirmed: tracker.unconfirmed(),
+            latency_ms: tracker.latencies(),
+            cps: sampler.cps,
+            queue_depths: sampler.queue_depths,
+            forks,
+        };
+        self.print_summary(&report);
+        if let Some(path) = &self.args.report_path {
+            report.save(path)?;
+            println!("Report written to {:?}", path);
+        }
+
         self.wait_for_nodes_to_catch_up(&expected_account_info)
             .await?;
         self.stop_nodes().await?;
@@ -32,7 +93,7 @@ impl LoadTest {
     }
 
     async fn start_nodes(&mut self) -> Result<()> {
-        for i in 0..self.args.node_count {
+        for i in 0..self.scenario.node_count {
             let mut node = TestNode::new(i)?;
             println!(
                 "starting node, port {}, data dir {:?}",
@@ -58,17 +119,97 @@ impl LoadTest {
         Ok(())
     }
 
-    async fn create_send_and_receive_blocks(&self) -> Result<HashMap<Account, AccountInfoDto>> {
+    async fn distribute_rep_weights(&self, genesis_wallet: WalletId) -> Result<()> {
+        for (node, percent) in self.nodes.iter().zip(self.scenario.rep_weights.iter()) {
+            if *percent == 0 {
+                continue;
+            }
+            println!(
+                "Delegating {}% of the weight to node {}",
+                percent, node.rpc_port
+            );
+            let representative = node.create_representative().await?;
+            self.primary_node()
+                .send_genesis_weight(genesis_wallet, representative, *percent)
+                .await?;
+            node.wait_for_account(representative, self.scenario.timeout())
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn create_send_and_receive_blocks(
+        &self,
+        genesis_wallet: WalletId,
+        tracker: Arc<ConfirmationTracker>,
+    ) -> Result<HashMap<Account, AccountInfoDto>> {
         println!("Beginning tests");
         self.primary_node()
-            .create_send_and_receive_blocks(
-                self.args.destination_count,
-                self.args.send_count,
-                self.args.simultaneous_process_calls,
-            )
+            .create_send_and_receive_blocks(&self.scenario, genesis_wallet, tracker)
             .await
     }
 
+    /// Polls the primary node until all sends are confirmed or the timeout is reached
+    async fn track_confirmations(&self, tracker: &ConfirmationTracker, sends_done: &AtomicBool) {
+        let mut sends_done_at = None;
+        loop {
+            if let Err(e) = tracker.poll().await {
+                println!("Could not poll confirmations: {:?}", e);
+            }
+
+            if sends_done.load(Ordering::SeqCst) {
+                let sends_done_at = *sends_done_at.get_or_insert_with(Instant::now);
+                if tracker.unconfirmed() == 0 || sends_done_at.elapsed() > self.scenario.timeout() {
+                    break;
+                }
+            }
+            sleep(CONFIRMATION_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn collect_metrics(&self, sampler: &mut MetricsSampler, done: &AtomicBool) {
+        while !done.load(Ordering::SeqCst) {
+            if let Err(e) = sampler.sample().await {
+                println!("Could not collect metrics: {:?}", e);
+            }
+            sleep(self.scenario.sample_interval()).await;
+        }
+    }
+
+    async fn inject_forks(&self) -> Result<ForkReport> {
+        if self.scenario.forks == 0 {
+            return Ok(ForkReport::default());
+        }
+        println!("Injecting {} forks...", self.scenario.forks);
+        inject_forks(
+            &self.nodes[0].client(),
+            &self.nodes[1].client(),
+            self.scenario.forks,
+            self.scenario.timeout(),
+        )
+        .await
+    }
+
+    fn print_summary(&self, report: &LoadTestReport) {
+        println!(
+            "Confirmed {} of {} sends in {} ms",
+            report.confirmed, report.send_count, report.duration_ms
+        );
+        println!(
+            "Latency ms: p50 {}, p90 {}, p99 {}, max {}",
+            report.latency_ms.p50,
+            report.latency_ms.p90,
+            report.latency_ms.p99,
+            report.latency_ms.max
+        );
+        if report.forks.injected > 0 {
+            println!(
+                "Forks resolved: {} of {}",
+                report.forks.resolved, report.forks.injected
+            );
+        }
+    }
+
     async fn wait_for_nodes_to_catch_up(
         &self,
         expected_account_info: &HashMap<Account, AccountInfoDto>,
@@ -84,7 +225,7 @@ impl LoadTest {
                             break;
                         }
                     }
-                    if timer.elapsed() > Duration::from_secs(120) {
+                    if timer.elapsed() > self.scenario.timeout() {
                         bail!("Timed out");
                     }
 
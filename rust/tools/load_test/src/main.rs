@@ -1,13 +1,21 @@
 mod block_factory;
+mod forks;
 mod load_test;
+mod metrics;
 mod program_args;
+mod report;
+mod scenario;
 mod test_node;
 
 use anyhow::Result;
 pub use block_factory::*;
+pub use forks::*;
 use load_test::*;
+pub use metrics::*;
 pub use program_args::*;
+pub use report::*;
 use rsnano_node::config::force_nano_dev_network;
+pub use scenario::*;
 pub use test_node::*;
 
 #[tokio::main]
@@ -15,5 +23,9 @@ async fn main() -> Result<()> {
     force_nano_dev_network();
     let args = ProgramArgs::parse()?;
     args.validate_paths()?;
-    LoadTest::new(args).run().await
+    let scenario = match &args.scenario_path {
+        Some(path) => Scenario::load(path)?,
+        None => Scenario::from_args(&args),
+    };
+    LoadTest::new(args, scenario).run().await
 }
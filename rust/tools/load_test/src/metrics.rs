@@ -0,0 +1,187 @@
+use crate::{CpsSample, LatencyPercentiles, QueueDepthSample};
+use anyhow::Result;
+use rsnano_core::BlockHash;
+use rsnano_rpc_client::NanoRpcClient;
+use rsnano_rpc_messages::StatsType;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::time::Instant;
+
+const BLOCKS_INFO_BATCH_SIZE: usize = 1000;
+
+/// Measures the time from publishing a block until the node reports it as confirmed
+pub struct ConfirmationTracker {
+    node_client: Arc<NanoRpcClient>,
+    published: Mutex<HashMap<BlockHash, Instant>>,
+    latencies_ms: Mutex<Vec<u64>>,
+}
+
+impl ConfirmationTracker {
+    pub fn new(node_client: Arc<NanoRpcClient>) -> Self {
+        Self {
+            node_client,
+            published: Mutex::new(HashMap::new()),
+            latencies_ms: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn published(&self, hash: BlockHash) {
+        self.published.lock().unwrap().insert(hash, Instant::now());
+    }
+
+    pub fn unconfirmed(&self) -> usize {
+        self.published.lock().unwrap().len()
+    }
+
+    pub fn confirmed(&self) -> usize {
+        self.latencies_ms.lock().unwrap().len()
+    }
+
+    pub fn latencies(&self) -> LatencyPercentiles {
+        LatencyPercentiles::from_samples(self.latencies_ms.lock().unwrap().clone())
+    }
+
+    /// Asks the node which of the published blocks are confirmed by now
+    pub async fn poll(&self) -> Result<()> {
+        let hashes: Vec<BlockHash> = self.published.lock().unwrap().keys().cloned().collect();
+        for batch in hashes.chunks(BLOCKS_INFO_BATCH_SIZE) {
+            let infos = self.node_client.blocks_info(batch.to_vec()).await?;
+            let now = Instant::now();
+            let mut published = self.published.lock().unwrap();
+            let mut latencies = self.latencies_ms.lock().unwrap();
+            for (hash, info) in infos.blocks {
+                if !info.confirmed {
+                    continue;
+                }
+                if let Some(published_at) = published.remove(&hash) {
+                    latencies.push(now.duration_since(published_at).as_millis() as u64);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Periodically collects the confirmation rate of the primary node
+/// and the queue depths of all nodes
+pub struct MetricsSampler {
+    node_clients: Vec<Arc<NanoRpcClient>>,
+    start: Instant,
+    last_cemented: Option<(Instant, u64)>,
+    pub cps: Vec<CpsSample>,
+    pub queue_depths: Vec<QueueDepthSample>,
+}
+
+impl MetricsSampler {
+    /// The first client must belong to the primary node
+    pub fn new(node_clients: Vec<Arc<NanoRpcClient>>, start: Instant) -> Self {
+        Self {
+            node_clients,
+            start,
+            last_cemented: None,
+            cps: Vec::new(),
+            queue_depths: Vec::new(),
+        }
+    }
+
+    pub async fn sample(&mut self) -> Result<()> {
+        self.sample_cps().await?;
+        for node in 0..self.node_clients.len() {
+            let objects = self.node_clients[node].stats(StatsType::Objects).await?;
+            let elapsed_ms = self.start.elapsed().as_millis() as u64;
+            self.queue_depths
+                .push(queue_depth_sample(node, elapsed_ms, &objects));
+        }
+        Ok(())
+    }
+
+    async fn sample_cps(&mut self) -> Result<()> {
+        let cemented = self.node_clients[0].block_count().await?.cemented;
+        let now = Instant::now();
+        let cps = match self.last_cemented {
+            Some((last_time, last_cemented)) => {
+                let elapsed = now.duration_since(last_time).as_secs_f64();
+                if elapsed > 0.0 {
+                    cemented.saturating_sub(last_cemented) as f64 / elapsed
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        };
+        self.last_cemented = Some((now, cemented));
+        self.cps.push(CpsSample {
+            elapsed_ms: now.duration_since(self.start).as_millis() as u64,
+            cemented,
+            cps,
+        });
+        Ok(())
+    }
+}
+
+fn queue_depth_sample(node: usize, elapsed_ms: u64, objects: &Value) -> QueueDepthSample {
+    let objects = &objects["node"];
+    QueueDepthSample {
+        node,
+        elapsed_ms,
+        block_processor: container_count(&objects["block_processor"]["blocks"])
+            + container_count(&objects["block_processor"]["forced"]),
+        vote_processor: container_count(&objects["vote_processor"]["votes"]),
+        message_processor: container_count(&objects["message_processor"]["queue"]["total_size"]),
+        confirming_set: container_count(&objects["confirming_set"]["set"]),
+        active_elections: container_count(&objects["active"]["roots"]),
+    }
+}
+
+fn container_count(container: &Value) -> u64 {
+    container["count"]
+        .as_str()
+        .and_then(|count| count.parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_queue_depths() {
+        let objects = json!({
+            "node": {
+                "block_processor": {
+                    "blocks": {"count": "3", "size": "8"},
+                    "forced": {"count": "1", "size": "8"}
+                },
+                "vote_processor": {"votes": {"count": "4", "size": "8"}},
+                "message_processor": {"queue": {"total_size": {"count": "5", "size": "8"}}},
+                "confirming_set": {"set": {"count": "6", "size": "8"}},
+                "active": {"roots": {"count": "7", "size": "8"}}
+            }
+        });
+
+        assert_eq!(
+            queue_depth_sample(2, 100, &objects),
+            QueueDepthSample {
+                node: 2,
+                elapsed_ms: 100,
+                block_processor: 4,
+                vote_processor: 4,
+                message_processor: 5,
+                confirming_set: 6,
+                active_elections: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn missing_containers_count_as_empty() {
+        assert_eq!(
+            queue_depth_sample(0, 0, &json!({})),
+            QueueDepthSample::default()
+        );
+    }
+}
@@ -9,6 +9,10 @@ pub struct ProgramArgs {
     pub simultaneous_process_calls: usize,
     pub node_path: PathBuf,
     pub rpc_path: PathBuf,
+    /// TOML file that describes the scenario. Overrides the count arguments
+    pub scenario_path: Option<PathBuf>,
+    /// Where the JSON report gets written to
+    pub report_path: Option<PathBuf>,
 }
 
 impl ProgramArgs {
@@ -21,6 +25,8 @@ impl ProgramArgs {
         .arg(Arg::with_name("destination_count").long("destination_count").takes_value(true).default_value("2").help("How many destination accounts to choose between"))
         .arg(Arg::with_name("send_count").short("s").long("send_count").takes_value(true).default_value("2000").help("How many send blocks to generate"))
         .arg(Arg::with_name("simultaneous_process_calls").long("simultaneous_process_calls").takes_value(true).value_name("count").default_value("20").help("Number of simultaneous rpc sends to do"))
+        .arg(Arg::with_name("scenario").long("scenario").takes_value(true).value_name("file").help("TOML file that describes the test scenario"))
+        .arg(Arg::with_name("report").long("report").takes_value(true).value_name("file").help("Write a JSON report of the run to this file"))
         .get_matches();
 
         let node_count = matches.value_of("node_count").unwrap().parse::<usize>()?;
@@ -54,6 +60,8 @@ impl ProgramArgs {
             simultaneous_process_calls,
             node_path,
             rpc_path,
+            scenario_path: matches.value_of("scenario").map(PathBuf::from),
+            report_path: matches.value_of("report").map(PathBuf::from),
         };
 
         Ok(args)
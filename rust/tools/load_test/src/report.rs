@@ -0,0 +1,130 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+/// Result of a load test run. It gets written as JSON, so that runs can be compared
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LoadTestReport {
+    pub scenario: String,
+    pub node_count: usize,
+    pub send_count: usize,
+    pub duration_ms: u64,
+    pub confirmed: usize,
+    pub unconfirmed: usize,
+    /// Time from publishing a send on the primary node until it was seen confirmed there
+    pub latency_ms: LatencyPercentiles,
+    pub cps: Vec<CpsSample>,
+    pub queue_depths: Vec<QueueDepthSample>,
+    pub forks: ForkReport,
+}
+
+impl LoadTestReport {
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct LatencyPercentiles {
+    pub count: usize,
+    pub min: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p95: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl LatencyPercentiles {
+    pub fn from_samples(mut samples: Vec<u64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        Self {
+            count: samples.len(),
+            min: samples[0],
+            p50: percentile(&samples, 50.0),
+            p90: percentile(&samples, 90.0),
+            p95: percentile(&samples, 95.0),
+            p99: percentile(&samples, 99.0),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[u64], percent: f64) -> u64 {
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Cemented blocks per second on the primary node
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CpsSample {
+    pub elapsed_ms: u64,
+    pub cemented: u64,
+    pub cps: f64,
+}
+
+/// Queue sizes of one node, as reported by its `stats` RPC
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct QueueDepthSample {
+    pub node: usize,
+    pub elapsed_ms: u64,
+    pub block_processor: u64,
+    pub vote_processor: u64,
+    pub message_processor: u64,
+    pub confirming_set: u64,
+    pub active_elections: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ForkReport {
+    pub injected: usize,
+    /// Forks for which one of the two blocks got confirmed
+    pub resolved: usize,
+    pub resolution_ms: LatencyPercentiles,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_samples() {
+        assert_eq!(
+            LatencyPercentiles::from_samples(Vec::new()),
+            LatencyPercentiles::default()
+        );
+    }
+
+    #[test]
+    fn single_sample() {
+        let percentiles = LatencyPercentiles::from_samples(vec![42]);
+        assert_eq!(percentiles.count, 1);
+        assert_eq!(percentiles.min, 42);
+        assert_eq!(percentiles.p50, 42);
+        assert_eq!(percentiles.p99, 42);
+        assert_eq!(percentiles.max, 42);
+    }
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let samples = (1..=100).rev().collect();
+        let percentiles = LatencyPercentiles::from_samples(samples);
+        assert_eq!(
+            percentiles,
+            LatencyPercentiles {
+                count: 100,
+                min: 1,
+                p50: 50,
+                p90: 90,
+                p95: 95,
+                p99: 99,
+                max: 100
+            }
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::ProgramArgs;
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::{path::Path, time::Duration};
+
+/// Describes a load test run. Scenarios are read from a TOML file,
+/// see `scenarios/burst_with_forks.toml` for an example
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    pub node_count: usize,
+    pub rep_weights: Vec<u8>,
+    /// Number of destination accounts the sends get spread over
+    pub destination_count: usize,
+    pub send_count: usize,
+    pub simultaneous_process_calls: usize,
+    /// Number of conflicting send pairs that get published on different nodes
+    pub forks: usize,
+    pub rate: SendRate,
+    pub sample_interval_ms: u64,
+    pub timeout_s: u64,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: "default".to_owned(),
+            node_count: 10,
+            rep_weights: Vec::new(),
+            destination_count: 2,
+            send_count: 2000,
+            simultaneous_process_calls: 20,
+            forks: 0,
+            rate: SendRate::Unlimited,
+            sample_interval_ms: 1000,
+            timeout_s: 120,
+        }
+    }
+}
+
+impl Scenario {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let toml_str = std::fs::read_to_string(path)?;
+        Self::parse(&toml_str)
+    }
+
+    pub fn parse(toml_str: &str) -> Result<Self> {
+        let scenario: Scenario = toml::from_str(toml_str)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// The scenario that is used when no scenario file is given
+    pub fn from_args(args: &ProgramArgs) -> Self {
+        Self {
+            node_count: args.node_count,
+            destination_count: args.destination_count,
+            send_count: args.send_count,
+            simultaneous_process_calls: args.simultaneous_process_calls,
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.node_count == 0 {
+            bail!("node_count must be at least 1");
+        }
+        if self.destination_count == 0 {
+            bail!("destination_count must be at least 1");
+        }
+        if self.simultaneous_process_calls == 0 {
+            bail!("simultaneous_process_calls must be at least 1");
+        }
+        if self.rep_weights.len() > self.node_count {
+            bail!("there are more rep_weights than nodes");
+        }
+        if self.rep_weights.iter().map(|w| *w as u32).sum::<u32>() > 100 {
+            bail!("rep_weights must not add up to more than 100 percent");
+        }
+        if self.forks > 0 && self.node_count < 2 {
+            bail!("fork injection needs at least 2 nodes");
+        }
+        self.rate.validate()?;
+        let last_send = self.send_count.saturating_sub(1);
+        if self.rate.send_offset(last_send) > self.timeout() {
+            bail!("the send rate is too low to publish all sends before the timeout");
+        }
+        Ok(())
+    }
+
+    pub fn sample_interval(&self) -> Duration {
+        Duration::from_millis(self.sample_interval_ms.max(1))
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+#[serde(tag = "mode", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SendRate {
+    /// Publish as fast as the node accepts the blocks
+    #[default]
+    Unlimited,
+    Steady {
+        blocks_per_second: f64,
+    },
+    /// Publish `burst_size` blocks at once and then wait for `burst_interval_ms`
+    Burst {
+        burst_size: usize,
+        burst_interval_ms: u64,
+    },
+}
+
+impl SendRate {
+    fn validate(&self) -> Result<()> {
+        match self {
+            SendRate::Unlimited => {}
+            SendRate::Steady { blocks_per_second } => {
+                if !blocks_per_second.is_finite() || *blocks_per_second <= 0.0 {
+                    bail!("blocks_per_second must be a finite number greater than 0");
+                }
+            }
+            SendRate::Burst { burst_size, .. } => {
+                if *burst_size == 0 {
+                    bail!("burst_size must be at least 1");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// When the send with the given number should be published, relative to the start of the test
+    pub fn send_offset(&self, send_no: usize) -> Duration {
+        match self {
+            SendRate::Unlimited => Duration::ZERO,
+            SendRate::Steady { blocks_per_second } => {
+                // Saturate for very small rates instead of panicking
+                Duration::try_from_secs_f64(send_no as f64 / blocks_per_second)
+                    .unwrap_or(Duration::MAX)
+            }
+            SendRate::Burst {
+                burst_size,
+                burst_interval_ms,
+            } => Duration::from_millis(
+                ((send_no / burst_size) as u64).saturating_mul(*burst_interval_ms),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scenario() {
+        let scenario = Scenario::parse(
+            r#"
+            name = "test"
+            node_count = 4
+            rep_weights = [40, 20, 10]
+            destination_count = 10
+            send_count = 100
+            forks = 2
+
+            [rate]
+            mode = "steady"
+            blocks_per_second = 50.0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            scenario,
+            Scenario {
+                name: "test".to_owned(),
+                node_count: 4,
+                rep_weights: vec![40, 20, 10],
+                destination_count: 10,
+                send_count: 100,
+                forks: 2,
+                rate: SendRate::Steady {
+                    blocks_per_second: 50.0
+                },
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_example_scenario() {
+        let scenario = Scenario::parse(include_str!("../scenarios/burst_with_forks.toml")).unwrap();
+        assert_eq!(scenario.forks, 5);
+        assert_eq!(
+            scenario.rate,
+            SendRate::Burst {
+                burst_size: 500,
+                burst_interval_ms: 5000
+            }
+        );
+    }
+
+    #[test]
+    fn empty_scenario_uses_defaults() {
+        assert_eq!(Scenario::parse("").unwrap(), Scenario::default());
+    }
+
+    #[test]
+    fn reject_unknown_fields() {
+        assert!(Scenario::parse("foo = 1").is_err());
+    }
+
+    #[test]
+    fn reject_rep_weights_above_100_percent() {
+        assert!(Scenario::parse("rep_weights = [60, 50]").is_err());
+    }
+
+    #[test]
+    fn reject_forks_with_single_node() {
+        assert!(Scenario::parse("node_count = 1\nforks = 1").is_err());
+    }
+
+    #[test]
+    fn steady_send_offset() {
+        let rate = SendRate::Steady {
+            blocks_per_second: 10.0,
+        };
+        assert_eq!(rate.send_offset(0), Duration::ZERO);
+        assert_eq!(rate.send_offset(5), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn reject_invalid_steady_rates() {
+        for blocks_per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let scenario = Scenario {
+                rate: SendRate::Steady { blocks_per_second },
+                ..Default::default()
+            };
+            assert!(scenario.validate().is_err(), "{}", blocks_per_second);
+        }
+    }
+
+    #[test]
+    fn burst_send_offset() {
+        let rate = SendRate::Burst {
+            burst_size: 3,
+            burst_interval_ms: 1000,
+        };
+        assert_eq!(rate.send_offset(2), Duration::ZERO);
+        assert_eq!(rate.send_offset(3), Duration::from_secs(1));
+        assert_eq!(rate.send_offset(7), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn reject_sends_after_timeout() {
+        let scenario = Scenario {
+            send_count: 3,
+            rate: SendRate::Burst {
+                burst_size: 1,
+                burst_interval_ms: u64::MAX,
+            },
+            ..Default::default()
+        };
+        assert!(scenario.validate().is_err());
+    }
+
+    #[test]
+    fn burst_send_offset_saturates() {
+        let rate = SendRate::Burst {
+            burst_size: 1,
+            burst_interval_ms: u64::MAX,
+        };
+        assert_eq!(rate.send_offset(2), Duration::from_millis(u64::MAX));
+    }
+}
@@ -1,7 +1,8 @@
-use crate::create_send_and_receive_blocks;
-use anyhow::{anyhow, Result};
+use crate::{create_send_and_receive_blocks, ConfirmationTracker, Scenario};
+use anyhow::{anyhow, bail, Result};
 use reqwest::Url;
-use rsnano_core::{utils::get_cpu_count, Account, WalletId, DEV_GENESIS_KEY};
+use rsnano_core::{utils::get_cpu_count, Account, Amount, WalletId, DEV_GENESIS_KEY};
+use rsnano_ledger::DEV_GENESIS_ACCOUNT;
 use rsnano_node::{
     config::{
         get_node_toml_config_path, get_rpc_toml_config_path, DaemonConfig, DaemonToml,
@@ -10,7 +11,9 @@ use rsnano_node::{
     unique_path, NetworkParams, DEV_NETWORK_PARAMS,
 };
 use rsnano_rpc_client::NanoRpcClient;
-use rsnano_rpc_messages::{AccountInfoDto, KeyPairDto, StartedDto, WalletAddArgs};
+use rsnano_rpc_messages::{
+    AccountInfoDto, KeyPairDto, SendArgs, StartedDto, WalletAddArgs, WalletRepresentativeSetArgs,
+};
 use rsnano_rpc_server::{RpcServerConfig, RpcServerToml};
 use std::{
     collections::HashMap,
@@ -21,7 +24,7 @@ use std::{
     sync::Arc,
     time::Duration,
 };
-use tokio::time::sleep;
+use tokio::time::{sleep, Instant};
 use toml::to_string;
 
 const RPC_PORT_START: u16 = 60000;
@@ -84,28 +87,84 @@ impl TestNode {
             .await
     }
 
+    pub fn client(&self) -> Arc<NanoRpcClient> {
+        self.node_client.clone()
+    }
+
+    /// Creates a wallet that contains the genesis account
+    pub async fn create_genesis_wallet(&self) -> Result<WalletId> {
+        let wallet = self.node_client.wallet_create(None).await?.wallet;
+        self.add_genesis_account(wallet).await?;
+        Ok(wallet)
+    }
+
     pub async fn create_send_and_receive_blocks(
         &self,
-        destination_count: usize,
-        send_count: usize,
-        simultaneous_process_calls: usize,
+        scenario: &Scenario,
+        genesis_wallet: WalletId,
+        tracker: Arc<ConfirmationTracker>,
     ) -> Result<HashMap<Account, AccountInfoDto>> {
-        let destination_accounts = self.create_destination_accounts(destination_count).await?;
-        let wallet = self.node_client.wallet_create(None).await?.wallet;
-        self.add_genesis_account(wallet).await?;
-        self.add_destination_accounts(&destination_accounts, wallet)
+        let destination_accounts = self
+            .create_destination_accounts(scenario.destination_count)
+            .await?;
+        self.add_destination_accounts(&destination_accounts, genesis_wallet)
             .await?;
 
         create_send_and_receive_blocks(
-            send_count,
-            simultaneous_process_calls,
+            scenario,
             destination_accounts,
-            wallet,
+            genesis_wallet,
             self.node_client.clone(),
+            tracker,
         )
         .await
     }
 
+    /// Creates an account on this node that represents itself, so that the node votes
+    /// with the weight which gets sent to that account
+    pub async fn create_representative(&self) -> Result<Account> {
+        let wallet = self.node_client.wallet_create(None).await?.wallet;
+        let representative = self.node_client.account_create(wallet).await?.account;
+        self.node_client
+            .wallet_representative_set(WalletRepresentativeSetArgs::new(wallet, representative))
+            .await?;
+        Ok(representative)
+    }
+
+    /// Sends `percent` of the initial genesis supply to `destination`
+    pub async fn send_genesis_weight(
+        &self,
+        genesis_wallet: WalletId,
+        destination: Account,
+        percent: u8,
+    ) -> Result<()> {
+        let amount = genesis_weight_share(percent);
+        self.node_client
+            .send(SendArgs::new(
+                genesis_wallet,
+                *DEV_GENESIS_ACCOUNT,
+                destination,
+                amount,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Waits until the wallet of this node received funds for the given account
+    pub async fn wait_for_account(&self, account: Account, timeout: Duration) -> Result<()> {
+        let start = Instant::now();
+        while self.account_info(account).await.is_err() {
+            if start.elapsed() > timeout {
+                bail!(
+                    "account {} was not opened in time",
+                    account.encode_account()
+                );
+            }
+            sleep(Duration::from_millis(500)).await;
+        }
+        Ok(())
+    }
+
     async fn add_genesis_account(&self, wallet: WalletId) -> Result<()> {
         self.node_client
             .wallet_add(WalletAddArgs::new(wallet, DEV_GENESIS_KEY.private_key()))
@@ -214,3 +273,22 @@ fn write_rpc_config(index: usize, data_path: &Path, network_params: &NetworkPara
     )?;
     Ok(())
 }
+
+/// The shares are based on the initial supply, so that they don't depend on
+/// the order in which the representatives get their weight
+fn genesis_weight_share(percent: u8) -> Amount {
+    Amount::raw(DEV_NETWORK_PARAMS.ledger.genesis_amount.number() / 100 * percent as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weight_shares_are_based_on_initial_supply() {
+        let supply = DEV_NETWORK_PARAMS.ledger.genesis_amount.number();
+        assert_eq!(genesis_weight_share(40).number(), supply / 100 * 40);
+        assert_eq!(genesis_weight_share(20).number(), supply / 100 * 20);
+        assert_eq!(genesis_weight_share(10).number(), supply / 100 * 10);
+    }
+}
@@ -0,0 +1,5 @@
+mod sim_network;
+mod simulator;
+
+pub use sim_network::*;
+pub use simulator::*;
@@ -0,0 +1,412 @@
+use rand::Rng;
+use rsnano_nullable_clock::{SteadyClock, Timestamp};
+use rsnano_nullable_random::NullableRng;
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::DefaultHasher, BinaryHeap, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Position of a node in the simulation
+pub type NodeIndex = usize;
+
+/// How messages travel over a link from one node to another
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinkConditions {
+    pub latency: Duration,
+    /// A random delay between zero and `jitter` gets added to the latency of every message
+    pub jitter: Duration,
+    /// Probability between 0.0 and 1.0 that a message gets lost
+    pub packet_loss: f64,
+}
+
+impl Default for LinkConditions {
+    fn default() -> Self {
+        Self {
+            latency: Duration::from_millis(20),
+            jitter: Duration::ZERO,
+            packet_loss: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct SimNetworkStats {
+    pub sent: usize,
+    pub delivered: usize,
+    /// Messages that were dropped because of packet loss
+    pub lost: usize,
+    /// Messages that were dropped because the nodes were in different partitions
+    pub blocked: usize,
+}
+
+/// A message that arrived at its destination
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Packet {
+    pub from: NodeIndex,
+    pub to: NodeIndex,
+    pub bytes: Vec<u8>,
+}
+
+/// In-memory transport between simulated nodes.
+///
+/// Whether a message gets dropped and how much it gets delayed is derived from the
+/// seed, the link and the content of the message (plus how often the same content
+/// was sent over that link before). So the fate of a message doesn't depend on
+/// which other messages the nodes happened to send in between.
+/// Messages are delivered when the virtual clock reaches their arrival time.
+pub struct SimNetwork {
+    seed: u64,
+    default_conditions: LinkConditions,
+    clock: Arc<SteadyClock>,
+    state: Mutex<SimNetworkState>,
+}
+
+impl SimNetwork {
+    pub fn new(seed: u64, default_conditions: LinkConditions, clock: Arc<SteadyClock>) -> Self {
+        Self {
+            seed,
+            default_conditions,
+            clock,
+            state: Mutex::new(SimNetworkState::default()),
+        }
+    }
+
+    pub fn clock(&self) -> &Arc<SteadyClock> {
+        &self.clock
+    }
+
+    /// Overrides the conditions for messages from `from` to `to`
+    pub fn set_link_conditions(&self, from: NodeIndex, to: NodeIndex, conditions: LinkConditions) {
+        let mut state = self.state.lock().unwrap();
+        let link = state.link(self.default_conditions, from, to);
+        link.conditions = conditions;
+    }
+
+    /// Splits the network into groups. Nodes can only talk to nodes of their own group.
+    /// Nodes that aren't listed form a group of their own.
+    pub fn partition(&self, groups: &[&[NodeIndex]]) {
+        let mut partition = HashMap::new();
+        for (group, nodes) in groups.iter().enumerate() {
+            for node in nodes.iter() {
+                partition.insert(*node, group);
+            }
+        }
+        self.state.lock().unwrap().partition = Some(partition);
+    }
+
+    pub fn heal(&self) {
+        self.state.lock().unwrap().partition = None;
+    }
+
+    pub fn can_reach(&self, from: NodeIndex, to: NodeIndex) -> bool {
+        self.state.lock().unwrap().can_reach(from, to)
+    }
+
+    pub fn send(&self, from: NodeIndex, to: NodeIndex, bytes: &[u8]) {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        state.stats.sent += 1;
+        if !state.can_reach(from, to) {
+            state.stats.blocked += 1;
+            return;
+        }
+
+        let link = state.link(self.default_conditions, from, to);
+        let conditions = link.conditions;
+        let occurrence = {
+            let count = link.occurrences.entry(content_hash(bytes)).or_default();
+            *count += 1;
+            *count
+        };
+        let mut rng = NullableRng::new_seeded(message_seed(self.seed, from, to, bytes, occurrence));
+
+        // Both random values are always drawn, so that the delay doesn't depend
+        // on the loss probability
+        let lost = rng.gen_bool(conditions.packet_loss);
+        let jitter = rng.gen_range(0..=conditions.jitter.as_millis() as u64);
+        let deliver_at = now + conditions.latency + Duration::from_millis(jitter);
+
+        if lost {
+            state.stats.lost += 1;
+            return;
+        }
+
+        state.in_flight.push(Reverse(InFlight {
+            deliver_at,
+            from,
+            to,
+            bytes: bytes.to_vec(),
+            occurrence,
+        }));
+    }
+
+    /// Removes all messages that arrived by now. Messages that were sent before
+    /// a partition was created don't arrive either.
+    pub fn take_arrived(&self) -> Vec<Packet> {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        let mut arrived = Vec::new();
+        while let Some(Reverse(next)) = state.in_flight.peek() {
+            if next.deliver_at > now {
+                break;
+            }
+            let Reverse(message) = state.in_flight.pop().unwrap();
+            if state.can_reach(message.from, message.to) {
+                state.stats.delivered += 1;
+                arrived.push(Packet {
+                    from: message.from,
+                    to: message.to,
+                    bytes: message.bytes,
+                });
+            } else {
+                state.stats.blocked += 1;
+            }
+        }
+        arrived
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.state.lock().unwrap().in_flight.len()
+    }
+
+    pub fn next_arrival(&self) -> Option<Timestamp> {
+        self.state
+            .lock()
+            .unwrap()
+            .in_flight
+            .peek()
+            .map(|Reverse(m)| m.deliver_at)
+    }
+
+    pub fn stats(&self) -> SimNetworkStats {
+        self.state.lock().unwrap().stats
+    }
+}
+
+#[derive(Default)]
+struct SimNetworkState {
+    links: HashMap<(NodeIndex, NodeIndex), Link>,
+    partition: Option<HashMap<NodeIndex, usize>>,
+    in_flight: BinaryHeap<Reverse<InFlight>>,
+    stats: SimNetworkStats,
+}
+
+impl SimNetworkState {
+    fn link(
+        &mut self,
+        default_conditions: LinkConditions,
+        from: NodeIndex,
+        to: NodeIndex,
+    ) -> &mut Link {
+        self.links.entry((from, to)).or_insert_with(|| Link {
+            conditions: default_conditions,
+            occurrences: HashMap::new(),
+        })
+    }
+
+    fn can_reach(&self, from: NodeIndex, to: NodeIndex) -> bool {
+        match &self.partition {
+            Some(partition) => partition.get(&from) == partition.get(&to),
+            None => true,
+        }
+    }
+}
+
+fn content_hash(bytes: &[u8]) -> u64 {
+    // DefaultHasher::new() always uses the same keys
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn message_seed(seed: u64, from: NodeIndex, to: NodeIndex, bytes: &[u8], occurrence: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (seed, from, to, occurrence).hash(&mut hasher);
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct Link {
+    conditions: LinkConditions,
+    /// How often each message content was sent over this link
+    occurrences: HashMap<u64, u64>,
+}
+
+/// Messages are ordered by arrival time. Messages that arrive at the same
+/// time are ordered by link and content, so that the order doesn't depend
+/// on the thread scheduling of the nodes
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct InFlight {
+    deliver_at: Timestamp,
+    from: NodeIndex,
+    to: NodeIndex,
+    bytes: Vec<u8>,
+    occurrence: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deliver_after_latency() {
+        let network = create_network(1, LinkConditions::default());
+        network.send(0, 1, &[1, 2, 3]);
+
+        network.clock().advance(Duration::from_millis(19));
+        assert_eq!(network.take_arrived(), Vec::new());
+
+        network.clock().advance(Duration::from_millis(1));
+        assert_eq!(
+            network.take_arrived(),
+            vec![Packet {
+                from: 0,
+                to: 1,
+                bytes: vec![1, 2, 3]
+            }]
+        );
+        assert_eq!(network.in_flight(), 0);
+    }
+
+    #[test]
+    fn order_by_arrival_time() {
+        let network = create_network(1, LinkConditions::default());
+        network.set_link_conditions(
+            1,
+            0,
+            LinkConditions {
+                latency: Duration::from_millis(5),
+                ..Default::default()
+            },
+        );
+        network.send(0, 1, &[1]);
+        network.send(1, 0, &[2]);
+        network.send(0, 1, &[3]);
+
+        network.clock().advance(Duration::from_secs(1));
+        let arrived: Vec<_> = network
+            .take_arrived()
+            .into_iter()
+            .map(|p| p.bytes[0])
+            .collect();
+        assert_eq!(arrived, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn partition_blocks_messages() {
+        let network = create_network(1, LinkConditions::default());
+        network.partition(&[&[0, 1], &[2]]);
+        assert!(network.can_reach(0, 1));
+        assert!(!network.can_reach(0, 2));
+
+        network.send(0, 2, &[1]);
+        network.send(0, 1, &[2]);
+        network.clock().advance(Duration::from_secs(1));
+
+        assert_eq!(network.take_arrived().len(), 1);
+        assert_eq!(
+            network.stats(),
+            SimNetworkStats {
+                sent: 2,
+                delivered: 1,
+                lost: 0,
+                blocked: 1
+            }
+        );
+    }
+
+    #[test]
+    fn messages_in_flight_dont_cross_new_partition() {
+        let network = create_network(1, LinkConditions::default());
+        network.send(0, 1, &[1]);
+        network.partition(&[&[0], &[1]]);
+        network.clock().advance(Duration::from_secs(1));
+        assert_eq!(network.take_arrived(), Vec::new());
+
+        network.heal();
+        network.send(0, 1, &[2]);
+        network.clock().advance(Duration::from_secs(1));
+        assert_eq!(network.take_arrived().len(), 1);
+    }
+
+    #[test]
+    fn same_seed_gives_same_outcome() {
+        let conditions = LinkConditions {
+            latency: Duration::from_millis(10),
+            jitter: Duration::from_millis(50),
+            packet_loss: 0.3,
+        };
+        let run = |seed| {
+            let network = create_network(seed, conditions);
+            for i in 0..100u8 {
+                network.send((i % 3) as usize, ((i + 1) % 3) as usize, &[i]);
+            }
+            network.clock().advance(Duration::from_secs(1));
+            let arrived: Vec<u8> = network
+                .take_arrived()
+                .into_iter()
+                .map(|p| p.bytes[0])
+                .collect();
+            (arrived, network.stats())
+        };
+
+        let (arrived_a, stats_a) = run(42);
+        let (arrived_b, stats_b) = run(42);
+        let (arrived_c, _) = run(43);
+
+        assert_eq!(arrived_a, arrived_b);
+        assert_eq!(stats_a, stats_b);
+        assert_ne!(arrived_a, arrived_c);
+        assert!(stats_a.lost > 0);
+        assert_eq!(stats_a.lost + stats_a.delivered, 100);
+    }
+
+    #[test]
+    fn fate_of_a_message_doesnt_depend_on_other_traffic() {
+        let conditions = LinkConditions {
+            latency: Duration::from_millis(10),
+            jitter: Duration::from_millis(50),
+            packet_loss: 0.5,
+        };
+        let run = |noise: bool| {
+            let network = create_network(7, conditions);
+            for i in 0..50u8 {
+                if noise {
+                    network.send(0, 1, &[i, 0xff]);
+                }
+                network.send(0, 1, &[i]);
+            }
+            network.clock().advance(Duration::from_secs(1));
+            network
+                .take_arrived()
+                .into_iter()
+                .filter(|p| p.bytes.len() == 1)
+                .map(|p| p.bytes[0])
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(false), run(true));
+    }
+
+    #[test]
+    fn repeated_content_gets_its_own_fate() {
+        let conditions = LinkConditions {
+            packet_loss: 0.5,
+            ..Default::default()
+        };
+        let network = create_network(1, conditions);
+        for _ in 0..100 {
+            network.send(0, 1, &[1]);
+        }
+        let stats = network.stats();
+        assert!(stats.lost > 0);
+        assert!(stats.lost < 100);
+    }
+
+    fn create_network(seed: u64, conditions: LinkConditions) -> SimNetwork {
+        SimNetwork::new(seed, conditions, Arc::new(SteadyClock::new_null()))
+    }
+}
@@ -0,0 +1,434 @@
+use crate::{LinkConditions, NodeIndex, Packet, SimNetwork};
+use anyhow::anyhow;
+use rand::{Rng, RngCore};
+use rsnano_core::{work::WorkPoolImpl, Amount, KeyPair, Networks, RawKey, WalletId};
+use rsnano_messages::ParseMessageError;
+use rsnano_network::{ChannelDirection, ChannelInfo, ChannelMode};
+use rsnano_node::{
+    config::{NodeConfig, NodeFlags},
+    transport::{MessageDeserializer, VecBufferReader},
+    unique_path,
+    utils::AsyncRuntime,
+    wallets::WalletsExt,
+    NetworkParams, Node, NodeBuilder, NodeExt,
+};
+use rsnano_nullable_clock::{SteadyClock, Timestamp};
+use rsnano_nullable_random::NullableRng;
+use rsnano_nullable_tcp::TcpStream;
+use std::{
+    collections::HashMap,
+    net::{Ipv4Addr, SocketAddrV6},
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+/// All simulated nodes use the same port, they are told apart by their IP address
+const SIMULATED_PORT: u16 = 7075;
+
+/// How often the nodes are polled while waiting for them to become idle
+const IDLE_POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+/// The nodes count as idle when nothing changed for this many polls in a row
+const IDLE_POLLS: usize = 20;
+
+#[derive(Clone)]
+pub struct SimulatorConfig {
+    /// Seeds the packet loss and jitter of all links, the node ids, the wallet ids
+    /// and the random peer selection of the nodes
+    pub seed: u64,
+    pub node_count: usize,
+    pub link_conditions: LinkConditions,
+    /// Virtual time that passes with every step
+    pub step: Duration,
+    /// The simulator waits until all nodes have processed a delivered message and sent
+    /// their replies before it delivers the next one. This is the real time it waits at most.
+    pub max_settle_time: Duration,
+    pub flags: NodeFlags,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            node_count: 3,
+            link_conditions: LinkConditions::default(),
+            step: Duration::from_millis(10),
+            max_settle_time: Duration::from_secs(5),
+            flags: Self::default_flags(),
+        }
+    }
+}
+
+impl SimulatorConfig {
+    /// Disables everything that would open real TCP connections
+    /// or close idle links behind the back of the simulation.
+    /// Background tasks that run on real time are disabled too, because they would
+    /// make the outcome depend on the speed of the machine
+    pub fn default_flags() -> NodeFlags {
+        NodeFlags {
+            disable_legacy_bootstrap: true,
+            disable_lazy_bootstrap: true,
+            disable_wallet_bootstrap: true,
+            disable_ongoing_bootstrap: true,
+            disable_bootstrap_listener: true,
+            disable_connection_cleanup: true,
+            disable_backup: true,
+            disable_ascending_bootstrap: true,
+            disable_rep_crawler: true,
+            disable_providing_telemetry_metrics: true,
+            // The elections are driven by the virtual clock of the simulator
+            disable_request_loop: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Runs several nodes in one process and connects them through a `SimNetwork`.
+///
+/// The network is driven by a shared virtual clock: every `step` advances the clock and
+/// delivers the messages that arrived by then, one by one. After every message it waits
+/// until all nodes are idle again, so that a node sees the messages in arrival order.
+/// The request loop of the elections runs on the virtual clock as well.
+/// Which messages get lost, delayed or blocked by a partition, the node ids and the
+/// random peer selection of the nodes only depend on the seed, so the same seed leads
+/// to the same outcome. Node background tasks that run on real time (e.g. the rep crawler
+/// or the ascending bootstrapper) are disabled by default. Instead of the rep crawler the
+/// simulator tells the nodes which peers host voting representatives. Tests that enable them should
+/// assert on outcomes (confirmations, the ledger after bootstrap) and not on the exact
+/// message interleaving.
+pub struct Simulator {
+    config: SimulatorConfig,
+    runtime: Arc<AsyncRuntime>,
+    network_params: NetworkParams,
+    work: Arc<WorkPoolImpl>,
+    clock: Arc<SteadyClock>,
+    network: Arc<SimNetwork>,
+    nodes: Vec<Arc<Node>>,
+    /// The channel on which a node (second index) receives the messages of a peer (first index)
+    receiving_channels: HashMap<(NodeIndex, NodeIndex), Arc<ChannelInfo>>,
+    start: Timestamp,
+    next_request_loop: Mutex<Timestamp>,
+    /// Derives the identities of the nodes from the seed
+    rng: Mutex<NullableRng>,
+}
+
+impl Simulator {
+    pub fn new(config: SimulatorConfig) -> anyhow::Result<Self> {
+        let mut network_params = NetworkParams::new(Networks::NanoDevNetwork);
+        // The simulator connects the peers itself, so keepalives aren't needed
+        network_params.network.keepalive_period = Duration::from_secs(60 * 60);
+        let clock = Arc::new(SteadyClock::new_null());
+        let network = Arc::new(SimNetwork::new(
+            config.seed,
+            config.link_conditions,
+            clock.clone(),
+        ));
+        let mut simulator = Self {
+            runtime: Arc::new(AsyncRuntime::default()),
+            work: Arc::new(WorkPoolImpl::new(
+                network_params.work.clone(),
+                1,
+                Duration::ZERO,
+            )),
+            network_params,
+            start: clock.now(),
+            next_request_loop: Mutex::new(clock.now()),
+            clock,
+            network,
+            nodes: Vec::new(),
+            receiving_channels: HashMap::new(),
+            rng: Mutex::new(NullableRng::new_seeded(config.seed)),
+            config,
+        };
+
+        for _ in 0..simulator.config.node_count {
+            simulator.add_node()?;
+        }
+        Ok(simulator)
+    }
+
+    /// Starts a new node and connects it to all existing nodes
+    pub fn add_node(&mut self) -> anyhow::Result<NodeIndex> {
+        let index = self.nodes.len();
+        let node = self.create_node()?;
+        node.start();
+        self.nodes.push(node);
+
+        for peer in 0..index {
+            self.connect(peer, index)?;
+        }
+        Ok(index)
+    }
+
+    pub fn node(&self, index: NodeIndex) -> &Arc<Node> {
+        &self.nodes[index]
+    }
+
+    pub fn nodes(&self) -> &[Arc<Node>] {
+        &self.nodes
+    }
+
+    pub fn network(&self) -> &Arc<SimNetwork> {
+        &self.network
+    }
+
+    pub fn clock(&self) -> &Arc<SteadyClock> {
+        &self.clock
+    }
+
+    /// Virtual time since the simulation started
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed(self.clock.now())
+    }
+
+    pub fn partition(&self, groups: &[&[NodeIndex]]) {
+        self.network.partition(groups);
+    }
+
+    pub fn heal(&self) {
+        self.network.heal();
+    }
+
+    /// Advances the virtual clock by one step and delivers all messages that arrived.
+    /// Returns the number of delivered messages
+    pub fn step(&self) -> usize {
+        // Blocks that were passed to a node directly must be flooded before the time moves on
+        self.wait_until_idle();
+        self.clock.advance(self.config.step);
+        let arrived = self.network.take_arrived();
+        let count = arrived.len();
+        for packet in arrived {
+            self.deliver(packet);
+            self.wait_until_idle();
+        }
+        self.run_request_loops();
+        count
+    }
+
+    /// Runs one iteration of the election request loop on every node, once per `aec_loop_interval`
+    fn run_request_loops(&self) {
+        let now = self.clock.now();
+        {
+            let mut next = self.next_request_loop.lock().unwrap();
+            if now < *next {
+                return;
+            }
+            *next = now + self.network_params.network.aec_loop_interval;
+        }
+        if self.config.flags.disable_rep_crawler {
+            self.discover_representatives(now);
+        }
+        for node in &self.nodes {
+            node.active.tick();
+            self.wait_until_idle();
+        }
+    }
+
+    /// Stands in for the rep crawler, which queries random peers on real time.
+    /// Every node learns on which channel it can reach the voting representatives of its peers
+    fn discover_representatives(&self, now: Timestamp) {
+        let mut links: Vec<_> = self.receiving_channels.iter().collect();
+        links.sort_by_key(|(link, _)| **link);
+        for (&(peer, owner), channel) in links {
+            let mut reps = Vec::new();
+            self.nodes[peer]
+                .wallets
+                .foreach_representative(|key| reps.push(key.public_key()));
+            let mut online_reps = self.nodes[owner].online_reps.lock().unwrap();
+            for rep in reps {
+                online_reps.vote_observed_directly(rep, channel.channel_id(), now);
+            }
+        }
+    }
+
+    /// Waits until no node has queued work and no new messages were sent for a while
+    fn wait_until_idle(&self) {
+        let deadline = Instant::now() + self.config.max_settle_time;
+        let mut last_sent = self.network.stats().sent;
+        let mut idle_polls = 0;
+        while idle_polls < IDLE_POLLS && Instant::now() < deadline {
+            sleep(IDLE_POLL_INTERVAL);
+            let sent = self.network.stats().sent;
+            if sent == last_sent && self.nodes.iter().all(|n| is_idle(n)) {
+                idle_polls += 1;
+            } else {
+                idle_polls = 0;
+            }
+            last_sent = sent;
+        }
+    }
+
+    /// Runs the simulation for the given amount of virtual time
+    pub fn run_for(&self, duration: Duration) {
+        let end = self.clock.now() + duration;
+        while self.clock.now() < end {
+            self.step();
+        }
+    }
+
+    /// Steps until the condition is met. Returns false if it wasn't met within `timeout` of virtual time
+    pub fn run_until(&self, timeout: Duration, mut condition: impl FnMut(&Self) -> bool) -> bool {
+        let end = self.clock.now() + timeout;
+        loop {
+            if condition(self) {
+                return true;
+            }
+            if self.clock.now() >= end {
+                return false;
+            }
+            self.step();
+        }
+    }
+
+    fn create_node(&self) -> anyhow::Result<Arc<Node>> {
+        let mut config = NodeConfig::new(Some(SIMULATED_PORT), &self.network_params, 1);
+        config.representative_vote_weight_minimum = Amount::zero();
+        // Peers are connected by the simulator and not via TCP
+        config.tcp_incoming_connections_max = 0;
+        // Votes are broadcast quickly, so that steps don't wait long for them
+        config.vote_generator_delay_ms = 10;
+
+        let (node_id, wallet_id, peer_selection_seed) = {
+            let mut rng = self.rng.lock().unwrap();
+            let mut node_key = [0; 32];
+            rng.fill_bytes(&mut node_key);
+            let mut wallet_id = [0; 32];
+            rng.fill_bytes(&mut wallet_id);
+            (
+                KeyPair::from(RawKey::from_bytes(node_key)),
+                WalletId::from_bytes(wallet_id),
+                rng.gen::<u64>(),
+            )
+        };
+
+        let data_path = unique_path().ok_or_else(|| anyhow!("Could not get a unique path"))?;
+        // The node loads its id from this file instead of generating a random one
+        std::fs::write(
+            data_path.join("node_id_private.key"),
+            format!("{}\n", node_id.private_key().encode_hex()),
+        )?;
+        let node = NodeBuilder::new(self.network_params.network.current_network)
+            .runtime(self.runtime.tokio.handle().clone())
+            .data_path(data_path)
+            .config(config)
+            .network_params(self.network_params.clone())
+            .flags(self.config.flags.clone())
+            .work(self.work.clone())
+            .clock(self.clock.clone())
+            .finish()?;
+        node.network_info
+            .write()
+            .unwrap()
+            .set_rng_seed(peer_selection_seed);
+        let node = Arc::new(node);
+        node.wallets.create(wallet_id);
+        Ok(node)
+    }
+
+    fn connect(&mut self, a: NodeIndex, b: NodeIndex) -> anyhow::Result<()> {
+        let channel_a = self.add_channel(a, b, ChannelDirection::Outbound)?;
+        let channel_b = self.add_channel(b, a, ChannelDirection::Inbound)?;
+        self.receiving_channels.insert((b, a), channel_a);
+        self.receiving_channels.insert((a, b), channel_b);
+        Ok(())
+    }
+
+    /// Adds a realtime channel to `owner`, which sends everything into the simulated network
+    fn add_channel(
+        &self,
+        owner: NodeIndex,
+        peer: NodeIndex,
+        direction: ChannelDirection,
+    ) -> anyhow::Result<Arc<ChannelInfo>> {
+        let network = self.network.clone();
+        let stream = TcpStream::new_in_memory(
+            simulated_address(owner),
+            simulated_address(peer),
+            move |buffer| network.send(owner, peer, buffer),
+        );
+
+        let node = &self.nodes[owner];
+        let channel = node.network.add(
+            stream,
+            simulated_address(peer),
+            direction,
+            ChannelMode::Realtime,
+        )?;
+        let info = channel.info.clone();
+        info.set_peering_addr(simulated_address(peer));
+        // Links are only cut by partitions and never by idle timeouts
+        info.set_timeout(Duration::from_secs(u32::MAX as u64));
+
+        let (info, observers) = node
+            .network_info
+            .read()
+            .unwrap()
+            .upgrade_to_realtime_connection(
+                info.channel_id(),
+                self.nodes[peer].node_id.public_key(),
+            )
+            .ok_or_else(|| anyhow!("Could not upgrade channel to realtime"))?;
+        for observer in observers {
+            observer(info.clone());
+        }
+        Ok(info)
+    }
+
+    fn deliver(&self, packet: Packet) {
+        let Some(channel) = self.receiving_channels.get(&(packet.from, packet.to)) else {
+            return;
+        };
+        let node = &self.nodes[packet.to];
+        let mut deserializer = MessageDeserializer::new(
+            self.network_params.network.protocol_info(),
+            self.network_params.work.clone(),
+            node.publish_filter.clone(),
+            VecBufferReader::new(packet.bytes),
+        );
+        // A buffer can contain several messages
+        loop {
+            match self.runtime.tokio.block_on(deserializer.read()) {
+                Ok(deserialized) => {
+                    channel.set_last_activity(self.clock.now());
+                    node.inbound_message_queue
+                        .put(deserialized.message, channel.clone());
+                }
+                // These messages were read completely, so the next message can still be parsed.
+                // Duplicate publishes are rejected by the publish filter, just like on a real channel
+                Err(ParseMessageError::DuplicatePublishMessage)
+                | Err(ParseMessageError::InsufficientWork)
+                | Err(ParseMessageError::InvalidMessage(_)) => {}
+                // The buffer is used up or the rest of it can't be parsed
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        for node in &self.nodes {
+            node.stop();
+            let _ = std::fs::remove_dir_all(&node.data_path);
+        }
+        self.work.stop();
+    }
+}
+
+fn is_idle(node: &Node) -> bool {
+    node.inbound_message_queue.size() == 0
+        && node.block_processor.total_queue_len() == 0
+        && node.vote_processor_queue.is_empty()
+        && node.confirming_set.len() == 0
+        && node.request_aggregator.is_empty()
+        && node.vote_generators.queue_len() == 0
+        && !node.election_schedulers.priority.activation_pending()
+}
+
+/// Every node gets its own private address
+pub fn simulated_address(index: NodeIndex) -> SocketAddrV6 {
+    let index = index as u32 + 1;
+    let ip = Ipv4Addr::from(0x0a00_0000 | index);
+    SocketAddrV6::new(ip.to_ipv6_mapped(), SIMULATED_PORT, 0, 0)
+}
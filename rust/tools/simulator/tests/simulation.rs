@@ -0,0 +1,184 @@
+use rsnano_core::{
+    work::{WorkPool, WorkPoolImpl},
+    Amount, BlockEnum, BlockHash, KeyPair, StateBlock, DEV_GENESIS_KEY,
+};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_node::{config::NodeFlags, Node, DEV_NETWORK_PARAMS};
+use simulator::{LinkConditions, Simulator, SimulatorConfig};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+#[test]
+fn confirm_block_on_all_nodes() {
+    let sim = Simulator::new(SimulatorConfig {
+        seed: 1,
+        link_conditions: LinkConditions {
+            latency: Duration::from_millis(50),
+            jitter: Duration::from_millis(20),
+            packet_loss: 0.05,
+        },
+        // The block is confirmed by its only representative before it gets flooded,
+        // so the other nodes have to bootstrap it
+        flags: with_ascending_bootstrap(),
+        ..Default::default()
+    })
+    .unwrap();
+    sim.node(0).insert_into_wallet(&DEV_GENESIS_KEY);
+
+    let send = genesis_send(sim.node(0), &KeyPair::new());
+    sim.node(0).process_active(send.clone());
+
+    assert!(sim.run_until(TIMEOUT, |sim| all_confirmed(sim, &send.hash())));
+    assert!(sim.network().stats().delivered > 0);
+}
+
+#[test]
+fn partition_prevents_propagation_until_healed() {
+    let sim = Simulator::new(SimulatorConfig {
+        seed: 2,
+        flags: with_ascending_bootstrap(),
+        ..Default::default()
+    })
+    .unwrap();
+    sim.node(0).insert_into_wallet(&DEV_GENESIS_KEY);
+    sim.partition(&[&[0, 1], &[2]]);
+
+    let send = genesis_send(sim.node(0), &KeyPair::new());
+    sim.node(0).process_active(send.clone());
+
+    assert!(sim.run_until(TIMEOUT, |sim| { sim.node(1).block_confirmed(&send.hash()) }));
+    sim.run_for(Duration::from_secs(1));
+    assert!(!sim.node(2).block_exists(&send.hash()));
+    assert!(sim.network().stats().blocked > 0);
+
+    sim.heal();
+    assert!(sim.run_until(TIMEOUT, |sim| all_confirmed(sim, &send.hash())));
+}
+
+#[test]
+fn resolve_fork() {
+    let sim = Simulator::new(SimulatorConfig {
+        seed: 3,
+        ..Default::default()
+    })
+    .unwrap();
+    sim.node(0).insert_into_wallet(&DEV_GENESIS_KEY);
+
+    let fork_a = genesis_send(sim.node(0), &KeyPair::new());
+    let fork_b = genesis_send(sim.node(0), &KeyPair::new());
+    sim.node(1).process_active(fork_a.clone());
+    sim.node(2).process_active(fork_b.clone());
+
+    let winner = |sim: &Simulator| {
+        [fork_a.hash(), fork_b.hash()]
+            .into_iter()
+            .find(|hash| all_confirmed(sim, hash))
+    };
+    assert!(sim.run_until(TIMEOUT, |sim| winner(sim).is_some()));
+
+    let winner = winner(&sim).unwrap();
+    let loser = if winner == fork_a.hash() {
+        fork_b.hash()
+    } else {
+        fork_a.hash()
+    };
+    assert!(sim.nodes().iter().all(|n| !n.block_confirmed(&loser)));
+}
+
+#[test]
+fn bootstrap_new_node() {
+    let mut sim = Simulator::new(SimulatorConfig {
+        seed: 4,
+        node_count: 2,
+        flags: with_ascending_bootstrap(),
+        ..Default::default()
+    })
+    .unwrap();
+    sim.node(0).insert_into_wallet(&DEV_GENESIS_KEY);
+
+    let send = genesis_send(sim.node(0), &KeyPair::new());
+    sim.node(0).process_active(send.clone());
+    assert!(sim.run_until(TIMEOUT, |sim| all_confirmed(sim, &send.hash())));
+
+    let new_node = sim.add_node().unwrap();
+    assert!(sim.run_until(TIMEOUT, |sim| {
+        sim.node(new_node).block_exists(&send.hash())
+    }));
+}
+
+#[test]
+fn same_seed_gives_same_outcome() {
+    // Both runs must get exactly the same blocks, so the work is only generated once
+    let work_pool = WorkPoolImpl::new(DEV_NETWORK_PARAMS.work.clone(), 1, Duration::ZERO);
+    let work = work_pool.generate_dev2((*DEV_GENESIS_HASH).into()).unwrap();
+    let fork_a = genesis_send_with_work(&KeyPair::from(1), work);
+    let fork_b = genesis_send_with_work(&KeyPair::from(2), work);
+
+    let run = || {
+        let sim = Simulator::new(SimulatorConfig {
+            seed: 5,
+            node_count: 4,
+            link_conditions: LinkConditions {
+                latency: Duration::from_millis(30),
+                jitter: Duration::from_millis(40),
+                packet_loss: 0.1,
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        sim.node(0).insert_into_wallet(&DEV_GENESIS_KEY);
+        sim.node(1).process_active(fork_a.clone());
+        sim.node(3).process_active(fork_b.clone());
+
+        let winner = |sim: &Simulator| {
+            [fork_a.hash(), fork_b.hash()]
+                .into_iter()
+                .find(|hash| all_confirmed(sim, hash))
+        };
+        assert!(sim.run_until(TIMEOUT, |sim| winner(sim).is_some()));
+
+        let node_ids: Vec<_> = sim.nodes().iter().map(|n| n.node_id.public_key()).collect();
+        let heads: Vec<_> = sim
+            .nodes()
+            .iter()
+            .map(|n| n.latest(&DEV_GENESIS_ACCOUNT))
+            .collect();
+        (winner(&sim).unwrap(), node_ids, heads)
+    };
+
+    let first = run();
+    let second = run();
+    assert_eq!(first, second);
+    work_pool.stop();
+}
+
+fn with_ascending_bootstrap() -> NodeFlags {
+    NodeFlags {
+        disable_ascending_bootstrap: false,
+        ..SimulatorConfig::default_flags()
+    }
+}
+
+fn genesis_send(node: &Node, destination: &KeyPair) -> BlockEnum {
+    genesis_send_with_work(
+        destination,
+        node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+    )
+}
+
+fn genesis_send_with_work(destination: &KeyPair, work: u64) -> BlockEnum {
+    BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::nano(1000),
+        destination.account().into(),
+        &DEV_GENESIS_KEY,
+        work,
+    ))
+}
+
+fn all_confirmed(sim: &Simulator, hash: &BlockHash) -> bool {
+    sim.nodes().iter().all(|n| n.block_confirmed(hash))
+}
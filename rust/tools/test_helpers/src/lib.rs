@@ -1,6 +1,6 @@
 use rsnano_core::{
-    work::WorkPoolImpl, Account, Amount, BlockEnum, BlockHash, KeyPair, Networks, StateBlock,
-    WalletId, DEV_GENESIS_KEY,
+    utils::TEST_ENDPOINT_1, work::WorkPoolImpl, Account, Amount, BlockEnum, BlockHash, KeyPair,
+    Networks, StateBlock, WalletId, DEV_GENESIS_KEY,
 };
 use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
 use rsnano_network::{Channel, ChannelDirection, ChannelInfo, ChannelMode};
@@ -296,6 +296,7 @@ pub fn make_fake_channel(node: &Node) -> Arc<Channel> {
     node.network
         .add(
             TcpStream::new_null(),
+            TEST_ENDPOINT_1,
             ChannelDirection::Inbound,
             ChannelMode::Realtime,
         )
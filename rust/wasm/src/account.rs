@@ -0,0 +1,23 @@
+use crate::to_js_error;
+use rsnano_core::{Account, PublicKey};
+use wasm_bindgen::prelude::*;
+
+/// Encodes a hex public key as nano_ address
+#[wasm_bindgen(js_name = accountEncode)]
+pub fn account_encode(public_key: &str) -> Result<String, JsError> {
+    let public_key = PublicKey::decode_hex(public_key).map_err(to_js_error)?;
+    Ok(Account::from(public_key).encode_account())
+}
+
+/// Decodes a nano_ address into the hex public key
+#[wasm_bindgen(js_name = accountDecode)]
+pub fn account_decode(account: &str) -> Result<String, JsError> {
+    let account = Account::decode_account(account).map_err(to_js_error)?;
+    Ok(account.encode_hex())
+}
+
+/// Checks the format and the checksum of a nano_ address
+#[wasm_bindgen(js_name = accountValidate)]
+pub fn account_validate(account: &str) -> bool {
+    Account::decode_account(account).is_ok()
+}
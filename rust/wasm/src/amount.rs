@@ -0,0 +1,16 @@
+use crate::to_js_error;
+use rsnano_core::Amount;
+use wasm_bindgen::prelude::*;
+
+/// Converts whole nano into a decimal raw amount
+#[wasm_bindgen(js_name = amountFromNano)]
+pub fn amount_from_nano(nano: u32) -> String {
+    Amount::nano(nano as u128).to_string_dec()
+}
+
+/// Formats a decimal raw amount as nano with at most `precision` decimals
+#[wasm_bindgen(js_name = amountFormat)]
+pub fn amount_format(raw: &str, precision: usize) -> Result<String, JsError> {
+    let amount = Amount::decode_dec(raw).map_err(to_js_error)?;
+    Ok(amount.format_balance(precision))
+}
@@ -0,0 +1,70 @@
+use crate::{to_js_error, JsKeyPair};
+use rsnano_core::{sign_message, Account, BlockBuilder, BlockEnum, JsonBlock, Link};
+use wasm_bindgen::prelude::*;
+
+/// Creates a signed state block. `link` is either a hex block hash or, for sends,
+/// the address of the destination. `work` is hex encoded
+#[wasm_bindgen(js_name = stateBlockCreate)]
+pub fn state_block_create(
+    account: &str,
+    previous: &str,
+    representative: &str,
+    balance: &str,
+    link: &str,
+    keys: &JsKeyPair,
+    work: &str,
+) -> Result<String, JsError> {
+    let account = Account::decode_account(account).map_err(to_js_error)?;
+    if keys.keys().account() != account {
+        return Err(JsError::new("the key does not belong to the account"));
+    }
+
+    let block = BlockBuilder::state()
+        .account(account)
+        .previous_hex(previous)
+        .and_then(|b| b.representative_address(representative))
+        .and_then(|b| b.balance_dec(balance))
+        .map_err(to_js_error)?
+        .link(parse_link(link)?)
+        .sign(keys.keys())
+        .work(parse_work(work)?)
+        .build();
+
+    block_to_json(&block)
+}
+
+/// Calculates the hash of a JSON block
+#[wasm_bindgen(js_name = blockHash)]
+pub fn block_hash(block: &str) -> Result<String, JsError> {
+    Ok(parse_block(block)?.hash().encode_hex())
+}
+
+/// Replaces the signature of a JSON block
+#[wasm_bindgen(js_name = blockSign)]
+pub fn block_sign(block: &str, keys: &JsKeyPair) -> Result<String, JsError> {
+    let mut block = parse_block(block)?;
+    let signature = sign_message(&keys.keys().private_key(), block.hash().as_bytes());
+    block.set_block_signature(&signature);
+    block_to_json(&block)
+}
+
+pub(crate) fn parse_block(json: &str) -> Result<BlockEnum, JsError> {
+    let block: JsonBlock = serde_json::from_str(json).map_err(to_js_error)?;
+    Ok(block.into())
+}
+
+fn block_to_json(block: &BlockEnum) -> Result<String, JsError> {
+    serde_json::to_string(&block.json_representation()).map_err(to_js_error)
+}
+
+fn parse_link(link: &str) -> Result<Link, JsError> {
+    if link.starts_with("nano_") || link.starts_with("xrb_") {
+        Ok(Account::decode_account(link).map_err(to_js_error)?.into())
+    } else {
+        Link::decode_hex(link).map_err(to_js_error)
+    }
+}
+
+fn parse_work(work: &str) -> Result<u64, JsError> {
+    u64::from_str_radix(work, 16).map_err(to_js_error)
+}
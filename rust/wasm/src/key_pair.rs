@@ -0,0 +1,51 @@
+use crate::to_js_error;
+use rsnano_core::{sign_message, KeyPair};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = KeyPair)]
+pub struct JsKeyPair {
+    keys: KeyPair,
+}
+
+#[wasm_bindgen(js_class = KeyPair)]
+impl JsKeyPair {
+    /// Creates a random key pair using the random number generator of the JS host
+    pub fn generate() -> JsKeyPair {
+        Self {
+            keys: KeyPair::new(),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromPrivateKey)]
+    pub fn from_private_key(private_key: &str) -> Result<JsKeyPair, JsError> {
+        let keys = KeyPair::from_priv_key_hex(private_key).map_err(to_js_error)?;
+        Ok(Self { keys })
+    }
+
+    #[wasm_bindgen(getter, js_name = privateKey)]
+    pub fn private_key(&self) -> String {
+        self.keys.private_key().encode_hex()
+    }
+
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> String {
+        self.keys.public_key().encode_hex()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn account(&self) -> String {
+        self.keys.account().encode_account()
+    }
+
+    /// Signs the hex encoded message and returns the hex signature
+    pub fn sign(&self, message: &str) -> Result<String, JsError> {
+        let message = hex::decode(message).map_err(to_js_error)?;
+        Ok(sign_message(&self.keys.private_key(), &message).encode_hex())
+    }
+}
+
+impl JsKeyPair {
+    pub(crate) fn keys(&self) -> &KeyPair {
+        &self.keys
+    }
+}
@@ -0,0 +1,25 @@
+//! JavaScript bindings for the offline parts of `rsnano_core`: account encoding,
+//! amounts, key pairs, block creation/hashing/signing and work validation.
+//! Blocks are passed around in the JSON format of the RPC.
+//!
+//! Build with `wasm-pack build rust/wasm` or run the tests with
+//! `cargo test -p rsnano_wasm --target wasm32-unknown-unknown`.
+
+mod account;
+mod amount;
+mod blocks;
+mod key_pair;
+mod work;
+
+pub use account::*;
+pub use amount::*;
+pub use blocks::*;
+pub use key_pair::*;
+pub use work::*;
+
+use std::fmt::Display;
+use wasm_bindgen::JsError;
+
+fn to_js_error(error: impl Display) -> JsError {
+    JsError::new(&error.to_string())
+}
@@ -0,0 +1,29 @@
+use crate::{blocks::parse_block, to_js_error};
+use rsnano_core::{work::WorkThresholds, Networks};
+use wasm_bindgen::prelude::*;
+
+/// Difficulty of the work of a JSON block
+#[wasm_bindgen(js_name = workDifficulty)]
+pub fn work_difficulty(block: &str, network: &str) -> Result<u64, JsError> {
+    let block = parse_block(block)?;
+    Ok(thresholds(network)?.difficulty_block(&block))
+}
+
+/// Checks that the work of a JSON block is high enough to enter the network.
+/// `network` is one of "live", "beta", "dev" or "test"
+#[wasm_bindgen(js_name = workValidate)]
+pub fn work_validate(block: &str, network: &str) -> Result<bool, JsError> {
+    let block = parse_block(block)?;
+    // validate_entry_block returns true if the work is invalid
+    Ok(!thresholds(network)?.validate_entry_block(&block))
+}
+
+fn thresholds(network: &str) -> Result<&'static WorkThresholds, JsError> {
+    match network.parse::<Networks>().map_err(to_js_error)? {
+        Networks::NanoLiveNetwork => Ok(WorkThresholds::publish_full()),
+        Networks::NanoBetaNetwork => Ok(WorkThresholds::publish_beta()),
+        Networks::NanoDevNetwork => Ok(WorkThresholds::publish_dev()),
+        Networks::NanoTestNetwork => Ok(WorkThresholds::publish_test()),
+        Networks::Invalid => Err(JsError::new("Invalid network")),
+    }
+}
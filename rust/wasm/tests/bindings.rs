@@ -0,0 +1,102 @@
+#![cfg(target_arch = "wasm32")]
+
+use rsnano_wasm::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const GENESIS_PUBLIC_KEY: &str = "E89208DD038FBB269987689621D52292AE9C35941A7484756ECCED92A65093BA";
+const GENESIS_ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+const LEGACY_SEND: &str = r#"{
+  "type": "send",
+  "previous": "991CF190094C00F0B68E2E5F75F6BEE95A2E0BD93CEAA4A6734DB9F19B728948",
+  "destination": "nano_13ezf4od79h1tgj9aiu4djzcmmguendtjfuhwfukhuucboua8cpoihmh8byo",
+  "balance": "FD89D89D89D89D89D89D89D89D89D89D",
+  "signature": "5B11B17DB9C8FE0CC58CAC6A6EECEF9CB122DA8A81C6D3DB1B5EE3AB065AA8F8CB1D6765C8EB91B58530C5FF5987AD95E6D34BB57F44257E20795EE412E61600",
+  "work": "3c82cc724905ee95"
+}"#;
+
+#[wasm_bindgen_test]
+fn account_encoding() {
+    assert_eq!(account_encode(GENESIS_PUBLIC_KEY).unwrap(), GENESIS_ACCOUNT);
+    assert_eq!(account_decode(GENESIS_ACCOUNT).unwrap(), GENESIS_PUBLIC_KEY);
+    assert!(account_validate(GENESIS_ACCOUNT));
+    assert!(!account_validate(&GENESIS_ACCOUNT.replace('3', "4")));
+    assert!(account_decode("nano_123").is_err());
+}
+
+#[wasm_bindgen_test]
+fn amounts() {
+    assert_eq!(amount_from_nano(2), "2000000000000000000000000000000");
+    assert_eq!(
+        amount_format("1500000000000000000000000000000", 2).unwrap(),
+        "1.5"
+    );
+    assert!(amount_format("abc", 2).is_err());
+}
+
+#[wasm_bindgen_test]
+fn key_pair() {
+    let keys = JsKeyPair::from_private_key(
+        "34F0A37AAD20F4A260F0A5B3CB3D7FB50673212263E58A380BC10474BB039CE4",
+    )
+    .unwrap();
+    assert_eq!(account_encode(&keys.public_key()).unwrap(), keys.account());
+    assert_eq!(keys.sign("00FF").unwrap().len(), 128);
+    assert!(keys.sign("XY").is_err());
+
+    let random = JsKeyPair::generate();
+    assert_ne!(random.private_key(), keys.private_key());
+}
+
+#[wasm_bindgen_test]
+fn hash_and_validate_legacy_block() {
+    assert_eq!(
+        block_hash(LEGACY_SEND).unwrap(),
+        "A170D51B94E00371ACE76E35AC81DC9405D5D04D4CEBC399AEACE07AE05DD293"
+    );
+    assert!(work_validate(LEGACY_SEND, "live").unwrap());
+    assert_eq!(
+        work_difficulty(LEGACY_SEND, "live").unwrap(),
+        18446743921403126366
+    );
+    assert!(work_validate(LEGACY_SEND, "nonsense").is_err());
+}
+
+#[wasm_bindgen_test]
+fn create_and_sign_state_block() {
+    let keys = JsKeyPair::generate();
+    let block = state_block_create(
+        &keys.account(),
+        "991CF190094C00F0B68E2E5F75F6BEE95A2E0BD93CEAA4A6734DB9F19B728948",
+        GENESIS_ACCOUNT,
+        "1000",
+        GENESIS_ACCOUNT,
+        &keys,
+        "3c82cc724905ee95",
+    )
+    .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&block).unwrap();
+    assert_eq!(json["type"], "state");
+    assert_eq!(json["balance"], "1000");
+    assert_eq!(json["link"], GENESIS_PUBLIC_KEY);
+    let hash = block_hash(&block).unwrap();
+    assert_eq!(json["signature"], keys.sign(&hash).unwrap());
+
+    // Re-signing with another key changes only the signature
+    let other = JsKeyPair::generate();
+    let resigned = block_sign(&block, &other).unwrap();
+    assert_eq!(block_hash(&resigned).unwrap(), hash);
+    assert_ne!(resigned, block);
+
+    assert!(state_block_create(
+        GENESIS_ACCOUNT,
+        "991CF190094C00F0B68E2E5F75F6BEE95A2E0BD93CEAA4A6734DB9F19B728948",
+        GENESIS_ACCOUNT,
+        "1000",
+        GENESIS_ACCOUNT,
+        &keys,
+        "3c82cc724905ee95",
+    )
+    .is_err());
+}
@@ -0,0 +1,64 @@
+use anyhow::Result;
+use clap::Parser;
+use rsnano_core::{
+    work::{CpuWorkBackend, WorkThresholds},
+    Networks,
+};
+use rsnano_work_server::{run_work_server, WorkServer, WorkServerConfig};
+use std::{net::SocketAddr, str::FromStr, sync::Arc};
+use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
+
+/// Generates proof of work for nodes that list this server in their work_peers
+#[derive(Parser)]
+struct Args {
+    /// Address to listen on for work requests
+    #[arg(long, default_value = "[::1]:7076")]
+    listen: SocketAddr,
+    /// Network whose work thresholds are used (live, test, beta or dev)
+    #[arg(long, default_value = "live")]
+    network: String,
+    /// Number of work threads. Defaults to the number of CPU cores
+    #[arg(long)]
+    threads: Option<usize>,
+    /// CPU work backend (simple or batched)
+    #[arg(long, default_value = "batched")]
+    backend: String,
+    /// Maximum number of queued requests
+    #[arg(long)]
+    max_queue: Option<usize>,
+    /// Maximum number of queued and running requests per client IP
+    #[arg(long)]
+    max_per_client: Option<usize>,
+    /// Requests above this multiplier of the base difficulty are rejected
+    #[arg(long)]
+    max_multiplier: Option<f64>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let dirs =
+        std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or(String::from("rsnano_work_server=info"));
+    tracing_subscriber::fmt::fmt()
+        .with_env_filter(EnvFilter::new(dirs))
+        .init();
+
+    let network = Networks::from_str(&args.network).map_err(anyhow::Error::msg)?;
+    let defaults = WorkServerConfig::default();
+    let config = WorkServerConfig {
+        threads: args.threads.unwrap_or(defaults.threads),
+        backend: CpuWorkBackend::from_str(&args.backend)?,
+        max_queue: args.max_queue.unwrap_or(defaults.max_queue),
+        max_per_client: args.max_per_client.unwrap_or(defaults.max_per_client),
+        max_multiplier: args.max_multiplier.unwrap_or(defaults.max_multiplier),
+    };
+
+    let server = Arc::new(WorkServer::new(
+        config,
+        WorkThresholds::default_for(network),
+    ));
+    let listener = TcpListener::bind(args.listen).await?;
+    run_work_server(server, listener).await
+}
@@ -0,0 +1,6 @@
+mod messages;
+mod server;
+mod work_queue;
+
+pub use messages::*;
+pub use server::*;
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+/// Requests in the format that `DistributedWorkFactory` and the nano work server use.
+/// Difficulties and work values are hex strings
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum WorkServerRequest {
+    WorkGenerate(WorkGenerateRequest),
+    WorkCancel(WorkCancelRequest),
+    WorkValidate(WorkValidateRequest),
+    Status,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WorkGenerateRequest {
+    pub hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<String>,
+    /// Only used for logging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WorkCancelRequest {
+    pub hash: String,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WorkValidateRequest {
+    pub hash: String,
+    pub work: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<String>,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct WorkGenerateResponse {
+    pub work: String,
+    pub difficulty: String,
+    pub multiplier: String,
+    pub hash: String,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct WorkValidateResponse {
+    /// "1" if the work reaches the requested difficulty, "0" otherwise
+    pub valid: String,
+    pub difficulty: String,
+    pub multiplier: String,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct WorkCancelResponse {
+    /// Number of queued or running requests that were cancelled
+    pub cancelled: u64,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Default, Clone)]
+pub struct WorkServerStatus {
+    pub generating: bool,
+    pub queue_size: u64,
+    pub queued_clients: u64,
+    pub completed: u64,
+    pub cancelled: u64,
+    pub rejected: u64,
+    pub failed: u64,
+    /// Average time to generate work in milliseconds
+    pub average_generation_ms: u64,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+impl ErrorResponse {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string_pretty};
+
+    #[test]
+    fn deserialize_distributed_work_factory_request() {
+        let request: WorkServerRequest = from_str(
+            r#"{
+  "action": "work_generate",
+  "hash": "000000000000000000000000000000000000000000000000000000000000007B",
+  "difficulty": "fffffff800000000",
+  "account": "nano_1111111111111111111111111111111111111111111111111111hifc8npp"
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            request,
+            WorkServerRequest::WorkGenerate(WorkGenerateRequest {
+                hash: "000000000000000000000000000000000000000000000000000000000000007B".to_owned(),
+                difficulty: Some("fffffff800000000".to_owned()),
+                account: Some(
+                    "nano_1111111111111111111111111111111111111111111111111111hifc8npp".to_owned()
+                ),
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_status_request() {
+        assert_eq!(
+            to_string_pretty(&WorkServerRequest::Status).unwrap(),
+            r#"{
+  "action": "status"
+}"#
+        );
+    }
+}
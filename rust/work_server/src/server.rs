@@ -0,0 +1,462 @@
+use crate::{
+    messages::{
+        ErrorResponse, WorkCancelRequest, WorkCancelResponse, WorkGenerateRequest,
+        WorkGenerateResponse, WorkServerRequest, WorkServerStatus, WorkValidateRequest,
+        WorkValidateResponse,
+    },
+    work_queue::{EnqueueError, WorkJob, WorkQueue},
+};
+use anyhow::Context;
+use axum::{
+    extract::{ConnectInfo, State},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use rsnano_core::{
+    to_hex_string, u64_from_hex_str,
+    work::{CpuWorkBackend, WorkPool, WorkPoolImpl, WorkThresholds},
+    DifficultyV1, Root, WorkVersion,
+};
+use serde::Serialize;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::{
+    net::TcpListener,
+    sync::{oneshot, Notify},
+};
+use tracing::{debug, info};
+
+pub struct WorkServerConfig {
+    pub threads: usize,
+    pub backend: CpuWorkBackend,
+    /// Maximum number of queued requests
+    pub max_queue: usize,
+    /// Maximum number of queued and running requests per client IP
+    pub max_per_client: usize,
+    /// Requests with a higher difficulty multiplier are rejected
+    pub max_multiplier: f64,
+}
+
+impl Default for WorkServerConfig {
+    fn default() -> Self {
+        Self {
+            threads: std::thread::available_parallelism()
+                .map(|i| i.get())
+                .unwrap_or(1),
+            backend: CpuWorkBackend::default(),
+            max_queue: 1024,
+            max_per_client: 64,
+            max_multiplier: 64.0,
+        }
+    }
+}
+
+/// Answers the work requests of `DistributedWorkFactory`. All pool threads work
+/// on one request at a time, so requests are queued and handed to the pool one
+/// after another, hardest first
+pub struct WorkServer {
+    work_pool: WorkPoolImpl,
+    thresholds: WorkThresholds,
+    max_multiplier: f64,
+    queue: Mutex<WorkQueue>,
+    current: Mutex<Option<Root>>,
+    job_added: Notify,
+    metrics: Mutex<Metrics>,
+}
+
+#[derive(Default)]
+struct Metrics {
+    completed: u64,
+    cancelled: u64,
+    rejected: u64,
+    failed: u64,
+    generation_time: Duration,
+}
+
+impl WorkServer {
+    pub fn new(config: WorkServerConfig, thresholds: WorkThresholds) -> Self {
+        let work_pool = WorkPoolImpl::with_backend(
+            thresholds.clone(),
+            config.threads,
+            Duration::ZERO,
+            config.backend,
+        );
+        Self::with_work_pool(config, thresholds, work_pool)
+    }
+
+    pub fn with_work_pool(
+        config: WorkServerConfig,
+        thresholds: WorkThresholds,
+        work_pool: WorkPoolImpl,
+    ) -> Self {
+        Self {
+            work_pool,
+            thresholds,
+            max_multiplier: config.max_multiplier,
+            queue: Mutex::new(WorkQueue::new(config.max_queue, config.max_per_client)),
+            current: Mutex::new(None),
+            job_added: Notify::new(),
+            metrics: Mutex::new(Metrics::default()),
+        }
+    }
+
+    pub fn status(&self) -> WorkServerStatus {
+        let (queue_size, queued_clients) = {
+            let queue = self.queue.lock().unwrap();
+            (queue.len() as u64, queue.client_count() as u64)
+        };
+        let metrics = self.metrics.lock().unwrap();
+        let average_generation_ms = match metrics.completed {
+            0 => 0,
+            completed => metrics.generation_time.as_millis() as u64 / completed,
+        };
+
+        WorkServerStatus {
+            generating: self.current.lock().unwrap().is_some(),
+            queue_size,
+            queued_clients,
+            completed: metrics.completed,
+            cancelled: metrics.cancelled,
+            rejected: metrics.rejected,
+            failed: metrics.failed,
+            average_generation_ms,
+        }
+    }
+
+    /// Hands queued requests to the work pool until the process exits
+    pub async fn run_dispatcher(&self) {
+        loop {
+            let job_added = self.job_added.notified();
+            let job = self.queue.lock().unwrap().pop();
+            match job {
+                // The client gave up waiting
+                Some(job) if job.responder.is_closed() => {
+                    self.queue.lock().unwrap().finished(job.client);
+                }
+                Some(job) => self.generate(job).await,
+                None => job_added.await,
+            }
+        }
+    }
+
+    async fn generate(&self, job: WorkJob) {
+        *self.current.lock().unwrap() = Some(job.root);
+        let start = Instant::now();
+
+        let (tx, rx) = oneshot::channel();
+        self.work_pool.generate_async(
+            WorkVersion::Work1,
+            job.root,
+            job.difficulty,
+            Some(Box::new(move |work| {
+                let _ = tx.send(work);
+            })),
+        );
+        let work = rx.await.ok().flatten();
+
+        *self.current.lock().unwrap() = None;
+        self.queue.lock().unwrap().finished(job.client);
+        {
+            let mut metrics = self.metrics.lock().unwrap();
+            if work.is_some() {
+                metrics.completed += 1;
+                metrics.generation_time += start.elapsed();
+            } else {
+                metrics.cancelled += 1;
+            }
+        }
+        let _ = job.responder.send(work);
+    }
+
+    async fn work_generate(&self, client: IpAddr, request: WorkGenerateRequest) -> Response {
+        let Ok(root) = Root::decode_hex(&request.hash) else {
+            return error_response("Bad hash");
+        };
+        let difficulty = match &request.difficulty {
+            Some(difficulty) => match u64_from_hex_str(difficulty) {
+                Ok(difficulty) => difficulty,
+                Err(_) => return error_response("Bad difficulty"),
+            },
+            None => self.thresholds.base,
+        };
+        if difficulty < self.thresholds.entry
+            || DifficultyV1::to_multiplier(difficulty, self.thresholds.base) > self.max_multiplier
+        {
+            self.metrics.lock().unwrap().rejected += 1;
+            return error_response("Difficulty out of valid range");
+        }
+
+        let (responder, rx) = oneshot::channel();
+        let result = self.queue.lock().unwrap().push(WorkJob {
+            root,
+            difficulty,
+            client,
+            responder,
+        });
+        if let Err(e) = result {
+            self.metrics.lock().unwrap().rejected += 1;
+            return error_response(match e {
+                EnqueueError::QueueFull => "Work queue is full",
+                EnqueueError::ClientLimitReached => "Too many requests from this client",
+            });
+        }
+        self.job_added.notify_one();
+        debug!(%client, %root, account = ?request.account, "Work request queued");
+
+        match rx.await {
+            Ok(Some(work)) => {
+                let result_difficulty = self.thresholds.difficulty(WorkVersion::Work1, &root, work);
+                json_response(WorkGenerateResponse {
+                    work: to_hex_string(work),
+                    difficulty: to_hex_string(result_difficulty),
+                    multiplier: self.multiplier(result_difficulty),
+                    hash: request.hash,
+                })
+            }
+            Ok(None) => error_response("Cancelled"),
+            Err(_) => {
+                self.metrics.lock().unwrap().failed += 1;
+                error_response("Work generation failed")
+            }
+        }
+    }
+
+    fn work_cancel(&self, request: WorkCancelRequest) -> Response {
+        let Ok(root) = Root::decode_hex(&request.hash) else {
+            return error_response("Bad hash");
+        };
+
+        let queued = self.queue.lock().unwrap().cancel(&root);
+        let mut cancelled = queued.len() as u64;
+        self.metrics.lock().unwrap().cancelled += cancelled;
+        for job in queued {
+            let _ = job.responder.send(None);
+        }
+
+        // The dispatcher counts the cancellation of the running request
+        if *self.current.lock().unwrap() == Some(root) {
+            self.work_pool.cancel(&root);
+            cancelled += 1;
+        }
+
+        json_response(WorkCancelResponse { cancelled })
+    }
+
+    fn work_validate(&self, request: WorkValidateRequest) -> Response {
+        let Ok(root) = Root::decode_hex(&request.hash) else {
+            return error_response("Bad hash");
+        };
+        let Ok(work) = u64_from_hex_str(&request.work) else {
+            return error_response("Bad work");
+        };
+        let required = match &request.difficulty {
+            Some(difficulty) => match u64_from_hex_str(difficulty) {
+                Ok(difficulty) => difficulty,
+                Err(_) => return error_response("Bad difficulty"),
+            },
+            None => self.thresholds.base,
+        };
+
+        let difficulty = self.thresholds.difficulty(WorkVersion::Work1, &root, work);
+        json_response(WorkValidateResponse {
+            valid: if difficulty >= required { "1" } else { "0" }.to_owned(),
+            difficulty: to_hex_string(difficulty),
+            multiplier: self.multiplier(difficulty),
+        })
+    }
+
+    fn multiplier(&self, difficulty: u64) -> String {
+        DifficultyV1::to_multiplier(difficulty, self.thresholds.base).to_string()
+    }
+}
+
+pub async fn run_work_server(server: Arc<WorkServer>, listener: TcpListener) -> anyhow::Result<()> {
+    let dispatcher = server.clone();
+    tokio::spawn(async move { dispatcher.run_dispatcher().await });
+
+    let app = Router::new()
+        .route("/", post(handle_request))
+        .with_state(server);
+
+    info!("Work server listening address: {}", listener.local_addr()?);
+
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .context("Failed to run the work server")?;
+
+    Ok(())
+}
+
+async fn handle_request(
+    State(server): State<Arc<WorkServer>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    Json(request): Json<WorkServerRequest>,
+) -> Response {
+    match request {
+        WorkServerRequest::WorkGenerate(request) => {
+            server.work_generate(client.ip(), request).await
+        }
+        WorkServerRequest::WorkCancel(request) => server.work_cancel(request),
+        WorkServerRequest::WorkValidate(request) => server.work_validate(request),
+        WorkServerRequest::Status => json_response(server.status()),
+    }
+}
+
+fn json_response(value: impl Serialize) -> Response {
+    Json(value).into_response()
+}
+
+fn error_response(error: &str) -> Response {
+    json_response(ErrorResponse::new(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::WorkServerStatus;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn generate_work() {
+        let (url, server) = start_server(WorkServerConfig::default()).await;
+        let root = Root::from(123);
+
+        let response = post(&url, distributed_work_factory_request(root)).await;
+
+        let work = u64_from_hex_str(response["work"].as_str().unwrap()).unwrap();
+        let thresholds = WorkThresholds::publish_dev();
+        assert!(thresholds.difficulty(WorkVersion::Work1, &root, work) >= thresholds.base);
+        assert_eq!(server.status().completed, 1);
+    }
+
+    #[tokio::test]
+    async fn validate_work() {
+        let (url, _server) = start_server(WorkServerConfig::default()).await;
+        let root = Root::from(123);
+        let generated = post(&url, distributed_work_factory_request(root)).await;
+
+        let response = post(
+            &url,
+            json!({
+                "action": "work_validate",
+                "hash": root.to_string(),
+                "work": generated["work"],
+            }),
+        )
+        .await;
+
+        assert_eq!(response["valid"], "1");
+        assert_eq!(response["difficulty"], generated["difficulty"]);
+    }
+
+    #[tokio::test]
+    async fn reject_difficulty_out_of_range() {
+        let (url, server) = start_server(WorkServerConfig {
+            max_multiplier: 2.0,
+            ..Default::default()
+        })
+        .await;
+
+        let response = post(
+            &url,
+            json!({
+                "action": "work_generate",
+                "hash": Root::from(1).to_string(),
+                "difficulty": "ffffffffffffffff",
+            }),
+        )
+        .await;
+
+        assert_eq!(response["error"], "Difficulty out of valid range");
+        assert_eq!(server.status().rejected, 1);
+    }
+
+    #[tokio::test]
+    async fn cancel_queued_request() {
+        let server = Arc::new(WorkServer::with_work_pool(
+            WorkServerConfig::default(),
+            WorkThresholds::publish_dev().clone(),
+            WorkPoolImpl::disabled(),
+        ));
+        let root = Root::from(7);
+        let request = {
+            let server = server.clone();
+            tokio::spawn(async move {
+                server
+                    .work_generate(
+                        IpAddr::from([127, 0, 0, 1]),
+                        WorkGenerateRequest {
+                            hash: root.to_string(),
+                            difficulty: None,
+                            account: None,
+                        },
+                    )
+                    .await
+            })
+        };
+        while server.status().queue_size == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        server.work_cancel(WorkCancelRequest {
+            hash: root.to_string(),
+        });
+
+        let response = request.await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Cancelled");
+        assert_eq!(
+            server.status(),
+            WorkServerStatus {
+                cancelled: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    async fn start_server(config: WorkServerConfig) -> (String, Arc<WorkServer>) {
+        let server = Arc::new(WorkServer::new(
+            WorkServerConfig {
+                threads: 1,
+                ..config
+            },
+            WorkThresholds::publish_dev().clone(),
+        ));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(run_work_server(server.clone(), listener));
+        (url, server)
+    }
+
+    /// Same format as `HttpWorkRequest`
+    fn distributed_work_factory_request(root: Root) -> Value {
+        json!({
+            "action": "work_generate",
+            "hash": root.to_string(),
+            "difficulty": to_hex_string(WorkThresholds::publish_dev().base),
+            "account": "nano_1111111111111111111111111111111111111111111111111111hifc8npp",
+        })
+    }
+
+    async fn post(url: &str, body: Value) -> Value {
+        reqwest::Client::new()
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap()
+    }
+}
@@ -0,0 +1,205 @@
+use rsnano_core::Root;
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+};
+use tokio::sync::oneshot;
+
+pub(crate) struct WorkJob {
+    pub root: Root,
+    pub difficulty: u64,
+    pub client: IpAddr,
+    pub responder: oneshot::Sender<Option<u64>>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub(crate) enum EnqueueError {
+    QueueFull,
+    ClientLimitReached,
+}
+
+/// Pending work requests. The hardest request is generated first and requests
+/// with the same difficulty are generated in arrival order
+pub(crate) struct WorkQueue {
+    max_size: usize,
+    max_per_client: usize,
+    /// Keyed by (difficulty, reverse arrival order), so that the last entry is next
+    jobs: BTreeMap<(u64, u64), WorkJob>,
+    /// Queued and running requests per client
+    per_client: HashMap<IpAddr, usize>,
+    next_sequence: u64,
+}
+
+impl WorkQueue {
+    pub fn new(max_size: usize, max_per_client: usize) -> Self {
+        Self {
+            max_size,
+            max_per_client,
+            jobs: BTreeMap::new(),
+            per_client: HashMap::new(),
+            next_sequence: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Number of clients with queued or running requests
+    pub fn client_count(&self) -> usize {
+        self.per_client.len()
+    }
+
+    pub fn push(&mut self, job: WorkJob) -> Result<(), EnqueueError> {
+        if self.jobs.len() >= self.max_size {
+            return Err(EnqueueError::QueueFull);
+        }
+
+        let client_jobs = self
+            .per_client
+            .get(&job.client)
+            .cloned()
+            .unwrap_or_default();
+        if client_jobs >= self.max_per_client {
+            return Err(EnqueueError::ClientLimitReached);
+        }
+
+        *self.per_client.entry(job.client).or_default() += 1;
+        let key = (job.difficulty, u64::MAX - self.next_sequence);
+        self.next_sequence += 1;
+        self.jobs.insert(key, job);
+        Ok(())
+    }
+
+    /// Takes the next job. The job still counts against the client limit until
+    /// `finished` is called
+    pub fn pop(&mut self) -> Option<WorkJob> {
+        self.jobs.pop_last().map(|(_, job)| job)
+    }
+
+    pub fn finished(&mut self, client: IpAddr) {
+        if let Some(count) = self.per_client.get_mut(&client) {
+            *count -= 1;
+            if *count == 0 {
+                self.per_client.remove(&client);
+            }
+        }
+    }
+
+    /// Removes all queued jobs for the given root
+    pub fn cancel(&mut self, root: &Root) -> Vec<WorkJob> {
+        let keys: Vec<_> = self
+            .jobs
+            .iter()
+            .filter(|(_, job)| job.root == *root)
+            .map(|(key, _)| *key)
+            .collect();
+
+        let mut cancelled = Vec::with_capacity(keys.len());
+        for key in keys {
+            if let Some(job) = self.jobs.remove(&key) {
+                self.finished(job.client);
+                cancelled.push(job);
+            }
+        }
+        cancelled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn empty() {
+        let mut queue = WorkQueue::new(10, 10);
+        assert_eq!(queue.len(), 0);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn hardest_job_first() {
+        let mut queue = WorkQueue::new(10, 10);
+        queue.push(job(1, 100, CLIENT_A)).unwrap();
+        queue.push(job(2, 300, CLIENT_A)).unwrap();
+        queue.push(job(3, 200, CLIENT_A)).unwrap();
+
+        assert_eq!(queue.pop().unwrap().root, Root::from(2));
+        assert_eq!(queue.pop().unwrap().root, Root::from(3));
+        assert_eq!(queue.pop().unwrap().root, Root::from(1));
+    }
+
+    #[test]
+    fn same_difficulty_in_arrival_order() {
+        let mut queue = WorkQueue::new(10, 10);
+        queue.push(job(1, 100, CLIENT_A)).unwrap();
+        queue.push(job(2, 100, CLIENT_A)).unwrap();
+
+        assert_eq!(queue.pop().unwrap().root, Root::from(1));
+        assert_eq!(queue.pop().unwrap().root, Root::from(2));
+    }
+
+    #[test]
+    fn reject_when_full() {
+        let mut queue = WorkQueue::new(1, 10);
+        queue.push(job(1, 100, CLIENT_A)).unwrap();
+        assert_eq!(
+            queue.push(job(2, 100, CLIENT_B)).err(),
+            Some(EnqueueError::QueueFull)
+        );
+    }
+
+    #[test]
+    fn limit_requests_per_client() {
+        let mut queue = WorkQueue::new(10, 1);
+        queue.push(job(1, 100, CLIENT_A)).unwrap();
+        assert_eq!(
+            queue.push(job(2, 100, CLIENT_A)).err(),
+            Some(EnqueueError::ClientLimitReached)
+        );
+        queue.push(job(3, 100, CLIENT_B)).unwrap();
+        assert_eq!(queue.client_count(), 2);
+    }
+
+    #[test]
+    fn running_job_counts_against_client_limit_until_finished() {
+        let mut queue = WorkQueue::new(10, 1);
+        queue.push(job(1, 100, CLIENT_A)).unwrap();
+        queue.pop().unwrap();
+        assert_eq!(
+            queue.push(job(2, 100, CLIENT_A)).err(),
+            Some(EnqueueError::ClientLimitReached)
+        );
+
+        queue.finished(CLIENT_A);
+        queue.push(job(2, 100, CLIENT_A)).unwrap();
+    }
+
+    #[test]
+    fn cancel() {
+        let mut queue = WorkQueue::new(10, 10);
+        queue.push(job(1, 100, CLIENT_A)).unwrap();
+        queue.push(job(2, 100, CLIENT_A)).unwrap();
+        queue.push(job(1, 200, CLIENT_B)).unwrap();
+
+        let cancelled = queue.cancel(&Root::from(1));
+
+        assert_eq!(cancelled.len(), 2);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.client_count(), 1);
+    }
+
+    const CLIENT_A: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    const CLIENT_B: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+    fn job(root: u64, difficulty: u64, client: IpAddr) -> WorkJob {
+        let (responder, _) = oneshot::channel();
+        WorkJob {
+            root: Root::from(root),
+            difficulty,
+            client,
+            responder,
+        }
+    }
+}